@@ -90,6 +90,10 @@ pub struct Parameters {
     /// Tonic network settings.
     #[serde(default = "TonicParameters::default")]
     pub tonic: TonicParameters,
+
+    /// Compression settings of block and commit payloads sent to peers.
+    #[serde(default = "CompressionParameters::default")]
+    pub compression: CompressionParameters,
 }
 
 impl Parameters {
@@ -208,6 +212,7 @@ impl Default for Parameters {
             commit_sync_batches_ahead: Parameters::default_commit_sync_batches_ahead(),
             anemo: AnemoParameters::default(),
             tonic: TonicParameters::default(),
+            compression: CompressionParameters::default(),
         }
     }
 }
@@ -293,3 +298,47 @@ impl Default for TonicParameters {
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CompressionParameters {
+    /// Whether block and commit payloads are compressed when exchanged with peers.
+    /// Compression is negotiated per request, so peers with compression disabled or without
+    /// support for it keep exchanging uncompressed payloads.
+    ///
+    /// If unspecified, this will default to false.
+    #[serde(default = "CompressionParameters::default_enabled")]
+    pub enabled: bool,
+
+    /// zstd compression level used for outgoing payloads.
+    ///
+    /// If unspecified, this will default to 3.
+    #[serde(default = "CompressionParameters::default_level")]
+    pub level: i32,
+
+    /// Path to a zstd dictionary trained on block payloads. The dictionary is only used with
+    /// peers that are configured with the same dictionary, and plain zstd is used otherwise.
+    ///
+    /// If unspecified, no dictionary is used.
+    #[serde(default)]
+    pub dictionary_path: Option<PathBuf>,
+}
+
+impl CompressionParameters {
+    fn default_enabled() -> bool {
+        false
+    }
+
+    fn default_level() -> i32 {
+        3
+    }
+}
+
+impl Default for CompressionParameters {
+    fn default() -> Self {
+        Self {
+            enabled: CompressionParameters::default_enabled(),
+            level: CompressionParameters::default_level(),
+            dictionary_path: None,
+        }
+    }
+}
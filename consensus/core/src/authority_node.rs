@@ -165,6 +165,9 @@ where
     subscriber: Option<Subscriber<N::Client, AuthorityService<ChannelCoreThreadDispatcher>>>,
     network_manager: N,
     sync_last_known_own_block: bool,
+    // Floods peers with fetch requests when running as a byzantine authority in simtests.
+    #[cfg(msim)]
+    byzantine_flooder: Option<JoinHandle<()>>,
 }
 
 impl<N> AuthorityNode<N>
@@ -205,14 +208,20 @@ where
         );
         info!("Consensus parameters: {:?}", parameters);
         info!("Consensus committee: {:?}", committee);
-        let context = Arc::new(Context::new(
+        let context = Context::new(
             own_index,
             committee,
             parameters,
             protocol_config,
             initialise_metrics(registry),
             clock,
-        ));
+        );
+        #[cfg(msim)]
+        let context = Context {
+            adversary: crate::byzantine::Adversary::from_registry(&context, &protocol_keypair),
+            ..context
+        };
+        let context = Arc::new(context);
         let start_time = Instant::now();
 
         let (tx_client, tx_receiver) = TransactionClient::new(context.clone());
@@ -263,6 +272,15 @@ where
                 .is_zero();
         info!("Sync last known own block: {sync_last_known_own_block}");
 
+        #[cfg(msim)]
+        let byzantine_flooder = context.adversary.as_ref().and_then(|adversary| {
+            adversary.start_fetch_flooder(
+                context.clone(),
+                network_client.clone(),
+                dag_state.clone(),
+            )
+        });
+
         let block_verifier = Arc::new(SignedBlockVerifier::new(
             context.clone(),
             transaction_verifier,
@@ -399,6 +417,8 @@ where
             subscriber,
             network_manager,
             sync_last_known_own_block,
+            #[cfg(msim)]
+            byzantine_flooder,
         }
     }
 
@@ -423,6 +443,10 @@ where
             round_prober_handle.stop().await;
         }
         self.proposed_block_handler.abort();
+        #[cfg(msim)]
+        if let Some(byzantine_flooder) = self.byzantine_flooder.take() {
+            byzantine_flooder.abort();
+        }
         self.leader_timeout_handle.stop().await;
         // Shutdown Core to stop block productions and broadcast.
         // When using streaming, all subscribers to broadcasted blocks stop after this.
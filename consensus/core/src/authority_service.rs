@@ -354,9 +354,15 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
         );
 
         // Return a stream of blocks that first yields missed blocks as requested, then new blocks.
-        Ok(Box::pin(missed_blocks.chain(
-            broadcasted_blocks.map(ExtendedSerializedBlock::from),
-        )))
+        let blocks: BlockStream =
+            Box::pin(missed_blocks.chain(broadcasted_blocks.map(ExtendedSerializedBlock::from)));
+
+        #[cfg(msim)]
+        if let Some(adversary) = self.context.adversary.clone() {
+            return Ok(adversary.tamper_block_stream(self.context.clone(), peer, blocks));
+        }
+
+        Ok(blocks)
     }
 
     async fn handle_fetch_blocks(
@@ -414,6 +420,15 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
             }
         }
 
+        #[cfg(msim)]
+        let (blocks, ancestor_blocks) = match &self.context.adversary {
+            Some(adversary) => (
+                adversary.filter_fetched_blocks(&self.context, peer, blocks),
+                adversary.filter_fetched_blocks(&self.context, peer, ancestor_blocks),
+            ),
+            None => (blocks, ancestor_blocks),
+        };
+
         // Return the serialised blocks & the ancestor blocks
         let result = blocks
             .into_iter()
@@ -512,6 +527,15 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
             }
         }
 
+        #[cfg(msim)]
+        if let Some(adversary) = &self.context.adversary {
+            blocks = adversary
+                .filter_fetched_blocks(&self.context, peer, blocks.into_iter().map(Some).collect())
+                .into_iter()
+                .flatten()
+                .collect();
+        }
+
         // Return the serialised blocks
         let result = blocks
             .into_iter()
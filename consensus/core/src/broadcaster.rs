@@ -138,6 +138,20 @@ impl Broadcaster {
                             continue;
                         }
                     };
+                    #[cfg(msim)]
+                    let block = match &context.adversary {
+                        Some(adversary) => {
+                            let mut blocks = adversary.blocks_for_peer(&context, peer, &block);
+                            let Some(block) = blocks.pop() else {
+                                continue;
+                            };
+                            for tampered in blocks {
+                                requests.push(send_block(network_client.clone(), peer, rtt_estimate, tampered));
+                            }
+                            block
+                        }
+                        None => block,
+                    };
                     requests.push(send_block(network_client.clone(), peer, rtt_estimate, block.clone()));
                     if last_block.is_none() || last_block.as_ref().unwrap().round() < block.round() {
                         last_block = Some(block);
@@ -0,0 +1,480 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Byzantine behavior injection for consensus simtests.
+//!
+//! An authority registered with a [`ByzantineConfig`] keeps running the honest protocol locally,
+//! but deviates from it in what it sends to its peers: it can equivocate, withhold its own blocks,
+//! send blocks with invalid ancestors or timestamps, delay its commit votes and flood peers with
+//! `fetch_blocks` requests. Honest authorities are expected to stay safe and live against any
+//! combination of these behaviors, as long as the byzantine stake stays below the validity
+//! threshold.
+//!
+//! Authorities are looked up by their network address when they start, so simtests can register
+//! a configuration before starting a node and the node picks it up on every (re)start.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use consensus_config::{AuthorityIndex, ProtocolKeyPair};
+use futures::{stream, StreamExt as _};
+use mysten_network::Multiaddr;
+use parking_lot::{const_mutex, Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+use crate::{
+    block::{
+        Block, BlockAPI as _, BlockRef, BlockV1, BlockV2, SignedBlock, VerifiedBlock, GENESIS_ROUND,
+    },
+    context::Context,
+    dag_state::DagState,
+    network::{BlockStream, ExtendedSerializedBlock, NetworkClient},
+    BlockTimestampMs, Round,
+};
+
+/// A single deviation from the honest protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ByzantineBehavior {
+    /// Signs `extra_blocks` conflicting variants of every own block. Peers are split into
+    /// `extra_blocks + 1` groups, and each group only receives one of the variants.
+    Equivocate { extra_blocks: usize },
+    /// Never sends own blocks to `peers`, neither via broadcast nor in fetch responses.
+    WithholdBlocks { peers: BTreeSet<AuthorityIndex> },
+    /// Sends peers a copy of every own block whose last ancestor references the block's own
+    /// round, ahead of the valid block.
+    InvalidAncestors,
+    /// Sends peers a copy of every own block with a timestamp older than its ancestors, ahead of
+    /// the valid block.
+    InvalidTimestamps,
+    /// Only includes commit votes in own blocks every `rounds` rounds.
+    DelayCommitVotes { rounds: Round },
+    /// Sends `requests_per_peer` concurrent `fetch_blocks` requests to every peer each
+    /// `interval`, asking for as many recent blocks as allowed.
+    FloodFetchBlocks {
+        requests_per_peer: usize,
+        interval: Duration,
+    },
+}
+
+/// The set of byzantine behaviors an authority exhibits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ByzantineConfig {
+    behaviors: Vec<ByzantineBehavior>,
+}
+
+impl ByzantineConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_behavior(mut self, behavior: ByzantineBehavior) -> Self {
+        self.behaviors.push(behavior);
+        self
+    }
+
+    pub fn behaviors(&self) -> &[ByzantineBehavior] {
+        &self.behaviors
+    }
+
+    pub fn is_honest(&self) -> bool {
+        self.behaviors.is_empty()
+    }
+}
+
+static BYZANTINE_AUTHORITIES: Mutex<BTreeMap<Multiaddr, ByzantineConfig>> =
+    const_mutex(BTreeMap::new());
+
+/// Makes the authority listening on `address` byzantine from its next start onwards.
+pub fn register_byzantine_authority(address: &Multiaddr, config: ByzantineConfig) {
+    BYZANTINE_AUTHORITIES.lock().insert(address.clone(), config);
+}
+
+/// Makes the authority listening on `address` honest from its next start onwards.
+pub fn unregister_byzantine_authority(address: &Multiaddr) {
+    BYZANTINE_AUTHORITIES.lock().remove(address);
+}
+
+/// Runtime state of a byzantine authority, shared via `Context`.
+pub(crate) struct Adversary {
+    config: ByzantineConfig,
+    // Own protocol key pair, used to sign tampered blocks.
+    protocol_keypair: ProtocolKeyPair,
+}
+
+impl Adversary {
+    /// Returns the adversary registered for the authority of `context`, if any.
+    pub(crate) fn from_registry(
+        context: &Context,
+        protocol_keypair: &ProtocolKeyPair,
+    ) -> Option<Arc<Self>> {
+        let address = &context.committee.authority(context.own_index).address;
+        let config = BYZANTINE_AUTHORITIES.lock().get(address).cloned()?;
+        if config.is_honest() {
+            return None;
+        }
+        info!(
+            "Authority {} is running with byzantine behaviors: {:?}",
+            context.own_index,
+            config.behaviors()
+        );
+        Some(Arc::new(Self {
+            config,
+            protocol_keypair: protocol_keypair.clone(),
+        }))
+    }
+
+    /// Whether own blocks should never be sent to `peer`.
+    pub(crate) fn withholds_blocks_from(&self, peer: AuthorityIndex) -> bool {
+        self.config.behaviors.iter().any(|behavior| {
+            matches!(behavior, ByzantineBehavior::WithholdBlocks { peers } if peers.contains(&peer))
+        })
+    }
+
+    /// Returns the blocks to send to `peer` in place of the own `block`, in sending order.
+    pub(crate) fn blocks_for_peer(
+        &self,
+        context: &Context,
+        peer: AuthorityIndex,
+        block: &VerifiedBlock,
+    ) -> Vec<VerifiedBlock> {
+        if block.author() != context.own_index || block.round() == GENESIS_ROUND {
+            return vec![block.clone()];
+        }
+        if self.withholds_blocks_from(peer) {
+            return vec![];
+        }
+
+        let mut blocks = vec![];
+        let mut valid_block = block.clone();
+        for behavior in &self.config.behaviors {
+            match behavior {
+                ByzantineBehavior::Equivocate { extra_blocks } => {
+                    // Variant 0 is the block accepted locally, the others only differ in their
+                    // timestamps so they stay valid.
+                    let variant = peer.value() % (extra_blocks + 1);
+                    if variant > 0 {
+                        valid_block = self.sign(rebuild_block(
+                            block,
+                            block.timestamp_ms() + variant as BlockTimestampMs,
+                            block.ancestors().to_vec(),
+                        ));
+                    }
+                }
+                ByzantineBehavior::InvalidAncestors => {
+                    let mut ancestors = block.ancestors().to_vec();
+                    if let Some(last) = ancestors.last_mut() {
+                        last.round = block.round();
+                    }
+                    blocks.push(self.sign(rebuild_block(block, block.timestamp_ms(), ancestors)));
+                }
+                ByzantineBehavior::InvalidTimestamps => {
+                    blocks.push(self.sign(rebuild_block(block, 0, block.ancestors().to_vec())));
+                }
+                _ => {}
+            }
+        }
+        blocks.push(valid_block);
+        blocks
+    }
+
+    /// Applies `blocks_for_peer()` to a stream of serialized own blocks sent to `peer`.
+    pub(crate) fn tamper_block_stream(
+        self: Arc<Self>,
+        context: Arc<Context>,
+        peer: AuthorityIndex,
+        blocks: BlockStream,
+    ) -> BlockStream {
+        Box::pin(blocks.flat_map(move |serialized| {
+            let tampered = match bcs::from_bytes::<SignedBlock>(&serialized.block) {
+                Ok(signed_block) => {
+                    let block = VerifiedBlock::new_verified(signed_block, serialized.block);
+                    self.blocks_for_peer(&context, peer, &block)
+                        .into_iter()
+                        .map(|block| ExtendedSerializedBlock {
+                            block: block.serialized().clone(),
+                            excluded_ancestors: serialized.excluded_ancestors.clone(),
+                        })
+                        .collect()
+                }
+                Err(e) => {
+                    debug!("Failed to deserialize own block for tampering: {e:?}");
+                    vec![]
+                }
+            };
+            stream::iter(tampered)
+        }))
+    }
+
+    /// Drops own blocks from a fetch response to `peer`, if they are withheld from it.
+    pub(crate) fn filter_fetched_blocks(
+        &self,
+        context: &Context,
+        peer: AuthorityIndex,
+        blocks: Vec<Option<VerifiedBlock>>,
+    ) -> Vec<Option<VerifiedBlock>> {
+        if !self.withholds_blocks_from(peer) {
+            return blocks;
+        }
+        blocks
+            .into_iter()
+            .map(|block| block.filter(|b| b.author() != context.own_index))
+            .collect()
+    }
+
+    /// Returns the maximum number of commit votes to include in the own block of `round`.
+    pub(crate) fn commit_votes_limit(&self, round: Round, limit: usize) -> usize {
+        for behavior in &self.config.behaviors {
+            if let ByzantineBehavior::DelayCommitVotes { rounds } = behavior {
+                if *rounds > 0 && round % rounds != 0 {
+                    return 0;
+                }
+            }
+        }
+        limit
+    }
+
+    /// Starts flooding peers with `fetch_blocks` requests, if configured to.
+    pub(crate) fn start_fetch_flooder<C: NetworkClient>(
+        &self,
+        context: Arc<Context>,
+        network_client: Arc<C>,
+        dag_state: Arc<RwLock<DagState>>,
+    ) -> Option<JoinHandle<()>> {
+        let (requests_per_peer, interval) =
+            self.config
+                .behaviors
+                .iter()
+                .find_map(|behavior| match behavior {
+                    ByzantineBehavior::FloodFetchBlocks {
+                        requests_per_peer,
+                        interval,
+                    } => Some((*requests_per_peer, *interval)),
+                    _ => None,
+                })?;
+
+        Some(tokio::spawn(async move {
+            const FETCH_TIMEOUT: Duration = Duration::from_secs(2);
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+
+                let block_refs = recent_block_refs(&context, &dag_state);
+                if block_refs.is_empty() {
+                    continue;
+                }
+                // Claiming to have accepted nothing maximizes the ancestors served by peers.
+                let highest_accepted_rounds = vec![GENESIS_ROUND; context.committee.size()];
+
+                let mut requests = vec![];
+                for (peer, _) in context.committee.authorities() {
+                    if peer == context.own_index {
+                        continue;
+                    }
+                    for _ in 0..requests_per_peer {
+                        let network_client = network_client.clone();
+                        let block_refs = block_refs.clone();
+                        let highest_accepted_rounds = highest_accepted_rounds.clone();
+                        requests.push(async move {
+                            network_client
+                                .fetch_blocks(
+                                    peer,
+                                    block_refs,
+                                    highest_accepted_rounds,
+                                    FETCH_TIMEOUT,
+                                )
+                                .await
+                                .map(|blocks: Vec<Bytes>| blocks.len())
+                        });
+                    }
+                }
+                let results = futures::future::join_all(requests).await;
+                debug!(
+                    "Flooded peers with {} fetch_blocks requests, {} failed",
+                    results.len(),
+                    results.iter().filter(|r| r.is_err()).count()
+                );
+            }
+        }))
+    }
+
+    fn sign(&self, block: Block) -> VerifiedBlock {
+        let signed_block =
+            SignedBlock::new(block, &self.protocol_keypair).expect("Block signing failed.");
+        let serialized = signed_block
+            .serialize()
+            .expect("Block serialization failed.");
+        VerifiedBlock::new_verified(signed_block, serialized)
+    }
+}
+
+/// Returns up to `max_blocks_per_fetch` references of recent blocks from all authorities.
+fn recent_block_refs(context: &Context, dag_state: &RwLock<DagState>) -> Vec<BlockRef> {
+    let dag_state = dag_state.read();
+    let start_round = dag_state.highest_accepted_round().saturating_sub(2).max(1);
+    context
+        .committee
+        .authorities()
+        .flat_map(|(authority, _)| dag_state.get_cached_blocks(authority, start_round))
+        .map(|block| block.reference())
+        .take(context.parameters.max_blocks_per_fetch)
+        .collect()
+}
+
+/// Rebuilds `block` with the given timestamp and ancestors, keeping all other fields.
+fn rebuild_block(
+    block: &VerifiedBlock,
+    timestamp_ms: BlockTimestampMs,
+    ancestors: Vec<BlockRef>,
+) -> Block {
+    let inner: &Block = block;
+    match inner {
+        Block::V1(_) => Block::V1(BlockV1::new(
+            block.epoch(),
+            block.round(),
+            block.author(),
+            timestamp_ms,
+            ancestors,
+            block.transactions().to_vec(),
+            block.commit_votes().to_vec(),
+            block.misbehavior_reports().to_vec(),
+        )),
+        Block::V2(_) => Block::V2(BlockV2::new(
+            block.epoch(),
+            block.round(),
+            block.author(),
+            timestamp_ms,
+            ancestors,
+            block.transactions().to_vec(),
+            block.commit_votes().to_vec(),
+            block.transaction_votes().to_vec(),
+            block.misbehavior_reports().to_vec(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        block::TestBlock,
+        block_verifier::{BlockVerifier as _, SignedBlockVerifier},
+        error::ConsensusError,
+        transaction::NoopTransactionVerifier,
+    };
+
+    fn signed_block(
+        context: &Context,
+        keypair: &ProtocolKeyPair,
+        timestamp_ms: BlockTimestampMs,
+    ) -> VerifiedBlock {
+        let ancestors = context
+            .committee
+            .authorities()
+            .map(|(authority, _)| BlockRef::new(1, authority, Default::default()))
+            .collect::<Vec<_>>();
+        let block = TestBlock::new(2, context.own_index.value() as u32)
+            .set_timestamp_ms(timestamp_ms)
+            .set_ancestors(ancestors)
+            .build();
+        let signed_block = SignedBlock::new(block, keypair).unwrap();
+        let serialized = signed_block.serialize().unwrap();
+        VerifiedBlock::new_verified(signed_block, serialized)
+    }
+
+    fn adversary(keypair: &ProtocolKeyPair, config: ByzantineConfig) -> Adversary {
+        Adversary {
+            config,
+            protocol_keypair: keypair.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_equivocating_blocks_are_valid() {
+        let (context, keypairs) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let keypair = &keypairs[context.own_index].1;
+        let adversary = adversary(
+            keypair,
+            ByzantineConfig::new().with_behavior(ByzantineBehavior::Equivocate { extra_blocks: 1 }),
+        );
+        let verifier =
+            SignedBlockVerifier::new(context.clone(), Arc::new(NoopTransactionVerifier {}));
+        let block = signed_block(&context, keypair, 1_000);
+
+        let to_1 = adversary.blocks_for_peer(&context, AuthorityIndex::new_for_test(1), &block);
+        let to_2 = adversary.blocks_for_peer(&context, AuthorityIndex::new_for_test(2), &block);
+        assert_eq!(to_1.len(), 1);
+        assert_eq!(to_2.len(), 1);
+        assert_eq!(to_2[0].reference(), block.reference());
+        assert_ne!(to_1[0].reference(), block.reference());
+        assert_eq!(to_1[0].slot(), block.slot());
+
+        let equivocation: SignedBlock = bcs::from_bytes(to_1[0].serialized()).unwrap();
+        verifier.verify_and_vote(&equivocation).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_blocks_are_rejected() {
+        let (context, keypairs) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let keypair = &keypairs[context.own_index].1;
+        let adversary = adversary(
+            keypair,
+            ByzantineConfig::new()
+                .with_behavior(ByzantineBehavior::InvalidAncestors)
+                .with_behavior(ByzantineBehavior::InvalidTimestamps),
+        );
+        let verifier =
+            SignedBlockVerifier::new(context.clone(), Arc::new(NoopTransactionVerifier {}));
+        let block = signed_block(&context, keypair, 1_000);
+
+        let blocks = adversary.blocks_for_peer(&context, AuthorityIndex::new_for_test(1), &block);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[2].reference(), block.reference());
+
+        let invalid_ancestors: SignedBlock = bcs::from_bytes(blocks[0].serialized()).unwrap();
+        assert!(matches!(
+            verifier.verify_and_vote(&invalid_ancestors),
+            Err(ConsensusError::InvalidAncestorRound { .. })
+        ));
+        assert_eq!(blocks[1].timestamp_ms(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_withhold_and_delay_commit_votes() {
+        let (context, keypairs) = Context::new_for_test(4);
+        let keypair = &keypairs[context.own_index].1;
+        let withheld = AuthorityIndex::new_for_test(3);
+        let adversary = adversary(
+            keypair,
+            ByzantineConfig::new()
+                .with_behavior(ByzantineBehavior::WithholdBlocks {
+                    peers: BTreeSet::from([withheld]),
+                })
+                .with_behavior(ByzantineBehavior::DelayCommitVotes { rounds: 5 }),
+        );
+        let block = signed_block(&context, keypair, 1_000);
+
+        assert!(adversary
+            .blocks_for_peer(&context, withheld, &block)
+            .is_empty());
+        assert_eq!(
+            adversary
+                .blocks_for_peer(&context, AuthorityIndex::new_for_test(1), &block)
+                .len(),
+            1
+        );
+        assert_eq!(
+            adversary.filter_fetched_blocks(&context, withheld, vec![Some(block.clone())]),
+            vec![None]
+        );
+
+        assert_eq!(adversary.commit_votes_limit(4, 100), 0);
+        assert_eq!(adversary.commit_votes_limit(5, 100), 100);
+    }
+}
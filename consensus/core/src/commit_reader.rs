@@ -0,0 +1,121 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{ops::RangeInclusive, sync::Arc};
+
+use crate::{
+    commit::{load_committed_subdag_from_store, CommitAPI as _, CommitIndex, CommittedSubDag},
+    storage::{rocksdb_store::RocksDBStore, Store},
+};
+
+/// Reads committed sub-dags back from a consensus store, outside of a running authority.
+///
+/// This is meant for offline tooling, e.g. replaying consensus output through the post-consensus
+/// processing of a validator to investigate a divergence. The store should not be opened by a
+/// running authority at the same time.
+pub struct CommitReader {
+    store: Arc<dyn Store>,
+}
+
+impl CommitReader {
+    /// Opens the consensus store of an epoch, located at `path`.
+    pub fn open(path: &str) -> Self {
+        Self::new(Arc::new(RocksDBStore::new(path)))
+    }
+
+    pub(crate) fn new(store: Arc<dyn Store>) -> Self {
+        Self { store }
+    }
+
+    /// Returns the index of the last commit in the store, if any.
+    pub fn last_commit_index(&self) -> Option<CommitIndex> {
+        self.store
+            .read_last_commit()
+            .expect("Reading the last commit should not fail")
+            .map(|commit| commit.index())
+    }
+
+    /// Reads the committed sub-dags for the commits in `range`, in commit order. Commits missing
+    /// from the store are skipped.
+    ///
+    /// Reputation scores are not stored along with commits, so the returned sub-dags do not carry
+    /// any.
+    pub fn read_committed_sub_dags(
+        &self,
+        range: RangeInclusive<CommitIndex>,
+    ) -> Vec<CommittedSubDag> {
+        self.store
+            .scan_commits(range.into())
+            .expect("Scanning commits should not fail")
+            .into_iter()
+            .map(|commit| load_committed_subdag_from_store(self.store.as_ref(), commit, vec![]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        block::BlockAPI as _,
+        context::Context,
+        storage::{mem_store::MemStore, WriteBatch},
+        test_dag_builder::DagBuilder,
+    };
+
+    #[tokio::test]
+    async fn test_read_committed_sub_dags() {
+        let (context, _keys) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let reader = CommitReader::new(store.clone());
+        assert_eq!(reader.last_commit_index(), None);
+
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=10).build();
+        let (sub_dags, commits): (Vec<_>, Vec<_>) = dag_builder
+            .get_sub_dag_and_commits(1..=8)
+            .into_iter()
+            .unzip();
+        store
+            .write(
+                WriteBatch::default()
+                    .blocks(dag_builder.all_blocks())
+                    .commits(commits),
+            )
+            .unwrap();
+
+        assert_eq!(
+            reader.last_commit_index(),
+            Some(sub_dags.len() as CommitIndex)
+        );
+
+        let read_sub_dags = reader.read_committed_sub_dags(2..=5);
+        assert_eq!(read_sub_dags.len(), 4);
+        for (read, expected) in read_sub_dags.iter().zip(&sub_dags[1..5]) {
+            assert_eq!(read.commit_ref, expected.commit_ref);
+            assert_eq!(read.leader, expected.leader);
+            assert_eq!(read.timestamp_ms, expected.timestamp_ms);
+            assert_eq!(
+                read.blocks
+                    .iter()
+                    .map(|b| b.reference())
+                    .collect::<Vec<_>>(),
+                expected
+                    .blocks
+                    .iter()
+                    .map(|b| b.reference())
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        // Reading past the last commit returns what is available.
+        let last = sub_dags.len() as CommitIndex;
+        let read_sub_dags = reader.read_committed_sub_dags(last..=last + 10);
+        assert_eq!(read_sub_dags.len(), 1);
+        assert_eq!(
+            read_sub_dags[0].commit_ref,
+            sub_dags.last().unwrap().commit_ref
+        );
+    }
+}
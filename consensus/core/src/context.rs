@@ -11,6 +11,8 @@ use sui_protocol_config::ProtocolConfig;
 use tempfile::TempDir;
 use tokio::time::Instant;
 
+#[cfg(msim)]
+use crate::byzantine::Adversary;
 #[cfg(test)]
 use crate::metrics::test_metrics;
 use crate::{block::BlockTimestampMs, metrics::Metrics};
@@ -31,6 +33,9 @@ pub(crate) struct Context {
     pub metrics: Arc<Metrics>,
     /// Access to local clock
     pub clock: Arc<Clock>,
+    /// Byzantine behaviors injected into this authority in simtests.
+    #[cfg(msim)]
+    pub adversary: Option<Arc<Adversary>>,
 }
 
 impl Context {
@@ -49,6 +54,8 @@ impl Context {
             protocol_config,
             metrics,
             clock,
+            #[cfg(msim)]
+            adversary: None,
         }
     }
 
@@ -638,10 +638,17 @@ impl Core {
             .observe(transactions.len() as f64);
 
         // Consume the commit votes to be included.
-        let commit_votes = self
-            .dag_state
-            .write()
-            .take_commit_votes(MAX_COMMIT_VOTES_PER_BLOCK);
+        #[cfg(not(msim))]
+        let commit_votes_limit = MAX_COMMIT_VOTES_PER_BLOCK;
+        #[cfg(msim)]
+        let commit_votes_limit = self
+            .context
+            .adversary
+            .as_ref()
+            .map_or(MAX_COMMIT_VOTES_PER_BLOCK, |adversary| {
+                adversary.commit_votes_limit(clock_round, MAX_COMMIT_VOTES_PER_BLOCK)
+            });
+        let commit_votes = self.dag_state.write().take_commit_votes(commit_votes_limit);
 
         // Create the block and insert to storage.
         let block = Block::V1(BlockV1::new(
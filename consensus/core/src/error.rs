@@ -190,6 +190,9 @@ pub(crate) enum ConsensusError {
     #[error("Request timeout: {0:?}")]
     NetworkRequestTimeout(String),
 
+    #[error("Malformed compressed payload: {0}")]
+    MalformedCompressedPayload(String),
+
     #[error("Consensus has shut down!")]
     Shutdown,
 }
@@ -10,6 +10,7 @@ use std::{
 use consensus_config::{AuthorityIndex, Stake};
 use parking_lot::RwLock;
 use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+use sui_protocol_config::ConsensusLeaderScoringStrategy;
 
 use crate::{
     commit::CommitRange, context::Context, dag_state::DagState, leader_scoring::ReputationScores,
@@ -24,6 +25,9 @@ pub(crate) struct LeaderSchedule {
     pub leader_swap_table: Arc<RwLock<LeaderSwapTable>>,
     context: Arc<Context>,
     num_commits_per_schedule: u64,
+    // One cycle of the stake-weighted round robin schedule, only set when the
+    // `StakeWeightedRoundRobin` strategy is selected.
+    round_robin_schedule: Option<Arc<Vec<AuthorityIndex>>>,
 }
 
 impl LeaderSchedule {
@@ -36,10 +40,14 @@ impl LeaderSchedule {
     const CONSENSUS_COMMITS_PER_SCHEDULE: u64 = 10;
 
     pub(crate) fn new(context: Arc<Context>, leader_swap_table: LeaderSwapTable) -> Self {
+        let round_robin_schedule = (context.protocol_config.consensus_leader_scoring_strategy()
+            == ConsensusLeaderScoringStrategy::StakeWeightedRoundRobin)
+            .then(|| Arc::new(stake_weighted_round_robin(&context)));
         Self {
             context,
             num_commits_per_schedule: Self::CONSENSUS_COMMITS_PER_SCHEDULE,
             leader_swap_table: Arc::new(RwLock::new(leader_swap_table)),
+            round_robin_schedule,
         }
     }
 
@@ -137,6 +145,11 @@ impl LeaderSchedule {
     }
 
     pub(crate) fn elect_leader(&self, round: u32, leader_offset: u32) -> AuthorityIndex {
+        // The round robin schedule is fixed for the epoch, reputation scores do not swap leaders.
+        if let Some(schedule) = &self.round_robin_schedule {
+            return Self::elect_leader_round_robin(schedule, round, leader_offset);
+        }
+
         cfg_if::cfg_if! {
             // TODO: we need to differentiate the leader strategy in tests, so for
             // some type of testing (ex sim tests) we can use the staked approach.
@@ -181,6 +194,27 @@ impl LeaderSchedule {
         leader_index
     }
 
+    /// Elects the leader at `offset` for `round`, walking the round robin schedule from the
+    /// position of the round and skipping authorities elected at lower offsets.
+    fn elect_leader_round_robin(
+        schedule: &[AuthorityIndex],
+        round: u32,
+        offset: u32,
+    ) -> AuthorityIndex {
+        let start = round as usize % schedule.len();
+        let mut elected = Vec::with_capacity(offset as usize + 1);
+        for leader in schedule[start..].iter().chain(&schedule[..start]) {
+            if elected.contains(leader) {
+                continue;
+            }
+            if elected.len() == offset as usize {
+                return *leader;
+            }
+            elected.push(*leader);
+        }
+        panic!("Leader offset {offset} exceeds the number of authorities in the schedule");
+    }
+
     /// Atomically updates the `LeaderSwapTable` with the new provided one. Any
     /// leader queried from now on will get calculated according to this swap
     /// table until a new one is provided again.
@@ -208,6 +242,52 @@ impl LeaderSchedule {
     }
 }
 
+/// Builds one cycle of a smooth weighted round robin over the stake of the authorities. After
+/// dividing all stakes by their greatest common divisor, each authority appears as many times as
+/// its stake, and its appearances are spread as evenly as possible across the cycle.
+fn stake_weighted_round_robin(context: &Context) -> Vec<AuthorityIndex> {
+    fn gcd(a: Stake, b: Stake) -> Stake {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    let authorities = context
+        .committee
+        .authorities()
+        .map(|(index, authority)| (index, authority.stake))
+        .filter(|(_, stake)| *stake > 0)
+        .collect::<Vec<_>>();
+    let divisor = authorities
+        .iter()
+        .fold(0, |divisor, (_, stake)| gcd(divisor, *stake));
+    let weights = authorities
+        .iter()
+        .map(|(_, stake)| (stake / divisor) as i64)
+        .collect::<Vec<_>>();
+    let total_weight: i64 = weights.iter().sum();
+
+    let mut current_weights = vec![0_i64; weights.len()];
+    (0..total_weight)
+        .map(|_| {
+            for (current, weight) in current_weights.iter_mut().zip(&weights) {
+                *current += weight;
+            }
+            // Ties are broken in favor of the lowest authority index.
+            let (selected, _) = current_weights
+                .iter()
+                .enumerate()
+                .rev()
+                .max_by_key(|(_, current)| **current)
+                .unwrap();
+            current_weights[selected] -= total_weight;
+            authorities[selected].0
+        })
+        .collect()
+}
+
 #[derive(Default, Clone)]
 pub(crate) struct LeaderSwapTable {
     /// The list of `f` (by configurable stake) authorities with best scores as
@@ -434,6 +514,8 @@ impl Debug for LeaderSwapTable {
 #[cfg(test)]
 mod tests {
 
+    use consensus_config::local_committee_and_keys;
+
     use super::*;
     use crate::{
         block::{BlockDigest, BlockRef, BlockTimestampMs, TestBlock, VerifiedBlock},
@@ -466,6 +548,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_elect_leader_stake_weighted_round_robin() {
+        let (committee, _) = local_committee_and_keys(0, vec![1, 2, 3, 4]);
+        let mut context = Context::new_for_test(4).0.with_committee(committee);
+        context
+            .protocol_config
+            .set_consensus_leader_scoring_strategy_for_testing(
+                ConsensusLeaderScoringStrategy::StakeWeightedRoundRobin,
+            );
+        let context = Arc::new(context);
+        let leader_schedule = LeaderSchedule::new(context.clone(), LeaderSwapTable::default());
+
+        // Every authority is elected as many times as its stake in each cycle of 10 rounds,
+        // and its rounds are spread across the cycle.
+        let leaders = (0..10)
+            .map(|round| leader_schedule.elect_leader(round, 0).value())
+            .collect::<Vec<_>>();
+        assert_eq!(leaders, vec![3, 2, 1, 3, 0, 2, 3, 1, 2, 3]);
+        assert_eq!(
+            leader_schedule.elect_leader(12, 0),
+            AuthorityIndex::new_for_test(1)
+        );
+
+        // Leaders at higher offsets are the next distinct authorities of the schedule.
+        let leaders = (0..4)
+            .map(|offset| leader_schedule.elect_leader(4, offset).value())
+            .collect::<Vec<_>>();
+        assert_eq!(leaders, vec![0, 2, 3, 1]);
+
+        // Reputation scores do not change the round robin schedule.
+        let swap_table = LeaderSwapTable::new_inner(
+            context.clone(),
+            33,
+            10,
+            ReputationScores::new((1..=10).into(), vec![1, 10, 10, 10]),
+        );
+        assert!(swap_table
+            .bad_nodes
+            .contains_key(&AuthorityIndex::new_for_test(0)));
+        leader_schedule.update_leader_swap_table(swap_table);
+        assert_eq!(
+            leader_schedule.elect_leader(4, 0),
+            AuthorityIndex::new_for_test(0)
+        );
+    }
+
     #[tokio::test]
     async fn test_elect_leader_stake_based() {
         let context = Arc::new(Context::new_for_test(4).0);
@@ -2,15 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap},
     sync::Arc,
 };
 
 use consensus_config::AuthorityIndex;
 use serde::{Deserialize, Serialize};
+use sui_protocol_config::ConsensusLeaderScoringStrategy;
 
 use crate::{
-    block::{BlockAPI, BlockRef},
+    block::{BlockAPI, BlockRef, BlockTimestampMs},
     commit::{CommitRange, CommittedSubDag},
     context::Context,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
@@ -72,6 +73,10 @@ impl ReputationScores {
     }
 }
 
+/// Delay between a leader and a vote for it, at which the vote is worth half of its stake with the
+/// latency weighted scoring strategy.
+const VOTE_DELAY_HALF_WEIGHT_MS: u64 = 250;
+
 /// ScoringSubdag represents the scoring votes in a collection of subdags across
 /// multiple commits.
 /// These subdags are "scoring" for the purposes of leader schedule change. As
@@ -81,13 +86,16 @@ impl ReputationScores {
 pub(crate) struct ScoringSubdag {
     pub(crate) context: Arc<Context>,
     pub(crate) commit_range: Option<CommitRange>,
-    // Only includes committed leaders for now.
+    // Only includes committed leaders for now, along with their timestamps.
     // TODO: Include skipped leaders as well
-    pub(crate) leaders: HashSet<BlockRef>,
+    pub(crate) leaders: HashMap<BlockRef, BlockTimestampMs>,
     // A map of votes to the stake of strongly linked blocks that include that vote
     // Note: Including stake aggregator so that we can quickly check if it exceeds
     // quourum threshold and only include those scores for certain scoring strategies.
     pub(crate) votes: BTreeMap<BlockRef, StakeAggregator<QuorumThreshold>>,
+    // How long after the leader each vote was proposed, used by the latency weighted
+    // scoring strategy.
+    pub(crate) vote_delays_ms: BTreeMap<BlockRef, u64>,
 }
 
 impl ScoringSubdag {
@@ -95,8 +103,9 @@ impl ScoringSubdag {
         Self {
             context,
             commit_range: None,
-            leaders: HashSet::new(),
+            leaders: HashMap::new(),
             votes: BTreeMap::new(),
+            vote_delays_ms: BTreeMap::new(),
         }
     }
 
@@ -122,7 +131,12 @@ impl ScoringSubdag {
 
             // Add the committed leader to the list of leaders we will be scoring.
             tracing::trace!("Adding new committed leader {} for scoring", subdag.leader);
-            self.leaders.insert(subdag.leader);
+            let leader_timestamp_ms = subdag
+                .blocks
+                .iter()
+                .find(|block| block.reference() == subdag.leader)
+                .map_or(subdag.timestamp_ms, |block| block.timestamp_ms());
+            self.leaders.insert(subdag.leader, leader_timestamp_ms);
 
             // Check each block in subdag. Blocks are in order so we should traverse the
             // oldest blocks first
@@ -136,7 +150,7 @@ impl ScoringSubdag {
 
                     // If a blocks strong linked ancestor is in leaders, then
                     // it's a vote for leader.
-                    if self.leaders.contains(ancestor) {
+                    if let Some(leader_timestamp_ms) = self.leaders.get(ancestor) {
                         // There should never be duplicate references to blocks
                         // with strong linked ancestors to leader.
                         tracing::trace!(
@@ -148,6 +162,10 @@ impl ScoringSubdag {
                             .votes
                             .insert(block.reference(), StakeAggregator::new())
                             .is_none(), "Vote {block} already exists. Duplicate vote found for leader {ancestor}");
+                        self.vote_delays_ms.insert(
+                            block.reference(),
+                            block.timestamp_ms().saturating_sub(*leader_timestamp_ms),
+                        );
                     }
 
                     if let Some(stake) = self.votes.get_mut(ancestor) {
@@ -165,10 +183,24 @@ impl ScoringSubdag {
     }
 
     // Iterate through votes and calculate scores for each authority based on
-    // distributed vote scoring strategy.
-    pub(crate) fn calculate_distributed_vote_scores(&self) -> ReputationScores {
-        let scores_per_authority = self.distributed_votes_scores();
+    // the scoring strategy selected in the protocol config.
+    pub(crate) fn calculate_scores(&self) -> ReputationScores {
+        let scores_per_authority = match self
+            .context
+            .protocol_config
+            .consensus_leader_scoring_strategy()
+        {
+            ConsensusLeaderScoringStrategy::DistributedVotes
+            | ConsensusLeaderScoringStrategy::StakeWeightedRoundRobin => {
+                self.distributed_votes_scores()
+            }
+            ConsensusLeaderScoringStrategy::CertifiedVotes => self.certified_votes_scores(),
+            ConsensusLeaderScoringStrategy::LatencyWeighted => self.latency_weighted_votes_scores(),
+        };
+        self.reputation_scores(scores_per_authority)
+    }
 
+    fn reputation_scores(&self, scores_per_authority: Vec<u64>) -> ReputationScores {
         // TODO: Normalize scores
         ReputationScores::new(
             self.commit_range
@@ -206,6 +238,52 @@ impl ScoringSubdag {
         scores_per_authority
     }
 
+    /// This scoring strategy only rewards votes that are certified, i.e. included by
+    /// blocks with a quorum of stake. Each certified vote is worth one point
+    /// regardless of how much stake included it.
+    fn certified_votes_scores(&self) -> Vec<u64> {
+        let _s = self
+            .context
+            .metrics
+            .node_metrics
+            .scope_processing_time
+            .with_label_values(&["ScoringSubdag::score_certified_votes"])
+            .start_timer();
+
+        let mut scores_per_authority = vec![0_u64; self.context.committee.size()];
+        for (vote, stake_agg) in self.votes.iter() {
+            if stake_agg.reached_threshold(&self.context.committee) {
+                scores_per_authority[vote.author.value()] += 1;
+            }
+        }
+        scores_per_authority
+    }
+
+    /// This scoring strategy weighs distributed votes by how promptly they were
+    /// proposed after the leader, so that authorities voting late score less even
+    /// if their votes end up widely included. A vote proposed
+    /// `VOTE_DELAY_HALF_WEIGHT_MS` after its leader is worth half of its stake.
+    /// Scores are accumulated in thousandths of stake, so that late votes still
+    /// count with small stakes.
+    fn latency_weighted_votes_scores(&self) -> Vec<u64> {
+        let _s = self
+            .context
+            .metrics
+            .node_metrics
+            .scope_processing_time
+            .with_label_values(&["ScoringSubdag::score_latency_weighted_votes"])
+            .start_timer();
+
+        let mut scores_per_authority = vec![0_u64; self.context.committee.size()];
+        for (vote, stake_agg) in self.votes.iter() {
+            let delay_ms = self.vote_delays_ms.get(vote).copied().unwrap_or_default();
+            scores_per_authority[vote.author.value()] +=
+                stake_agg.stake() * 1000 * VOTE_DELAY_HALF_WEIGHT_MS
+                    / (VOTE_DELAY_HALF_WEIGHT_MS + delay_ms);
+        }
+        scores_per_authority
+    }
+
     pub(crate) fn scored_subdags_count(&self) -> usize {
         if let Some(commit_range) = &self.commit_range {
             commit_range.size()
@@ -221,6 +299,7 @@ impl ScoringSubdag {
     pub(crate) fn clear(&mut self) {
         self.leaders.clear();
         self.votes.clear();
+        self.vote_delays_ms.clear();
         self.commit_range = None;
     }
 }
@@ -285,7 +364,13 @@ mod tests {
     #[tokio::test]
     async fn test_scoring_subdag() {
         telemetry_subscribers::init_for_testing();
-        let context = Arc::new(Context::new_for_test(4).0);
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_consensus_leader_scoring_strategy_for_testing(
+                ConsensusLeaderScoringStrategy::DistributedVotes,
+            );
+        let context = Arc::new(context);
 
         // Populate fully connected test blocks for round 0 ~ 3, authorities 0 ~ 3.
         let mut dag_builder = DagBuilder::new(context.clone());
@@ -307,8 +392,74 @@ mod tests {
             scoring_subdag.add_subdags(vec![sub_dag]);
         }
 
-        let scores = scoring_subdag.calculate_distributed_vote_scores();
+        let scores = scoring_subdag.calculate_scores();
         assert_eq!(scores.scores_per_authority, vec![5, 5, 5, 5]);
         assert_eq!(scores.commit_range, (1..=4).into());
     }
+
+    #[tokio::test]
+    async fn test_scoring_subdag_strategies() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+
+        // Populate fully connected test blocks for round 1 ~ 3, with authority 3
+        // proposing its blocks for round 2 & 3 two seconds late.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layer(1).build();
+        for round in 2..=3 {
+            dag_builder
+                .layer(round)
+                .authorities(vec![AuthorityIndex::new_for_test(3)])
+                .with_timestamps(vec![round as BlockTimestampMs * 1000 + 2000])
+                .build();
+        }
+        // Build round 4 but with just the leader block
+        dag_builder
+            .layer(4)
+            .authorities(vec![
+                AuthorityIndex::new_for_test(1),
+                AuthorityIndex::new_for_test(2),
+                AuthorityIndex::new_for_test(3),
+            ])
+            .skip_block()
+            .build();
+
+        let scores_with_strategy = |strategy| {
+            let mut context = Context::new_for_test(4).0;
+            context
+                .protocol_config
+                .set_consensus_leader_scoring_strategy_for_testing(strategy);
+            let mut scoring_subdag = ScoringSubdag::new(Arc::new(context));
+            for (sub_dag, _commit) in dag_builder.get_sub_dag_and_commits(1..=4) {
+                scoring_subdag.add_subdags(vec![sub_dag]);
+            }
+            scoring_subdag.calculate_scores().scores_per_authority
+        };
+
+        // Late votes are still included by the other authorities, so they are
+        // scored like any other vote.
+        assert_eq!(
+            scores_with_strategy(ConsensusLeaderScoringStrategy::DistributedVotes),
+            vec![5, 5, 5, 5]
+        );
+        assert_eq!(
+            scores_with_strategy(ConsensusLeaderScoringStrategy::StakeWeightedRoundRobin),
+            vec![5, 5, 5, 5]
+        );
+
+        // Only the votes for round 1 leader are included by a quorum.
+        assert_eq!(
+            scores_with_strategy(ConsensusLeaderScoringStrategy::CertifiedVotes),
+            vec![1, 1, 1, 1]
+        );
+
+        // Authority 3 votes late, so it scores less than the others.
+        let scores = scores_with_strategy(ConsensusLeaderScoringStrategy::LatencyWeighted);
+        for authority in 0..3 {
+            assert!(
+                scores[3] < scores[authority],
+                "Late authority should score less: {scores:?}"
+            );
+        }
+    }
 }
@@ -9,9 +9,12 @@ mod block;
 mod block_manager;
 mod block_verifier;
 mod broadcaster;
+#[cfg(msim)]
+pub mod byzantine;
 mod commit;
 mod commit_consumer;
 mod commit_observer;
+mod commit_reader;
 mod commit_syncer;
 mod commit_vote_monitor;
 mod context;
@@ -41,6 +44,9 @@ pub mod transaction;
 
 mod universal_committer;
 
+#[cfg(test)]
+#[path = "tests/leader_schedule_simulation_tests.rs"]
+mod leader_schedule_simulation_tests;
 #[cfg(test)]
 #[path = "tests/randomized_tests.rs"]
 mod randomized_tests;
@@ -64,6 +70,7 @@ pub use block::{
 pub use block::{BlockTimestampMs, TestBlock, Transaction, VerifiedBlock};
 pub use commit::{CommitDigest, CommitIndex, CommitRef, CommittedSubDag};
 pub use commit_consumer::{CommitConsumer, CommitConsumerMonitor};
+pub use commit_reader::CommitReader;
 pub use context::Clock;
 pub use network::{
     connection_monitor::{AnemoConnectionMonitor, ConnectionMonitorHandle, ConnectionStatus},
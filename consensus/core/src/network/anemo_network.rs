@@ -32,6 +32,7 @@ use super::{
         consensus_rpc_client::ConsensusRpcClient,
         consensus_rpc_server::{ConsensusRpc, ConsensusRpcServer},
     },
+    compression::{PayloadCompressor, PayloadEncoding, ACCEPT_ENCODING_HEADER, ENCODING_HEADER},
     connection_monitor::{AnemoConnectionMonitor, ConnectionMonitorHandle},
     epoch_filter::{AllowedEpoch, EPOCH_HEADER_KEY},
     metrics_layer::{MetricsCallbackMaker, MetricsResponseCallback, SizedRequest, SizedResponse},
@@ -49,18 +50,37 @@ use crate::{
 pub(crate) struct AnemoClient {
     context: Arc<Context>,
     network: Arc<ArcSwapOption<anemo::Network>>,
+    compressor: Arc<PayloadCompressor>,
 }
 
 impl AnemoClient {
     const GET_CLIENT_INTERVAL: Duration = Duration::from_millis(10);
 
-    pub(crate) fn new(context: Arc<Context>) -> Self {
+    pub(crate) fn new(context: Arc<Context>, compressor: Arc<PayloadCompressor>) -> Self {
         Self {
             context,
             network: Arc::new(ArcSwapOption::default()),
+            compressor,
         }
     }
 
+    // Creates a request advertising the payload encodings accepted by this authority.
+    fn request_with_accept_encoding<T>(&self, body: T, timeout: Duration) -> anemo::Request<T> {
+        let mut request = anemo::Request::new(body).with_timeout(timeout);
+        if let Some(value) = self.compressor.accept_encoding() {
+            request
+                .headers_mut()
+                .insert(ACCEPT_ENCODING_HEADER.to_string(), value);
+        }
+        request
+    }
+
+    // Returns the encoding of payloads chosen by the peer.
+    fn response_encoding<T>(&self, response: &Response<T>) -> ConsensusResult<PayloadEncoding> {
+        self.compressor
+            .response_encoding(response.headers().get(ENCODING_HEADER).map(String::as_str))
+    }
+
     pub(crate) fn set_network(&self, network: anemo::Network) {
         self.network.store(Some(Arc::new(network)));
     }
@@ -178,7 +198,7 @@ impl NetworkClient for AnemoClient {
             highest_accepted_rounds,
         };
         let response = client
-            .fetch_blocks(anemo::Request::new(request).with_timeout(timeout))
+            .fetch_blocks(self.request_with_accept_encoding(request, timeout))
             .await
             .map_err(|e: Status| {
                 if e.status() == StatusCode::RequestTimeout {
@@ -187,8 +207,10 @@ impl NetworkClient for AnemoClient {
                     ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}"))
                 }
             })?;
+        let encoding = self.response_encoding(&response)?;
         let body = response.into_body();
-        Ok(body.blocks)
+        self.compressor
+            .decompress_batch("fetch_blocks", &encoding, body.blocks)
     }
 
     async fn fetch_commits(
@@ -203,11 +225,20 @@ impl NetworkClient for AnemoClient {
             end: commit_range.end(),
         };
         let response = client
-            .fetch_commits(anemo::Request::new(request).with_timeout(timeout))
+            .fetch_commits(self.request_with_accept_encoding(request, timeout))
             .await
             .map_err(|e| ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}")))?;
+        let encoding = self.response_encoding(&response)?;
         let response = response.into_body();
-        Ok((response.commits, response.certifier_blocks))
+        let commits =
+            self.compressor
+                .decompress_batch("fetch_commits", &encoding, response.commits)?;
+        let certifier_blocks = self.compressor.decompress_batch(
+            "fetch_commits",
+            &encoding,
+            response.certifier_blocks,
+        )?;
+        Ok((commits, certifier_blocks))
     }
 
     async fn fetch_latest_blocks(
@@ -219,7 +250,7 @@ impl NetworkClient for AnemoClient {
         let mut client = self.get_client(peer, timeout).await?;
         let request = FetchLatestBlocksRequest { authorities };
         let response = client
-            .fetch_latest_blocks(anemo::Request::new(request).with_timeout(timeout))
+            .fetch_latest_blocks(self.request_with_accept_encoding(request, timeout))
             .await
             .map_err(|e: Status| {
                 if e.status() == StatusCode::RequestTimeout {
@@ -230,8 +261,10 @@ impl NetworkClient for AnemoClient {
                     ConsensusError::NetworkRequest(format!("fetch_latest_blocks failed: {e:?}"))
                 }
             })?;
+        let encoding = self.response_encoding(&response)?;
         let body = response.into_body();
-        Ok(body.blocks)
+        self.compressor
+            .decompress_batch("fetch_latest_blocks", &encoding, body.blocks)
     }
 
     async fn get_latest_rounds(
@@ -262,10 +295,11 @@ impl NetworkClient for AnemoClient {
 struct AnemoServiceProxy<S: NetworkService> {
     peer_map: BTreeMap<PeerId, AuthorityIndex>,
     service: Arc<S>,
+    compressor: Arc<PayloadCompressor>,
 }
 
 impl<S: NetworkService> AnemoServiceProxy<S> {
-    fn new(context: Arc<Context>, service: Arc<S>) -> Self {
+    fn new(context: Arc<Context>, service: Arc<S>, compressor: Arc<PayloadCompressor>) -> Self {
         let peer_map = context
             .committee
             .authorities()
@@ -274,10 +308,52 @@ impl<S: NetworkService> AnemoServiceProxy<S> {
                 (peer_id, index)
             })
             .collect();
-        Self { peer_map, service }
+        Self {
+            peer_map,
+            service,
+            compressor,
+        }
+    }
+
+    // Chooses the encoding of response payloads among the ones accepted by the peer.
+    fn negotiate_encoding<T>(&self, request: &anemo::Request<T>) -> PayloadEncoding {
+        self.compressor.negotiate(
+            request
+                .headers()
+                .get(ACCEPT_ENCODING_HEADER)
+                .map(String::as_str),
+        )
+    }
+
+    fn compress_batch(
+        &self,
+        route: &str,
+        encoding: &PayloadEncoding,
+        payloads: Vec<Bytes>,
+    ) -> Result<Vec<Bytes>, anemo::rpc::Status> {
+        self.compressor
+            .compress_batch(route, encoding, payloads)
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::InternalServerError,
+                    format!("{e}"),
+                )
+            })
     }
 }
 
+// Creates a response reporting the encoding of its payloads to the peer. Uncompressed responses
+// carry no header, same as responses from authorities without payload compression.
+fn response_with_encoding<T>(body: T, encoding: &PayloadEncoding) -> Response<T> {
+    let mut response = Response::new(body);
+    if *encoding != PayloadEncoding::Identity {
+        response
+            .headers_mut()
+            .insert(ENCODING_HEADER.to_string(), encoding.header_value());
+    }
+    response
+}
+
 #[async_trait]
 impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
     async fn send_block(
@@ -329,6 +405,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
+        let encoding = self.negotiate_encoding(&request);
         let body = request.into_body();
         let block_refs = body
             .block_refs
@@ -354,7 +431,11 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                     format!("{e}"),
                 )
             })?;
-        Ok(Response::new(FetchBlocksResponse { blocks }))
+        let blocks = self.compress_batch("fetch_blocks", &encoding, blocks)?;
+        Ok(response_with_encoding(
+            FetchBlocksResponse { blocks },
+            &encoding,
+        ))
     }
 
     async fn fetch_commits(
@@ -373,6 +454,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
+        let encoding = self.negotiate_encoding(&request);
         let request = request.into_body();
         let (commits, certifier_blocks) = self
             .service
@@ -392,10 +474,15 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
             .into_iter()
             .map(|b| b.serialized().clone())
             .collect();
-        Ok(Response::new(FetchCommitsResponse {
-            commits,
-            certifier_blocks,
-        }))
+        let commits = self.compress_batch("fetch_commits", &encoding, commits)?;
+        let certifier_blocks = self.compress_batch("fetch_commits", &encoding, certifier_blocks)?;
+        Ok(response_with_encoding(
+            FetchCommitsResponse {
+                commits,
+                certifier_blocks,
+            },
+            &encoding,
+        ))
     }
 
     async fn fetch_latest_blocks(
@@ -414,6 +501,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
+        let encoding = self.negotiate_encoding(&request);
         let body = request.into_body();
         let blocks = self
             .service
@@ -425,7 +513,11 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                     format!("{e}"),
                 )
             })?;
-        Ok(Response::new(FetchLatestBlocksResponse { blocks }))
+        let blocks = self.compress_batch("fetch_latest_blocks", &encoding, blocks)?;
+        Ok(response_with_encoding(
+            FetchLatestBlocksResponse { blocks },
+            &encoding,
+        ))
     }
 
     async fn get_latest_rounds(
@@ -471,16 +563,19 @@ pub(crate) struct AnemoManager {
     context: Arc<Context>,
     network_keypair: Option<NetworkKeyPair>,
     client: Arc<AnemoClient>,
+    compressor: Arc<PayloadCompressor>,
     network: Arc<ArcSwapOption<anemo::Network>>,
     connection_monitor_handle: Option<ConnectionMonitorHandle>,
 }
 
 impl AnemoManager {
     pub(crate) fn new(context: Arc<Context>, network_keypair: NetworkKeyPair) -> Self {
+        let compressor = Arc::new(PayloadCompressor::new(context.clone()));
         Self {
             context: context.clone(),
             network_keypair: Some(network_keypair),
-            client: Arc::new(AnemoClient::new(context)),
+            client: Arc::new(AnemoClient::new(context, compressor.clone())),
+            compressor,
             network: Arc::new(ArcSwapOption::default()),
             connection_monitor_handle: None,
         }
@@ -508,7 +603,11 @@ impl<S: NetworkService> NetworkManager<S> for AnemoManager {
 
         debug!("Starting anemo service");
 
-        let server = ConsensusRpcServer::new(AnemoServiceProxy::new(self.context.clone(), service));
+        let server = ConsensusRpcServer::new(AnemoServiceProxy::new(
+            self.context.clone(),
+            service,
+            self.compressor.clone(),
+        ));
         let authority = self.context.committee.authority(self.context.own_index);
         // By default, bind to the unspecified address to allow the actual address to be assigned.
         // But bind to localhost if it is requested.
@@ -0,0 +1,510 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Negotiated compression of block and commit payloads returned by peers.
+//!
+//! A client with compression enabled lists the payload encodings it accepts in the
+//! `consensus-accept-encoding` request header, most preferred first. A server with compression
+//! enabled picks the first of them it also supports, compresses the payloads of its response with
+//! it, and reports the choice in the `consensus-encoding` response header. Authorities that do not
+//! support or enable compression neither send nor honor these headers, so mixed committees keep
+//! exchanging uncompressed payloads.
+//!
+//! This is independent from transport level compression of tonic. Payloads are compressed
+//! before chunking, and batches of payloads are compressed together so redundancy across blocks
+//! is exploited. A shared zstd dictionary trained on block payloads improves the ratio of
+//! individually streamed blocks further.
+
+use std::{
+    fmt::Write as _,
+    io::{BufReader, Read as _},
+    sync::Arc,
+};
+
+use bytes::{Buf as _, BufMut as _, Bytes, BytesMut};
+use consensus_config::DefaultHashFunction;
+use fastcrypto::hash::HashFunction as _;
+use tracing::{info, warn};
+
+use crate::{
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+};
+
+/// Request header listing the payload encodings accepted by the client.
+pub(crate) const ACCEPT_ENCODING_HEADER: &str = "consensus-accept-encoding";
+
+/// Response header with the payload encoding chosen by the server.
+pub(crate) const ENCODING_HEADER: &str = "consensus-encoding";
+
+/// Maximum total size of decompressed payloads in a single message, to bound the memory a
+/// malicious peer can make this authority allocate.
+const MAX_DECOMPRESSED_BYTES: usize = 128 << 20;
+
+/// Number of bytes of the dictionary digest used to identify the dictionary.
+const DICTIONARY_ID_LENGTH: usize = 8;
+
+/// Encoding of payloads in a response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PayloadEncoding {
+    /// Payloads are not compressed.
+    Identity,
+    /// Payloads are compressed with zstd, without a dictionary.
+    Zstd,
+    /// Payloads are compressed with zstd and the shared dictionary with the given id.
+    ZstdDictionary(String),
+}
+
+impl PayloadEncoding {
+    const ZSTD: &'static str = "zstd";
+    const ZSTD_DICTIONARY_PREFIX: &'static str = "zstd-dict-";
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "identity" => Some(PayloadEncoding::Identity),
+            Self::ZSTD => Some(PayloadEncoding::Zstd),
+            value => value
+                .strip_prefix(Self::ZSTD_DICTIONARY_PREFIX)
+                .map(|id| PayloadEncoding::ZstdDictionary(id.to_string())),
+        }
+    }
+
+    pub(crate) fn header_value(&self) -> String {
+        match self {
+            PayloadEncoding::Identity => "identity".to_string(),
+            PayloadEncoding::Zstd => Self::ZSTD.to_string(),
+            PayloadEncoding::ZstdDictionary(id) => format!("{}{id}", Self::ZSTD_DICTIONARY_PREFIX),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            PayloadEncoding::Identity => "identity",
+            PayloadEncoding::Zstd => "zstd",
+            PayloadEncoding::ZstdDictionary(_) => "zstd_dictionary",
+        }
+    }
+}
+
+struct Dictionary {
+    id: String,
+    data: Vec<u8>,
+}
+
+/// Compresses and decompresses payloads exchanged with peers, with the encodings supported by
+/// this authority.
+pub(crate) struct PayloadCompressor {
+    context: Arc<Context>,
+    enabled: bool,
+    level: i32,
+    dictionary: Option<Dictionary>,
+}
+
+impl PayloadCompressor {
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        let parameters = &context.parameters.compression;
+        let dictionary = if parameters.enabled {
+            parameters
+                .dictionary_path
+                .as_ref()
+                .and_then(|path| match std::fs::read(path) {
+                    Ok(data) => {
+                        let dictionary = Dictionary {
+                            id: dictionary_id(&data),
+                            data,
+                        };
+                        info!(
+                            "Loaded compression dictionary {} from {}",
+                            dictionary.id,
+                            path.display()
+                        );
+                        Some(dictionary)
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Failed to read compression dictionary from {}, compressing without it: {e:?}",
+                            path.display()
+                        );
+                        None
+                    }
+                })
+        } else {
+            None
+        };
+        Self {
+            enabled: parameters.enabled,
+            level: parameters.level,
+            dictionary,
+            context,
+        }
+    }
+
+    /// Returns the value of the accept encoding header for outgoing requests, or None when
+    /// compression is disabled.
+    pub(crate) fn accept_encoding(&self) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+        let mut encodings = vec![];
+        if let Some(dictionary) = &self.dictionary {
+            encodings.push(PayloadEncoding::ZstdDictionary(dictionary.id.clone()).header_value());
+        }
+        encodings.push(PayloadEncoding::Zstd.header_value());
+        Some(encodings.join(","))
+    }
+
+    /// Chooses the encoding of a response, given the accept encoding header of the request.
+    pub(crate) fn negotiate(&self, accept_encoding: Option<&str>) -> PayloadEncoding {
+        if !self.enabled {
+            return PayloadEncoding::Identity;
+        }
+        let Some(accept_encoding) = accept_encoding else {
+            return PayloadEncoding::Identity;
+        };
+        accept_encoding
+            .split(',')
+            .filter_map(PayloadEncoding::parse)
+            .find(|encoding| self.supports(encoding))
+            .unwrap_or(PayloadEncoding::Identity)
+    }
+
+    /// Parses the encoding header of a response. A missing header means the peer did not
+    /// compress the payloads.
+    pub(crate) fn response_encoding(
+        &self,
+        encoding: Option<&str>,
+    ) -> ConsensusResult<PayloadEncoding> {
+        let Some(value) = encoding else {
+            return Ok(PayloadEncoding::Identity);
+        };
+        match PayloadEncoding::parse(value) {
+            Some(encoding) if self.supports(&encoding) => Ok(encoding),
+            _ => Err(ConsensusError::MalformedCompressedPayload(format!(
+                "unsupported payload encoding {value}"
+            ))),
+        }
+    }
+
+    fn supports(&self, encoding: &PayloadEncoding) -> bool {
+        match encoding {
+            PayloadEncoding::Identity => true,
+            PayloadEncoding::Zstd => self.enabled,
+            PayloadEncoding::ZstdDictionary(id) => self
+                .dictionary
+                .as_ref()
+                .is_some_and(|dictionary| &dictionary.id == id),
+        }
+    }
+
+    /// Compresses a single payload, e.g. a streamed block.
+    pub(crate) fn compress(
+        &self,
+        route: &str,
+        encoding: &PayloadEncoding,
+        payload: Bytes,
+    ) -> ConsensusResult<Bytes> {
+        if *encoding == PayloadEncoding::Identity {
+            return Ok(payload);
+        }
+        let compressed = self.zstd_compress(encoding, &payload)?;
+        self.report(route, "sent", encoding, payload.len(), compressed.len());
+        Ok(compressed)
+    }
+
+    /// Decompresses a single payload compressed with `compress()`.
+    pub(crate) fn decompress(
+        &self,
+        route: &str,
+        encoding: &PayloadEncoding,
+        payload: Bytes,
+    ) -> ConsensusResult<Bytes> {
+        if *encoding == PayloadEncoding::Identity {
+            return Ok(payload);
+        }
+        let decompressed = self.zstd_decompress(encoding, &payload)?;
+        self.report(
+            route,
+            "received",
+            encoding,
+            decompressed.len(),
+            payload.len(),
+        );
+        Ok(decompressed)
+    }
+
+    /// Compresses a batch of payloads together. The result is empty if the batch is empty, and
+    /// contains a single compressed payload otherwise.
+    pub(crate) fn compress_batch(
+        &self,
+        route: &str,
+        encoding: &PayloadEncoding,
+        payloads: Vec<Bytes>,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        if *encoding == PayloadEncoding::Identity || payloads.is_empty() {
+            return Ok(payloads);
+        }
+        let total_size: usize = payloads.iter().map(|p| p.len() + 4).sum();
+        let mut framed = BytesMut::with_capacity(total_size);
+        for payload in &payloads {
+            framed.put_u32_le(payload.len() as u32);
+            framed.put_slice(payload);
+        }
+        let compressed = self.zstd_compress(encoding, &framed)?;
+        self.report(route, "sent", encoding, framed.len(), compressed.len());
+        Ok(vec![compressed])
+    }
+
+    /// Decompresses payloads compressed with `compress_batch()`.
+    pub(crate) fn decompress_batch(
+        &self,
+        route: &str,
+        encoding: &PayloadEncoding,
+        payloads: Vec<Bytes>,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        if *encoding == PayloadEncoding::Identity || payloads.is_empty() {
+            return Ok(payloads);
+        }
+        let mut result = vec![];
+        for payload in payloads {
+            let mut framed = self.zstd_decompress(encoding, &payload)?;
+            self.report(route, "received", encoding, framed.len(), payload.len());
+            while framed.has_remaining() {
+                if framed.remaining() < 4 {
+                    return Err(ConsensusError::MalformedCompressedPayload(
+                        "truncated payload length".to_string(),
+                    ));
+                }
+                let len = framed.get_u32_le() as usize;
+                if framed.remaining() < len {
+                    return Err(ConsensusError::MalformedCompressedPayload(format!(
+                        "payload length {len} exceeds remaining {} bytes",
+                        framed.remaining()
+                    )));
+                }
+                result.push(framed.split_to(len));
+            }
+        }
+        Ok(result)
+    }
+
+    fn zstd_compress(&self, encoding: &PayloadEncoding, data: &[u8]) -> ConsensusResult<Bytes> {
+        let mut compressor = match encoding {
+            PayloadEncoding::ZstdDictionary(_) => {
+                zstd::bulk::Compressor::with_dictionary(self.level, self.dictionary_data(encoding)?)
+            }
+            _ => zstd::bulk::Compressor::new(self.level),
+        }
+        .map_err(|e| ConsensusError::MalformedCompressedPayload(format!("{e:?}")))?;
+        let compressed = compressor
+            .compress(data)
+            .map_err(|e| ConsensusError::MalformedCompressedPayload(format!("{e:?}")))?;
+        Ok(compressed.into())
+    }
+
+    fn zstd_decompress(&self, encoding: &PayloadEncoding, data: &[u8]) -> ConsensusResult<Bytes> {
+        let reader = BufReader::new(data);
+        let decoder = match encoding {
+            PayloadEncoding::ZstdDictionary(_) => zstd::stream::read::Decoder::with_dictionary(
+                reader,
+                self.dictionary_data(encoding)?,
+            ),
+            _ => zstd::stream::read::Decoder::with_buffer(reader),
+        }
+        .map_err(|e| ConsensusError::MalformedCompressedPayload(format!("{e:?}")))?;
+        let mut decompressed = vec![];
+        decoder
+            .take(MAX_DECOMPRESSED_BYTES as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|e| ConsensusError::MalformedCompressedPayload(format!("{e:?}")))?;
+        if decompressed.len() > MAX_DECOMPRESSED_BYTES {
+            return Err(ConsensusError::MalformedCompressedPayload(format!(
+                "decompressed size exceeds limit {MAX_DECOMPRESSED_BYTES}"
+            )));
+        }
+        Ok(decompressed.into())
+    }
+
+    fn dictionary_data(&self, encoding: &PayloadEncoding) -> ConsensusResult<&[u8]> {
+        match &self.dictionary {
+            Some(dictionary) if self.supports(encoding) => Ok(&dictionary.data),
+            _ => Err(ConsensusError::MalformedCompressedPayload(format!(
+                "unknown dictionary for encoding {}",
+                encoding.header_value()
+            ))),
+        }
+    }
+
+    fn report(
+        &self,
+        route: &str,
+        direction: &str,
+        encoding: &PayloadEncoding,
+        uncompressed_size: usize,
+        compressed_size: usize,
+    ) {
+        let metrics = &self.context.metrics.network_metrics;
+        let labels = &[route, direction, encoding.label()];
+        metrics
+            .payload_uncompressed_bytes
+            .with_label_values(labels)
+            .inc_by(uncompressed_size as u64);
+        metrics
+            .payload_compressed_bytes
+            .with_label_values(labels)
+            .inc_by(compressed_size as u64);
+        if compressed_size > 0 {
+            metrics
+                .payload_compression_ratio
+                .with_label_values(labels)
+                .observe(uncompressed_size as f64 / compressed_size as f64);
+        }
+    }
+}
+
+/// Identifies a dictionary by a prefix of its digest, so peers only use dictionaries with
+/// identical contents.
+fn dictionary_id(data: &[u8]) -> String {
+    let digest = DefaultHashFunction::digest(data).digest;
+    digest[..DICTIONARY_ID_LENGTH]
+        .iter()
+        .fold(String::new(), |mut id, b| {
+            let _ = write!(id, "{b:02x}");
+            id
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use consensus_config::CompressionParameters;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::context::Context;
+
+    fn compressor(enabled: bool, dictionary: Option<&[u8]>, dir: &TempDir) -> PayloadCompressor {
+        let (mut context, _) = Context::new_for_test(4);
+        let dictionary_path = dictionary.map(|data| {
+            let path = dir
+                .path()
+                .join(format!("dictionary-{}", dictionary_id(data)));
+            std::fs::write(&path, data).unwrap();
+            path
+        });
+        context.parameters.compression = CompressionParameters {
+            enabled,
+            level: 3,
+            dictionary_path,
+        };
+        PayloadCompressor::new(Arc::new(context))
+    }
+
+    fn payloads() -> Vec<Bytes> {
+        (0..10u8)
+            .map(|i| Bytes::from(vec![i; 100 + i as usize]))
+            .chain(std::iter::once(Bytes::new()))
+            .collect()
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let dir = TempDir::new().unwrap();
+        let disabled = compressor(false, None, &dir);
+        let plain = compressor(true, None, &dir);
+        let with_dictionary = compressor(true, Some(b"dictionary one".as_slice()), &dir);
+        let other_dictionary = compressor(true, Some(b"dictionary two".as_slice()), &dir);
+
+        assert_eq!(disabled.accept_encoding(), None);
+        assert_eq!(plain.accept_encoding().unwrap(), "zstd");
+
+        // Peers that do not support compression get uncompressed payloads.
+        assert_eq!(plain.negotiate(None), PayloadEncoding::Identity);
+        assert_eq!(
+            disabled.negotiate(plain.accept_encoding().as_deref()),
+            PayloadEncoding::Identity
+        );
+        // Unknown encodings are skipped.
+        assert_eq!(plain.negotiate(Some("brotli,zstd")), PayloadEncoding::Zstd);
+        assert_eq!(plain.negotiate(Some("brotli")), PayloadEncoding::Identity);
+
+        // The dictionary is only used when both peers have the same one.
+        let encoding = with_dictionary.negotiate(with_dictionary.accept_encoding().as_deref());
+        assert!(matches!(encoding, PayloadEncoding::ZstdDictionary(_)));
+        assert_eq!(
+            other_dictionary.negotiate(with_dictionary.accept_encoding().as_deref()),
+            PayloadEncoding::Zstd
+        );
+        assert_eq!(
+            with_dictionary.negotiate(plain.accept_encoding().as_deref()),
+            PayloadEncoding::Zstd
+        );
+
+        // Responses with encodings the client did not advertise are rejected.
+        assert!(other_dictionary
+            .response_encoding(Some(&encoding.header_value()))
+            .is_err());
+        assert_eq!(
+            disabled.response_encoding(None).unwrap(),
+            PayloadEncoding::Identity
+        );
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let dictionary = vec![7u8; 256];
+        let compressor = compressor(true, Some(dictionary.as_slice()), &dir);
+        let encodings = [
+            PayloadEncoding::Identity,
+            PayloadEncoding::Zstd,
+            PayloadEncoding::ZstdDictionary(dictionary_id(&dictionary)),
+        ];
+
+        for encoding in &encodings {
+            let compressed = compressor
+                .compress_batch("test", encoding, payloads())
+                .unwrap();
+            if *encoding != PayloadEncoding::Identity {
+                assert_eq!(compressed.len(), 1);
+            }
+            let decompressed = compressor
+                .decompress_batch("test", encoding, compressed)
+                .unwrap();
+            assert_eq!(decompressed, payloads());
+
+            for payload in payloads() {
+                let compressed = compressor
+                    .compress("test", encoding, payload.clone())
+                    .unwrap();
+                let decompressed = compressor.decompress("test", encoding, compressed).unwrap();
+                assert_eq!(decompressed, payload);
+            }
+        }
+
+        assert!(compressor
+            .compress_batch("test", &PayloadEncoding::Zstd, vec![])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_decompress_malformed() {
+        let dir = TempDir::new().unwrap();
+        let compressor = compressor(true, None, &dir);
+        assert!(compressor
+            .decompress(
+                "test",
+                &PayloadEncoding::Zstd,
+                Bytes::from_static(b"garbage")
+            )
+            .is_err());
+
+        // A compressed frame with a truncated length prefix.
+        let compressed = zstd::bulk::compress(&[1, 0], 3).unwrap();
+        assert!(compressor
+            .decompress_batch("test", &PayloadEncoding::Zstd, vec![compressed.into()])
+            .is_err());
+    }
+}
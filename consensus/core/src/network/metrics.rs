@@ -11,6 +11,10 @@ use prometheus::{
 };
 use tracing::warn;
 
+const COMPRESSION_RATIO_BUCKETS: &[f64] = &[
+    1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0, 4.0, 5.0, 7.5, 10.0, 20.0,
+];
+
 // Fields for network-agnostic metrics can be added here
 pub(crate) struct NetworkMetrics {
     pub(crate) network_type: IntGaugeVec,
@@ -19,6 +23,12 @@ pub(crate) struct NetworkMetrics {
     #[cfg_attr(msim, allow(dead_code))]
     pub(crate) tcp_connection_metrics: Arc<TcpConnectionMetrics>,
     pub(crate) quinn_connection_metrics: Arc<QuinnConnectionMetrics>,
+    /// Size of compressed block and commit payloads before compression.
+    pub(crate) payload_uncompressed_bytes: IntCounterVec,
+    /// Size of compressed block and commit payloads after compression.
+    pub(crate) payload_compressed_bytes: IntCounterVec,
+    /// Ratio of uncompressed to compressed size of each compressed message.
+    pub(crate) payload_compression_ratio: HistogramVec,
 }
 
 impl NetworkMetrics {
@@ -35,6 +45,28 @@ impl NetworkMetrics {
             outbound: Arc::new(NetworkRouteMetrics::new("", "outbound", registry)),
             tcp_connection_metrics: Arc::new(TcpConnectionMetrics::new(registry)),
             quinn_connection_metrics: Arc::new(QuinnConnectionMetrics::new("", registry)),
+            payload_uncompressed_bytes: register_int_counter_vec_with_registry!(
+                "payload_uncompressed_bytes",
+                "Size of compressed block and commit payloads before compression",
+                &["route", "direction", "encoding"],
+                registry
+            )
+            .unwrap(),
+            payload_compressed_bytes: register_int_counter_vec_with_registry!(
+                "payload_compressed_bytes",
+                "Size of compressed block and commit payloads after compression",
+                &["route", "direction", "encoding"],
+                registry
+            )
+            .unwrap(),
+            payload_compression_ratio: register_histogram_vec_with_registry!(
+                "payload_compression_ratio",
+                "Ratio of uncompressed to compressed size of each compressed message",
+                &["route", "direction", "encoding"],
+                COMPRESSION_RATIO_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
         }
     }
 }
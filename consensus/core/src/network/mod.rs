@@ -44,6 +44,7 @@ mod tonic_gen {
 pub mod connection_monitor;
 
 pub(crate) mod anemo_network;
+pub(crate) mod compression;
 pub(crate) mod epoch_filter;
 pub(crate) mod metrics;
 mod metrics_layer;
@@ -4,7 +4,7 @@
 use std::{sync::Arc, time::Duration};
 
 use bytes::Bytes;
-use consensus_config::NetworkKeyPair;
+use consensus_config::{CompressionParameters, NetworkKeyPair, Parameters};
 use futures::StreamExt as _;
 use parking_lot::Mutex;
 use rstest::rstest;
@@ -238,3 +238,68 @@ async fn subscribe_and_receive_blocks(
         .unwrap();
     assert!(receive_stream_1.next().await.is_none());
 }
+
+#[rstest]
+#[tokio::test]
+async fn subscribe_blocks_with_negotiated_compression(
+    #[values(TonicManagerBuilder {})] manager_builder: impl ManagerBuilder,
+    #[values(false, true)] compression_0: bool,
+    #[values(false, true)] compression_1: bool,
+) {
+    let (context, keys) = Context::new_for_test(4);
+    let parameters = |enabled| Parameters {
+        compression: CompressionParameters {
+            enabled,
+            ..Default::default()
+        },
+        ..context.parameters.clone()
+    };
+
+    let context_0 = Arc::new(
+        context
+            .clone()
+            .with_authority_index(context.committee.to_authority_index(0).unwrap())
+            .with_parameters(parameters(compression_0)),
+    );
+    let mut manager_0 = manager_builder.build(context_0.clone(), keys[0].0.clone());
+    let client_0 = manager_0.client();
+    let service_0 = service_with_own_blocks();
+    manager_0.install_service(service_0.clone()).await;
+
+    let context_1 = Arc::new(
+        context
+            .clone()
+            .with_authority_index(context.committee.to_authority_index(1).unwrap())
+            .with_parameters(parameters(compression_1)),
+    );
+    let mut manager_1 = manager_builder.build(context_1.clone(), keys[1].0.clone());
+    let service_1 = service_with_own_blocks();
+    manager_1.install_service(service_1.clone()).await;
+
+    // Blocks are received intact regardless of which side has compression enabled.
+    let client_0_round = 50;
+    let receive_stream_0 = client_0
+        .subscribe_blocks(
+            context_0.committee.to_authority_index(1).unwrap(),
+            client_0_round,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    let received = receive_stream_0.collect::<Vec<_>>().await;
+    assert_eq!(
+        received,
+        (client_0_round + 1..=100)
+            .map(block_for_round)
+            .collect::<Vec<_>>()
+    );
+
+    // Payloads are only compressed when both sides have compression enabled.
+    let compressed_bytes = context_1
+        .metrics
+        .network_metrics
+        .payload_compressed_bytes
+        .with_label_values(&["subscribe_blocks", "sent", "zstd"])
+        .get();
+    assert_eq!(compressed_bytes > 0, compression_0 && compression_1);
+}
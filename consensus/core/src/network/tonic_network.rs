@@ -22,11 +22,12 @@ use parking_lot::RwLock;
 use sui_http::ServerHandle;
 use sui_tls::AllowPublicKeys;
 use tokio_stream::{iter, Iter};
-use tonic::{codec::CompressionEncoding, Request, Response, Streaming};
+use tonic::{codec::CompressionEncoding, metadata::MetadataMap, Request, Response, Streaming};
 use tower_http::trace::{DefaultMakeSpan, DefaultOnFailure, TraceLayer};
 use tracing::{debug, error, info, trace, warn};
 
 use super::{
+    compression::{PayloadCompressor, PayloadEncoding, ACCEPT_ENCODING_HEADER, ENCODING_HEADER},
     metrics_layer::{MetricsCallbackMaker, MetricsResponseCallback, SizedRequest, SizedResponse},
     tonic_gen::{
         consensus_service_client::ConsensusServiceClient,
@@ -58,17 +59,44 @@ pub(crate) struct TonicClient {
     context: Arc<Context>,
     network_keypair: NetworkKeyPair,
     channel_pool: Arc<ChannelPool>,
+    compressor: Arc<PayloadCompressor>,
 }
 
 impl TonicClient {
-    pub(crate) fn new(context: Arc<Context>, network_keypair: NetworkKeyPair) -> Self {
+    pub(crate) fn new(
+        context: Arc<Context>,
+        network_keypair: NetworkKeyPair,
+        compressor: Arc<PayloadCompressor>,
+    ) -> Self {
         Self {
             context: context.clone(),
             network_keypair,
             channel_pool: Arc::new(ChannelPool::new(context)),
+            compressor,
+        }
+    }
+
+    // Advertises the payload encodings accepted by this authority to the peer.
+    fn set_accept_encoding<T>(&self, request: &mut Request<T>) {
+        if let Some(value) = self.compressor.accept_encoding() {
+            match value.parse() {
+                Ok(value) => {
+                    request.metadata_mut().insert(ACCEPT_ENCODING_HEADER, value);
+                }
+                Err(e) => warn!("Invalid accept encoding header {value}: {e:?}"),
+            }
         }
     }
 
+    // Returns the encoding of payloads chosen by the peer.
+    fn response_encoding(&self, metadata: &MetadataMap) -> ConsensusResult<PayloadEncoding> {
+        self.compressor.response_encoding(
+            metadata
+                .get(ENCODING_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        )
+    }
+
     async fn get_client(
         &self,
         peer: AuthorityIndex,
@@ -123,28 +151,40 @@ impl NetworkClient for TonicClient {
     ) -> ConsensusResult<BlockStream> {
         let mut client = self.get_client(peer, timeout).await?;
         // TODO: add sampled block acknowledgments for latency measurements.
-        let request = Request::new(stream::once(async move {
+        let mut request = Request::new(stream::once(async move {
             SubscribeBlocksRequest {
                 last_received_round: last_received,
             }
         }));
+        self.set_accept_encoding(&mut request);
         let response = client.subscribe_blocks(request).await.map_err(|e| {
             ConsensusError::NetworkRequest(format!("subscribe_blocks failed: {e:?}"))
         })?;
+        let encoding = self.response_encoding(response.metadata())?;
+        let compressor = self.compressor.clone();
         let stream = response
             .into_inner()
             .take_while(|b| futures::future::ready(b.is_ok()))
-            .filter_map(move |b| async move {
-                match b {
-                    Ok(response) => Some(ExtendedSerializedBlock {
-                        block: response.block,
-                        excluded_ancestors: response.excluded_ancestors,
-                    }),
+            .filter_map(move |b| {
+                let block = match b {
+                    Ok(response) => {
+                        match compressor.decompress("subscribe_blocks", &encoding, response.block) {
+                            Ok(block) => Some(ExtendedSerializedBlock {
+                                block,
+                                excluded_ancestors: response.excluded_ancestors,
+                            }),
+                            Err(e) => {
+                                debug!("Malformed block received from {}: {e:?}", peer);
+                                None
+                            }
+                        }
+                    }
                     Err(e) => {
                         debug!("Network error received from {}: {e:?}", peer);
                         None
                     }
-                }
+                };
+                futures::future::ready(block)
             });
         let rate_limited_stream =
             tokio_stream::StreamExt::throttle(stream, self.context.parameters.min_round_delay / 2)
@@ -174,26 +214,37 @@ impl NetworkClient for TonicClient {
             highest_accepted_rounds,
         });
         request.set_timeout(timeout);
-        let mut stream = client
-            .fetch_blocks(request)
-            .await
-            .map_err(|e| {
-                if e.code() == tonic::Code::DeadlineExceeded {
-                    ConsensusError::NetworkRequestTimeout(format!("fetch_blocks failed: {e:?}"))
-                } else {
-                    ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}"))
-                }
-            })?
-            .into_inner();
+        self.set_accept_encoding(&mut request);
+        let response = client.fetch_blocks(request).await.map_err(|e| {
+            if e.code() == tonic::Code::DeadlineExceeded {
+                ConsensusError::NetworkRequestTimeout(format!("fetch_blocks failed: {e:?}"))
+            } else {
+                ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}"))
+            }
+        })?;
+        let encoding = self.response_encoding(response.metadata())?;
+        let mut stream = response.into_inner();
         let mut blocks = vec![];
         let mut total_fetched_bytes = 0;
         loop {
             match stream.message().await {
                 Ok(Some(response)) => {
-                    for b in &response.blocks {
+                    let response_blocks = match self.compressor.decompress_batch(
+                        "fetch_blocks",
+                        &encoding,
+                        response.blocks,
+                    ) {
+                        Ok(response_blocks) => response_blocks,
+                        Err(e) if blocks.is_empty() => return Err(e),
+                        Err(e) => {
+                            warn!("fetch_blocks received malformed blocks mid-stream: {e:?}");
+                            break;
+                        }
+                    };
+                    for b in &response_blocks {
                         total_fetched_bytes += b.len();
                     }
-                    blocks.extend(response.blocks);
+                    blocks.extend(response_blocks);
                     if total_fetched_bytes > MAX_TOTAL_FETCHED_BYTES {
                         info!(
                             "fetch_blocks() fetched bytes exceeded limit: {} > {}, terminating stream.",
@@ -237,12 +288,22 @@ impl NetworkClient for TonicClient {
             end: commit_range.end(),
         });
         request.set_timeout(timeout);
+        self.set_accept_encoding(&mut request);
         let response = client
             .fetch_commits(request)
             .await
             .map_err(|e| ConsensusError::NetworkRequest(format!("fetch_commits failed: {e:?}")))?;
+        let encoding = self.response_encoding(response.metadata())?;
         let response = response.into_inner();
-        Ok((response.commits, response.certifier_blocks))
+        let commits =
+            self.compressor
+                .decompress_batch("fetch_commits", &encoding, response.commits)?;
+        let certifier_blocks = self.compressor.decompress_batch(
+            "fetch_commits",
+            &encoding,
+            response.certifier_blocks,
+        )?;
+        Ok((commits, certifier_blocks))
     }
 
     async fn fetch_latest_blocks(
@@ -259,26 +320,39 @@ impl NetworkClient for TonicClient {
                 .collect(),
         });
         request.set_timeout(timeout);
-        let mut stream = client
-            .fetch_latest_blocks(request)
-            .await
-            .map_err(|e| {
-                if e.code() == tonic::Code::DeadlineExceeded {
-                    ConsensusError::NetworkRequestTimeout(format!("fetch_blocks failed: {e:?}"))
-                } else {
-                    ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}"))
-                }
-            })?
-            .into_inner();
+        self.set_accept_encoding(&mut request);
+        let response = client.fetch_latest_blocks(request).await.map_err(|e| {
+            if e.code() == tonic::Code::DeadlineExceeded {
+                ConsensusError::NetworkRequestTimeout(format!("fetch_blocks failed: {e:?}"))
+            } else {
+                ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}"))
+            }
+        })?;
+        let encoding = self.response_encoding(response.metadata())?;
+        let mut stream = response.into_inner();
         let mut blocks = vec![];
         let mut total_fetched_bytes = 0;
         loop {
             match stream.message().await {
                 Ok(Some(response)) => {
-                    for b in &response.blocks {
+                    let response_blocks = match self.compressor.decompress_batch(
+                        "fetch_latest_blocks",
+                        &encoding,
+                        response.blocks,
+                    ) {
+                        Ok(response_blocks) => response_blocks,
+                        Err(e) if blocks.is_empty() => return Err(e),
+                        Err(e) => {
+                            warn!(
+                                "fetch_latest_blocks received malformed blocks mid-stream: {e:?}"
+                            );
+                            break;
+                        }
+                    };
+                    for b in &response_blocks {
                         total_fetched_bytes += b.len();
                     }
-                    blocks.extend(response.blocks);
+                    blocks.extend(response_blocks);
                     if total_fetched_bytes > MAX_TOTAL_FETCHED_BYTES {
                         info!(
                             "fetch_blocks() fetched bytes exceeded limit: {} > {}, terminating stream.",
@@ -436,11 +510,40 @@ impl ChannelPool {
 struct TonicServiceProxy<S: NetworkService> {
     context: Arc<Context>,
     service: Arc<S>,
+    compressor: Arc<PayloadCompressor>,
 }
 
 impl<S: NetworkService> TonicServiceProxy<S> {
-    fn new(context: Arc<Context>, service: Arc<S>) -> Self {
-        Self { context, service }
+    fn new(context: Arc<Context>, service: Arc<S>, compressor: Arc<PayloadCompressor>) -> Self {
+        Self {
+            context,
+            service,
+            compressor,
+        }
+    }
+
+    // Chooses the encoding of response payloads among the ones accepted by the peer.
+    fn negotiate_encoding<T>(&self, request: &Request<T>) -> PayloadEncoding {
+        self.compressor.negotiate(
+            request
+                .metadata()
+                .get(ACCEPT_ENCODING_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        )
+    }
+}
+
+// Reports the encoding of response payloads to the peer. Uncompressed responses carry no header,
+// same as responses from authorities without payload compression.
+fn set_encoding<T>(response: &mut Response<T>, encoding: &PayloadEncoding) {
+    if *encoding == PayloadEncoding::Identity {
+        return;
+    }
+    match encoding.header_value().parse() {
+        Ok(value) => {
+            response.metadata_mut().insert(ENCODING_HEADER, value);
+        }
+        Err(e) => warn!("Invalid encoding header {encoding:?}: {e:?}"),
     }
 }
 
@@ -483,6 +586,8 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         else {
             return Err(tonic::Status::internal("PeerInfo not found"));
         };
+        let encoding = self.negotiate_encoding(&request);
+        let compressor = self.compressor.clone();
         let mut request_stream = request.into_inner();
         let first_request = match request_stream.next().await {
             Some(Ok(r)) => r,
@@ -502,16 +607,23 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
             .handle_subscribe_blocks(peer_index, first_request.last_received_round)
             .await
             .map_err(|e| tonic::Status::internal(format!("{e:?}")))?
-            .map(|block| {
-                Ok(SubscribeBlocksResponse {
-                    block: block.block,
-                    excluded_ancestors: block.excluded_ancestors,
-                })
-            });
+            .map(
+                move |block| -> Result<SubscribeBlocksResponse, tonic::Status> {
+                    let serialized = compressor
+                        .compress("subscribe_blocks", &encoding, block.block)
+                        .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+                    Ok(SubscribeBlocksResponse {
+                        block: serialized,
+                        excluded_ancestors: block.excluded_ancestors,
+                    })
+                },
+            );
         let rate_limited_stream =
             tokio_stream::StreamExt::throttle(stream, self.context.parameters.min_round_delay / 2)
                 .boxed();
-        Ok(Response::new(rate_limited_stream))
+        let mut response = Response::new(rate_limited_stream);
+        set_encoding(&mut response, &encoding);
+        Ok(response)
     }
 
     type FetchBlocksStream = Iter<std::vec::IntoIter<Result<FetchBlocksResponse, tonic::Status>>>;
@@ -527,6 +639,7 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         else {
             return Err(tonic::Status::internal("PeerInfo not found"));
         };
+        let encoding = self.negotiate_encoding(&request);
         let inner = request.into_inner();
         let block_refs = inner
             .block_refs
@@ -548,11 +661,19 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         let responses: std::vec::IntoIter<Result<FetchBlocksResponse, tonic::Status>> =
             chunk_blocks(blocks, MAX_FETCH_RESPONSE_BYTES)
                 .into_iter()
-                .map(|blocks| Ok(FetchBlocksResponse { blocks }))
+                .map(|blocks| -> Result<FetchBlocksResponse, tonic::Status> {
+                    let blocks = self
+                        .compressor
+                        .compress_batch("fetch_blocks", &encoding, blocks)
+                        .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+                    Ok(FetchBlocksResponse { blocks })
+                })
                 .collect::<Vec<_>>()
                 .into_iter();
         let stream = iter(responses);
-        Ok(Response::new(stream))
+        let mut response = Response::new(stream);
+        set_encoding(&mut response, &encoding);
+        Ok(response)
     }
 
     async fn fetch_commits(
@@ -566,6 +687,7 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         else {
             return Err(tonic::Status::internal("PeerInfo not found"));
         };
+        let encoding = self.negotiate_encoding(&request);
         let request = request.into_inner();
         let (commits, certifier_blocks) = self
             .service
@@ -580,10 +702,20 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
             .into_iter()
             .map(|b| b.serialized().clone())
             .collect();
-        Ok(Response::new(FetchCommitsResponse {
+        let commits = self
+            .compressor
+            .compress_batch("fetch_commits", &encoding, commits)
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let certifier_blocks = self
+            .compressor
+            .compress_batch("fetch_commits", &encoding, certifier_blocks)
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let mut response = Response::new(FetchCommitsResponse {
             commits,
             certifier_blocks,
-        }))
+        });
+        set_encoding(&mut response, &encoding);
+        Ok(response)
     }
 
     type FetchLatestBlocksStream =
@@ -600,6 +732,7 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         else {
             return Err(tonic::Status::internal("PeerInfo not found"));
         };
+        let encoding = self.negotiate_encoding(&request);
         let inner = request.into_inner();
 
         // Convert the authority indexes and validate them
@@ -625,11 +758,21 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         let responses: std::vec::IntoIter<Result<FetchLatestBlocksResponse, tonic::Status>> =
             chunk_blocks(blocks, MAX_FETCH_RESPONSE_BYTES)
                 .into_iter()
-                .map(|blocks| Ok(FetchLatestBlocksResponse { blocks }))
+                .map(
+                    |blocks| -> Result<FetchLatestBlocksResponse, tonic::Status> {
+                        let blocks = self
+                            .compressor
+                            .compress_batch("fetch_latest_blocks", &encoding, blocks)
+                            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+                        Ok(FetchLatestBlocksResponse { blocks })
+                    },
+                )
                 .collect::<Vec<_>>()
                 .into_iter();
         let stream = iter(responses);
-        Ok(Response::new(stream))
+        let mut response = Response::new(stream);
+        set_encoding(&mut response, &encoding);
+        Ok(response)
     }
 
     async fn get_latest_rounds(
@@ -665,15 +808,22 @@ pub(crate) struct TonicManager {
     context: Arc<Context>,
     network_keypair: NetworkKeyPair,
     client: Arc<TonicClient>,
+    compressor: Arc<PayloadCompressor>,
     server: Option<ServerHandle>,
 }
 
 impl TonicManager {
     pub(crate) fn new(context: Arc<Context>, network_keypair: NetworkKeyPair) -> Self {
+        let compressor = Arc::new(PayloadCompressor::new(context.clone()));
         Self {
             context: context.clone(),
             network_keypair: network_keypair.clone(),
-            client: Arc::new(TonicClient::new(context, network_keypair)),
+            client: Arc::new(TonicClient::new(
+                context,
+                network_keypair,
+                compressor.clone(),
+            )),
+            compressor,
             server: None,
         }
     }
@@ -709,7 +859,8 @@ impl<S: NetworkService> NetworkManager<S> for TonicManager {
             authority.address.with_zero_ip()
         };
         let own_address = to_socket_addr(&own_address).unwrap();
-        let service = TonicServiceProxy::new(self.context.clone(), service);
+        let service =
+            TonicServiceProxy::new(self.context.clone(), service, self.compressor.clone());
         let config = &self.context.parameters.tonic;
 
         let connections_info = Arc::new(ConnectionsInfo::new(self.context.clone()));
@@ -374,15 +374,32 @@ impl DagBuilder {
         &mut self,
         connections: Vec<(AuthorityIndex, Vec<BlockRef>)>,
         round: Round,
+    ) {
+        let base_ts = round as BlockTimestampMs * 1000;
+        self.layer_with_timestamped_connections(
+            connections
+                .into_iter()
+                .map(|(authority, ancestors)| {
+                    (authority, base_ts + authority.value() as u64, ancestors)
+                })
+                .collect(),
+            round,
+        );
+    }
+
+    // Same as `layer_with_connections`, but the timestamp of each block is
+    // provided by the caller.
+    pub(crate) fn layer_with_timestamped_connections(
+        &mut self,
+        connections: Vec<(AuthorityIndex, BlockTimestampMs, Vec<BlockRef>)>,
+        round: Round,
     ) {
         let mut references = Vec::new();
-        for (authority, ancestors) in connections {
-            let author = authority.value() as u32;
-            let base_ts = round as BlockTimestampMs * 1000;
+        for (authority, timestamp_ms, ancestors) in connections {
             let block = VerifiedBlock::new_for_test(
-                TestBlock::new(round, author)
+                TestBlock::new(round, authority.value() as u32)
                     .set_ancestors(ancestors)
-                    .set_timestamp_ms(base_ts + author as u64)
+                    .set_timestamp_ms(timestamp_ms)
                     .build(),
             );
             references.push(block.reference());
@@ -0,0 +1,344 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Simulation harness comparing the leader scoring & schedule strategies
+//! selectable through `ConsensusLeaderScoringStrategy`.
+//!
+//! Each simulation builds a DAG round by round with `DagBuilder`, under synthetic
+//! network conditions: every authority proposes at the start of a round and its
+//! block is received by the others after a sampled latency. A round ends once a
+//! quorum of blocks has been received and the leader has been received, or the
+//! leader timeout has expired. The DAG is then run through the committer, the
+//! linearizer and the leader schedule updates the same way `Core` does, to
+//! measure commit latency and the rate of skipped leaders.
+
+use std::sync::Arc;
+
+use consensus_config::{local_committee_and_keys, Stake};
+use parking_lot::RwLock;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sui_protocol_config::ConsensusLeaderScoringStrategy;
+
+use crate::{
+    block::{BlockAPI, BlockRef, BlockTimestampMs, Round, Slot},
+    commit::DecidedLeader,
+    context::Context,
+    dag_state::DagState,
+    leader_schedule::{LeaderSchedule, LeaderSwapTable},
+    linearizer::Linearizer,
+    storage::mem_store::MemStore,
+    test_dag_builder::DagBuilder,
+    universal_committer::universal_committer_builder::UniversalCommitterBuilder,
+};
+
+const NUM_ROUNDS: Round = 300;
+const SEEDS: [u64; 3] = [7, 42, 1234];
+const LEADER_TIMEOUT_MS: u64 = 250;
+
+const STRATEGIES: [ConsensusLeaderScoringStrategy; 4] = [
+    ConsensusLeaderScoringStrategy::DistributedVotes,
+    ConsensusLeaderScoringStrategy::CertifiedVotes,
+    ConsensusLeaderScoringStrategy::LatencyWeighted,
+    ConsensusLeaderScoringStrategy::StakeWeightedRoundRobin,
+];
+
+/// How blocks of an authority propagate through the network.
+#[derive(Clone, Copy)]
+struct AuthorityProfile {
+    // Minimum delay for a block of the authority to be received by the others.
+    latency_ms: u64,
+    // Maximum random delay added to `latency_ms`.
+    jitter_ms: u64,
+    // Probability in percent that the authority does not propose in a round.
+    skip_round_pct: u32,
+}
+
+impl AuthorityProfile {
+    const fn new(latency_ms: u64, jitter_ms: u64, skip_round_pct: u32) -> Self {
+        Self {
+            latency_ms,
+            jitter_ms,
+            skip_round_pct,
+        }
+    }
+}
+
+struct NetworkConditions {
+    name: &'static str,
+    stakes: Vec<Stake>,
+    profiles: Vec<AuthorityProfile>,
+}
+
+fn network_conditions() -> Vec<NetworkConditions> {
+    let fast = AuthorityProfile::new(100, 50, 0);
+    vec![
+        NetworkConditions {
+            name: "uniform",
+            stakes: vec![1; 7],
+            profiles: vec![fast; 7],
+        },
+        NetworkConditions {
+            // Blocks of authority 6 are received long after the leader timeout.
+            name: "one slow authority",
+            stakes: vec![1; 7],
+            profiles: [vec![fast; 6], vec![AuthorityProfile::new(1_000, 200, 0)]].concat(),
+        },
+        NetworkConditions {
+            // Authority 4 misses 40% of the rounds and is late otherwise.
+            name: "flaky authority with unequal stake",
+            stakes: vec![1, 1, 1, 1, 2, 2, 3],
+            profiles: [
+                vec![fast; 4],
+                vec![AuthorityProfile::new(300, 100, 40)],
+                vec![fast; 2],
+            ]
+            .concat(),
+        },
+    ]
+}
+
+#[derive(Default)]
+struct SimulationResult {
+    committed_leaders: u64,
+    skipped_leaders: u64,
+    // Sum over committed leaders of the time from the leader proposal to its commit.
+    total_commit_latency_ms: u64,
+}
+
+impl SimulationResult {
+    fn merge(&mut self, other: SimulationResult) {
+        self.committed_leaders += other.committed_leaders;
+        self.skipped_leaders += other.skipped_leaders;
+        self.total_commit_latency_ms += other.total_commit_latency_ms;
+    }
+
+    fn skipped_leaders_pct(&self) -> f64 {
+        let decided = self.committed_leaders + self.skipped_leaders;
+        if decided == 0 {
+            return 0.0;
+        }
+        self.skipped_leaders as f64 * 100.0 / decided as f64
+    }
+
+    fn mean_commit_latency_ms(&self) -> u64 {
+        self.total_commit_latency_ms / self.committed_leaders.max(1)
+    }
+}
+
+/// Compares the strategies under each of the network conditions. Results are
+/// logged, and only the behaviors expected from every strategy are asserted.
+#[tokio::test]
+async fn test_leader_schedule_strategies_simulation() {
+    telemetry_subscribers::init_for_testing();
+
+    for conditions in network_conditions() {
+        let mut results = Vec::new();
+        for strategy in STRATEGIES {
+            let mut result = SimulationResult::default();
+            for seed in SEEDS {
+                result.merge(simulate(strategy, &conditions, seed));
+            }
+            tracing::info!(
+                "[{}] {strategy:?}: {} committed leaders, {} skipped leaders ({:.1}%), mean commit latency {}ms",
+                conditions.name,
+                result.committed_leaders,
+                result.skipped_leaders,
+                result.skipped_leaders_pct(),
+                result.mean_commit_latency_ms(),
+            );
+            assert!(result.committed_leaders > 0);
+            results.push((strategy, result));
+        }
+
+        match conditions.name {
+            "uniform" => {
+                // Every leader is received before the end of its round, so none
+                // should be skipped.
+                for (strategy, result) in &results {
+                    assert_eq!(result.skipped_leaders, 0, "{strategy:?}");
+                }
+            }
+            "one slow authority" => {
+                // Strategies swapping out low scoring leaders should avoid electing
+                // the slow authority, which a fixed round robin cannot.
+                let (_, round_robin) = results
+                    .iter()
+                    .find(|(strategy, _)| {
+                        *strategy == ConsensusLeaderScoringStrategy::StakeWeightedRoundRobin
+                    })
+                    .unwrap();
+                assert!(round_robin.skipped_leaders > 0);
+                for (strategy, result) in &results {
+                    assert!(
+                        result.skipped_leaders <= round_robin.skipped_leaders,
+                        "{strategy:?} skipped more leaders than round robin"
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn simulate(
+    strategy: ConsensusLeaderScoringStrategy,
+    conditions: &NetworkConditions,
+    seed: u64,
+) -> SimulationResult {
+    let mut context = Context::new_for_test(conditions.stakes.len())
+        .0
+        .with_committee(local_committee_and_keys(0, conditions.stakes.clone()).0);
+    context
+        .protocol_config
+        .set_consensus_leader_scoring_strategy_for_testing(strategy);
+    let context = Arc::new(context);
+    let committee = &context.committee;
+
+    let dag_state = Arc::new(RwLock::new(DagState::new(
+        context.clone(),
+        Arc::new(MemStore::new()),
+    )));
+    let leader_schedule = Arc::new(LeaderSchedule::new(
+        context.clone(),
+        LeaderSwapTable::default(),
+    ));
+    let committer =
+        UniversalCommitterBuilder::new(context.clone(), leader_schedule.clone(), dag_state.clone())
+            .with_pipeline(true)
+            .build();
+    let mut linearizer =
+        Linearizer::new(context.clone(), dag_state.clone(), leader_schedule.clone());
+    let mut dag_builder = DagBuilder::new(context.clone());
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut result = SimulationResult::default();
+    let mut last_decided = Slot::new_for_test(0, 0);
+    // Blocks of the previous round, along with the time they were received.
+    let mut previous_round: Vec<(BlockRef, BlockTimestampMs)> = dag_builder
+        .genesis_block_refs()
+        .into_iter()
+        .map(|block_ref| (block_ref, 0))
+        .collect();
+    let mut round_start_ms = 0;
+
+    for round in 1..=NUM_ROUNDS {
+        // Authorities propose with the blocks received by the start of the round,
+        // and always with their own previous block.
+        let mut connections = Vec::new();
+        for (authority, _) in committee.authorities() {
+            let profile = conditions.profiles[authority.value()];
+            if rng.gen_ratio(profile.skip_round_pct, 100) {
+                continue;
+            }
+            let ancestors = previous_round
+                .iter()
+                .filter(|(block_ref, received_ms)| {
+                    *received_ms <= round_start_ms || block_ref.author == authority
+                })
+                .map(|(block_ref, _)| *block_ref)
+                .collect::<Vec<_>>();
+            connections.push((authority, round_start_ms, ancestors));
+        }
+        dag_builder.layer_with_timestamped_connections(connections, round);
+        let blocks = dag_builder.blocks(round..=round);
+        dag_state.write().accept_blocks(blocks.clone());
+
+        let mut received = blocks
+            .iter()
+            .map(|block| {
+                let profile = conditions.profiles[block.author().value()];
+                let delay_ms = profile.latency_ms + rng.gen_range(0..=profile.jitter_ms);
+                (block.reference(), round_start_ms + delay_ms)
+            })
+            .collect::<Vec<_>>();
+        received.sort_by_key(|(_, received_ms)| *received_ms);
+
+        let mut stake = 0;
+        let quorum_received_ms = received
+            .iter()
+            .find_map(|(block_ref, received_ms)| {
+                stake += committee.stake(block_ref.author);
+                (stake >= committee.quorum_threshold()).then_some(*received_ms)
+            })
+            .expect("A quorum should propose in every round");
+        let leader = leader_schedule.elect_leader(round, 0);
+        let leader_received_ms = received
+            .iter()
+            .find(|(block_ref, _)| block_ref.author == leader)
+            .map(|(_, received_ms)| *received_ms);
+        let next_round_start_ms = match leader_received_ms {
+            Some(received_ms) if received_ms <= quorum_received_ms + LEADER_TIMEOUT_MS => {
+                received_ms.max(quorum_received_ms)
+            }
+            _ => quorum_received_ms + LEADER_TIMEOUT_MS,
+        };
+
+        // Leaders are decided once the blocks of the round have been received.
+        loop {
+            let mut commits_until_update =
+                leader_schedule.commits_until_leader_schedule_update(dag_state.clone());
+            if commits_until_update == 0 {
+                leader_schedule.update_leader_schedule_v2(&dag_state);
+                commits_until_update =
+                    leader_schedule.commits_until_leader_schedule_update(dag_state.clone());
+            }
+
+            let mut decided_leaders = committer.try_decide(last_decided);
+            decided_leaders.truncate(commits_until_update);
+            let Some(last) = decided_leaders.last() else {
+                break;
+            };
+            last_decided = last.slot();
+
+            let mut sequenced_leaders = Vec::new();
+            for decided_leader in decided_leaders {
+                match decided_leader {
+                    DecidedLeader::Commit(block) => {
+                        result.committed_leaders += 1;
+                        result.total_commit_latency_ms +=
+                            next_round_start_ms - block.timestamp_ms();
+                        sequenced_leaders.push(block);
+                    }
+                    DecidedLeader::Skip(_) => result.skipped_leaders += 1,
+                }
+            }
+            if sequenced_leaders.is_empty() {
+                break;
+            }
+
+            let subdags = linearizer.handle_commit(sequenced_leaders);
+            dag_state.write().add_scoring_subdags(subdags);
+        }
+
+        previous_round = received;
+        round_start_ms = next_round_start_ms;
+    }
+
+    result
+}
+
+#[test]
+fn test_network_conditions_are_consistent() {
+    for conditions in network_conditions() {
+        assert_eq!(
+            conditions.stakes.len(),
+            conditions.profiles.len(),
+            "{}",
+            conditions.name
+        );
+        // Authorities that may miss rounds should never prevent a quorum.
+        let committee = local_committee_and_keys(0, conditions.stakes.clone()).0;
+        let always_proposing_stake: Stake = conditions
+            .profiles
+            .iter()
+            .zip(&conditions.stakes)
+            .filter(|(profile, _)| profile.skip_round_pct == 0)
+            .map(|(_, stake)| stake)
+            .sum();
+        assert!(
+            always_proposing_stake >= committee.quorum_threshold(),
+            "{}",
+            conditions.name
+        );
+    }
+}
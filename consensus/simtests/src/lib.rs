@@ -4,6 +4,13 @@
 #[cfg(msim)]
 mod node;
 
+#[cfg(msim)]
+mod scenario;
+
 #[cfg(msim)]
 #[path = "tests/simtests.rs"]
 mod simtests;
+
+#[cfg(msim)]
+#[path = "tests/byzantine_simtests.rs"]
+mod byzantine_simtests;
@@ -10,16 +10,23 @@ use std::{
 use tracing::{info, trace};
 
 use anyhow::Result;
-use consensus_config::{AuthorityIndex, Committee, NetworkKeyPair, Parameters, ProtocolKeyPair};
+use consensus_config::{
+    Authority, AuthorityIndex, AuthorityKeyPair, Committee, Epoch, NetworkKeyPair, Parameters,
+    ProtocolKeyPair, Stake,
+};
+use mysten_network::Multiaddr;
 use parking_lot::Mutex;
 use prometheus::Registry;
+use rand::{rngs::StdRng, SeedableRng as _};
+use sui_config::local_ip_utils;
 use sui_protocol_config::{ConsensusNetwork, ProtocolConfig};
 use tempfile::TempDir;
 
+use consensus_core::byzantine::{register_byzantine_authority, ByzantineConfig};
 use consensus_core::network::tonic_network::to_socket_addr;
 use consensus_core::transaction::NoopTransactionVerifier;
 use consensus_core::{
-    BlockTimestampMs, Clock, CommitConsumer, CommitConsumerMonitor, CommittedSubDag,
+    BlockTimestampMs, Clock, CommitConsumer, CommitConsumerMonitor, CommitRef, CommittedSubDag,
     ConsensusAuthority, TransactionClient,
 };
 
@@ -39,6 +46,9 @@ pub(crate) struct Config {
 pub(crate) struct AuthorityNode {
     inner: Mutex<Option<AuthorityNodeInner>>,
     config: Config,
+    byzantine: Mutex<ByzantineConfig>,
+    // Sequence of commits handled by this node, kept across restarts.
+    commits: Arc<Mutex<Vec<CommitRef>>>,
 }
 
 impl AuthorityNode {
@@ -46,6 +56,8 @@ impl AuthorityNode {
         Self {
             inner: Default::default(),
             config,
+            byzantine: Default::default(),
+            commits: Default::default(),
         }
     }
 
@@ -68,9 +80,11 @@ impl AuthorityNode {
         if let Some(inner) = inner.as_ref() {
             let mut commit_receiver = inner.take_commit_receiver();
             let commit_consumer_monitor = inner.commit_consumer_monitor();
+            let commits = self.commits.clone();
             let _handle = tokio::spawn(async move {
                 while let Some(subdag) = commit_receiver.recv().await {
                     info!(index =% authority_index, "received committed subdag");
+                    record_commit(&mut commits.lock(), authority_index, subdag.commit_ref);
                     commit_consumer_monitor.set_highest_handled_commit(subdag.commit_ref.index);
                 }
             });
@@ -96,6 +110,23 @@ impl AuthorityNode {
         }
     }
 
+    /// Returns the sequence of commits handled by this Node so far.
+    pub fn commits(&self) -> Vec<CommitRef> {
+        self.commits.lock().clone()
+    }
+
+    /// Sets the byzantine behaviors of this Node, effective from its next start.
+    pub fn set_byzantine(&self, config: ByzantineConfig) {
+        let address = &self.config.committee.authority(self.index()).address;
+        register_byzantine_authority(address, config.clone());
+        *self.byzantine.lock() = config;
+    }
+
+    /// If this Node has been configured with any byzantine behavior
+    pub fn is_byzantine(&self) -> bool {
+        !self.byzantine.lock().is_honest()
+    }
+
     /// Stop this Node
     pub fn stop(&self) {
         info!(index =% self.config.authority_index, "stopping in-memory node");
@@ -109,6 +140,29 @@ impl AuthorityNode {
     }
 }
 
+/// Appends `commit_ref` to the commit sequence of a node. Commits replayed after a restart must
+/// match the ones handled before.
+fn record_commit(
+    commits: &mut Vec<CommitRef>,
+    authority_index: AuthorityIndex,
+    commit_ref: CommitRef,
+) {
+    let position = commit_ref.index as usize - 1;
+    match position.cmp(&commits.len()) {
+        std::cmp::Ordering::Less => assert_eq!(
+            commits[position], commit_ref,
+            "Authority {authority_index} replayed a different commit at index {}",
+            commit_ref.index
+        ),
+        std::cmp::Ordering::Equal => commits.push(commit_ref),
+        std::cmp::Ordering::Greater => panic!(
+            "Authority {authority_index} skipped commits: received {} after {}",
+            commit_ref.index,
+            commits.len()
+        ),
+    }
+}
+
 pub(crate) struct AuthorityNodeInner {
     handle: Option<NodeHandle>,
     cancel_sender: Option<tokio::sync::watch::Sender<bool>>,
@@ -292,3 +346,36 @@ pub(crate) async fn make_authority(
 
     (authority, commit_receiver, commit_consumer_monitor)
 }
+
+/// Creates a committee for local testing, and the corresponding key pairs for the authorities.
+pub(crate) fn local_committee_and_keys(
+    epoch: Epoch,
+    authorities_stake: Vec<Stake>,
+) -> (Committee, Vec<(NetworkKeyPair, ProtocolKeyPair)>) {
+    let mut authorities = vec![];
+    let mut key_pairs = vec![];
+    let mut rng = StdRng::from_seed([0; 32]);
+    for (i, stake) in authorities_stake.into_iter().enumerate() {
+        let authority_keypair = AuthorityKeyPair::generate(&mut rng);
+        let protocol_keypair = ProtocolKeyPair::generate(&mut rng);
+        let network_keypair = NetworkKeyPair::generate(&mut rng);
+        authorities.push(Authority {
+            stake,
+            address: get_available_local_address(),
+            hostname: format!("test_host_{i}").to_string(),
+            authority_key: authority_keypair.public(),
+            protocol_key: protocol_keypair.public(),
+            network_key: network_keypair.public(),
+        });
+        key_pairs.push((network_keypair, protocol_keypair));
+    }
+
+    let committee = Committee::new(epoch, authorities);
+    (committee, key_pairs)
+}
+
+fn get_available_local_address() -> Multiaddr {
+    let ip = local_ip_utils::get_new_ip();
+
+    local_ip_utils::new_udp_address_for_testing(&ip)
+}
@@ -0,0 +1,183 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scripted consensus scenarios with byzantine authorities.
+//!
+//! A scenario starts a committee where some authorities are configured with byzantine behaviors,
+//! keeps submitting transactions to the honest ones, and runs a script of steps against it.
+//! Safety of the commit sequences of honest authorities is checked after every step.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use consensus_config::AuthorityIndex;
+use consensus_core::{byzantine::ByzantineConfig, CommitIndex, CommitRef};
+use prometheus::Registry;
+use sui_protocol_config::{ConsensusNetwork, ProtocolConfig};
+use tempfile::TempDir;
+use tokio::time::sleep;
+use tracing::info;
+use typed_store::DBMetrics;
+
+use crate::node::{local_committee_and_keys, AuthorityNode, Config};
+
+/// A step of a scripted scenario.
+pub(crate) enum Step {
+    /// Lets the committee run for the given duration.
+    Run(Duration),
+    /// Stops an authority.
+    Stop(AuthorityIndex),
+    /// Starts a stopped authority again.
+    Start(AuthorityIndex),
+    /// Changes the byzantine behaviors of an authority, effective from its next start.
+    SetByzantine(AuthorityIndex, ByzantineConfig),
+    /// Asserts that every running honest authority handled at least `min_commits` new commits
+    /// since the previous progress check, or since the start of the scenario.
+    AssertProgress { min_commits: CommitIndex },
+}
+
+pub(crate) struct Scenario {
+    name: &'static str,
+    committee_size: usize,
+    byzantine: BTreeMap<AuthorityIndex, ByzantineConfig>,
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn new(name: &'static str, committee_size: usize) -> Self {
+        Self {
+            name,
+            committee_size,
+            byzantine: BTreeMap::new(),
+            steps: vec![],
+        }
+    }
+
+    /// Configures `authority` with byzantine behaviors from the start of the scenario.
+    pub fn with_byzantine(mut self, authority: u32, config: ByzantineConfig) -> Self {
+        self.byzantine
+            .insert(AuthorityIndex::new_for_test(authority), config);
+        self
+    }
+
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Runs the scenario to completion, panicking on the first violated invariant.
+    pub async fn run(self) {
+        info!("Running scenario {}", self.name);
+        let db_registry = Registry::new();
+        DBMetrics::init(&db_registry);
+
+        let (committee, keypairs) = local_committee_and_keys(0, vec![1; self.committee_size]);
+        let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+
+        let mut authorities = Vec::with_capacity(committee.size());
+        for (authority_index, _) in committee.authorities() {
+            let node = AuthorityNode::new(Config {
+                authority_index,
+                db_dir: Arc::new(TempDir::new().unwrap()),
+                committee: committee.clone(),
+                keypairs: keypairs.clone(),
+                network_type: ConsensusNetwork::Tonic,
+                boot_counter: 0,
+                protocol_config: protocol_config.clone(),
+                clock_drift: 0,
+            });
+            node.set_byzantine(
+                self.byzantine
+                    .get(&authority_index)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            node.start().await.unwrap();
+            node.spawn_committed_subdag_consumer().unwrap();
+            authorities.push(node);
+        }
+
+        let transaction_clients = authorities
+            .iter()
+            .filter(|node| !node.is_byzantine())
+            .map(|node| node.transaction_client())
+            .collect::<Vec<_>>();
+        let _handle = tokio::spawn(async move {
+            for i in 0u64.. {
+                let txn = i.to_le_bytes().to_vec();
+                // Clients of stopped authorities fail to submit, which is fine.
+                let _ = transaction_clients[i as usize % transaction_clients.len()]
+                    .submit(vec![txn])
+                    .await;
+                sleep(Duration::from_millis(20)).await;
+            }
+        });
+
+        let mut progress_checkpoints = vec![0; authorities.len()];
+        for step in self.steps {
+            match step {
+                Step::Run(duration) => sleep(duration).await,
+                Step::Stop(authority) => authorities[authority.value()].stop(),
+                Step::Start(authority) => {
+                    let node = &authorities[authority.value()];
+                    node.start().await.unwrap();
+                    node.spawn_committed_subdag_consumer().unwrap();
+                }
+                Step::SetByzantine(authority, config) => {
+                    authorities[authority.value()].set_byzantine(config)
+                }
+                Step::AssertProgress { min_commits } => {
+                    assert_progress(&authorities, &mut progress_checkpoints, min_commits)
+                }
+            }
+            assert_safety(&authorities);
+        }
+        info!("Scenario {} completed", self.name);
+    }
+}
+
+/// Asserts that the commit sequences of all honest authorities are prefixes of each other.
+pub(crate) fn assert_safety(authorities: &[AuthorityNode]) {
+    let sequences = authorities
+        .iter()
+        .filter(|node| !node.is_byzantine())
+        .map(|node| (node.index(), node.commits()))
+        .collect::<Vec<_>>();
+    for (i, (authority_a, commits_a)) in sequences.iter().enumerate() {
+        for (authority_b, commits_b) in &sequences[i + 1..] {
+            if let Some((a, b)) = first_divergence(commits_a, commits_b) {
+                panic!(
+                    "Safety violation: authority {authority_a} committed {a:?} while authority {authority_b} committed {b:?}"
+                );
+            }
+        }
+    }
+}
+
+/// Asserts that every running honest authority made at least `min_commits` commits since its
+/// last checkpoint, and moves the checkpoints forward.
+pub(crate) fn assert_progress(
+    authorities: &[AuthorityNode],
+    checkpoints: &mut [usize],
+    min_commits: CommitIndex,
+) {
+    for node in authorities {
+        let handled = node.commits().len();
+        let checkpoint = &mut checkpoints[node.index().value()];
+        if node.is_running() && !node.is_byzantine() {
+            let progress = handled - *checkpoint;
+            assert!(
+                progress >= min_commits as usize,
+                "Liveness violation: authority {} handled {progress} new commits, expected at least {min_commits}",
+                node.index()
+            );
+        }
+        *checkpoint = handled;
+    }
+}
+
+fn first_divergence(a: &[CommitRef], b: &[CommitRef]) -> Option<(CommitRef, CommitRef)> {
+    a.iter()
+        .zip(b.iter())
+        .find(|(a, b)| a != b)
+        .map(|(a, b)| (*a, *b))
+}
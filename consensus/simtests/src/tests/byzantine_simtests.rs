@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+#[cfg(msim)]
+mod test {
+    use std::{collections::BTreeSet, time::Duration};
+
+    use consensus_config::AuthorityIndex;
+    use consensus_core::byzantine::{ByzantineBehavior, ByzantineConfig};
+    use sui_macros::sim_test;
+    use sui_simulator::{
+        configs::{env_config, uniform_latency_ms},
+        SimConfig,
+    };
+
+    use crate::scenario::{Scenario, Step};
+
+    fn test_config() -> SimConfig {
+        env_config(uniform_latency_ms(10..20), [])
+    }
+
+    fn byzantine(behaviors: impl IntoIterator<Item = ByzantineBehavior>) -> ByzantineConfig {
+        behaviors
+            .into_iter()
+            .fold(ByzantineConfig::new(), ByzantineConfig::with_behavior)
+    }
+
+    fn authority(index: u32) -> AuthorityIndex {
+        AuthorityIndex::new_for_test(index)
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_equivocating_authority() {
+        telemetry_subscribers::init_for_testing();
+        Scenario::new("equivocation", 4)
+            .with_byzantine(
+                3,
+                byzantine([ByzantineBehavior::Equivocate { extra_blocks: 2 }]),
+            )
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .run()
+            .await;
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_withholding_authority() {
+        telemetry_subscribers::init_for_testing();
+        Scenario::new("withholding", 4)
+            .with_byzantine(
+                3,
+                byzantine([ByzantineBehavior::WithholdBlocks {
+                    peers: BTreeSet::from([authority(0), authority(1)]),
+                }]),
+            )
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            // Crash one of the authorities the blocks are withheld from, so it has to recover
+            // the byzantine authority's blocks from the honest ones.
+            .step(Step::Stop(authority(0)))
+            .step(Step::Run(Duration::from_secs(20)))
+            .step(Step::Start(authority(0)))
+            .step(Step::Run(Duration::from_secs(40)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .run()
+            .await;
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_invalid_blocks() {
+        telemetry_subscribers::init_for_testing();
+        Scenario::new("invalid_blocks", 4)
+            .with_byzantine(
+                0,
+                byzantine([
+                    ByzantineBehavior::InvalidAncestors,
+                    ByzantineBehavior::InvalidTimestamps,
+                ]),
+            )
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .run()
+            .await;
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_delayed_commit_votes_and_fetch_flooding() {
+        telemetry_subscribers::init_for_testing();
+        Scenario::new("delayed_votes_and_flooding", 4)
+            .with_byzantine(
+                2,
+                byzantine([
+                    ByzantineBehavior::DelayCommitVotes { rounds: 10 },
+                    ByzantineBehavior::FloodFetchBlocks {
+                        requests_per_peer: 10,
+                        interval: Duration::from_millis(100),
+                    },
+                ]),
+            )
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .run()
+            .await;
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_mixed_byzantine_authorities() {
+        telemetry_subscribers::init_for_testing();
+        Scenario::new("mixed", 7)
+            .with_byzantine(
+                5,
+                byzantine([
+                    ByzantineBehavior::Equivocate { extra_blocks: 1 },
+                    ByzantineBehavior::DelayCommitVotes { rounds: 5 },
+                ]),
+            )
+            .with_byzantine(
+                6,
+                byzantine([
+                    ByzantineBehavior::WithholdBlocks {
+                        peers: BTreeSet::from([authority(0), authority(1), authority(2)]),
+                    },
+                    ByzantineBehavior::InvalidAncestors,
+                    ByzantineBehavior::FloodFetchBlocks {
+                        requests_per_peer: 5,
+                        interval: Duration::from_millis(200),
+                    },
+                ]),
+            )
+            .step(Step::Run(Duration::from_secs(30)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            // Turn authority 5 honest after a restart, and let it catch up.
+            .step(Step::Stop(authority(5)))
+            .step(Step::SetByzantine(authority(5), ByzantineConfig::new()))
+            .step(Step::Run(Duration::from_secs(10)))
+            .step(Step::Start(authority(5)))
+            .step(Step::Run(Duration::from_secs(60)))
+            .step(Step::AssertProgress { min_commits: 20 })
+            .run()
+            .await;
+    }
+}
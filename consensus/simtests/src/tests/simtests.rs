@@ -2,16 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 #[cfg(msim)]
 mod test {
-    use crate::node::{AuthorityNode, Config};
-    use consensus_config::{
-        Authority, AuthorityIndex, AuthorityKeyPair, Committee, Epoch, NetworkKeyPair,
-        ProtocolKeyPair, Stake,
-    };
-    use mysten_network::Multiaddr;
+    use crate::node::{local_committee_and_keys, AuthorityNode, Config};
+    use consensus_config::AuthorityIndex;
     use prometheus::Registry;
-    use rand::{rngs::StdRng, SeedableRng as _};
     use std::{sync::Arc, time::Duration};
-    use sui_config::local_ip_utils;
     use sui_macros::sim_test;
     use sui_protocol_config::ProtocolConfig;
     use sui_simulator::{
@@ -122,37 +116,4 @@ mod test {
             );
         }
     }
-
-    /// Creates a committee for local testing, and the corresponding key pairs for the authorities.
-    pub fn local_committee_and_keys(
-        epoch: Epoch,
-        authorities_stake: Vec<Stake>,
-    ) -> (Committee, Vec<(NetworkKeyPair, ProtocolKeyPair)>) {
-        let mut authorities = vec![];
-        let mut key_pairs = vec![];
-        let mut rng = StdRng::from_seed([0; 32]);
-        for (i, stake) in authorities_stake.into_iter().enumerate() {
-            let authority_keypair = AuthorityKeyPair::generate(&mut rng);
-            let protocol_keypair = ProtocolKeyPair::generate(&mut rng);
-            let network_keypair = NetworkKeyPair::generate(&mut rng);
-            authorities.push(Authority {
-                stake,
-                address: get_available_local_address(),
-                hostname: format!("test_host_{i}").to_string(),
-                authority_key: authority_keypair.public(),
-                protocol_key: protocol_keypair.public(),
-                network_key: network_keypair.public(),
-            });
-            key_pairs.push((network_keypair, protocol_keypair));
-        }
-
-        let committee = Committee::new(epoch, authorities);
-        (committee, key_pairs)
-    }
-
-    fn get_available_local_address() -> Multiaddr {
-        let ip = local_ip_utils::get_new_ip();
-
-        local_ip_utils::new_udp_address_for_testing(&ip)
-    }
 }
@@ -608,6 +608,13 @@ impl<T: Send + Sync, V: store::SimulatorStore + Send + Sync> RpcStateReader for
     fn indexes(&self) -> Option<&dyn sui_types::storage::RpcIndexes> {
         None
     }
+
+    fn get_object_congestion_info(
+        &self,
+        _object_id: &ObjectID,
+    ) -> Option<sui_types::storage::ObjectCongestionInfo> {
+        None
+    }
 }
 
 impl Simulacrum {
@@ -9,6 +9,7 @@ use crate::metered_eth_provider::new_metered_eth_provider;
 use crate::metered_eth_provider::MeteredEthHttpProvier;
 use crate::metrics::BridgeMetrics;
 use crate::sui_client::SuiClient;
+use crate::sui_event_proof_verifier::SuiEventProofVerifier;
 use crate::types::{is_route_valid, BridgeAction};
 use crate::utils::get_eth_contract_addresses;
 use anyhow::anyhow;
@@ -91,6 +92,22 @@ pub struct SuiConfig {
     /// Otherwise, it will miss one event because of fullnode Event query semantics.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sui_bridge_module_last_processed_event_id_override: Option<EventID>,
+    /// When set, Sui bridge events reported by `sui_rpc_url` are only signed after they are
+    /// proven against a checkpoint certified by the Sui validator committee.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sui_event_proof_verification: Option<SuiEventProofVerificationConfig>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SuiEventProofVerificationConfig {
+    /// gRPC url of a Sui fullnode used to fetch certified checkpoints. Ideally this is
+    /// operated by a different provider than `sui_rpc_url`.
+    pub checkpoint_rpc_url: String,
+    /// Path of the genesis blob of the Sui network. Its committee is the root of trust,
+    /// later committees are derived from certified end of epoch checkpoints.
+    pub genesis_path: PathBuf,
 }
 
 #[serde_as]
@@ -206,6 +223,17 @@ impl BridgeNodeConfig {
         }
         let approved_governance_actions = self.approved_governance_actions.clone();
 
+        let sui_event_proof_verifier = self
+            .sui
+            .sui_event_proof_verification
+            .as_ref()
+            .map(SuiEventProofVerifier::new_from_config)
+            .transpose()?
+            .map(Arc::new);
+        if sui_event_proof_verifier.is_some() {
+            info!("Sui event proof verification is enabled");
+        }
+
         let bridge_server_config = BridgeServerConfig {
             key: bridge_authority_key,
             metrics_port: self.metrics_port,
@@ -214,6 +242,7 @@ impl BridgeNodeConfig {
             sui_client: sui_client.clone(),
             eth_client: eth_client.clone(),
             approved_governance_actions,
+            sui_event_proof_verifier,
         };
         if !self.run_client {
             return Ok((bridge_server_config, None));
@@ -414,6 +443,8 @@ pub struct BridgeServerConfig {
     pub eth_client: Arc<EthClient<MeteredEthHttpProvier>>,
     /// A list of approved governance actions. Action in this list will be signed when requested by client.
     pub approved_governance_actions: Vec<BridgeAction>,
+    /// If set, Sui bridge events are proven against certified checkpoints before signing.
+    pub sui_event_proof_verifier: Option<Arc<SuiEventProofVerifier>>,
 }
 
 pub struct BridgeClientConfig {
@@ -67,6 +67,8 @@ pub enum BridgeError {
     StorageError(String),
     // Rest API Error
     RestAPIError(String),
+    // Sui event could not be proven against a certified checkpoint
+    SuiEventProofError(String),
     // Uncategorized error
     Generic(String),
 }
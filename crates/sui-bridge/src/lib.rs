@@ -21,6 +21,7 @@ pub mod server;
 pub mod storage;
 pub mod sui_bridge_watchdog;
 pub mod sui_client;
+pub mod sui_event_proof_verifier;
 pub mod sui_syncer;
 pub mod sui_transaction_builder;
 pub mod types;
@@ -143,6 +143,7 @@ pub async fn run_bridge_node(
             server_config.sui_client,
             server_config.eth_client,
             server_config.approved_governance_actions,
+            server_config.sui_event_proof_verifier,
             metrics.clone(),
         ),
         metrics,
@@ -609,6 +610,7 @@ mod tests {
                 bridge_client_key_path: None,
                 bridge_client_gas_object: None,
                 sui_bridge_module_last_processed_event_id_override: None,
+                sui_event_proof_verification: None,
             },
             eth: EthConfig {
                 eth_rpc_url: bridge_test_cluster.eth_rpc_url(),
@@ -676,6 +678,7 @@ mod tests {
                     tx_digest: TransactionDigest::random(),
                     event_seq: 0,
                 }),
+                sui_event_proof_verification: None,
             },
             eth: EthConfig {
                 eth_rpc_url: bridge_test_cluster.eth_rpc_url(),
@@ -754,6 +757,7 @@ mod tests {
                     tx_digest: TransactionDigest::random(),
                     event_seq: 0,
                 }),
+                sui_event_proof_verification: None,
             },
             eth: EthConfig {
                 eth_rpc_url: bridge_test_cluster.eth_rpc_url(),
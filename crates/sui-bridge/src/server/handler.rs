@@ -8,6 +8,7 @@ use crate::error::{BridgeError, BridgeResult};
 use crate::eth_client::EthClient;
 use crate::metrics::BridgeMetrics;
 use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::sui_event_proof_verifier::SuiEventProofVerifier;
 use crate::types::{BridgeAction, SignedBridgeAction};
 use async_trait::async_trait;
 use axum::Json;
@@ -59,6 +60,7 @@ pub trait ActionVerifier<K>: Send + Sync {
 
 struct SuiActionVerifier<C> {
     sui_client: Arc<SuiClient<C>>,
+    event_proof_verifier: Option<Arc<SuiEventProofVerifier>>,
 }
 
 struct EthActionVerifier<P> {
@@ -76,10 +78,18 @@ where
 
     async fn verify(&self, key: (TransactionDigest, u16)) -> BridgeResult<BridgeAction> {
         let (tx_digest, event_idx) = key;
-        self.sui_client
+        let action = self
+            .sui_client
             .get_bridge_action_by_tx_digest_and_event_idx_maybe(&tx_digest, event_idx)
             .await
-            .tap_ok(|action| info!("Sui action found: {:?}", action))
+            .tap_ok(|action| info!("Sui action found: {:?}", action))?;
+        if let Some(event_proof_verifier) = &self.event_proof_verifier {
+            event_proof_verifier
+                .verify_bridge_action(tx_digest, event_idx, &action)
+                .await?;
+            info!("Sui action proven by certified checkpoint: {:?}", action);
+        }
+        Ok(action)
     }
 }
 
@@ -236,6 +246,7 @@ impl BridgeRequestHandler {
         sui_client: Arc<SuiClient<SC>>,
         eth_client: Arc<EthClient<EP>>,
         approved_governance_actions: Vec<BridgeAction>,
+        sui_event_proof_verifier: Option<Arc<SuiEventProofVerifier>>,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         let (sui_signer_tx, sui_rx) = mysten_metrics::metered_channel::channel(
@@ -263,7 +274,10 @@ impl BridgeRequestHandler {
 
         SignerWithCache::new(
             signer.clone(),
-            SuiActionVerifier { sui_client },
+            SuiActionVerifier {
+                sui_client,
+                event_proof_verifier: sui_event_proof_verifier,
+            },
             metrics.clone(),
         )
         .spawn(sui_rx);
@@ -371,6 +385,7 @@ mod tests {
         let sui_client_mock = SuiMockClient::default();
         let sui_verifier = SuiActionVerifier {
             sui_client: Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            event_proof_verifier: None,
         };
         let metrics = Arc::new(BridgeMetrics::new_for_testing());
         let mut sui_signer_with_cache = SignerWithCache::new(signer.clone(), sui_verifier, metrics);
@@ -157,15 +157,7 @@ where
         let event = events
             .get(event_idx as usize)
             .ok_or(BridgeError::NoBridgeEventsInTxPosition)?;
-        if event.type_.address.as_ref() != BRIDGE_PACKAGE_ID.as_ref() {
-            return Err(BridgeError::BridgeEventInUnrecognizedSuiPackage);
-        }
-        let bridge_event = SuiBridgeEvent::try_from_sui_event(event)?
-            .ok_or(BridgeError::NoBridgeEventsInTxPosition)?;
-
-        bridge_event
-            .try_into_bridge_action(*tx_digest, event_idx)
-            .ok_or(BridgeError::BridgeEventNotActionable)
+        bridge_action_from_sui_event(event, *tx_digest, event_idx)
     }
 
     pub async fn get_bridge_summary(&self) -> BridgeResult<BridgeSummary> {
@@ -381,6 +373,23 @@ where
     }
 }
 
+/// Converts the `event_idx`-th event of Sui transaction `tx_digest` into a `BridgeAction`.
+pub fn bridge_action_from_sui_event(
+    event: &SuiEvent,
+    tx_digest: TransactionDigest,
+    event_idx: u16,
+) -> BridgeResult<BridgeAction> {
+    if event.type_.address.as_ref() != BRIDGE_PACKAGE_ID.as_ref() {
+        return Err(BridgeError::BridgeEventInUnrecognizedSuiPackage);
+    }
+    let bridge_event = SuiBridgeEvent::try_from_sui_event(event)?
+        .ok_or(BridgeError::NoBridgeEventsInTxPosition)?;
+
+    bridge_event
+        .try_into_bridge_action(tx_digest, event_idx)
+        .ok_or(BridgeError::BridgeEventNotActionable)
+}
+
 /// Use a trait to abstract over the SuiSDKClient and SuiMockClient for testing.
 #[async_trait]
 pub trait SuiClientInner: Send + Sync {
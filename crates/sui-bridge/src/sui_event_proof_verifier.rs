@@ -0,0 +1,503 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifies Sui bridge events against checkpoints certified by the Sui validator committee,
+//! so that a bridge node does not have to trust the events reported by its fullnode.
+//!
+//! The verifier starts from a trusted committee (usually the genesis committee) and advances
+//! it epoch by epoch using the `next_epoch_committee` of certified end of epoch checkpoints.
+//! An event is accepted only if the checkpoint that includes its transaction is signed by the
+//! committee of that epoch and the event is part of the certified transaction events, as
+//! checked by `sui_light_client::verify_proof`.
+
+use crate::config::SuiEventProofVerificationConfig;
+use crate::error::{BridgeError, BridgeResult};
+use crate::sui_client::bridge_action_from_sui_event;
+use crate::types::BridgeAction;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use sui_config::genesis::Genesis;
+use sui_json_rpc_types::{BcsEvent, SuiEvent};
+use sui_light_client::{construct_proof, verify_proof, Proof, ProofTarget};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::digests::TransactionDigest;
+use sui_types::event::{Event, EventID};
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData,
+};
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// Source of checkpoint data for `SuiEventProofVerifier`. Nothing returned by it is trusted,
+/// everything is verified against the committee before use.
+#[async_trait]
+pub trait CheckpointClient: Send + Sync {
+    async fn get_transaction_checkpoint(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> BridgeResult<Option<CheckpointSequenceNumber>>;
+
+    async fn get_checkpoint_summary(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> BridgeResult<CertifiedCheckpointSummary>;
+
+    async fn get_full_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> BridgeResult<CheckpointData>;
+}
+
+#[async_trait]
+impl CheckpointClient for sui_rpc_api::Client {
+    async fn get_transaction_checkpoint(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> BridgeResult<Option<CheckpointSequenceNumber>> {
+        self.get_transaction_checkpoint(tx_digest)
+            .await
+            .map_err(|e| BridgeError::ProviderError(format!("Failed to get tx checkpoint: {e}")))
+    }
+
+    async fn get_checkpoint_summary(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> BridgeResult<CertifiedCheckpointSummary> {
+        self.get_checkpoint_summary(sequence_number)
+            .await
+            .map_err(|e| {
+                BridgeError::ProviderError(format!(
+                    "Failed to get checkpoint summary {sequence_number}: {e}"
+                ))
+            })
+    }
+
+    async fn get_full_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> BridgeResult<CheckpointData> {
+        self.get_full_checkpoint(sequence_number)
+            .await
+            .map_err(|e| {
+                BridgeError::ProviderError(format!(
+                    "Failed to get checkpoint {sequence_number}: {e}"
+                ))
+            })
+    }
+}
+
+struct VerifiedCommittees {
+    committees: BTreeMap<EpochId, Committee>,
+    // A checkpoint no later than the first checkpoint of the latest verified epoch.
+    latest_epoch_start: CheckpointSequenceNumber,
+}
+
+pub struct SuiEventProofVerifier<C = sui_rpc_api::Client> {
+    client: C,
+    committees: Mutex<VerifiedCommittees>,
+}
+
+impl SuiEventProofVerifier<sui_rpc_api::Client> {
+    pub fn new_from_config(config: &SuiEventProofVerificationConfig) -> anyhow::Result<Self> {
+        let client = sui_rpc_api::Client::new(config.checkpoint_rpc_url.as_str())?;
+        let genesis_committee = Genesis::load(&config.genesis_path)?
+            .committee()
+            .map_err(|e| anyhow!("Cannot load genesis committee: {e}"))?;
+        Ok(Self::new(client, genesis_committee))
+    }
+}
+
+impl<C> SuiEventProofVerifier<C>
+where
+    C: CheckpointClient,
+{
+    /// Create a verifier that trusts `committee` for its epoch and derives the committees
+    /// of later epochs from certified end of epoch checkpoints.
+    pub fn new(client: C, committee: Committee) -> Self {
+        Self {
+            client,
+            committees: Mutex::new(VerifiedCommittees {
+                committees: BTreeMap::from([(committee.epoch(), committee)]),
+                latest_epoch_start: 0,
+            }),
+        }
+    }
+
+    /// Verify that `action` is exactly the action derived from the `event_idx`-th event of
+    /// Sui transaction `tx_digest`, as certified by the Sui committee.
+    pub async fn verify_bridge_action(
+        &self,
+        tx_digest: TransactionDigest,
+        event_idx: u16,
+        action: &BridgeAction,
+    ) -> BridgeResult<()> {
+        let event = self.get_certified_event(tx_digest, event_idx).await?;
+        let certified_action = bridge_action_from_sui_event(
+            &to_sui_event(event, tx_digest, event_idx),
+            tx_digest,
+            event_idx,
+        )?;
+        if certified_action != *action {
+            return Err(BridgeError::SuiEventProofError(format!(
+                "Action {:?} does not match certified action {:?}",
+                action, certified_action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the `event_idx`-th event of Sui transaction `tx_digest` after proving it
+    /// against a certified checkpoint.
+    pub async fn get_certified_event(
+        &self,
+        tx_digest: TransactionDigest,
+        event_idx: u16,
+    ) -> BridgeResult<Event> {
+        let checkpoint = self
+            .client
+            .get_transaction_checkpoint(tx_digest)
+            .await?
+            .ok_or(BridgeError::TxNotFinalized)?;
+        let data = self.client.get_full_checkpoint(checkpoint).await?;
+        let committee = self
+            .get_committee(data.checkpoint_summary.epoch(), checkpoint)
+            .await?;
+
+        let event = data
+            .transactions
+            .iter()
+            .find(|tx| tx.transaction.digest() == &tx_digest)
+            .ok_or_else(|| {
+                BridgeError::SuiEventProofError(format!(
+                    "Transaction {tx_digest} not found in checkpoint {checkpoint}"
+                ))
+            })?
+            .events
+            .as_ref()
+            .and_then(|events| events.data.get(event_idx as usize))
+            .cloned()
+            .ok_or_else(|| {
+                BridgeError::SuiEventProofError(format!(
+                    "Event {event_idx} not found in transaction {tx_digest}"
+                ))
+            })?;
+        let event_id = EventID {
+            tx_digest,
+            event_seq: event_idx as u64,
+        };
+        let proof = construct_proof(ProofTarget::new().add_event(event_id, event.clone()), &data)
+            .map_err(|e| BridgeError::SuiEventProofError(e.to_string()))?;
+        verify_proof(&committee, &proof)
+            .map_err(|e| BridgeError::SuiEventProofError(e.to_string()))?;
+        Ok(event)
+    }
+
+    /// Returns the verified committee of `epoch`, walking forward through end of epoch
+    /// checkpoints if needed. `later_checkpoint` must be a checkpoint of `epoch`, which
+    /// bounds the search for the end of epoch checkpoints before it.
+    async fn get_committee(
+        &self,
+        epoch: EpochId,
+        later_checkpoint: CheckpointSequenceNumber,
+    ) -> BridgeResult<Committee> {
+        let mut verified = self.committees.lock().await;
+        loop {
+            if let Some(committee) = verified.committees.get(&epoch) {
+                return Ok(committee.clone());
+            }
+            // Unwrap safe: the map is initialized with the trusted committee
+            let (latest_epoch, latest_committee) = verified
+                .committees
+                .last_key_value()
+                .map(|(epoch, committee)| (*epoch, committee.clone()))
+                .unwrap();
+            if epoch < latest_epoch {
+                return Err(BridgeError::SuiEventProofError(format!(
+                    "Epoch {epoch} predates the trusted committee"
+                )));
+            }
+
+            let end_of_epoch = self
+                .find_end_of_epoch_checkpoint(
+                    latest_epoch,
+                    verified.latest_epoch_start,
+                    later_checkpoint,
+                )
+                .await?;
+            let summary = self.client.get_checkpoint_summary(end_of_epoch).await?;
+            let next_committee = verify_end_of_epoch(&latest_committee, summary)?;
+            info!(
+                "Verified Sui committee of epoch {} at checkpoint {}",
+                next_committee.epoch(),
+                end_of_epoch
+            );
+            verified
+                .committees
+                .insert(next_committee.epoch(), next_committee);
+            verified.latest_epoch_start = end_of_epoch + 1;
+        }
+    }
+
+    /// Binary searches for the last checkpoint of `epoch` in `[low, high)`, where `high` is
+    /// a checkpoint of a later epoch. The result does not need to be trusted since the found
+    /// summary is verified afterwards.
+    async fn find_end_of_epoch_checkpoint(
+        &self,
+        epoch: EpochId,
+        mut low: CheckpointSequenceNumber,
+        mut high: CheckpointSequenceNumber,
+    ) -> BridgeResult<CheckpointSequenceNumber> {
+        if low >= high {
+            return Err(BridgeError::SuiEventProofError(format!(
+                "Cannot find end of epoch {epoch} checkpoint before checkpoint {high}"
+            )));
+        }
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.client.get_checkpoint_summary(mid).await?.epoch() <= epoch {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+}
+
+fn to_sui_event(event: Event, tx_digest: TransactionDigest, event_idx: u16) -> SuiEvent {
+    SuiEvent {
+        id: EventID {
+            tx_digest,
+            event_seq: event_idx as u64,
+        },
+        package_id: event.package_id,
+        transaction_module: event.transaction_module,
+        sender: event.sender,
+        type_: event.type_,
+        parsed_json: serde_json::Value::Null,
+        bcs: BcsEvent::new(event.contents),
+        timestamp_ms: None,
+    }
+}
+
+fn verify_end_of_epoch(
+    committee: &Committee,
+    summary: CertifiedCheckpointSummary,
+) -> BridgeResult<Committee> {
+    let proof = Proof {
+        targets: ProofTarget::new(),
+        checkpoint_summary: summary,
+        contents_proof: None,
+    };
+    verify_proof(committee, &proof).map_err(|e| BridgeError::SuiEventProofError(e.to_string()))?;
+    let summary = proof.checkpoint_summary;
+    match &summary.end_of_epoch_data {
+        Some(EndOfEpochData {
+            next_epoch_committee,
+            ..
+        }) if summary.epoch() == committee.epoch() => Ok(Committee::new(
+            summary.epoch() + 1,
+            next_epoch_committee.iter().cloned().collect(),
+        )),
+        _ => Err(BridgeError::SuiEventProofError(format!(
+            "Checkpoint {} is not the end of epoch {}",
+            summary.sequence_number,
+            committee.epoch()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{init_all_struct_tags, MoveTokenDepositedEvent, SuiToEthTokenBridgeV1};
+    use ethers::types::Address as EthAddress;
+    use move_core_types::identifier::Identifier;
+    use std::collections::HashMap;
+    use sui_types::base_types::{AuthorityName, SuiAddress};
+    use sui_types::bridge::{BridgeChainId, TOKEN_ID_SUI};
+    use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+    use sui_types::messages_checkpoint::CheckpointSummary;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+    use sui_types::BRIDGE_PACKAGE_ID;
+
+    #[derive(Default)]
+    struct MockCheckpointClient {
+        checkpoints: HashMap<CheckpointSequenceNumber, CheckpointData>,
+        tx_checkpoints: HashMap<TransactionDigest, CheckpointSequenceNumber>,
+    }
+
+    impl MockCheckpointClient {
+        fn add_checkpoint(&mut self, mut data: CheckpointData) {
+            // The test builder always signs with the epoch 0 committee, re-sign for the
+            // epoch of the checkpoint.
+            data.checkpoint_summary = certify(data.checkpoint_summary.data().clone());
+            let sequence_number = data.checkpoint_summary.sequence_number;
+            for tx in &data.transactions {
+                self.tx_checkpoints
+                    .insert(*tx.transaction.digest(), sequence_number);
+            }
+            self.checkpoints.insert(sequence_number, data);
+        }
+
+        fn get(&self, sequence_number: CheckpointSequenceNumber) -> BridgeResult<CheckpointData> {
+            self.checkpoints
+                .get(&sequence_number)
+                .cloned()
+                .ok_or(BridgeError::Generic("checkpoint not found".into()))
+        }
+    }
+
+    #[async_trait]
+    impl CheckpointClient for MockCheckpointClient {
+        async fn get_transaction_checkpoint(
+            &self,
+            tx_digest: TransactionDigest,
+        ) -> BridgeResult<Option<CheckpointSequenceNumber>> {
+            Ok(self.tx_checkpoints.get(&tx_digest).cloned())
+        }
+
+        async fn get_checkpoint_summary(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> BridgeResult<CertifiedCheckpointSummary> {
+            Ok(self.get(sequence_number)?.checkpoint_summary)
+        }
+
+        async fn get_full_checkpoint(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> BridgeResult<CheckpointData> {
+            self.get(sequence_number)
+        }
+    }
+
+    fn test_committee(epoch: EpochId) -> Committee {
+        let (committee, _) = Committee::new_simple_test_committee();
+        Committee::new(epoch, committee.voting_rights.into_iter().collect())
+    }
+
+    fn certify(summary: CheckpointSummary) -> CertifiedCheckpointSummary {
+        let (_, keys) = Committee::new_simple_test_committee();
+        let committee = test_committee(summary.epoch);
+        CertifiedCheckpointSummary::new_from_keypairs_for_testing(summary, &keys, &committee)
+    }
+
+    fn bridge_event(amount: u64) -> Event {
+        init_all_struct_tags();
+        let event = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiTestnet as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthSepolia as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_SUI,
+            amount_sui_adjusted: amount,
+        };
+        Event {
+            package_id: BRIDGE_PACKAGE_ID,
+            transaction_module: Identifier::new("bridge").unwrap(),
+            sender: SuiAddress::random_for_testing_only(),
+            type_: SuiToEthTokenBridgeV1.get().unwrap().clone(),
+            contents: bcs::to_bytes(&event).unwrap(),
+        }
+    }
+
+    fn action_with_amount(action: &BridgeAction, amount: u64) -> BridgeAction {
+        let BridgeAction::SuiToEthBridgeAction(mut action) = action.clone() else {
+            panic!("Expected SuiToEthBridgeAction");
+        };
+        action.sui_bridge_event.amount_sui_adjusted = amount;
+        BridgeAction::SuiToEthBridgeAction(action)
+    }
+
+    #[tokio::test]
+    async fn test_verify_bridge_action_across_epochs() {
+        let mut client = MockCheckpointClient::default();
+        let mut builder = TestCheckpointDataBuilder::new(0);
+        // Epoch 0: checkpoint 0, end of epoch checkpoint 1
+        client.add_checkpoint(builder.build_checkpoint());
+        client.add_checkpoint(builder.advance_epoch(false));
+        // Epoch 1: checkpoint 2, checkpoint 3 with the bridge event
+        client.add_checkpoint(builder.build_checkpoint());
+        let mut builder = builder
+            .start_transaction(0)
+            .with_events(vec![bridge_event(100)])
+            .finish_transaction();
+        let data = builder.build_checkpoint();
+        let tx_digest = *data.transactions[0].transaction.digest();
+        let event = data.transactions[0].events.clone().unwrap().data[0].clone();
+        client.add_checkpoint(data);
+
+        let action =
+            bridge_action_from_sui_event(&to_sui_event(event, tx_digest, 0), tx_digest, 0).unwrap();
+
+        let verifier = SuiEventProofVerifier::new(client, test_committee(0));
+        verifier
+            .verify_bridge_action(tx_digest, 0, &action)
+            .await
+            .unwrap();
+        assert_eq!(
+            verifier
+                .committees
+                .lock()
+                .await
+                .committees
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        // A forged amount does not match the certified event
+        let forged = action_with_amount(&action, 1_000_000);
+        assert!(matches!(
+            verifier.verify_bridge_action(tx_digest, 0, &forged).await,
+            Err(BridgeError::SuiEventProofError(_))
+        ));
+
+        // There is no event at index 1
+        assert!(matches!(
+            verifier.verify_bridge_action(tx_digest, 1, &action).await,
+            Err(BridgeError::SuiEventProofError(_))
+        ));
+
+        // Unknown transactions are not finalized yet
+        assert!(matches!(
+            verifier
+                .verify_bridge_action(TransactionDigest::random(), 0, &action)
+                .await,
+            Err(BridgeError::TxNotFinalized)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_bridge_action_with_untrusted_committee() {
+        let mut client = MockCheckpointClient::default();
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .with_events(vec![bridge_event(100)])
+            .finish_transaction();
+        let data = builder.build_checkpoint();
+        let tx_digest = *data.transactions[0].transaction.digest();
+        client.add_checkpoint(data);
+
+        // Checkpoints signed by a committee other than the trusted one are rejected
+        let trusted_committee = Committee::new(
+            0,
+            (0..4)
+                .map(|_| {
+                    let (_, kp): (_, AuthorityKeyPair) = get_key_pair();
+                    (AuthorityName::from(kp.public()), 2500)
+                })
+                .collect(),
+        );
+        let verifier = SuiEventProofVerifier::new(client, trusted_committee);
+        assert!(matches!(
+            verifier.get_certified_event(tx_digest, 0).await,
+            Err(BridgeError::SuiEventProofError(_))
+        ));
+    }
+}
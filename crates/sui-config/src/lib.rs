@@ -14,6 +14,7 @@ pub mod genesis;
 pub mod local_ip_utils;
 pub mod node;
 pub mod node_config_metrics;
+pub mod node_config_validation;
 pub mod object_storage_config;
 pub mod p2p;
 pub mod transaction_deny_config;
@@ -206,6 +206,11 @@ pub struct NodeConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub execution_time_observer_config: Option<ExecutionTimeObserverConfig>,
 
+    /// When set, a sample of transactions is timed at each stage of the validator pipeline.
+    /// The latency breakdown of traced transactions can be retrieved from the admin interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_latency_tracing_config: Option<TransactionLatencyTracingConfig>,
+
     /// Allow overriding the chain for testing purposes. For instance, it allows you to
     /// create a test network that believes it is mainnet or testnet. Attempting to
     /// override this value on production networks will result in an error.
@@ -310,6 +315,32 @@ impl ExecutionTimeObserverConfig {
     }
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransactionLatencyTracingConfig {
+    /// Fraction of transactions to trace, between 0 and 1. Transactions are sampled by digest,
+    /// so all validators using the same sample rate trace the same transactions.
+    ///
+    /// If unspecified, this will default to `0.001`.
+    pub sample_rate: Option<f64>,
+
+    /// Number of traced transactions for which the latency breakdown is kept in memory.
+    ///
+    /// If unspecified, this will default to `10_000`.
+    pub max_traced_transactions: Option<NonZeroUsize>,
+}
+
+impl TransactionLatencyTracingConfig {
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate.unwrap_or(0.001).clamp(0.0, 1.0)
+    }
+
+    pub fn max_traced_transactions(&self) -> NonZeroUsize {
+        self.max_traced_transactions
+            .unwrap_or(nonzero!(10_000usize))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ExecutionCacheConfig {
@@ -339,6 +370,12 @@ pub enum ExecutionCacheConfig {
         /// Number of uncommitted transactions at which to refuse new transaction
         /// submissions. Defaults to backpressure_threshold if unset.
         backpressure_threshold_for_rpc: Option<u64>,
+
+        /// Fraction of object accesses sampled for the hot object report. Defaults to 0, i.e.
+        /// no accesses are sampled until sampling is enabled through the admin interface.
+        object_access_sample_rate: Option<f64>,
+        /// Number of objects in the hot object report. Defaults to 100.
+        hot_object_report_size: Option<usize>,
     },
 }
 
@@ -357,6 +394,8 @@ impl Default for ExecutionCacheConfig {
             effect_cache_size: None,
             events_cache_size: None,
             transaction_objects_cache_size: None,
+            object_access_sample_rate: None,
+            hot_object_report_size: None,
         }
     }
 }
@@ -511,6 +550,32 @@ impl ExecutionCacheConfig {
                 } => backpressure_threshold_for_rpc.unwrap_or(self.backpressure_threshold()),
             })
     }
+
+    pub fn object_access_sample_rate(&self) -> f64 {
+        std::env::var("SUI_OBJECT_ACCESS_SAMPLE_RATE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| match self {
+                ExecutionCacheConfig::PassthroughCache => fatal!("invalid cache config"),
+                ExecutionCacheConfig::WritebackCache {
+                    object_access_sample_rate,
+                    ..
+                } => object_access_sample_rate.unwrap_or(0.0),
+            })
+    }
+
+    pub fn hot_object_report_size(&self) -> usize {
+        std::env::var("SUI_HOT_OBJECT_REPORT_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| match self {
+                ExecutionCacheConfig::PassthroughCache => fatal!("invalid cache config"),
+                ExecutionCacheConfig::WritebackCache {
+                    hot_object_report_size,
+                    ..
+                } => hot_object_report_size.unwrap_or(100),
+            })
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -1334,6 +1399,17 @@ impl KeyPairWithPath {
         }
     }
 
+    /// Loads the keypair without panicking, returning an error if the keypair file is missing
+    /// or invalid.
+    pub fn try_load(&self) -> Result<Arc<SuiKeyPair>> {
+        match &self.location {
+            KeyPairLocation::InPlace { value } => Ok(value.clone()),
+            KeyPairLocation::File { path } => read_keypair_from_file(path)
+                .map(Arc::new)
+                .map_err(|e| anyhow::anyhow!("Invalid keypair file at path {:?}: {e}", path)),
+        }
+    }
+
     pub fn keypair(&self) -> &SuiKeyPair {
         self.keypair
             .get_or_init(|| match &self.location {
@@ -1396,6 +1472,17 @@ impl AuthorityKeyPairWithPath {
         }
     }
 
+    /// Loads the authority keypair without panicking, returning an error if the keypair file is
+    /// missing or invalid.
+    pub fn try_load(&self) -> Result<Arc<AuthorityKeyPair>> {
+        match &self.location {
+            AuthorityKeyPairLocation::InPlace { value } => Ok(value.clone()),
+            AuthorityKeyPairLocation::File { path } => read_authority_keypair_from_file(path)
+                .map(Arc::new)
+                .map_err(|e| anyhow::anyhow!("Invalid authority keypair file {:?}: {e}", path)),
+        }
+    }
+
     pub fn authority_keypair(&self) -> &AuthorityKeyPair {
         self.keypair
             .get_or_init(|| match &self.location {
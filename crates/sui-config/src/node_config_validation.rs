@@ -0,0 +1,635 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Semantic validation and diffing of `NodeConfig`s, meant to be run as a pre-flight check
+//! before a config is rolled out to nodes.
+
+use crate::NodeConfig;
+use anyhow::Result;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::net::{SocketAddr, ToSocketAddrs};
+use sui_types::crypto::{KeypairTraits, SuiKeyPair};
+use sui_types::multiaddr::{Multiaddr, Protocol};
+
+/// Fields which are expected to differ between any two nodes, such as keys, addresses and
+/// paths. They are usually ignored when comparing a config against the defaults.
+pub const INSTANCE_SPECIFIC_FIELDS: &[&str] = &[
+    "protocol-key-pair",
+    "worker-key-pair",
+    "account-key-pair",
+    "network-key-pair",
+    "db-path",
+    "network-address",
+    "json-rpc-address",
+    "metrics-address",
+    "admin-interface-port",
+    "genesis",
+    "p2p-config.listen-address",
+    "p2p-config.external-address",
+    "p2p-config.seed-peers",
+    "consensus-config.db-path",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The node will likely run, but not the way the operator intended.
+    Warning,
+    /// The node will fail to start or misbehave at runtime.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found while validating a `NodeConfig`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    /// Path of the offending field in the YAML config, e.g. `p2p-config.seed-peers[0]`.
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.field, self.message)
+    }
+}
+
+#[derive(Default)]
+struct Issues(Vec<ConfigIssue>);
+
+impl Issues {
+    fn error(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, field.into(), message.into());
+    }
+
+    fn warning(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, field.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, field: String, message: String) {
+        self.0.push(ConfigIssue {
+            severity,
+            field,
+            message,
+        });
+    }
+}
+
+impl NodeConfig {
+    /// Performs semantic validation of the config, beyond what deserialization checks.
+    /// Issues are sorted with errors first. Note that seed peers with DNS addresses are
+    /// resolved as part of validation.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Issues::default();
+        self.validate_keys(&mut issues);
+        self.validate_paths(&mut issues);
+        self.validate_addresses(&mut issues);
+        self.validate_pruning(&mut issues);
+        self.validate_p2p(&mut issues);
+        self.validate_state_sync_and_archive(&mut issues);
+
+        let mut issues = issues.0;
+        issues.sort_by(|a, b| b.severity.cmp(&a.severity));
+        issues
+    }
+
+    fn validate_keys(&self, issues: &mut Issues) {
+        if let Err(e) = self.protocol_key_pair.try_load() {
+            issues.error("protocol-key-pair", e.to_string());
+        }
+        if let Err(e) = self.account_key_pair.try_load() {
+            issues.error("account-key-pair", e.to_string());
+        }
+        for (field, key_pair) in [
+            ("network-key-pair", &self.network_key_pair),
+            ("worker-key-pair", &self.worker_key_pair),
+        ] {
+            match key_pair.try_load() {
+                Ok(kp) if !matches!(kp.as_ref(), SuiKeyPair::Ed25519(_)) => issues.error(
+                    field,
+                    format!(
+                        "only Ed25519 keys are allowed, found {}",
+                        kp.public().scheme()
+                    ),
+                ),
+                Ok(_) => (),
+                Err(e) => issues.error(field, e.to_string()),
+            }
+        }
+    }
+
+    fn validate_paths(&self, issues: &mut Issues) {
+        if self.db_path.exists() && !self.db_path.is_dir() {
+            issues.error(
+                "db-path",
+                format!("{} exists but is not a directory", self.db_path.display()),
+            );
+        }
+        if let Err(e) = self.genesis.genesis() {
+            issues.error("genesis", format!("cannot load genesis: {e}"));
+        }
+        if let Some(consensus_config) = &self.consensus_config {
+            if consensus_config.db_path == self.db_path {
+                issues.error(
+                    "consensus-config.db-path",
+                    "must be different from `db-path`, consensus uses its own database",
+                );
+            }
+        }
+    }
+
+    fn validate_addresses(&self, issues: &mut Issues) {
+        let mut listeners: Vec<(&str, SocketAddr)> = vec![
+            ("json-rpc-address", self.json_rpc_address),
+            ("metrics-address", self.metrics_address),
+            (
+                "admin-interface-port",
+                SocketAddr::from(([127, 0, 0, 1], self.admin_interface_port)),
+            ),
+        ];
+        if self.consensus_config.is_some() {
+            match self.network_address.to_socket_addr() {
+                Ok(addr) => listeners.push(("network-address", addr)),
+                Err(e) => issues.error(
+                    "network-address",
+                    format!("validators must listen on an ip/tcp address: {e}"),
+                ),
+            }
+        }
+
+        for (i, (field, addr)) in listeners.iter().enumerate() {
+            if addr.port() == 0 {
+                continue;
+            }
+            for (other_field, other) in &listeners[i + 1..] {
+                let ips_overlap = addr.ip() == other.ip()
+                    || addr.ip().is_unspecified()
+                    || other.ip().is_unspecified();
+                if addr.port() == other.port() && ips_overlap {
+                    issues.error(
+                        *other_field,
+                        format!("port {} is already used by `{field}`", addr.port()),
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_pruning(&self, issues: &mut Issues) {
+        const FIELD: &str = "authority-store-pruning-config";
+        let config = &self.authority_store_pruning_config;
+        let is_validator = self.consensus_config.is_some();
+
+        if is_validator && config.num_epochs_to_retain > 0 && config.num_epochs_to_retain < u64::MAX
+        {
+            issues.warning(
+                format!("{FIELD}.num-epochs-to-retain"),
+                format!(
+                    "validators only support 0 (aggressive pruning) or u64::MAX (no pruning), {} will be replaced by 0",
+                    config.num_epochs_to_retain
+                ),
+            );
+        }
+        if config.max_checkpoints_in_batch == 0 {
+            issues.error(
+                format!("{FIELD}.max-checkpoints-in-batch"),
+                "must be greater than 0, otherwise the pruner never makes progress",
+            );
+        }
+        if config.max_transactions_in_batch == 0 {
+            issues.error(
+                format!("{FIELD}.max-transactions-in-batch"),
+                "must be greater than 0, otherwise the pruner never makes progress",
+            );
+        }
+        if config.periodic_compaction_threshold_days == Some(0) {
+            issues.warning(
+                format!("{FIELD}.periodic-compaction-threshold-days"),
+                "0 days makes every SST file eligible for periodic compaction",
+            );
+        }
+        if let Some(checkpoint_epochs) = config.num_epochs_to_retain_for_checkpoints {
+            if checkpoint_epochs < 2 {
+                issues.warning(
+                    format!("{FIELD}.num-epochs-to-retain-for-checkpoints"),
+                    format!("must be at least 2, {checkpoint_epochs} will be rounded up to 2"),
+                );
+            }
+        }
+        if let (Some(index_epochs), Some(checkpoint_epochs)) = (
+            config.num_epochs_to_retain_for_indexes,
+            config.num_epochs_to_retain_for_checkpoints(),
+        ) {
+            if index_epochs > checkpoint_epochs {
+                issues.warning(
+                    format!("{FIELD}.num-epochs-to-retain-for-indexes"),
+                    format!(
+                        "indexes are retained for {index_epochs} epochs but the transactions they point to only for {checkpoint_epochs} epochs"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn validate_p2p(&self, issues: &mut Issues) {
+        let p2p_config = &self.p2p_config;
+        match &p2p_config.external_address {
+            None if self.consensus_config.is_some() => issues.error(
+                "p2p-config.external-address",
+                "validators must advertise an external address to their peers",
+            ),
+            Some(address) if address.to_anemo_address().is_err() => issues.error(
+                "p2p-config.external-address",
+                format!("{address} is not of the form /[ip4,ip6,dns]/<host>/udp/<port>"),
+            ),
+            _ => (),
+        }
+
+        let own_peer_id = match self.network_key_pair.try_load().as_deref() {
+            Ok(SuiKeyPair::Ed25519(kp)) => Some(anemo::PeerId(kp.public().0.to_bytes())),
+            _ => None,
+        };
+        let mut seen_addresses = BTreeSet::new();
+        let mut seen_peer_ids = BTreeMap::new();
+        for (i, seed_peer) in p2p_config.seed_peers.iter().enumerate() {
+            let field = format!("p2p-config.seed-peers[{i}]");
+            if let Err(e) = check_seed_peer_address(&seed_peer.address) {
+                issues.error(&field, e);
+            }
+            if p2p_config.external_address.as_ref() == Some(&seed_peer.address) {
+                issues.warning(&field, "is this node's own external address");
+            }
+            if !seen_addresses.insert(&seed_peer.address) {
+                issues.warning(&field, format!("duplicate address {}", seed_peer.address));
+            }
+            match seed_peer.peer_id {
+                None => issues.warning(
+                    &field,
+                    "has no peer-id, the identity of the peer will not be verified",
+                ),
+                Some(peer_id) if Some(peer_id) == own_peer_id => {
+                    issues.warning(&field, "is this node's own peer-id")
+                }
+                Some(peer_id) => {
+                    if let Some(first) = seen_peer_ids.insert(peer_id, i) {
+                        issues.warning(
+                            &field,
+                            format!("peer-id is already used by p2p-config.seed-peers[{first}]"),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_state_sync_and_archive(&self, issues: &mut Issues) {
+        if let Some(state_sync) = &self.p2p_config.state_sync {
+            const FIELD: &str = "p2p-config.state-sync";
+            for (field, value) in [
+                (
+                    "checkpoint-header-download-concurrency",
+                    state_sync.checkpoint_header_download_concurrency,
+                ),
+                (
+                    "checkpoint-content-download-concurrency",
+                    state_sync.checkpoint_content_download_concurrency,
+                ),
+                ("mailbox-capacity", state_sync.mailbox_capacity),
+            ] {
+                if value == Some(0) {
+                    issues.error(
+                        format!("{FIELD}.{field}"),
+                        "must be greater than 0, otherwise state sync stalls",
+                    );
+                }
+            }
+            if state_sync.checkpoint_content_download_tx_concurrency == Some(0) {
+                issues.error(
+                    format!("{FIELD}.checkpoint-content-download-tx-concurrency"),
+                    "must be greater than 0, otherwise state sync stalls",
+                );
+            }
+            if state_sync.timeout_ms == Some(0)
+                || state_sync.checkpoint_content_timeout_ms == Some(0)
+            {
+                issues.error(
+                    FIELD,
+                    "timeouts must be greater than 0, otherwise every request times out",
+                );
+            }
+            let mut pinned = BTreeMap::new();
+            for (sequence_number, digest) in &state_sync.pinned_checkpoints {
+                if let Some(other) = pinned.insert(sequence_number, digest) {
+                    if other != digest {
+                        issues.error(
+                            format!("{FIELD}.pinned-checkpoints"),
+                            format!(
+                                "checkpoint {sequence_number} is pinned to both {other} and {digest}"
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        for (i, archive) in self.state_archive_read_config.iter().enumerate() {
+            let field = format!("state-archive-read-config[{i}]");
+            if archive.object_store_config.is_none() {
+                issues.warning(
+                    &field,
+                    "has no object-store-config and is ignored, state sync cannot fall back to it",
+                );
+            } else if archive.concurrency == 0 {
+                issues.warning(
+                    format!("{field}.concurrency"),
+                    "0 is not a valid concurrency, 5 will be used instead",
+                );
+            }
+        }
+        let has_archive_fallback = self
+            .state_archive_read_config
+            .iter()
+            .any(|archive| archive.object_store_config.is_some());
+        let prunes_checkpoints = self
+            .authority_store_pruning_config
+            .num_epochs_to_retain_for_checkpoints()
+            .is_some();
+        if prunes_checkpoints && !has_archive_fallback && self.consensus_config.is_none() {
+            issues.warning(
+                "state-archive-read-config",
+                "checkpoints are pruned but no archive is configured; if this node falls behind the pruning horizon of its peers, state sync cannot catch up",
+            );
+        }
+        if !prunes_checkpoints
+            && self
+                .state_archive_read_config
+                .iter()
+                .any(|archive| archive.use_for_pruning_watermark)
+        {
+            issues.warning(
+                "state-archive-read-config",
+                "use-for-pruning-watermark has no effect since checkpoints are not pruned",
+            );
+        }
+    }
+}
+
+fn check_seed_peer_address(address: &Multiaddr) -> Result<(), String> {
+    let mut iter = address.iter();
+    match (iter.next(), iter.next()) {
+        (Some(Protocol::Ip4(_) | Protocol::Ip6(_)), Some(Protocol::Udp(_))) => Ok(()),
+        (Some(Protocol::Dns(host)), Some(Protocol::Udp(port))) => (host.as_ref(), port)
+            .to_socket_addrs()
+            .map_err(|e| format!("cannot resolve {host}: {e}"))?
+            .next()
+            .map(|_| ())
+            .ok_or_else(|| format!("{host} does not resolve to any address")),
+        _ => Err(format!(
+            "{address} is not of the form /[ip4,ip6,dns]/<host>/udp/<port>"
+        )),
+    }
+}
+
+/// A field which differs between two configs. `None` means the field is absent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigDifference {
+    pub field: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl ConfigDifference {
+    /// Whether the field is expected to differ between nodes, see `INSTANCE_SPECIFIC_FIELDS`.
+    pub fn is_instance_specific(&self) -> bool {
+        INSTANCE_SPECIFIC_FIELDS.iter().any(|prefix| {
+            self.field == *prefix
+                || self.field.starts_with(&format!("{prefix}."))
+                || self.field.starts_with(&format!("{prefix}["))
+        })
+    }
+}
+
+impl fmt::Display for ConfigDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let render = |value: &Option<String>| value.clone().unwrap_or_else(|| "<unset>".into());
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            render(&self.left),
+            render(&self.right)
+        )
+    }
+}
+
+/// Compares two configs field by field, in their serialized form. Values of in-place key pairs
+/// are redacted.
+pub fn diff_node_configs(left: &NodeConfig, right: &NodeConfig) -> Result<Vec<ConfigDifference>> {
+    let left = serde_yaml::to_value(left)?;
+    let right = serde_yaml::to_value(right)?;
+    let mut differences = vec![];
+    diff_values(String::new(), &left, &right, &mut differences);
+    Ok(differences)
+}
+
+fn diff_values(path: String, left: &Value, right: &Value, out: &mut Vec<ConfigDifference>) {
+    if left == right {
+        return;
+    }
+    match (left, right) {
+        (Value::Mapping(left), Value::Mapping(right)) => {
+            let keys = left
+                .keys()
+                .chain(right.keys().filter(|key| !left.contains_key(*key)));
+            for key in keys {
+                let key_path = if path.is_empty() {
+                    render_key(key)
+                } else {
+                    format!("{path}.{}", render_key(key))
+                };
+                diff_optional_values(key_path, left.get(key), right.get(key), out);
+            }
+        }
+        (Value::Sequence(left), Value::Sequence(right)) => {
+            for i in 0..left.len().max(right.len()) {
+                diff_optional_values(format!("{path}[{i}]"), left.get(i), right.get(i), out);
+            }
+        }
+        _ => out.push(difference(path, Some(left), Some(right))),
+    }
+}
+
+fn diff_optional_values(
+    path: String,
+    left: Option<&Value>,
+    right: Option<&Value>,
+    out: &mut Vec<ConfigDifference>,
+) {
+    match (left, right) {
+        (Some(left), Some(right)) => diff_values(path, left, right, out),
+        (left, right) => out.push(difference(path, left, right)),
+    }
+}
+
+fn difference(path: String, left: Option<&Value>, right: Option<&Value>) -> ConfigDifference {
+    let redact = path.contains("key-pair");
+    let render = |value: &Value| {
+        if redact {
+            "<redacted>".to_string()
+        } else {
+            serde_yaml::to_string(value)
+                .map(|s| s.trim_start_matches("---").trim().to_string())
+                .unwrap_or_else(|e| format!("<{e}>"))
+        }
+    };
+    ConfigDifference {
+        left: left.map(render),
+        right: right.map(render),
+        field: path,
+    }
+}
+
+fn render_key(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = include_str!("../data/fullnode-template.yaml");
+
+    fn template() -> NodeConfig {
+        serde_yaml::from_str(TEMPLATE).unwrap()
+    }
+
+    fn has_issue(issues: &[ConfigIssue], severity: Severity, field: &str) -> bool {
+        issues
+            .iter()
+            .any(|issue| issue.severity == severity && issue.field == field)
+    }
+
+    #[test]
+    fn validate_fullnode_template() {
+        let issues = template().validate();
+        // The template points to a genesis blob which does not exist here.
+        assert!(has_issue(&issues, Severity::Error, "genesis"));
+        assert!(!has_issue(
+            &issues,
+            Severity::Error,
+            "authority-store-pruning-config.max-checkpoints-in-batch"
+        ));
+        // Errors are sorted first.
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn validate_pruning_and_ports() {
+        let mut config = template();
+        config.authority_store_pruning_config.num_epochs_to_retain = 5;
+        config
+            .authority_store_pruning_config
+            .set_num_epochs_to_retain_for_checkpoints(Some(3));
+        config
+            .authority_store_pruning_config
+            .max_transactions_in_batch = 0;
+        config.metrics_address = config.json_rpc_address;
+
+        let issues = config.validate();
+        // Retaining objects for longer than checkpoints is a valid configuration.
+        assert!(!issues.iter().any(|issue| issue.field
+            == "authority-store-pruning-config.num-epochs-to-retain-for-checkpoints"));
+        assert!(has_issue(
+            &issues,
+            Severity::Error,
+            "authority-store-pruning-config.max-transactions-in-batch"
+        ));
+        assert!(has_issue(&issues, Severity::Error, "metrics-address"));
+    }
+
+    #[test]
+    fn validate_seed_peers() {
+        let mut config = template();
+        let address: Multiaddr = "/ip4/127.0.0.1/udp/8084".parse().unwrap();
+        config.p2p_config.seed_peers = vec![
+            crate::p2p::SeedPeer {
+                peer_id: None,
+                address: address.clone(),
+            },
+            crate::p2p::SeedPeer {
+                peer_id: None,
+                address,
+            },
+            crate::p2p::SeedPeer {
+                peer_id: None,
+                address: "/ip4/127.0.0.1/tcp/8084".parse().unwrap(),
+            },
+        ];
+
+        let issues = config.validate();
+        assert!(!has_issue(
+            &issues,
+            Severity::Error,
+            "p2p-config.seed-peers[0]"
+        ));
+        assert!(has_issue(
+            &issues,
+            Severity::Warning,
+            "p2p-config.seed-peers[1]"
+        ));
+        assert!(has_issue(
+            &issues,
+            Severity::Error,
+            "p2p-config.seed-peers[2]"
+        ));
+    }
+
+    #[test]
+    fn diff_configs() {
+        let left = template();
+        let mut right = template();
+        right.authority_store_pruning_config.num_epochs_to_retain = 2;
+        right.state_archive_read_config.clear();
+
+        let differences = diff_node_configs(&left, &right).unwrap();
+        let fields: Vec<_> = differences
+            .iter()
+            .filter(|d| !d.is_instance_specific())
+            .map(|d| d.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "authority-store-pruning-config.num-epochs-to-retain",
+                "state-archive-read-config[0]",
+            ]
+        );
+        let retain = &differences
+            .iter()
+            .find(|d| d.field == "authority-store-pruning-config.num-epochs-to-retain")
+            .unwrap();
+        assert_eq!(retain.left.as_deref(), Some("1"));
+        assert_eq!(retain.right.as_deref(), Some("2"));
+
+        // Key pairs are generated per config and must not be printed.
+        assert!(differences
+            .iter()
+            .filter(|d| d.field.contains("key-pair"))
+            .all(|d| d.left.as_deref() == Some("<redacted>")));
+    }
+}
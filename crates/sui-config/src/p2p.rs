@@ -3,6 +3,8 @@
 
 use std::{net::SocketAddr, num::NonZeroU32, time::Duration};
 
+use crate::object_storage_config::ObjectStoreConfig;
+
 use serde::{Deserialize, Serialize};
 use sui_types::{
     messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber},
@@ -31,6 +33,8 @@ pub struct P2pConfig {
     pub discovery: Option<DiscoveryConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub randomness: Option<RandomnessConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_reputation: Option<PeerReputationConfig>,
     /// Size in bytes above which network messages are considered excessively large. Excessively
     /// large messages will still be handled, but logged and reported in metrics for debugging.
     ///
@@ -53,6 +57,7 @@ impl Default for P2pConfig {
             state_sync: None,
             discovery: None,
             randomness: None,
+            peer_reputation: None,
             excessive_message_size: None,
         }
     }
@@ -187,6 +192,28 @@ pub struct StateSyncConfig {
     /// If unspecified, this will set to default value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wait_interval_when_no_peer_to_sync_content_ms: Option<u64>,
+
+    /// Remote object store or local directory of per-checkpoint `CheckpointData` blobs, named
+    /// `<sequence_number>.chk`, in the format written by `sui-data-ingestion`.
+    ///
+    /// When set, contents of checkpoints no longer available from peers are synced from this
+    /// store, and a node far behind its peers bootstraps from it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_ingestion_store: Option<ObjectStoreConfig>,
+
+    /// Set the upper bound on the number of checkpoints to be downloaded concurrently from the
+    /// checkpoint ingestion store.
+    ///
+    /// If unspecified, this will default to `100`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_ingestion_download_concurrency: Option<usize>,
+
+    /// Number of checkpoints the node needs to be behind the highest checkpoint known from peers
+    /// to sync checkpoints from the checkpoint ingestion store instead of peers.
+    ///
+    /// If unspecified, this will default to `10,000`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_ingestion_bootstrap_threshold: Option<u64>,
 }
 
 impl StateSyncConfig {
@@ -252,6 +279,20 @@ impl StateSyncConfig {
             .unwrap_or(self.default_wait_interval_when_no_peer_to_sync_content())
     }
 
+    pub fn checkpoint_ingestion_download_concurrency(&self) -> usize {
+        const CHECKPOINT_INGESTION_DOWNLOAD_CONCURRENCY: usize = 100;
+
+        self.checkpoint_ingestion_download_concurrency
+            .unwrap_or(CHECKPOINT_INGESTION_DOWNLOAD_CONCURRENCY)
+    }
+
+    pub fn checkpoint_ingestion_bootstrap_threshold(&self) -> u64 {
+        const CHECKPOINT_INGESTION_BOOTSTRAP_THRESHOLD: u64 = 10_000;
+
+        self.checkpoint_ingestion_bootstrap_threshold
+            .unwrap_or(CHECKPOINT_INGESTION_BOOTSTRAP_THRESHOLD)
+    }
+
     fn default_wait_interval_when_no_peer_to_sync_content(&self) -> Duration {
         if cfg!(msim) {
             Duration::from_secs(5)
@@ -347,6 +388,64 @@ impl DiscoveryConfig {
     }
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PeerReputationConfig {
+    /// Number of consecutive failed or timed out requests after which a peer is temporarily
+    /// banned.
+    ///
+    /// If unspecified, this will default to `5`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_consecutive_failures: Option<u32>,
+
+    /// Number of invalid responses (e.g. checkpoints or contents that fail verification) after
+    /// which a peer is temporarily banned.
+    ///
+    /// If unspecified, this will default to `3`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_invalid_responses: Option<u32>,
+
+    /// Duration of the first ban of a peer. The duration doubles with every subsequent ban of the
+    /// same peer, up to `max_ban_duration_ms`.
+    ///
+    /// If unspecified, this will default to `60,000` milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban_duration_ms: Option<u64>,
+
+    /// Upper bound on the duration of a ban.
+    ///
+    /// If unspecified, this will default to `3,600,000` milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_ban_duration_ms: Option<u64>,
+}
+
+impl PeerReputationConfig {
+    pub fn max_consecutive_failures(&self) -> u32 {
+        const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+        self.max_consecutive_failures
+            .unwrap_or(MAX_CONSECUTIVE_FAILURES)
+    }
+
+    pub fn max_invalid_responses(&self) -> u32 {
+        const MAX_INVALID_RESPONSES: u32 = 3;
+
+        self.max_invalid_responses.unwrap_or(MAX_INVALID_RESPONSES)
+    }
+
+    pub fn ban_duration(&self) -> Duration {
+        const BAN_DURATION_MS: u64 = 60_000;
+
+        Duration::from_millis(self.ban_duration_ms.unwrap_or(BAN_DURATION_MS))
+    }
+
+    pub fn max_ban_duration(&self) -> Duration {
+        const MAX_BAN_DURATION_MS: u64 = 3_600_000;
+
+        Duration::from_millis(self.max_ban_duration_ms.unwrap_or(MAX_BAN_DURATION_MS))
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct RandomnessConfig {
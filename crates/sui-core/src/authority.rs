@@ -4,10 +4,12 @@
 
 use crate::congestion_tracker::CongestionTracker;
 use crate::consensus_adapter::ConsensusOverloadChecker;
+use crate::execution_cache::object_access_stats::ObjectAccessStats;
 use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::execution_cache::TransactionCacheRead;
 use crate::jsonrpc_index::CoinIndexKey2;
 use crate::rpc_index::RpcIndexStore;
+use crate::transaction_latency_tracer::{TransactionLatencyTracer, TransactionStage};
 use crate::transaction_outputs::TransactionOutputs;
 use crate::verify_indexes::{fix_indexes, verify_indexes};
 use anyhow::anyhow;
@@ -123,7 +125,8 @@ use sui_types::messages_grpc::{
 use sui_types::metrics::{BytecodeVerifierMetrics, LimitsMetrics};
 use sui_types::object::{MoveObject, Owner, PastObjectRead, OBJECT_START_VERSION};
 use sui_types::storage::{
-    BackingPackageStore, BackingStore, ObjectKey, ObjectOrTombstone, ObjectStore, WriteKind,
+    BackingPackageStore, BackingStore, ObjectCongestionInfo, ObjectKey, ObjectOrTombstone,
+    ObjectStore, WriteKind,
 };
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::SuiSystemStateTrait;
@@ -145,7 +148,7 @@ use crate::authority::authority_per_epoch_store::{AuthorityPerEpochStore, CertTx
 use crate::authority::authority_per_epoch_store_pruner::AuthorityPerEpochStorePruner;
 use crate::authority::authority_store::{ExecutionLockReadGuard, ObjectLockStatus};
 use crate::authority::authority_store_pruner::{
-    AuthorityStorePruner, EPOCH_DURATION_MS_FOR_TESTING,
+    AuthorityStorePruner, AuthorityStorePrunerHandle, EPOCH_DURATION_MS_FOR_TESTING,
 };
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
@@ -797,6 +800,18 @@ impl AuthorityMetrics {
     }
 }
 
+/// Outcome of executing the advance epoch transaction without committing it, see
+/// [`AuthorityState::dry_run_advance_epoch_tx`].
+pub struct AdvanceEpochTxDryRun {
+    pub next_epoch_protocol_version: ProtocolVersion,
+    /// References of the system packages in the next epoch. Empty when the protocol version is
+    /// not upgraded.
+    pub next_epoch_system_packages: Vec<ObjectRef>,
+    /// System state written by the transaction.
+    pub system_state: SuiSystemState,
+    pub effects: TransactionEffects,
+}
+
 /// a Trait object for `Signer` that is:
 /// - Pin, i.e. confined to one place in memory (we don't want to copy private keys).
 /// - Sync, i.e. can be safely shared between threads.
@@ -840,7 +855,7 @@ pub struct AuthorityState {
     tx_execution_shutdown: Mutex<Option<oneshot::Sender<()>>>,
 
     pub metrics: Arc<AuthorityMetrics>,
-    _pruner: AuthorityStorePruner,
+    pruner: AuthorityStorePruner,
     _authority_per_epoch_pruner: AuthorityPerEpochStorePruner,
 
     /// Take db checkpoints of different dbs
@@ -857,6 +872,12 @@ pub struct AuthorityState {
     chain_identifier: ChainIdentifier,
 
     pub(crate) congestion_tracker: Arc<CongestionTracker>,
+
+    /// Records the latency breakdown of sampled transactions through the validator pipeline.
+    transaction_latency_tracer: Arc<TransactionLatencyTracer>,
+
+    /// Sampled accesses to objects, used to report the hottest ones.
+    object_access_stats: Arc<ObjectAccessStats>,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -993,6 +1014,8 @@ impl AuthorityState {
             tx_digest,
             signed_transaction.clone(),
         )?;
+        self.transaction_latency_tracer
+            .record(&tx_digest, TransactionStage::Signed);
 
         Ok(signed_transaction)
     }
@@ -1255,6 +1278,8 @@ impl AuthorityState {
         }
 
         let execution_start_time = Instant::now();
+        self.transaction_latency_tracer
+            .record(tx_digest, TransactionStage::ExecutionStarted);
 
         let input_objects =
             self.read_objects_for_execution(tx_guard.as_lock_guard(), certificate, epoch_store)?;
@@ -1279,6 +1304,8 @@ impl AuthorityState {
             .tap_ok(
             |(fx, _, _)| debug!(?tx_digest, fx_digest=?fx.digest(), "process_certificate succeeded"),
         )?;
+        self.transaction_latency_tracer
+            .record(tx_digest, TransactionStage::Executed);
 
         epoch_store.record_local_execution_time(
             certificate.data().transaction_data(),
@@ -2900,6 +2927,9 @@ impl AuthorityState {
 
         let metrics = Arc::new(AuthorityMetrics::new(prometheus_registry));
         let (tx_ready_certificates, rx_ready_certificates) = unbounded_channel();
+        let transaction_latency_tracer = Arc::new(TransactionLatencyTracer::new(
+            config.transaction_latency_tracing_config.as_ref(),
+        ));
         let transaction_manager = Arc::new(TransactionManager::new(
             execution_cache_trait_pointers.object_cache_reader.clone(),
             execution_cache_trait_pointers
@@ -2908,6 +2938,7 @@ impl AuthorityState {
             &epoch_store,
             tx_ready_certificates,
             metrics.clone(),
+            transaction_latency_tracer.clone(),
         ));
         let (tx_execution_shutdown, rx_execution_shutdown) = oneshot::channel();
 
@@ -2915,7 +2946,7 @@ impl AuthorityState {
             epoch_store.get_parent_path(),
             &config.authority_store_pruning_config,
         );
-        let _pruner = AuthorityStorePruner::new(
+        let pruner = AuthorityStorePruner::new(
             store.perpetual_tables.clone(),
             checkpoint_store.clone(),
             rpc_index.clone(),
@@ -2945,7 +2976,7 @@ impl AuthorityState {
             transaction_manager,
             tx_execution_shutdown: Mutex::new(Some(tx_execution_shutdown)),
             metrics,
-            _pruner,
+            pruner,
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
             config,
@@ -2953,6 +2984,8 @@ impl AuthorityState {
             validator_tx_finalizer,
             chain_identifier,
             congestion_tracker: Arc::new(CongestionTracker::new()),
+            transaction_latency_tracer,
+            object_access_stats: store.object_access_stats().clone(),
         });
 
         let state_clone = Arc::downgrade(&state);
@@ -3049,6 +3082,14 @@ impl AuthorityState {
         &self.transaction_manager
     }
 
+    pub fn transaction_latency_tracer(&self) -> &Arc<TransactionLatencyTracer> {
+        &self.transaction_latency_tracer
+    }
+
+    pub fn object_access_stats(&self) -> &Arc<ObjectAccessStats> {
+        &self.object_access_stats
+    }
+
     /// Adds transactions / certificates to transaction manager for ordered execution.
     pub fn enqueue_transactions_for_execution(
         &self,
@@ -3554,6 +3595,13 @@ impl AuthorityState {
         self.chain_identifier
     }
 
+    /// Congestion status of a shared object as observed in recently executed checkpoints, or
+    /// `None` if the object has not been congested recently.
+    pub fn get_object_congestion_info(&self, object_id: ObjectID) -> Option<ObjectCongestionInfo> {
+        self.congestion_tracker
+            .get_object_congestion_info(object_id)
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn get_move_object<T>(&self, object_id: &ObjectID) -> SuiResult<T>
     where
@@ -3962,6 +4010,10 @@ impl AuthorityState {
             .get_transactions(filter, cursor, limit, reverse)
     }
 
+    pub fn pruner_handle(&self) -> &Arc<AuthorityStorePrunerHandle> {
+        self.pruner.handle()
+    }
+
     pub fn get_checkpoint_store(&self) -> &Arc<CheckpointStore> {
         &self.checkpoint_store
     }
@@ -5099,28 +5151,17 @@ impl AuthorityState {
         Some(tx)
     }
 
-    /// Creates and execute the advance epoch transaction to effects without committing it to the database.
-    /// The effects of the change epoch tx are only written to the database after a certified checkpoint has been
-    /// formed and executed by CheckpointExecutor.
-    ///
-    /// When a framework upgraded has been decided on, but the validator does not have the new
-    /// versions of the packages locally, the validator cannot form the ChangeEpochTx. In this case
-    /// it returns Err, indicating that the checkpoint builder should give up trying to make the
-    /// final checkpoint. As long as the network is able to create a certified checkpoint (which
-    /// should be ensured by the capabilities vote), it will arrive via state sync and be executed
-    /// by CheckpointExecutor.
-    #[instrument(level = "error", skip_all)]
-    pub async fn create_and_execute_advance_epoch_tx(
+    /// Creates the transaction advancing the network to the next epoch, along with the protocol
+    /// version and system packages it upgrades to. Returns Err if the upgraded system packages
+    /// are not available in this binary.
+    async fn create_advance_epoch_tx(
         &self,
         epoch_store: &Arc<AuthorityPerEpochStore>,
         gas_cost_summary: &GasCostSummary,
-        checkpoint: CheckpointSequenceNumber,
         epoch_start_timestamp_ms: CheckpointTimestamp,
         end_of_epoch_observation_keys: Vec<ExecutionTimeObservationKey>,
-        // This may be less than `checkpoint - 1` if the end-of-epoch PendingCheckpoint produced
-        // >1 checkpoint.
         last_checkpoint: CheckpointSequenceNumber,
-    ) -> anyhow::Result<(SuiSystemState, TransactionEffects)> {
+    ) -> anyhow::Result<(VerifiedTransaction, ProtocolVersion, Vec<ObjectRef>)> {
         let mut txns = Vec::new();
 
         if let Some(tx) = self.create_authenticator_state_tx(epoch_store) {
@@ -5228,6 +5269,42 @@ impl AuthorityState {
             )
         };
 
+        Ok((tx, next_epoch_protocol_version, next_epoch_system_packages))
+    }
+
+    /// Creates and execute the advance epoch transaction to effects without committing it to the database.
+    /// The effects of the change epoch tx are only written to the database after a certified checkpoint has been
+    /// formed and executed by CheckpointExecutor.
+    ///
+    /// When a framework upgraded has been decided on, but the validator does not have the new
+    /// versions of the packages locally, the validator cannot form the ChangeEpochTx. In this case
+    /// it returns Err, indicating that the checkpoint builder should give up trying to make the
+    /// final checkpoint. As long as the network is able to create a certified checkpoint (which
+    /// should be ensured by the capabilities vote), it will arrive via state sync and be executed
+    /// by CheckpointExecutor.
+    #[instrument(level = "error", skip_all)]
+    pub async fn create_and_execute_advance_epoch_tx(
+        &self,
+        epoch_store: &Arc<AuthorityPerEpochStore>,
+        gas_cost_summary: &GasCostSummary,
+        checkpoint: CheckpointSequenceNumber,
+        epoch_start_timestamp_ms: CheckpointTimestamp,
+        end_of_epoch_observation_keys: Vec<ExecutionTimeObservationKey>,
+        // This may be less than `checkpoint - 1` if the end-of-epoch PendingCheckpoint produced
+        // >1 checkpoint.
+        last_checkpoint: CheckpointSequenceNumber,
+    ) -> anyhow::Result<(SuiSystemState, TransactionEffects)> {
+        let (tx, next_epoch_protocol_version, next_epoch_system_packages) = self
+            .create_advance_epoch_tx(
+                epoch_store,
+                gas_cost_summary,
+                epoch_start_timestamp_ms,
+                end_of_epoch_observation_keys,
+                last_checkpoint,
+            )
+            .await?;
+        let next_epoch = epoch_store.epoch() + 1;
+
         let executable_tx = VerifiedExecutableTransaction::new_from_checkpoint(
             tx.clone(),
             epoch_store.epoch(),
@@ -5296,6 +5373,52 @@ impl AuthorityState {
         Ok((system_obj, effects))
     }
 
+    /// Creates and executes the advance epoch transaction against the current state, like
+    /// `create_and_execute_advance_epoch_tx` does when building the last checkpoint of the epoch,
+    /// but without making the transaction available to state sync. Shared object versions of the
+    /// transaction are still assigned in the epoch store, so this must only run against a copy of
+    /// the database of a node.
+    pub async fn dry_run_advance_epoch_tx(
+        &self,
+        epoch_store: &Arc<AuthorityPerEpochStore>,
+        gas_cost_summary: &GasCostSummary,
+        checkpoint: CheckpointSequenceNumber,
+        epoch_start_timestamp_ms: CheckpointTimestamp,
+        end_of_epoch_observation_keys: Vec<ExecutionTimeObservationKey>,
+        last_checkpoint: CheckpointSequenceNumber,
+    ) -> anyhow::Result<AdvanceEpochTxDryRun> {
+        let (tx, next_epoch_protocol_version, next_epoch_system_packages) = self
+            .create_advance_epoch_tx(
+                epoch_store,
+                gas_cost_summary,
+                epoch_start_timestamp_ms,
+                end_of_epoch_observation_keys,
+                last_checkpoint,
+            )
+            .await?;
+        let executable_tx =
+            VerifiedExecutableTransaction::new_from_checkpoint(tx, epoch_store.epoch(), checkpoint);
+
+        let tx_lock = epoch_store.acquire_tx_lock(executable_tx.digest());
+        let execution_guard = self.execution_lock_for_executable_transaction(&executable_tx)?;
+        epoch_store.assign_shared_object_versions_idempotent(
+            self.get_object_cache_reader().as_ref(),
+            &[executable_tx.clone()],
+        )?;
+        let input_objects =
+            self.read_objects_for_execution(&tx_lock, &executable_tx, epoch_store)?;
+        let (temporary_store, effects, _timings, _execution_error_opt) =
+            self.prepare_certificate(&execution_guard, &executable_tx, input_objects, epoch_store)?;
+        let system_state = get_sui_system_state(&temporary_store.written)?;
+
+        Ok(AdvanceEpochTxDryRun {
+            next_epoch_protocol_version,
+            next_epoch_system_packages,
+            system_state,
+            effects,
+        })
+    }
+
     /// This function is called at the very end of the epoch.
     /// This step is required before updating new epoch in the db and calling reopen_epoch_db.
     #[instrument(level = "error", skip_all)]
@@ -124,6 +124,7 @@ use crate::module_cache_metrics::ResolverMetrics;
 use crate::post_consensus_tx_reorder::PostConsensusTxReorder;
 use crate::signature_verifier::*;
 use crate::stake_aggregator::{GenericMultiStakeAggregator, StakeAggregator};
+use crate::transaction_latency_tracer::{TransactionLatencyTracer, TransactionStage};
 
 /// The key where the latest consensus index is stored in the database.
 // TODO: Make a single table (e.g., called `variables`) storing all our lonely variables in one place.
@@ -1997,7 +1998,7 @@ impl AuthorityPerEpochStore {
         Ok(txns)
     }
 
-    pub fn get_all_deferred_transactions_for_test(
+    pub fn get_all_deferred_transactions(
         &self,
     ) -> Vec<(DeferralKey, Vec<VerifiedSequencedConsensusTransaction>)> {
         self.consensus_output_cache
@@ -2836,6 +2837,7 @@ impl AuthorityPerEpochStore {
         tx_reader: &dyn TransactionCacheRead,
         consensus_commit_info: &ConsensusCommitInfo,
         authority_metrics: &Arc<AuthorityMetrics>,
+        latency_tracer: &TransactionLatencyTracer,
     ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         // Split transactions into different types for processing.
         let verified_transactions: Vec<_> = transactions
@@ -3082,6 +3084,7 @@ impl AuthorityPerEpochStore {
                 randomness_round,
                 execution_time_estimator.as_ref(),
                 authority_metrics,
+                latency_tracer,
             )
             .await?;
         self.finish_consensus_certificate_process(&verified_transactions);
@@ -3364,6 +3367,7 @@ impl AuthorityPerEpochStore {
                 skip_consensus_commit_prologue_in_test,
             ),
             authority_metrics,
+            &TransactionLatencyTracer::new(None),
         )
         .await
     }
@@ -3426,6 +3430,7 @@ impl AuthorityPerEpochStore {
         randomness_round: Option<RandomnessRound>,
         execution_time_estimator: Option<&ExecutionTimeEstimator>,
         authority_metrics: &Arc<AuthorityMetrics>,
+        latency_tracer: &TransactionLatencyTracer,
     ) -> SuiResult<(
         Vec<VerifiedExecutableTransaction>,    // transactions to schedule
         Vec<SequencedConsensusTransactionKey>, // keys to notify as complete
@@ -3499,6 +3504,9 @@ impl AuthorityPerEpochStore {
                         .or_default()
                         .push(tx.clone());
                     filter_roots = true;
+                    if let Some(digest) = tx.0.transaction.executable_transaction_digest() {
+                        latency_tracer.record(&digest, TransactionStage::Deferred);
+                    }
                     if tx.0.transaction.is_executable_transaction() {
                         // Notify consensus adapter that the consensus handler has received the transaction.
                         notifications.push(key.clone());
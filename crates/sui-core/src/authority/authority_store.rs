@@ -11,6 +11,7 @@ use crate::authority::authority_store_pruner::{
 };
 use crate::authority::authority_store_types::{get_store_object, StoreObject, StoreObjectWrapper};
 use crate::authority::epoch_start_configuration::{EpochFlag, EpochStartConfiguration};
+use crate::execution_cache::object_access_stats::ObjectAccessStats;
 use crate::rpc_index::RpcIndexStore;
 use crate::state_accumulator::AccumulatorStore;
 use crate::transaction_outputs::TransactionOutputs;
@@ -21,6 +22,7 @@ use itertools::izip;
 use move_core_types::resolver::ModuleResolver;
 use serde::{Deserialize, Serialize};
 use sui_config::node::AuthorityStorePruningConfig;
+use sui_config::ExecutionCacheConfig;
 use sui_macros::fail_point_arg;
 use sui_storage::mutex_table::{MutexGuard, MutexTable};
 use sui_types::accumulator::Accumulator;
@@ -121,6 +123,9 @@ pub struct AuthorityStore {
     enable_epoch_sui_conservation_check: bool,
 
     metrics: AuthorityStoreMetrics,
+
+    /// Sampled accesses to objects through the execution cache and this store.
+    object_access_stats: Arc<ObjectAccessStats>,
 }
 
 pub type ExecutionLockReadGuard<'a> = tokio::sync::RwLockReadGuard<'a, EpochId>;
@@ -171,6 +176,7 @@ impl AuthorityStore {
             genesis,
             perpetual_tables,
             enable_epoch_sui_conservation_check,
+            &config.execution_cache,
             registry,
         )
         .await?;
@@ -219,13 +225,21 @@ impl AuthorityStore {
         // TODO: Since we always start at genesis, the committee should be technically the same
         // as the genesis committee.
         assert_eq!(committee.epoch, 0);
-        Self::open_inner(genesis, perpetual_tables, true, &Registry::new()).await
+        Self::open_inner(
+            genesis,
+            perpetual_tables,
+            true,
+            &Default::default(),
+            &Registry::new(),
+        )
+        .await
     }
 
     async fn open_inner(
         genesis: &Genesis,
         perpetual_tables: Arc<AuthorityPerpetualTables>,
         enable_epoch_sui_conservation_check: bool,
+        execution_cache_config: &ExecutionCacheConfig,
         registry: &Registry,
     ) -> SuiResult<Arc<Self>> {
         let store = Arc::new(Self {
@@ -235,6 +249,11 @@ impl AuthorityStore {
                 NotifyRead::<EpochId, (CheckpointSequenceNumber, Accumulator)>::new(),
             enable_epoch_sui_conservation_check,
             metrics: AuthorityStoreMetrics::new(registry),
+            object_access_stats: Arc::new(ObjectAccessStats::new(
+                execution_cache_config.object_access_sample_rate(),
+                execution_cache_config.hot_object_report_size(),
+                registry,
+            )),
         });
         // Only initialize an empty database.
         if store
@@ -290,6 +309,7 @@ impl AuthorityStore {
         enable_epoch_sui_conservation_check: bool,
         registry: &Registry,
     ) -> SuiResult<Arc<Self>> {
+        let execution_cache_config = ExecutionCacheConfig::default();
         let store = Arc::new(Self {
             mutex_table: MutexTable::new(NUM_SHARDS),
             perpetual_tables,
@@ -297,10 +317,19 @@ impl AuthorityStore {
                 NotifyRead::<EpochId, (CheckpointSequenceNumber, Accumulator)>::new(),
             enable_epoch_sui_conservation_check,
             metrics: AuthorityStoreMetrics::new(registry),
+            object_access_stats: Arc::new(ObjectAccessStats::new(
+                execution_cache_config.object_access_sample_rate(),
+                execution_cache_config.hot_object_report_size(),
+                registry,
+            )),
         });
         Ok(store)
     }
 
+    pub fn object_access_stats(&self) -> &Arc<ObjectAccessStats> {
+        &self.object_access_stats
+    }
+
     pub fn get_recovery_epoch_at_restart(&self) -> SuiResult<EpochId> {
         self.perpetual_tables.get_recovery_epoch_at_restart()
     }
@@ -529,6 +558,7 @@ impl AuthorityStore {
         object_id: &ObjectID,
         version: VersionNumber,
     ) -> SuiResult<bool> {
+        self.object_access_stats.record_db_read(object_id, None);
         Ok(self
             .perpetual_tables
             .objects
@@ -536,6 +566,9 @@ impl AuthorityStore {
     }
 
     pub fn multi_object_exists_by_key(&self, object_keys: &[ObjectKey]) -> SuiResult<Vec<bool>> {
+        for ObjectKey(object_id, _) in object_keys {
+            self.object_access_stats.record_db_read(object_id, None);
+        }
         Ok(self
             .perpetual_tables
             .objects
@@ -581,11 +614,13 @@ impl AuthorityStore {
         let mut ret = vec![];
 
         for (idx, w) in wrappers.into_iter().enumerate() {
-            ret.push(
-                w.map(|object| self.perpetual_tables.object(&object_keys[idx], object))
-                    .transpose()?
-                    .flatten(),
-            );
+            let object = w
+                .map(|object| self.perpetual_tables.object(&object_keys[idx], object))
+                .transpose()?
+                .flatten();
+            self.object_access_stats
+                .record_db_read(&object_keys[idx].0, object.as_ref());
+            ret.push(object);
         }
         Ok(ret)
     }
@@ -965,7 +1000,7 @@ impl AuthorityStore {
                     // no need to re-write lock
                     continue;
                 } else {
-                    // TODO: add metrics here
+                    self.object_access_stats.record_lock_conflict(&obj_ref.0);
                     info!(prev_tx_digest = ?previous_tx_digest,
                           cur_tx_digest = ?tx_digest,
                           "Cannot acquire lock: conflicting transaction!");
@@ -1297,6 +1332,7 @@ impl AuthorityStore {
         &self,
         object_id: ObjectID,
     ) -> Result<Option<ObjectRef>, SuiError> {
+        self.object_access_stats.record_db_read(&object_id, None);
         self.perpetual_tables
             .get_latest_object_ref_or_tombstone(object_id)
     }
@@ -1324,6 +1360,7 @@ impl AuthorityStore {
             .perpetual_tables
             .get_latest_object_or_tombstone(object_id)?
         else {
+            self.object_access_stats.record_db_read(&object_id, None);
             return Ok(None);
         };
 
@@ -1331,6 +1368,7 @@ impl AuthorityStore {
             .perpetual_tables
             .tombstone_reference(&object_key, &store_object)?
         {
+            self.object_access_stats.record_db_read(&object_id, None);
             return Ok(Some((object_key, ObjectOrTombstone::Tombstone(object_ref))));
         }
 
@@ -1338,6 +1376,8 @@ impl AuthorityStore {
             .perpetual_tables
             .object(&object_key, store_object)?
             .expect("Non tombstone store object could not be converted to object");
+        self.object_access_stats
+            .record_db_read(&object_id, Some(&object));
 
         Ok(Some((object_key, ObjectOrTombstone::Object(object))))
     }
@@ -1822,11 +1862,17 @@ impl AccumulatorStore for AuthorityStore {
 impl ObjectStore for AuthorityStore {
     /// Read an object and return it, or Ok(None) if the object was not found.
     fn get_object(&self, object_id: &ObjectID) -> Option<Object> {
-        self.perpetual_tables.as_ref().get_object(object_id)
+        let object = self.perpetual_tables.as_ref().get_object(object_id);
+        self.object_access_stats
+            .record_db_read(object_id, object.as_ref());
+        object
     }
 
     fn get_object_by_key(&self, object_id: &ObjectID, version: VersionNumber) -> Option<Object> {
-        self.perpetual_tables.get_object_by_key(object_id, version)
+        let object = self.perpetual_tables.get_object_by_key(object_id, version);
+        self.object_access_stats
+            .record_db_read(object_id, object.as_ref());
+        object
     }
 }
 
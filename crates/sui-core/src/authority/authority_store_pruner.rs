@@ -10,12 +10,14 @@ use anyhow::anyhow;
 use bincode::Options;
 use mysten_metrics::{monitored_scope, spawn_monitored_task};
 use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use prometheus::{
     register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
     Registry,
 };
 use std::cmp::{max, min};
 use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, Weak};
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{sync::Arc, time::Duration};
@@ -56,6 +58,7 @@ static PERIODIC_PRUNING_TABLES: Lazy<BTreeSet<String>> = Lazy::new(|| {
 pub const EPOCH_DURATION_MS_FOR_TESTING: u64 = 24 * 60 * 60 * 1000;
 pub struct AuthorityStorePruner {
     _objects_pruner_cancel_handle: oneshot::Sender<()>,
+    handle: Arc<AuthorityStorePrunerHandle>,
 }
 
 static MIN_PRUNING_TICK_DURATION_MS: u64 = 10 * 1000;
@@ -124,6 +127,197 @@ impl AuthorityStorePruningMetrics {
     }
 }
 
+/// Subset of `AuthorityStorePruningConfig` that can be changed while the node is running.
+#[derive(Debug, Default, Clone)]
+pub struct PruningConfigUpdate {
+    pub num_epochs_to_retain: Option<u64>,
+    pub num_epochs_to_retain_for_checkpoints: Option<u64>,
+    pub max_checkpoints_in_batch: Option<usize>,
+    pub max_transactions_in_batch: Option<usize>,
+    pub periodic_compaction_threshold_days: Option<usize>,
+    pub smooth: Option<bool>,
+}
+
+#[derive(Debug)]
+pub struct PrunerStatus {
+    pub paused: bool,
+    pub highest_executed_checkpoint: Option<CheckpointSequenceNumber>,
+    pub highest_pruned_objects_checkpoint: CheckpointSequenceNumber,
+    pub highest_pruned_checkpoint: CheckpointSequenceNumber,
+    pub last_pruned_indexes_transaction: i64,
+    pub config: AuthorityStorePruningConfig,
+}
+
+/// Runtime controls shared between the pruning tasks and the node admin interface.
+pub struct AuthorityStorePrunerHandle {
+    config: RwLock<AuthorityStorePruningConfig>,
+    paused: AtomicBool,
+    is_validator: bool,
+    perpetual_db: Arc<AuthorityPerpetualTables>,
+    checkpoint_store: Arc<CheckpointStore>,
+    metrics: Arc<AuthorityStorePruningMetrics>,
+}
+
+impl AuthorityStorePrunerHandle {
+    fn new(
+        config: AuthorityStorePruningConfig,
+        is_validator: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        checkpoint_store: Arc<CheckpointStore>,
+        metrics: Arc<AuthorityStorePruningMetrics>,
+    ) -> Self {
+        let this = Self {
+            config: RwLock::new(config),
+            paused: AtomicBool::new(false),
+            is_validator,
+            perpetual_db,
+            checkpoint_store,
+            metrics,
+        };
+        this.update_retention_metrics();
+        this
+    }
+
+    /// Returns a snapshot of the current pruning config.
+    pub fn config(&self) -> AuthorityStorePruningConfig {
+        self.config.read().clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Stops scheduling new pruning batches. A batch that is already running is not interrupted.
+    pub fn pause(&self) {
+        info!("Pausing authority store pruner");
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        info!("Resuming authority store pruner");
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Applies `update` to the live pruning config and returns the resulting config.
+    /// The update is rejected as a whole if any of the new values is invalid.
+    pub fn update_config(
+        &self,
+        update: PruningConfigUpdate,
+    ) -> anyhow::Result<AuthorityStorePruningConfig> {
+        let mut config = self.config.write();
+        let mut new_config = config.clone();
+        if let Some(num_epochs_to_retain) = update.num_epochs_to_retain {
+            if self.is_validator && num_epochs_to_retain > 0 && num_epochs_to_retain < u64::MAX {
+                return Err(anyhow!(
+                    "validators only support num_epochs_to_retain = 0 or u64::MAX, got {}",
+                    num_epochs_to_retain
+                ));
+            }
+            new_config.num_epochs_to_retain = num_epochs_to_retain;
+        }
+        if let Some(num_epochs_to_retain) = update.num_epochs_to_retain_for_checkpoints {
+            new_config.num_epochs_to_retain_for_checkpoints = Some(num_epochs_to_retain);
+        }
+        if let Some(max_checkpoints_in_batch) = update.max_checkpoints_in_batch {
+            if max_checkpoints_in_batch == 0 {
+                return Err(anyhow!("max_checkpoints_in_batch must be positive"));
+            }
+            new_config.max_checkpoints_in_batch = max_checkpoints_in_batch;
+        }
+        if let Some(max_transactions_in_batch) = update.max_transactions_in_batch {
+            if max_transactions_in_batch == 0 {
+                return Err(anyhow!("max_transactions_in_batch must be positive"));
+            }
+            new_config.max_transactions_in_batch = max_transactions_in_batch;
+        }
+        if let Some(delay_days) = update.periodic_compaction_threshold_days {
+            new_config.periodic_compaction_threshold_days = Some(delay_days);
+        }
+        if let Some(smooth) = update.smooth {
+            new_config.smooth = smooth;
+        }
+        info!(
+            "Updating authority store pruning config to {:?}",
+            new_config
+        );
+        *config = new_config.clone();
+        drop(config);
+        self.update_retention_metrics();
+        Ok(new_config)
+    }
+
+    pub fn status(&self) -> anyhow::Result<PrunerStatus> {
+        Ok(PrunerStatus {
+            paused: self.is_paused(),
+            highest_executed_checkpoint: self
+                .checkpoint_store
+                .get_highest_executed_checkpoint_seq_number()?,
+            highest_pruned_objects_checkpoint: self.perpetual_db.get_highest_pruned_checkpoint()?,
+            highest_pruned_checkpoint: self
+                .checkpoint_store
+                .get_highest_pruned_checkpoint_seq_number()?,
+            last_pruned_indexes_transaction: self.metrics.last_pruned_indexes_transaction.get(),
+            config: self.config(),
+        })
+    }
+
+    /// Runs a full manual compaction of the given column families. Column families are looked up
+    /// in the perpetual tables first and then in the checkpoint store.
+    pub async fn compact(&self, column_families: Vec<String>) -> anyhow::Result<()> {
+        let perpetual_db = self.perpetual_db.clone();
+        let checkpoint_store = self.checkpoint_store.clone();
+        tokio::task::spawn_blocking(move || {
+            let dbs = [
+                perpetual_db.objects.rocksdb.clone(),
+                checkpoint_store
+                    .tables
+                    .certified_checkpoints
+                    .rocksdb
+                    .clone(),
+            ];
+            // Resolve all names before compacting anything so that a typo doesn't leave the
+            // request half done.
+            for cf_name in &column_families {
+                if !dbs.iter().any(|db| db.cf_handle(cf_name).is_some()) {
+                    return Err(anyhow!("unknown column family: {}", cf_name));
+                }
+            }
+            for cf_name in &column_families {
+                let _scope = monitored_scope("PrunerManualCompaction");
+                let db = dbs
+                    .iter()
+                    .find(|db| db.cf_handle(cf_name).is_some())
+                    .expect("column family was resolved above");
+                let cf = db
+                    .cf_handle(cf_name)
+                    .expect("column family was resolved above");
+                info!("Starting manual compaction of column family {}", cf_name);
+                let start = Instant::now();
+                db.compact_range_cf(&cf, None::<Vec<u8>>, None::<Vec<u8>>);
+                info!(
+                    "Finished manual compaction of column family {} in {:?}",
+                    cf_name,
+                    start.elapsed()
+                );
+            }
+            Ok(())
+        })
+        .await?
+    }
+
+    fn update_retention_metrics(&self) {
+        let config = self.config.read();
+        self.metrics
+            .num_epochs_to_retain_for_objects
+            .set(config.num_epochs_to_retain as i64);
+        self.metrics.num_epochs_to_retain_for_checkpoints.set(
+            config
+                .num_epochs_to_retain_for_checkpoints
+                .unwrap_or_default() as i64,
+        );
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PruningMode {
     Objects,
@@ -639,7 +833,7 @@ impl AuthorityStorePruner {
     }
 
     fn setup_pruning(
-        config: AuthorityStorePruningConfig,
+        handle: Arc<AuthorityStorePrunerHandle>,
         epoch_duration_ms: u64,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
@@ -650,6 +844,7 @@ impl AuthorityStorePruner {
         archive_readers: ArchiveReaderBalancer,
     ) -> Sender<()> {
         let (sender, mut recv) = tokio::sync::oneshot::channel();
+        let config = handle.config();
         debug!(
             "Starting object pruning service with num_epochs_to_retain={}",
             config.num_epochs_to_retain
@@ -670,53 +865,64 @@ impl AuthorityStorePruner {
             tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
 
         let perpetual_db_for_compaction = perpetual_db.clone();
-        if let Some(delay_days) = config.periodic_compaction_threshold_days {
-            spawn_monitored_task!(async move {
-                let last_processed = Arc::new(Mutex::new(HashMap::new()));
-                loop {
-                    let db = perpetual_db_for_compaction.clone();
-                    let state = Arc::clone(&last_processed);
-                    let result = tokio::task::spawn_blocking(move || {
-                        Self::compact_next_sst_file(db, delay_days, state)
-                    })
-                    .await;
-                    let mut sleep_interval_secs = 1;
-                    match result {
-                        Err(err) => error!("Failed to compact sst file: {:?}", err),
-                        Ok(Err(err)) => error!("Failed to compact sst file: {:?}", err),
-                        Ok(Ok(None)) => {
-                            sleep_interval_secs = 3600;
-                        }
-                        _ => {}
+        let handle_for_compaction = handle.clone();
+        spawn_monitored_task!(async move {
+            let last_processed = Arc::new(Mutex::new(HashMap::new()));
+            loop {
+                // Periodic compaction can be enabled at runtime through the admin interface,
+                // so the threshold is re-read on every iteration.
+                let Some(delay_days) = handle_for_compaction
+                    .config()
+                    .periodic_compaction_threshold_days
+                else {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    continue;
+                };
+                let db = perpetual_db_for_compaction.clone();
+                let state = Arc::clone(&last_processed);
+                let result = tokio::task::spawn_blocking(move || {
+                    Self::compact_next_sst_file(db, delay_days, state)
+                })
+                .await;
+                let mut sleep_interval_secs = 1;
+                match result {
+                    Err(err) => error!("Failed to compact sst file: {:?}", err),
+                    Ok(Err(err)) => error!("Failed to compact sst file: {:?}", err),
+                    Ok(Ok(None)) => {
+                        sleep_interval_secs = 3600;
                     }
-                    tokio::time::sleep(Duration::from_secs(sleep_interval_secs)).await;
+                    _ => {}
                 }
-            });
-        }
-
-        metrics
-            .num_epochs_to_retain_for_objects
-            .set(config.num_epochs_to_retain as i64);
-        metrics.num_epochs_to_retain_for_checkpoints.set(
-            config
-                .num_epochs_to_retain_for_checkpoints
-                .unwrap_or_default() as i64,
-        );
+                tokio::time::sleep(Duration::from_secs(sleep_interval_secs)).await;
+            }
+        });
 
         tokio::task::spawn(async move {
             loop {
                 tokio::select! {
-                    _ = objects_prune_interval.tick(), if config.num_epochs_to_retain != u64::MAX => {
-                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, rpc_index.as_deref(), pruner_db.as_ref(), config.clone(), metrics.clone(), epoch_duration_ms).await {
+                    _ = objects_prune_interval.tick() => {
+                        let config = handle.config();
+                        if handle.is_paused() || config.num_epochs_to_retain == u64::MAX {
+                            continue;
+                        }
+                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, rpc_index.as_deref(), pruner_db.as_ref(), config, metrics.clone(), epoch_duration_ms).await {
                             error!("Failed to prune objects: {:?}", err);
                         }
                     },
-                    _ = checkpoints_prune_interval.tick(), if !matches!(config.num_epochs_to_retain_for_checkpoints(), None | Some(u64::MAX) | Some(0)) => {
-                        if let Err(err) = Self::prune_checkpoints_for_eligible_epochs(&perpetual_db, &checkpoint_store, rpc_index.as_deref(), pruner_db.as_ref(), config.clone(), metrics.clone(), archive_readers.clone(), epoch_duration_ms).await {
+                    _ = checkpoints_prune_interval.tick() => {
+                        let config = handle.config();
+                        if handle.is_paused() || matches!(config.num_epochs_to_retain_for_checkpoints(), None | Some(u64::MAX) | Some(0)) {
+                            continue;
+                        }
+                        if let Err(err) = Self::prune_checkpoints_for_eligible_epochs(&perpetual_db, &checkpoint_store, rpc_index.as_deref(), pruner_db.as_ref(), config, metrics.clone(), archive_readers.clone(), epoch_duration_ms).await {
                             error!("Failed to prune checkpoints: {:?}", err);
                         }
                     },
-                    _ = indexes_prune_interval.tick(), if config.num_epochs_to_retain_for_indexes.is_some() => {
+                    _ = indexes_prune_interval.tick() => {
+                        let config = handle.config();
+                        if handle.is_paused() || config.num_epochs_to_retain_for_indexes.is_none() {
+                            continue;
+                        }
                         if let Err(err) = Self::prune_indexes(jsonrpc_index.as_deref(), &config, epoch_duration_ms, &metrics) {
                             error!("Failed to prune indexes: {:?}", err);
                         }
@@ -750,21 +956,34 @@ impl AuthorityStorePruner {
                 warn!("Consider using an aggressive pruner (num_epochs_to_retain = 0)");
             }
         }
+        let metrics = AuthorityStorePruningMetrics::new(registry);
+        let handle = Arc::new(AuthorityStorePrunerHandle::new(
+            pruning_config,
+            is_validator,
+            perpetual_db.clone(),
+            checkpoint_store.clone(),
+            metrics.clone(),
+        ));
         AuthorityStorePruner {
             _objects_pruner_cancel_handle: Self::setup_pruning(
-                pruning_config,
+                handle.clone(),
                 epoch_duration_ms,
                 perpetual_db,
                 checkpoint_store,
                 rpc_index,
                 jsonrpc_index,
                 pruner_db,
-                AuthorityStorePruningMetrics::new(registry),
+                metrics,
                 archive_readers,
             ),
+            handle,
         }
     }
 
+    pub fn handle(&self) -> &Arc<AuthorityStorePrunerHandle> {
+        &self.handle
+    }
+
     pub fn compact(perpetual_db: &Arc<AuthorityPerpetualTables>) -> Result<(), TypedStoreError> {
         perpetual_db.objects.compact_range(
             &ObjectKey(ObjectID::ZERO, SequenceNumber::MIN),
@@ -849,12 +1068,16 @@ mod tests {
     use std::{collections::HashSet, sync::Arc};
     use tracing::log::info;
 
-    use crate::authority::authority_store_pruner::AuthorityStorePruningMetrics;
+    use crate::authority::authority_store_pruner::{
+        AuthorityStorePrunerHandle, AuthorityStorePruningMetrics, PruningConfigUpdate,
+    };
     use crate::authority::authority_store_tables::AuthorityPerpetualTables;
     use crate::authority::authority_store_types::{
         get_store_object, StoreObject, StoreObjectWrapper,
     };
+    use crate::checkpoints::CheckpointStore;
     use prometheus::Registry;
+    use sui_config::node::AuthorityStorePruningConfig;
     use sui_types::base_types::ObjectDigest;
     use sui_types::effects::TransactionEffects;
     use sui_types::effects::TransactionEffectsAPI;
@@ -1020,6 +1243,65 @@ mod tests {
         assert_eq!(get_keys_after_pruning(&path).unwrap().len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_pruner_handle_config_update() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let handle = AuthorityStorePrunerHandle::new(
+            AuthorityStorePruningConfig::default(),
+            true,
+            perpetual_db,
+            CheckpointStore::new_for_tests(),
+            AuthorityStorePruningMetrics::new_for_test(),
+        );
+
+        let config = handle
+            .update_config(PruningConfigUpdate {
+                num_epochs_to_retain_for_checkpoints: Some(5),
+                max_checkpoints_in_batch: Some(100),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.num_epochs_to_retain_for_checkpoints, Some(5));
+        assert_eq!(config.max_checkpoints_in_batch, 100);
+        assert_eq!(handle.metrics.num_epochs_to_retain_for_checkpoints.get(), 5);
+
+        // Validators only support the aggressive pruner, and an invalid update is not
+        // partially applied.
+        assert!(handle
+            .update_config(PruningConfigUpdate {
+                num_epochs_to_retain: Some(2),
+                max_checkpoints_in_batch: Some(1),
+                ..Default::default()
+            })
+            .is_err());
+        assert!(handle
+            .update_config(PruningConfigUpdate {
+                max_transactions_in_batch: Some(0),
+                ..Default::default()
+            })
+            .is_err());
+        assert_eq!(handle.config().max_checkpoints_in_batch, 100);
+
+        assert!(!handle.is_paused());
+        handle.pause();
+        assert!(handle.status().unwrap().paused);
+        handle.resume();
+        assert!(!handle.is_paused());
+
+        assert!(handle
+            .compact(vec![
+                "objects".to_string(),
+                "checkpoint_content".to_string()
+            ])
+            .await
+            .is_ok());
+        assert!(handle
+            .compact(vec!["no_such_column_family".to_string()])
+            .await
+            .is_err());
+    }
+
     #[cfg(not(target_env = "msvc"))]
     #[tokio::test]
     async fn test_db_size_after_compaction() -> Result<(), anyhow::Error> {
@@ -64,7 +64,9 @@ use sui_types::messages_checkpoint::{CheckpointRequestV2, SignedCheckpointSummar
 use sui_types::messages_consensus::ConsensusTransactionKey;
 use sui_types::signature::GenericSignature;
 use sui_types::sui_system_state::{SuiSystemState, SuiSystemStateTrait};
-use sui_types::transaction::{TransactionDataAPI, TransactionKey, TransactionKind};
+use sui_types::transaction::{
+    TransactionData, TransactionDataAPI, TransactionKey, TransactionKind,
+};
 use tokio::{sync::Notify, task::JoinSet, time::timeout};
 use tracing::{debug, error, info, instrument, trace, warn};
 use typed_store::traits::{TableSummary, TypedStoreDebug};
@@ -1051,6 +1053,27 @@ impl CheckpointAccumulator {
     }
 }
 
+/// Keys of the execution time observations of the commands in `transactions`, which are stored at
+/// the end of the epoch when they are included in its last checkpoint.
+pub(crate) fn execution_time_observation_keys<'a>(
+    transactions: impl IntoIterator<Item = &'a TransactionData>,
+) -> Vec<ExecutionTimeObservationKey> {
+    transactions
+        .into_iter()
+        .flat_map(|tx| {
+            if let TransactionKind::ProgrammableTransaction(ptb) = tx.kind() {
+                itertools::Either::Left(
+                    ptb.commands
+                        .iter()
+                        .map(ExecutionTimeObservationKey::from_command),
+                )
+            } else {
+                itertools::Either::Right(std::iter::empty())
+            }
+        })
+        .collect()
+}
+
 pub struct CheckpointBuilder {
     state: Arc<AuthorityState>,
     store: Arc<CheckpointStore>,
@@ -1622,24 +1645,9 @@ impl CheckpointBuilder {
         );
 
         let mut end_of_epoch_observation_keys: Option<Vec<_>> = if details.last_of_epoch {
-            Some(
-                transactions
-                    .iter()
-                    .flat_map(|tx| {
-                        if let TransactionKind::ProgrammableTransaction(ptb) =
-                            tx.transaction_data().kind()
-                        {
-                            itertools::Either::Left(
-                                ptb.commands
-                                    .iter()
-                                    .map(ExecutionTimeObservationKey::from_command),
-                            )
-                        } else {
-                            itertools::Either::Right(std::iter::empty())
-                        }
-                    })
-                    .collect(),
-            )
+            Some(execution_time_observation_keys(
+                transactions.iter().map(|tx| tx.transaction_data()),
+            ))
         } else {
             None
         };
@@ -4,11 +4,12 @@
 use moka::ops::compute::Op;
 use moka::sync::Cache;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use sui_types::base_types::ObjectID;
 use sui_types::effects::{InputSharedObject, TransactionEffects, TransactionEffectsAPI};
 use sui_types::execution_status::CongestedObjects;
 use sui_types::messages_checkpoint::{CheckpointTimestamp, VerifiedCheckpoint};
+use sui_types::storage::{CongestionCancellation, ObjectCongestionInfo};
 use sui_types::transaction::{TransactionData, TransactionDataAPI};
 
 use crate::execution_cache::TransactionCacheRead;
@@ -40,6 +41,30 @@ impl CongestionInfo {
         }
     }
 
+    /// The price a transaction touching this object needs to bid in order to be likely to
+    /// clear, or `None` if the object no longer appears to be congested.
+    fn clearing_price(&self) -> Option<u64> {
+        match self
+            .last_success_time
+            .cmp(&Some(self.last_cancellation_time))
+        {
+            std::cmp::Ordering::Greater => {
+                // there were no cancellations in the most recent checkpoint,
+                // so the object is probably not congested any more
+                None
+            }
+            std::cmp::Ordering::Less => {
+                // there were no successes in the most recent checkpoint. This should be a rare case,
+                // but we know we will have to bid at least as much as the highest cancelled price.
+                Some(self.highest_cancelled_gas_price)
+            }
+            std::cmp::Ordering::Equal => {
+                // there were both successes and cancellations.
+                self.lowest_executed_gas_price
+            }
+        }
+    }
+
     fn update_cancellation_gas_price(&mut self, gas_price: u64) {
         self.highest_cancelled_gas_price =
             std::cmp::max(self.highest_cancelled_gas_price, gas_price);
@@ -54,8 +79,12 @@ impl CongestionInfo {
     }
 }
 
+/// Number of checkpoints with cancellations that are remembered per object.
+const MAX_RECENT_CANCELLATIONS: usize = 32;
+
 pub struct CongestionTracker {
     pub congestion_clearing_prices: Cache<ObjectID, CongestionInfo>,
+    recent_cancellations: Cache<ObjectID, VecDeque<CongestionCancellation>>,
 }
 
 impl Default for CongestionTracker {
@@ -68,6 +97,7 @@ impl CongestionTracker {
     pub fn new() -> Self {
         Self {
             congestion_clearing_prices: Cache::new(10_000),
+            recent_cancellations: Cache::new(10_000),
         }
     }
 
@@ -126,6 +156,38 @@ impl CongestionTracker {
                 .map(|id| id.id),
         )
     }
+
+    /// Returns the congestion status of a shared object along with the cancellations it has
+    /// seen in recent checkpoints, or `None` if the object has not been congested recently.
+    pub fn get_object_congestion_info(&self, object_id: ObjectID) -> Option<ObjectCongestionInfo> {
+        let info = self.get_congestion_info(object_id)?;
+        let recent_cancellations = self
+            .recent_cancellations
+            .get(&object_id)
+            .map(Vec::from)
+            .unwrap_or_default();
+
+        Some(ObjectCongestionInfo {
+            object_id,
+            last_cancellation_timestamp_ms: info.last_cancellation_time,
+            highest_cancelled_gas_price: info.highest_cancelled_gas_price,
+            last_success_timestamp_ms: info.last_success_time,
+            lowest_executed_gas_price: info.lowest_executed_gas_price,
+            suggested_gas_price: info.clearing_price(),
+            recent_cancellations,
+        })
+    }
+
+    /// Suggested gas price for a transaction that mutates all of the provided shared objects.
+    pub fn get_suggested_gas_price_for_objects(
+        &self,
+        objects: impl Iterator<Item = ObjectID>,
+    ) -> Option<u64> {
+        objects
+            .filter_map(|object_id| self.get_congestion_info(object_id))
+            .filter_map(|info| info.clearing_price())
+            .max()
+    }
 }
 
 impl CongestionTracker {
@@ -138,38 +200,43 @@ impl CongestionTracker {
         let congestion_info_map =
             self.compute_per_checkpoint_congestion_info(now, congestion_events, cleared_events);
         self.process_checkpoint_congestion(congestion_info_map);
+        self.record_cancellations(now, congestion_events);
     }
 
-    fn get_suggested_gas_price_for_objects(
+    fn record_cancellations(
         &self,
-        objects: impl Iterator<Item = ObjectID>,
-    ) -> Option<u64> {
-        let mut clearing_price = None;
-        for object_id in objects {
-            if let Some(info) = self.get_congestion_info(object_id) {
-                let clearing_price_for_object = match info
-                    .last_success_time
-                    .cmp(&Some(info.last_cancellation_time))
-                {
-                    std::cmp::Ordering::Greater => {
-                        // there were no cancellations in the most recent checkpoint,
-                        // so the object is probably not congested any more
-                        None
-                    }
-                    std::cmp::Ordering::Less => {
-                        // there were no successes in the most recent checkpoint. This should be a rare case,
-                        // but we know we will have to bid at least as much as the highest cancelled price.
-                        Some(info.highest_cancelled_gas_price)
-                    }
-                    std::cmp::Ordering::Equal => {
-                        // there were both successes and cancellations.
-                        info.lowest_executed_gas_price
-                    }
-                };
-                clearing_price = std::cmp::max(clearing_price, clearing_price_for_object);
+        now: CheckpointTimestamp,
+        congestion_events: &[(u64, Vec<ObjectID>)],
+    ) {
+        let mut cancellations: HashMap<ObjectID, CongestionCancellation> = HashMap::new();
+        for (gas_price, objects) in congestion_events {
+            for object in objects {
+                let cancellation =
+                    cancellations
+                        .entry(*object)
+                        .or_insert_with(|| CongestionCancellation {
+                            timestamp_ms: now,
+                            cancelled_transactions: 0,
+                            highest_cancelled_gas_price: *gas_price,
+                        });
+                cancellation.cancelled_transactions += 1;
+                cancellation.highest_cancelled_gas_price =
+                    std::cmp::max(cancellation.highest_cancelled_gas_price, *gas_price);
             }
         }
-        clearing_price
+
+        for (object_id, cancellation) in cancellations {
+            self.recent_cancellations
+                .entry(object_id)
+                .and_compute_with(|maybe_entry| {
+                    let mut history = maybe_entry.map(|e| e.into_value()).unwrap_or_default();
+                    if history.len() == MAX_RECENT_CANCELLATIONS {
+                        history.pop_front();
+                    }
+                    history.push_back(cancellation);
+                    Op::Put(history)
+                });
+        }
     }
 
     fn compute_per_checkpoint_congestion_info(
@@ -330,4 +397,45 @@ mod tests {
             Some(150)
         );
     }
+
+    #[test]
+    fn test_object_congestion_info() {
+        let tracker = CongestionTracker::new();
+        let obj = ObjectID::random();
+
+        assert!(tracker.get_object_congestion_info(obj).is_none());
+
+        tracker.process_per_checkpoint_events(1000, &[(100, vec![obj]), (150, vec![obj])], &[]);
+        tracker.process_per_checkpoint_events(2000, &[(120, vec![obj])], &[(130, vec![obj])]);
+
+        let info = tracker.get_object_congestion_info(obj).unwrap();
+        assert_eq!(info.last_cancellation_timestamp_ms, 2000);
+        assert_eq!(info.highest_cancelled_gas_price, 120);
+        assert_eq!(info.last_success_timestamp_ms, Some(2000));
+        assert_eq!(info.lowest_executed_gas_price, Some(130));
+        assert_eq!(info.suggested_gas_price, Some(130));
+        assert_eq!(
+            info.recent_cancellations,
+            vec![
+                CongestionCancellation {
+                    timestamp_ms: 1000,
+                    cancelled_transactions: 2,
+                    highest_cancelled_gas_price: 150,
+                },
+                CongestionCancellation {
+                    timestamp_ms: 2000,
+                    cancelled_transactions: 1,
+                    highest_cancelled_gas_price: 120,
+                },
+            ]
+        );
+
+        // Only the most recent cancellations are retained
+        for i in 0..MAX_RECENT_CANCELLATIONS as u64 {
+            tracker.process_per_checkpoint_events(3000 + i, &[(100, vec![obj])], &[]);
+        }
+        let info = tracker.get_object_congestion_info(obj).unwrap();
+        assert_eq!(info.recent_cancellations.len(), MAX_RECENT_CANCELLATIONS);
+        assert_eq!(info.recent_cancellations[0].timestamp_ms, 3000);
+    }
 }
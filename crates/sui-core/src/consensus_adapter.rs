@@ -53,6 +53,7 @@ use crate::consensus_handler::{classify, SequencedConsensusTransactionKey};
 use crate::consensus_throughput_calculator::{ConsensusThroughputProfiler, Level};
 use crate::epoch::reconfiguration::{ReconfigState, ReconfigurationInitiator};
 use crate::metrics::LatencyObserver;
+use crate::transaction_latency_tracer::{TransactionLatencyTracer, TransactionStage};
 
 #[cfg(test)]
 #[path = "unit_tests/consensus_tests.rs"]
@@ -266,6 +267,8 @@ pub struct ConsensusAdapter {
     submit_semaphore: Arc<Semaphore>,
     latency_observer: LatencyObserver,
     protocol_config: ProtocolConfig,
+    /// Records when sampled transactions are queued and submitted to consensus.
+    transaction_latency_tracer: ArcSwapOption<TransactionLatencyTracer>,
 }
 
 pub trait CheckConnection: Send + Sync {
@@ -318,6 +321,7 @@ impl ConsensusAdapter {
             latency_observer: LatencyObserver::new(),
             consensus_throughput_profiler: ArcSwapOption::empty(),
             protocol_config,
+            transaction_latency_tracer: ArcSwapOption::empty(),
         }
     }
 
@@ -332,6 +336,27 @@ impl ConsensusAdapter {
         self.consensus_throughput_profiler.store(Some(profiler))
     }
 
+    pub fn set_transaction_latency_tracer(&self, tracer: Arc<TransactionLatencyTracer>) {
+        self.transaction_latency_tracer.store(Some(tracer))
+    }
+
+    fn record_latency_stage(&self, transactions: &[ConsensusTransaction], stage: TransactionStage) {
+        let Some(tracer) = self.transaction_latency_tracer.load_full() else {
+            return;
+        };
+        for transaction in transactions {
+            match &transaction.kind {
+                ConsensusTransactionKind::CertifiedTransaction(certificate) => {
+                    tracer.record(certificate.digest(), stage)
+                }
+                ConsensusTransactionKind::UserTransaction(transaction) => {
+                    tracer.record(transaction.digest(), stage)
+                }
+                _ => {}
+            }
+        }
+    }
+
     pub fn submit_recovered(self: &Arc<Self>, epoch_store: &Arc<AuthorityPerEpochStore>) {
         // Currently narwhal worker might lose transactions on restart, so we need to resend them
         // todo - get_all_pending_consensus_transactions is called twice when
@@ -706,6 +731,7 @@ impl ConsensusAdapter {
         // - If is_soft_bundle, then all transactions are of UserTransaction kind.
         // - If not is_soft_bundle, then transactions must contain exactly 1 tx, and transactions[0] can be of any kind.
         let is_soft_bundle = transactions.len() > 1;
+        self.record_latency_stage(&transactions, TransactionStage::ConsensusSubmissionQueued);
 
         let mut transaction_keys = Vec::new();
 
@@ -814,6 +840,10 @@ impl ConsensusAdapter {
                             is_soft_bundle,
                         )
                         .await;
+                    self.record_latency_stage(
+                        &transactions,
+                        TransactionStage::SubmittedToConsensus,
+                    );
 
                     match status_waiter.await {
                         Ok(BlockStatus::Sequenced(_)) => {
@@ -0,0 +1,357 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline replay of stored consensus commits through a fresh [`ConsensusHandler`].
+//!
+//! When post-consensus processing diverges between validators (e.g. a checkpoint fork), the
+//! committed sub-dags of the epoch can be read back from the consensus store and fed to a
+//! `ConsensusHandler` backed by an epoch store opened from a copy of the node database. The
+//! transactions scheduled for execution, the shared object versions assigned to them and the
+//! transactions deferred by each commit are logged. Nothing is executed, and no checkpoint is
+//! built.
+//!
+//! Only commits the node had not processed when its database was copied can be replayed: the
+//! output of the others is already in the epoch store, and the handler would not process them
+//! again. Commits already processed, such as the divergent commits of a fork, are replayed from a
+//! node database restored to a point before them, e.g. from a database checkpoint.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{ensure, Context as _};
+use arc_swap::ArcSwap;
+use consensus_core::{CommitIndex, CommittedSubDag};
+use prometheus::Registry;
+use sui_config::NodeConfig;
+use sui_network::randomness;
+use sui_types::{
+    digests::ChainIdentifier, error::SuiResult,
+    executable_transaction::VerifiedExecutableTransaction,
+    messages_consensus::ConsensusTransaction,
+    sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tracing::{debug, info};
+
+use crate::{
+    authority::{
+        authority_per_epoch_store::AuthorityPerEpochStore,
+        authority_store_tables::AuthorityPerpetualTables, backpressure::BackpressureManager,
+        transaction_deferral::DeferralKey, AuthorityMetrics, AuthorityStore,
+    },
+    checkpoints::{CheckpointServiceNoop, CheckpointStore},
+    consensus_adapter::SubmitToConsensus,
+    consensus_handler::{ConsensusHandler, SequencedConsensusTransactionKey},
+    consensus_throughput_calculator::ConsensusThroughputCalculator,
+    epoch::{
+        committee_store::CommitteeStore, epoch_metrics::EpochMetrics, randomness::RandomnessManager,
+    },
+    execution_cache::build_execution_cache,
+    module_cache_metrics::ResolverMetrics,
+    signature_verifier::SignatureVerifierMetrics,
+    transaction_latency_tracer::TransactionLatencyTracer,
+    transaction_manager::{PendingCertificate, TransactionManager},
+};
+
+/// Feeds committed sub-dags to a [`ConsensusHandler`] running against the epoch store of a node
+/// database, and logs the decisions taken for each commit.
+pub struct ConsensusCommitReplayer {
+    epoch_store: Arc<AuthorityPerEpochStore>,
+    consensus_handler: ConsensusHandler<CheckpointServiceNoop>,
+    last_processed_commit_index: CommitIndex,
+    // Transactions are never enqueued, but the receiver is kept so that the channel stays open.
+    _ready_certificates: Option<UnboundedReceiver<PendingCertificate>>,
+}
+
+/// The output of a replayed commit.
+#[derive(Debug)]
+pub struct ReplayedCommit {
+    pub commit_index: CommitIndex,
+    /// Transactions scheduled for execution by the commit, in order
+    pub scheduled_transactions: Vec<VerifiedExecutableTransaction>,
+}
+
+impl ConsensusCommitReplayer {
+    /// Opens the database of the node configured by `config`, at the epoch it would recover to
+    /// on restart.
+    ///
+    /// Replaying writes to the epoch store, so `config` must point to a copy of the node
+    /// database and not to the database of a running node.
+    pub async fn open(config: &NodeConfig) -> anyhow::Result<Self> {
+        let registry = Registry::new();
+        let genesis = config.genesis()?.clone();
+        let db_path = config.db_path();
+
+        let committee_store =
+            CommitteeStore::new(db_path.join("epochs"), &genesis.committee()?, None);
+        let perpetual_tables =
+            Arc::new(AuthorityPerpetualTables::open(&db_path.join("store"), None));
+        let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+        let backpressure_manager =
+            BackpressureManager::new_from_checkpoint_store(&checkpoint_store);
+        let store = AuthorityStore::open(perpetual_tables, &genesis, config, &registry).await?;
+
+        let epoch = store.get_recovery_epoch_at_restart()?;
+        let committee = committee_store
+            .get_committee(&epoch)?
+            .with_context(|| format!("Committee of epoch {epoch} not found"))?;
+        let epoch_start_configuration = store
+            .get_epoch_start_configuration()?
+            .context("Epoch start configuration not found")?;
+        let cache_traits = build_execution_cache(
+            &config.execution_cache,
+            &registry,
+            &store,
+            backpressure_manager.clone(),
+        );
+
+        let chain_id = ChainIdentifier::from(*genesis.checkpoint().digest());
+        let chain = config
+            .chain_override_for_testing
+            .unwrap_or_else(|| chain_id.chain());
+        let epoch_store = AuthorityPerEpochStore::new(
+            config.protocol_public_key(),
+            committee,
+            &db_path.join("store"),
+            None,
+            EpochMetrics::new(&registry),
+            epoch_start_configuration,
+            cache_traits.backing_package_store.clone(),
+            cache_traits.object_store.clone(),
+            Arc::new(ResolverMetrics::new(&registry)),
+            SignatureVerifierMetrics::new(&registry),
+            &config.expensive_safety_check_config,
+            (chain_id, chain),
+            checkpoint_store
+                .get_highest_executed_checkpoint_seq_number()?
+                .unwrap_or(0),
+        )?;
+        info!(
+            epoch = epoch_store.epoch(),
+            "Opened epoch store for consensus commit replay"
+        );
+
+        if epoch_store.randomness_state_enabled() {
+            // DKG output is recovered from the epoch store, messages that would be sent to
+            // other validators are only logged.
+            let randomness_manager = RandomnessManager::try_new(
+                Arc::downgrade(&epoch_store),
+                Box::new(LoggingConsensusSubmitter),
+                randomness::Handle::new_stub(),
+                config.protocol_key_pair(),
+            )
+            .await
+            .context("Failed to create randomness manager")?;
+            epoch_store
+                .set_randomness_manager(randomness_manager)
+                .await?;
+        }
+
+        let metrics = Arc::new(AuthorityMetrics::new(&registry));
+        let (ready_certificates_sender, ready_certificates) = unbounded_channel();
+        let transaction_manager = Arc::new(TransactionManager::new(
+            cache_traits.object_cache_reader.clone(),
+            cache_traits.transaction_cache_reader.clone(),
+            &epoch_store,
+            ready_certificates_sender,
+            metrics.clone(),
+            Arc::new(TransactionLatencyTracer::new(None)),
+        ));
+        let consensus_handler = ConsensusHandler::new(
+            epoch_store.clone(),
+            Arc::new(CheckpointServiceNoop {}),
+            transaction_manager,
+            cache_traits.object_cache_reader.clone(),
+            cache_traits.transaction_cache_reader.clone(),
+            Arc::new(ArcSwap::new(Arc::new(HashMap::new()))),
+            epoch_store.epoch_start_state().get_consensus_committee(),
+            metrics.clone(),
+            Arc::new(ConsensusThroughputCalculator::new(None, metrics)),
+            backpressure_manager.subscribe(),
+        );
+        let mut replayer = Self::new(epoch_store, consensus_handler);
+        replayer._ready_certificates = Some(ready_certificates);
+        Ok(replayer)
+    }
+
+    pub(crate) fn new(
+        epoch_store: Arc<AuthorityPerEpochStore>,
+        consensus_handler: ConsensusHandler<CheckpointServiceNoop>,
+    ) -> Self {
+        let last_processed_commit_index =
+            consensus_handler.last_processed_subdag_index() as CommitIndex;
+        Self {
+            epoch_store,
+            consensus_handler,
+            last_processed_commit_index,
+            _ready_certificates: None,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch_store.epoch()
+    }
+
+    /// Index of the last processed commit, by the node before its database was copied or by
+    /// replaying.
+    pub fn last_processed_commit_index(&self) -> CommitIndex {
+        self.last_processed_commit_index
+    }
+
+    /// Index of the first commit the node would request from consensus on restart. Observing the
+    /// commits from there up to the last processed one rebuilds the same handler state as a
+    /// restarting node.
+    pub fn first_prior_commit_index(&self) -> CommitIndex {
+        let num_prior_commits = self
+            .epoch_store
+            .protocol_config()
+            .consensus_num_requested_prior_commits_at_startup();
+        self.last_processed_commit_index
+            .saturating_sub(num_prior_commits)
+            + 1
+    }
+
+    /// Observes commits already processed by the node, to rebuild the handler state before
+    /// replaying the following ones.
+    pub fn observe_prior_commits(&mut self, commits: Vec<CommittedSubDag>) -> anyhow::Result<()> {
+        for commit in commits {
+            let commit_index = commit.commit_ref.index;
+            ensure!(
+                commit_index <= self.last_processed_commit_index,
+                "Commit {commit_index} has not been processed yet, and must be replayed"
+            );
+            debug!(commit_index, "Observing previously processed commit");
+            self.consensus_handler.handle_prior_consensus_commit(commit);
+        }
+        Ok(())
+    }
+
+    /// Replays `commits` in commit order, logging and returning their output.
+    ///
+    /// Commits that have already been processed cannot be replayed, as their output is already in
+    /// the epoch store: replaying them requires a copy of the node database made before they were
+    /// processed.
+    pub async fn replay(
+        &mut self,
+        commits: Vec<CommittedSubDag>,
+    ) -> anyhow::Result<Vec<ReplayedCommit>> {
+        let mut replayed = Vec::with_capacity(commits.len());
+        for commit in commits {
+            let commit_index = commit.commit_ref.index;
+            ensure!(
+                commit_index > self.last_processed_commit_index,
+                "Commit {commit_index} cannot be replayed, as commits up to {} have already been \
+                processed. Replaying it requires a node database restored to a point before it was \
+                processed",
+                self.last_processed_commit_index
+            );
+
+            let leader = commit.leader;
+            let deferred_before = self.deferred_transactions();
+            let scheduled_transactions = self
+                .consensus_handler
+                .process_consensus_commit(commit)
+                .await
+                .with_context(|| {
+                    format!("Commit {commit_index} was skipped as already processed")
+                })?;
+            info!(commit_index, %leader, "Replayed consensus commit");
+            self.log_commit_output(commit_index, &scheduled_transactions, &deferred_before);
+            self.last_processed_commit_index = commit_index;
+            replayed.push(ReplayedCommit {
+                commit_index,
+                scheduled_transactions,
+            });
+        }
+        Ok(replayed)
+    }
+
+    // Keys of the transactions currently deferred, along with the key they are deferred to.
+    fn deferred_transactions(&self) -> HashSet<(DeferralKey, SequencedConsensusTransactionKey)> {
+        self.epoch_store
+            .get_all_deferred_transactions()
+            .into_iter()
+            .flat_map(|(deferral_key, transactions)| {
+                transactions
+                    .into_iter()
+                    .map(move |transaction| (deferral_key, transaction.0.key()))
+            })
+            .collect()
+    }
+
+    fn log_commit_output(
+        &self,
+        commit_index: CommitIndex,
+        executable_transactions: &[VerifiedExecutableTransaction],
+        deferred_before: &HashSet<(DeferralKey, SequencedConsensusTransactionKey)>,
+    ) {
+        info!(
+            commit_index,
+            "{} transactions scheduled for execution",
+            executable_transactions.len()
+        );
+        for (position, transaction) in executable_transactions.iter().enumerate() {
+            let assigned_versions = self
+                .epoch_store
+                .get_assigned_shared_object_versions(&transaction.key());
+            info!(
+                commit_index,
+                position,
+                digest = ?transaction.digest(),
+                ?assigned_versions,
+                "Scheduled transaction"
+            );
+        }
+        // Only the transactions deferred by this commit are logged, including the ones it deferred
+        // again, and not the ones deferred by earlier commits.
+        for (deferral_key, transactions) in self.epoch_store.get_all_deferred_transactions() {
+            let keys: Vec<_> = transactions
+                .iter()
+                .map(|tx| tx.0.key())
+                .filter(|key| !deferred_before.contains(&(deferral_key, key.clone())))
+                .collect();
+            if keys.is_empty() {
+                continue;
+            }
+            info!(
+                commit_index,
+                ?deferral_key,
+                ?keys,
+                "Transactions deferred by commit"
+            );
+        }
+    }
+}
+
+/// Drops consensus submissions made while replaying, which would otherwise go to other validators.
+struct LoggingConsensusSubmitter;
+
+impl SubmitToConsensus for LoggingConsensusSubmitter {
+    fn submit_to_consensus(
+        &self,
+        transactions: &[ConsensusTransaction],
+        _epoch_store: &Arc<AuthorityPerEpochStore>,
+    ) -> SuiResult {
+        for transaction in transactions {
+            debug!(key = ?transaction.key(), "Dropping consensus submission during replay");
+        }
+        Ok(())
+    }
+
+    fn submit_best_effort(
+        &self,
+        transaction: &ConsensusTransaction,
+        _epoch_store: &Arc<AuthorityPerEpochStore>,
+        _timeout: Duration,
+    ) -> SuiResult {
+        debug!(key = ?transaction.key(), "Dropping consensus submission during replay");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/consensus_commit_replay_tests.rs"]
+mod consensus_commit_replay_tests;
@@ -54,6 +54,7 @@ use crate::{
     consensus_types::consensus_output_api::{parse_block_transactions, ConsensusCommitAPI},
     execution_cache::{ObjectCacheRead, TransactionCacheRead},
     scoring_decision::update_low_scoring_authorities,
+    transaction_latency_tracer::{TransactionLatencyTracer, TransactionStage},
     transaction_manager::TransactionManager,
 };
 
@@ -488,6 +489,8 @@ pub struct ConsensusHandler<C> {
     additional_consensus_state: AdditionalConsensusState,
 
     backpressure_subscriber: BackpressureSubscriber,
+
+    latency_tracer: Arc<TransactionLatencyTracer>,
 }
 
 const PROCESSED_CACHE_CAP: usize = 1024 * 1024;
@@ -513,6 +516,7 @@ impl<C> ConsensusHandler<C> {
         if !last_consensus_stats.stats.is_initialized() {
             last_consensus_stats.stats = ConsensusStats::new(committee.size());
         }
+        let latency_tracer = transaction_manager.latency_tracer().clone();
         let transaction_manager_sender =
             TransactionManagerSender::start(transaction_manager, epoch_store.clone());
         let commit_rate_estimate_window_size = epoch_store
@@ -536,6 +540,7 @@ impl<C> ConsensusHandler<C> {
                 commit_rate_estimate_window_size,
             ),
             backpressure_subscriber,
+            latency_tracer,
         }
     }
 
@@ -553,7 +558,10 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
     /// Called during startup to allow us to observe commits we previously processed, for crash recovery.
     /// Any state computed here must be a pure function of the commits observed, it cannot depend on any
     /// state recorded in the epoch db.
-    fn handle_prior_consensus_commit(&mut self, consensus_commit: impl ConsensusCommitAPI) {
+    pub(crate) fn handle_prior_consensus_commit(
+        &mut self,
+        consensus_commit: impl ConsensusCommitAPI,
+    ) {
         assert!(self
             .epoch_store
             .protocol_config()
@@ -562,8 +570,22 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             .observe_commit(self.epoch_store.protocol_config(), &consensus_commit);
     }
 
-    #[instrument(level = "debug", skip_all)]
     async fn handle_consensus_commit(&mut self, consensus_commit: impl ConsensusCommitAPI) {
+        if let Some(executable_transactions) = self.process_consensus_commit(consensus_commit).await
+        {
+            self.transaction_manager_sender
+                .send(executable_transactions);
+        }
+    }
+
+    /// Processes a consensus commit and returns the transactions scheduled for execution, in
+    /// order, without enqueueing them to the transaction manager. Returns `None` if the commit
+    /// was already processed.
+    #[instrument(level = "debug", skip_all)]
+    pub(crate) async fn process_consensus_commit(
+        &mut self,
+        consensus_commit: impl ConsensusCommitAPI,
+    ) -> Option<Vec<VerifiedExecutableTransaction>> {
         // This may block until one of two conditions happens:
         // - Number of uncommitted transactions in the writeback cache goes below the
         //   backpressure threshold.
@@ -603,7 +625,7 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                 "Ignoring consensus output for round {} as it is already committed. NOTE: This is only expected if consensus is running.",
                 commit_info.round
             );
-            return;
+            return None;
         }
 
         /* (transaction, serialized length) */
@@ -810,6 +832,13 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                     continue;
                 }
 
+                if let Some(digest) = sequenced_transaction
+                    .transaction
+                    .executable_transaction_digest()
+                {
+                    self.latency_tracer
+                        .record(&digest, TransactionStage::Sequenced);
+                }
                 all_transactions.push(sequenced_transaction);
             }
         }
@@ -824,6 +853,7 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                 self.tx_reader.as_ref(),
                 &commit_info,
                 &self.metrics,
+                &self.latency_tracer,
             )
             .await
             .expect("Unrecoverable error in consensus handler");
@@ -841,8 +871,7 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
 
         fail_point!("crash"); // for tests that produce random crashes
 
-        self.transaction_manager_sender
-            .send(executable_transactions);
+        Some(executable_transactions)
     }
 }
 
@@ -42,6 +42,7 @@ use typed_store::rocks::DBBatch;
 
 pub(crate) mod cache_types;
 pub mod metrics;
+pub mod object_access_stats;
 mod object_locks;
 pub mod writeback_cache;
 
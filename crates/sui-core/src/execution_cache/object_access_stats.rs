@@ -0,0 +1,382 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sampled per-object access statistics, used to find the objects creating hot spots.
+//!
+//! Reads and writes through the execution cache, reads from the object store and conflicts on
+//! owned object locks are sampled, and each sampled access is weighted by the inverse of the
+//! sample rate, so that the counts reported are estimates of the actual number of accesses. Only
+//! the objects accessed most often are tracked, and the hottest ones are reported through the
+//! admin interface. Sampling is disabled by default.
+
+use std::{
+    cell::OnceCell,
+    collections::{BTreeSet, HashMap},
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use itertools::Itertools;
+use parking_lot::Mutex;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use rand::Rng;
+use sui_types::{
+    base_types::ObjectID,
+    inner_temporary_store::WrittenObjects,
+    object::{Object, Owner},
+    transaction::{TransactionDataAPI, VerifiedTransaction},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/object_access_stats_tests.rs"]
+mod object_access_stats_tests;
+
+// Sample rates are stored in parts per million, so that they can be updated atomically.
+const PARTS_PER_MILLION: u64 = 1_000_000;
+
+// Number of objects for which accesses are counted. When this is reached, the least accessed
+// half of the objects is dropped.
+const MAX_TRACKED_OBJECTS: usize = 10_000;
+
+// Number of packages recorded for each object.
+const MAX_PACKAGES_PER_OBJECT: usize = 8;
+
+/// How an object is owned, as of the last sampled access that loaded it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ObjectOwnership {
+    /// The object was not loaded by any sampled access, e.g. because it was only seen through
+    /// lock conflicts or tombstones.
+    #[default]
+    Unknown,
+    /// Owned by an address.
+    Owned,
+    /// Owned by another object, e.g. a dynamic field.
+    Child,
+    Shared,
+    Immutable,
+}
+
+impl ObjectOwnership {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Owned => "owned",
+            Self::Child => "child",
+            Self::Shared => "shared",
+            Self::Immutable => "immutable",
+        }
+    }
+}
+
+impl From<&Owner> for ObjectOwnership {
+    fn from(owner: &Owner) -> Self {
+        match owner {
+            Owner::AddressOwner(_) => Self::Owned,
+            Owner::ObjectOwner(_) => Self::Child,
+            Owner::Shared { .. } | Owner::ConsensusV2 { .. } => Self::Shared,
+            Owner::Immutable => Self::Immutable,
+        }
+    }
+}
+
+/// Estimated number of accesses to an object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectAccessCounts {
+    /// Reads through the execution cache, including the ones that missed the cache.
+    pub reads: u64,
+    /// Reads that missed the execution cache, and went to the object store.
+    pub cache_misses: u64,
+    /// Reads from the object store, either after a cache miss or bypassing the cache.
+    pub db_reads: u64,
+    /// New versions, deletions and wraps of the object written by executed transactions.
+    pub writes: u64,
+    /// Attempts to lock the object for a transaction that failed because it was already locked
+    /// by another transaction.
+    pub lock_conflicts: u64,
+}
+
+impl ObjectAccessCounts {
+    /// Number of accesses objects are ranked by.
+    pub fn accesses(&self) -> u64 {
+        self.reads + self.db_reads + self.writes + self.lock_conflicts
+    }
+}
+
+/// An entry of the hot object report.
+#[derive(Clone, Debug)]
+pub struct HotObject {
+    pub object_id: ObjectID,
+    pub ownership: ObjectOwnership,
+    pub object_type: Option<String>,
+    /// Packages of the Move functions called by the transactions that wrote or read the object.
+    pub packages: Vec<ObjectID>,
+    pub counts: ObjectAccessCounts,
+}
+
+impl fmt::Display for HotObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} {}",
+            self.object_id,
+            self.ownership.as_str(),
+            self.object_type.as_deref().unwrap_or("-")
+        )?;
+        writeln!(
+            f,
+            "  reads {} (cache misses {}), db reads {}, writes {}, lock conflicts {}",
+            self.counts.reads,
+            self.counts.cache_misses,
+            self.counts.db_reads,
+            self.counts.writes,
+            self.counts.lock_conflicts
+        )?;
+        if !self.packages.is_empty() {
+            writeln!(f, "  packages {}", self.packages.iter().join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct TrackedObject {
+    ownership: ObjectOwnership,
+    object_type: Option<String>,
+    packages: BTreeSet<ObjectID>,
+    counts: ObjectAccessCounts,
+}
+
+impl TrackedObject {
+    fn update_from(&mut self, object: &Object) {
+        self.ownership = object.owner().into();
+        if self.object_type.is_none() {
+            self.object_type = Some(match object.type_() {
+                Some(object_type) => object_type.to_string(),
+                None => "package".to_string(),
+            });
+        }
+    }
+
+    fn add_packages(&mut self, packages: &BTreeSet<ObjectID>) {
+        for package in packages {
+            if self.packages.len() >= MAX_PACKAGES_PER_OBJECT {
+                break;
+            }
+            self.packages.insert(*package);
+        }
+    }
+
+    fn hot_object(&self, object_id: ObjectID) -> HotObject {
+        HotObject {
+            object_id,
+            ownership: self.ownership,
+            object_type: self.object_type.clone(),
+            packages: self.packages.iter().copied().collect(),
+            counts: self.counts.clone(),
+        }
+    }
+}
+
+struct ObjectAccessMetrics {
+    sampled_accesses: IntCounterVec,
+}
+
+impl ObjectAccessMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            sampled_accesses: register_int_counter_vec_with_registry!(
+                "object_access_sampled_accesses",
+                "Number of object accesses sampled for the hot object report",
+                &["access"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Counts sampled accesses to objects, and reports the most accessed ones.
+pub struct ObjectAccessStats {
+    sample_rate_ppm: AtomicU64,
+    report_size: usize,
+    objects: Mutex<HashMap<ObjectID, TrackedObject>>,
+    metrics: ObjectAccessMetrics,
+}
+
+impl ObjectAccessStats {
+    pub fn new(sample_rate: f64, report_size: usize, registry: &Registry) -> Self {
+        Self {
+            sample_rate_ppm: AtomicU64::new(Self::to_ppm(sample_rate)),
+            report_size,
+            objects: Mutex::new(HashMap::new()),
+            metrics: ObjectAccessMetrics::new(registry),
+        }
+    }
+
+    fn to_ppm(sample_rate: f64) -> u64 {
+        (sample_rate.clamp(0.0, 1.0) * PARTS_PER_MILLION as f64) as u64
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate_ppm.load(Ordering::Relaxed) as f64 / PARTS_PER_MILLION as f64
+    }
+
+    /// Changes the fraction of accesses sampled. Setting it to 0 disables sampling, while
+    /// keeping the counts so far.
+    pub fn set_sample_rate(&self, sample_rate: f64) {
+        self.sample_rate_ppm
+            .store(Self::to_ppm(sample_rate), Ordering::Relaxed);
+    }
+
+    /// Number of objects in the hot object report by default.
+    pub fn report_size(&self) -> usize {
+        self.report_size
+    }
+
+    // Returns the weight of the access if it is sampled.
+    fn sample(&self) -> Option<u64> {
+        let sample_rate_ppm = self.sample_rate_ppm.load(Ordering::Relaxed);
+        if sample_rate_ppm == 0
+            || rand::thread_rng().gen_range(0..PARTS_PER_MILLION) >= sample_rate_ppm
+        {
+            return None;
+        }
+        Some(PARTS_PER_MILLION / sample_rate_ppm)
+    }
+
+    fn record(&self, access: &str, object_id: &ObjectID, update: impl FnOnce(&mut TrackedObject)) {
+        self.metrics
+            .sampled_accesses
+            .with_label_values(&[access])
+            .inc();
+        let mut objects = self.objects.lock();
+        if objects.len() >= MAX_TRACKED_OBJECTS && !objects.contains_key(object_id) {
+            Self::evict_least_accessed(&mut objects);
+        }
+        update(objects.entry(*object_id).or_default());
+    }
+
+    fn evict_least_accessed(objects: &mut HashMap<ObjectID, TrackedObject>) {
+        let mut tracked = std::mem::take(objects).into_iter().collect::<Vec<_>>();
+        tracked.sort_by_key(|(_, object)| std::cmp::Reverse(object.counts.accesses()));
+        tracked.truncate(tracked.len() / 2);
+        objects.extend(tracked);
+    }
+
+    /// Records a read through the execution cache. `object` is the object read, if it exists and
+    /// was found in the cache.
+    pub fn record_cache_read(&self, object_id: &ObjectID, object: Option<&Object>, miss: bool) {
+        let Some(weight) = self.sample() else {
+            return;
+        };
+        self.record("read", object_id, |tracked| {
+            if let Some(object) = object {
+                tracked.update_from(object);
+            }
+            tracked.counts.reads += weight;
+            if miss {
+                tracked.counts.cache_misses += weight;
+            }
+        });
+    }
+
+    /// Records a read from the object store.
+    pub fn record_db_read(&self, object_id: &ObjectID, object: Option<&Object>) {
+        let Some(weight) = self.sample() else {
+            return;
+        };
+        self.record("db_read", object_id, |tracked| {
+            if let Some(object) = object {
+                tracked.update_from(object);
+            }
+            tracked.counts.db_reads += weight;
+        });
+    }
+
+    /// Records the deletion or wrapping of an object by a transaction.
+    pub fn record_removal(&self, object_id: &ObjectID) {
+        let Some(weight) = self.sample() else {
+            return;
+        };
+        self.record("write", object_id, |tracked| {
+            tracked.counts.writes += weight;
+        });
+    }
+
+    /// Records the objects written by `transaction`, along with the packages of the Move
+    /// functions it calls. The packages are also recorded for the tracked input objects that
+    /// `transaction` did not write, whose reads are recorded through the execution cache.
+    pub fn record_writes(&self, transaction: &VerifiedTransaction, written: &WrittenObjects) {
+        let transaction_data = transaction.data().transaction_data();
+        let packages: OnceCell<BTreeSet<ObjectID>> = OnceCell::new();
+        let move_call_packages = || {
+            packages.get_or_init(|| {
+                transaction_data
+                    .move_calls()
+                    .into_iter()
+                    .map(|(package, _, _)| *package)
+                    .collect()
+            })
+        };
+
+        for (object_id, object) in written {
+            let Some(weight) = self.sample() else {
+                continue;
+            };
+            let packages = move_call_packages();
+            self.record("write", object_id, |tracked| {
+                tracked.update_from(object);
+                tracked.add_packages(packages);
+                tracked.counts.writes += weight;
+            });
+        }
+
+        // Inputs the transaction did not write are not counted, so they are sampled per
+        // transaction rather than per object.
+        if self.sample().is_none() || move_call_packages().is_empty() {
+            return;
+        }
+        let Ok(input_objects) = transaction_data.input_objects() else {
+            return;
+        };
+        let mut objects = self.objects.lock();
+        for input_object in input_objects {
+            let object_id = input_object.object_id();
+            if written.contains_key(&object_id) {
+                continue;
+            }
+            if let Some(tracked) = objects.get_mut(&object_id) {
+                tracked.add_packages(move_call_packages());
+            }
+        }
+    }
+
+    /// Records a failed attempt to lock an owned object, because it was already locked by
+    /// another transaction.
+    pub fn record_lock_conflict(&self, object_id: &ObjectID) {
+        let Some(weight) = self.sample() else {
+            return;
+        };
+        self.record("lock_conflict", object_id, |tracked| {
+            tracked.counts.lock_conflicts += weight;
+        });
+    }
+
+    /// Returns up to `limit` of the most accessed objects, most accessed first.
+    pub fn hot_objects(&self, limit: usize) -> Vec<HotObject> {
+        let objects = self.objects.lock();
+        objects
+            .iter()
+            .sorted_by_key(|(object_id, object)| {
+                (std::cmp::Reverse(object.counts.accesses()), **object_id)
+            })
+            .take(limit)
+            .map(|(object_id, object)| object.hot_object(*object_id))
+            .collect()
+    }
+
+    /// Drops the counts so far.
+    pub fn reset(&self) {
+        self.objects.lock().clear();
+    }
+}
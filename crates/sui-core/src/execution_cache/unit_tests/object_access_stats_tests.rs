@@ -0,0 +1,205 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use prometheus::Registry;
+use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_types::{
+    base_types::{random_object_ref, ObjectID, SuiAddress},
+    crypto::{get_key_pair, AccountKeyPair},
+    inner_temporary_store::WrittenObjects,
+    object::Object,
+    transaction::{CallArg, ObjectArg, VerifiedTransaction},
+};
+
+use super::{ObjectAccessCounts, ObjectAccessStats, ObjectOwnership, MAX_TRACKED_OBJECTS};
+
+fn stats_with_sample_rate(sample_rate: f64) -> ObjectAccessStats {
+    ObjectAccessStats::new(sample_rate, 100, &Registry::new())
+}
+
+fn move_call_transaction(package: ObjectID) -> VerifiedTransaction {
+    move_call_transaction_with_args(package, vec![])
+}
+
+fn move_call_transaction_with_args(package: ObjectID, args: Vec<CallArg>) -> VerifiedTransaction {
+    let (sender, keypair): (SuiAddress, AccountKeyPair) = get_key_pair();
+    let tx = TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+        .move_call(package, "counter", "increment", args)
+        .build_and_sign(&keypair);
+    VerifiedTransaction::new_unchecked(tx)
+}
+
+#[test]
+fn test_sampling_disabled() {
+    let stats = stats_with_sample_rate(0.0);
+    let object = Object::shared_for_testing();
+    stats.record_cache_read(&object.id(), Some(&object), false);
+    stats.record_db_read(&object.id(), Some(&object));
+    stats.record_lock_conflict(&object.id());
+    assert!(stats.hot_objects(10).is_empty());
+}
+
+#[test]
+fn test_record_accesses() {
+    let stats = stats_with_sample_rate(1.0);
+    let shared = Object::shared_for_testing();
+    let owned = Object::with_id_owner_for_testing(ObjectID::random(), SuiAddress::ZERO);
+
+    for _ in 0..3 {
+        stats.record_cache_read(&shared.id(), Some(&shared), false);
+    }
+    stats.record_cache_read(&shared.id(), None, true);
+    stats.record_db_read(&shared.id(), Some(&shared));
+    stats.record_cache_read(&owned.id(), Some(&owned), false);
+    stats.record_lock_conflict(&owned.id());
+
+    let hot_objects = stats.hot_objects(10);
+    assert_eq!(hot_objects.len(), 2);
+
+    assert_eq!(hot_objects[0].object_id, shared.id());
+    assert_eq!(hot_objects[0].ownership, ObjectOwnership::Shared);
+    assert_eq!(
+        hot_objects[0].object_type,
+        shared.type_().map(ToString::to_string)
+    );
+    assert_eq!(
+        hot_objects[0].counts,
+        ObjectAccessCounts {
+            reads: 4,
+            cache_misses: 1,
+            db_reads: 1,
+            ..Default::default()
+        }
+    );
+    assert_eq!(hot_objects[0].counts.accesses(), 5);
+
+    assert_eq!(hot_objects[1].object_id, owned.id());
+    assert_eq!(hot_objects[1].ownership, ObjectOwnership::Owned);
+    assert_eq!(
+        hot_objects[1].counts,
+        ObjectAccessCounts {
+            reads: 1,
+            lock_conflicts: 1,
+            ..Default::default()
+        }
+    );
+
+    assert_eq!(stats.hot_objects(1).len(), 1);
+    stats.reset();
+    assert!(stats.hot_objects(10).is_empty());
+}
+
+#[test]
+fn test_record_writes() {
+    let stats = stats_with_sample_rate(1.0);
+    let package = ObjectID::random();
+    let shared = Object::shared_for_testing();
+    let child = Object::with_object_owner_for_testing(ObjectID::random(), shared.id());
+    let written: WrittenObjects = [(shared.id(), shared.clone()), (child.id(), child.clone())]
+        .into_iter()
+        .collect();
+
+    stats.record_writes(&move_call_transaction(package), &written);
+    stats.record_writes(&move_call_transaction(package), &written);
+    stats.record_removal(&child.id());
+
+    let hot_objects = stats.hot_objects(10);
+    assert_eq!(hot_objects.len(), 2);
+    assert_eq!(hot_objects[0].object_id, child.id());
+    assert_eq!(hot_objects[0].ownership, ObjectOwnership::Child);
+    assert_eq!(hot_objects[0].counts.writes, 3);
+    assert_eq!(hot_objects[1].object_id, shared.id());
+    assert_eq!(hot_objects[1].ownership, ObjectOwnership::Shared);
+    assert_eq!(hot_objects[1].counts.writes, 2);
+    for hot_object in &hot_objects {
+        assert_eq!(hot_object.packages, vec![package]);
+    }
+}
+
+#[test]
+fn test_record_packages_of_read_only_inputs() {
+    let stats = stats_with_sample_rate(1.0);
+    let package = ObjectID::random();
+    let immutable = Object::immutable_for_testing();
+    let untracked = Object::immutable_for_testing();
+    let written = Object::shared_for_testing();
+    stats.record_cache_read(&immutable.id(), Some(&immutable), false);
+
+    let args = [&immutable, &untracked]
+        .into_iter()
+        .map(|object| {
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                object.compute_object_reference(),
+            ))
+        })
+        .collect();
+    stats.record_writes(
+        &move_call_transaction_with_args(package, args),
+        &[(written.id(), written.clone())].into_iter().collect(),
+    );
+
+    // The packages are recorded for the tracked object the transaction read, without counting
+    // an access, while untracked objects are left untracked.
+    let hot_objects = stats.hot_objects(10);
+    assert_eq!(hot_objects.len(), 2);
+    let read_only = hot_objects
+        .iter()
+        .find(|hot_object| hot_object.object_id == immutable.id())
+        .unwrap();
+    assert_eq!(read_only.packages, vec![package]);
+    assert_eq!(
+        read_only.counts,
+        ObjectAccessCounts {
+            reads: 1,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_db_reads_are_ranked() {
+    let stats = stats_with_sample_rate(1.0);
+    let read = Object::immutable_for_testing();
+    let db_read = Object::immutable_for_testing();
+    stats.record_cache_read(&read.id(), Some(&read), false);
+    stats.record_db_read(&db_read.id(), Some(&db_read));
+    stats.record_db_read(&db_read.id(), Some(&db_read));
+
+    let hot_objects = stats.hot_objects(1);
+    assert_eq!(hot_objects[0].object_id, db_read.id());
+    assert_eq!(hot_objects[0].counts.accesses(), 2);
+}
+
+#[test]
+fn test_sampled_accesses_are_weighted() {
+    let stats = stats_with_sample_rate(0.5);
+    let object_id = ObjectID::random();
+    for _ in 0..1000 {
+        stats.record_lock_conflict(&object_id);
+    }
+
+    // Each sampled access counts for 2.
+    let counts = &stats.hot_objects(1)[0].counts;
+    assert_eq!(counts.lock_conflicts % 2, 0);
+    assert!(counts.lock_conflicts > 500 && counts.lock_conflicts < 1500);
+
+    stats.set_sample_rate(2.0);
+    assert_eq!(stats.sample_rate(), 1.0);
+}
+
+#[test]
+fn test_least_accessed_objects_are_evicted() {
+    let stats = stats_with_sample_rate(1.0);
+    let hot_object_id = ObjectID::random();
+    stats.record_lock_conflict(&hot_object_id);
+    stats.record_lock_conflict(&hot_object_id);
+    for _ in 0..MAX_TRACKED_OBJECTS {
+        stats.record_lock_conflict(&ObjectID::random());
+    }
+
+    // The least accessed half of the objects is evicted when the last one is recorded.
+    let hot_objects = stats.hot_objects(MAX_TRACKED_OBJECTS);
+    assert_eq!(hot_objects.len(), MAX_TRACKED_OBJECTS / 2 + 1);
+    assert_eq!(hot_objects[0].object_id, hot_object_id);
+    assert_eq!(hot_objects[0].counts.lock_conflicts, 2);
+}
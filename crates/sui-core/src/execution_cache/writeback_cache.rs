@@ -600,12 +600,42 @@ impl WritebackCache {
         cb(dirty_entry, cached_entry)
     }
 
+    fn record_object_cache_read<T>(
+        &self,
+        object_id: &ObjectID,
+        result: &CacheResult<T>,
+        entry: impl FnOnce(&T) -> &ObjectEntry,
+    ) {
+        let object = match result {
+            CacheResult::Hit(hit) => match entry(hit) {
+                ObjectEntry::Object(object) => Some(object),
+                ObjectEntry::Deleted | ObjectEntry::Wrapped => None,
+            },
+            CacheResult::NegativeHit | CacheResult::Miss => None,
+        };
+        self.store.object_access_stats().record_cache_read(
+            object_id,
+            object,
+            matches!(result, CacheResult::Miss),
+        );
+    }
+
     // Attempt to get an object from the cache. The DB is not consulted.
     // Can return Hit, Miss, or NegativeHit (if the object is known to not exist).
     fn get_object_entry_by_key_cache_only(
         &self,
         object_id: &ObjectID,
         version: SequenceNumber,
+    ) -> CacheResult<ObjectEntry> {
+        let result = self.get_object_entry_by_key_cache_only_impl(object_id, version);
+        self.record_object_cache_read(object_id, &result, |entry| entry);
+        result
+    }
+
+    fn get_object_entry_by_key_cache_only_impl(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
     ) -> CacheResult<ObjectEntry> {
         Self::with_locked_cache_entries(
             &self.dirty.objects,
@@ -650,6 +680,16 @@ impl WritebackCache {
         &self,
         request_type: &'static str,
         object_id: &ObjectID,
+    ) -> CacheResult<(SequenceNumber, ObjectEntry)> {
+        let result = self.get_object_entry_by_id_cache_only_impl(request_type, object_id);
+        self.record_object_cache_read(object_id, &result, |(_, entry)| entry);
+        result
+    }
+
+    fn get_object_entry_by_id_cache_only_impl(
+        &self,
+        request_type: &'static str,
+        object_id: &ObjectID,
     ) -> CacheResult<(SequenceNumber, ObjectEntry)> {
         self.metrics
             .record_cache_request(request_type, "object_by_id");
@@ -864,6 +904,12 @@ impl WritebackCache {
             self.write_marker_value(epoch_id, *object_key, *marker_value);
         }
 
+        let object_access_stats = self.store.object_access_stats();
+        for ObjectKey(id, _) in deleted.iter().chain(wrapped.iter()) {
+            object_access_stats.record_removal(id);
+        }
+        object_access_stats.record_writes(transaction, written);
+
         // Write children before parents to ensure that readers do not observe a parent object
         // before its most recent children are visible.
         for (object_id, object) in written.iter() {
@@ -2049,13 +2095,19 @@ impl ExecutionCacheWrite for WritebackCache {
         tx_digest: TransactionDigest,
         signed_transaction: Option<VerifiedSignedTransaction>,
     ) -> SuiResult {
-        self.object_locks.acquire_transaction_locks(
+        let result = self.object_locks.acquire_transaction_locks(
             self,
             epoch_store,
             owned_input_objects,
             tx_digest,
             signed_transaction,
-        )
+        );
+        if let Err(SuiError::ObjectLockConflict { obj_ref, .. }) = &result {
+            self.store
+                .object_access_stats()
+                .record_lock_conflict(&obj_ref.0);
+        }
+        result
     }
 
     fn write_transaction_outputs(&self, epoch_id: EpochId, tx_outputs: Arc<TransactionOutputs>) {
@@ -11,6 +11,7 @@ pub mod authority_server;
 pub mod checkpoints;
 pub mod congestion_tracker;
 pub mod consensus_adapter;
+pub mod consensus_commit_replay;
 pub mod consensus_handler;
 pub mod consensus_manager;
 pub mod consensus_throughput_calculator;
@@ -30,6 +31,7 @@ pub mod overload_monitor;
 mod par_index_live_object_set;
 pub(crate) mod post_consensus_tx_reorder;
 pub mod quorum_driver;
+pub mod reconfiguration_dry_run;
 pub mod rpc_index;
 pub mod safe_client;
 mod scoring_decision;
@@ -41,6 +43,7 @@ pub mod subscription_handler;
 pub mod test_utils;
 pub mod traffic_controller;
 mod transaction_input_loader;
+pub mod transaction_latency_tracer;
 mod transaction_manager;
 pub mod transaction_orchestrator;
 mod transaction_outputs;
@@ -0,0 +1,535 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline dry-run of the reconfiguration at the end of an epoch.
+//!
+//! An [`AuthorityState`] is opened against a copy of the database of a node whose last executed
+//! checkpoint is in the last moments of an epoch. The advance epoch transaction is then created and
+//! executed the same way the checkpoint builder does when forming the last checkpoint of the epoch,
+//! with the system packages and supported protocol versions of the running binary. Its outputs are
+//! never committed. Instead, the system state of the next epoch is compared to the current one,
+//! and checked to produce a valid committee. This lets operators check a candidate binary against
+//! real state before the epoch boundary.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{bail, ensure, Context as _};
+use fastcrypto::traits::KeyPair as _;
+use prometheus::Registry;
+use serde_json::Value;
+use sui_archival::reader::ArchiveReaderBalancer;
+use sui_config::{node::DBCheckpointConfig, NodeConfig};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    committee::{Committee, EpochId, ProtocolVersion},
+    digests::ChainIdentifier,
+    effects::TransactionEffectsAPI,
+    execution_status::ExecutionStatus,
+    messages_checkpoint::{CheckpointSequenceNumber, CheckpointTimestamp},
+    sui_system_state::{
+        epoch_start_sui_system_state::EpochStartSystemStateTrait, get_sui_system_state,
+        sui_system_state_summary::SuiSystemStateSummary, SuiSystemStateTrait,
+    },
+    supported_protocol_versions::SupportedProtocolVersions,
+};
+use tokio::time::{sleep, Instant};
+use tracing::{info, warn};
+
+use crate::{
+    authority::{
+        authority_per_epoch_store::AuthorityPerEpochStore,
+        authority_store_tables::AuthorityPerpetualTables, backpressure::BackpressureManager,
+        AuthorityState, AuthorityStore,
+    },
+    checkpoints::{execution_time_observation_keys, CheckpointStore},
+    epoch::{committee_store::CommitteeStore, epoch_metrics::EpochMetrics},
+    execution_cache::build_execution_cache,
+    module_cache_metrics::ResolverMetrics,
+    signature_verifier::SignatureVerifierMetrics,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/reconfiguration_dry_run_tests.rs"]
+mod reconfiguration_dry_run_tests;
+
+// Maximum time to wait for the certificates pending execution in the database to be executed.
+const PENDING_EXECUTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Runs the end of epoch transaction of a node database without committing it.
+pub struct ReconfigurationDryRun {
+    state: Arc<AuthorityState>,
+    epoch_store: Arc<AuthorityPerEpochStore>,
+    checkpoint_store: Arc<CheckpointStore>,
+    supported_protocol_versions: SupportedProtocolVersions,
+}
+
+impl ReconfigurationDryRun {
+    /// Opens the database of the node configured by `config`, at the epoch it would recover to on
+    /// restart.
+    ///
+    /// Shared object versions are assigned to the advance epoch transaction and certificates
+    /// pending execution are executed, so `config` must point to a copy of the node database and
+    /// not to the database of a running node.
+    pub async fn open(mut config: NodeConfig) -> anyhow::Result<Self> {
+        // Nothing should be pruned from the copy while it is used.
+        config.authority_store_pruning_config.num_epochs_to_retain = u64::MAX;
+        config
+            .authority_store_pruning_config
+            .num_epochs_to_retain_for_checkpoints = None;
+        config
+            .authority_store_pruning_config
+            .periodic_compaction_threshold_days = None;
+
+        let registry = Registry::new();
+        let genesis = config.genesis()?.clone();
+        let db_path = config.db_path();
+
+        let committee_store = Arc::new(CommitteeStore::new(
+            db_path.join("epochs"),
+            &genesis.committee()?,
+            None,
+        ));
+        let perpetual_tables =
+            Arc::new(AuthorityPerpetualTables::open(&db_path.join("store"), None));
+        let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+        let backpressure_manager =
+            BackpressureManager::new_from_checkpoint_store(&checkpoint_store);
+        let store = AuthorityStore::open(perpetual_tables, &genesis, &config, &registry).await?;
+
+        let epoch = store.get_recovery_epoch_at_restart()?;
+        let committee = committee_store
+            .get_committee(&epoch)?
+            .with_context(|| format!("Committee of epoch {epoch} not found"))?;
+        let epoch_start_configuration = store
+            .get_epoch_start_configuration()?
+            .context("Epoch start configuration not found")?;
+        let cache_traits = build_execution_cache(
+            &config.execution_cache,
+            &registry,
+            &store,
+            backpressure_manager,
+        );
+
+        let chain_id = ChainIdentifier::from(*genesis.checkpoint().digest());
+        let chain = config
+            .chain_override_for_testing
+            .unwrap_or_else(|| chain_id.chain());
+        let epoch_store = AuthorityPerEpochStore::new(
+            config.protocol_public_key(),
+            committee,
+            &db_path.join("store"),
+            None,
+            EpochMetrics::new(&registry),
+            epoch_start_configuration,
+            cache_traits.backing_package_store.clone(),
+            cache_traits.object_store.clone(),
+            Arc::new(ResolverMetrics::new(&registry)),
+            SignatureVerifierMetrics::new(&registry),
+            &config.expensive_safety_check_config,
+            (chain_id, chain),
+            checkpoint_store
+                .get_highest_executed_checkpoint_seq_number()?
+                .unwrap_or(0),
+        )?;
+        info!(
+            epoch = epoch_store.epoch(),
+            protocol_version = ?epoch_store.protocol_version(),
+            "Opened epoch store for reconfiguration dry-run"
+        );
+
+        // The protocol versions supported by this binary, unless overridden for testing.
+        let supported_protocol_versions = config
+            .supported_protocol_versions
+            .unwrap_or(SupportedProtocolVersions::SYSTEM_DEFAULT);
+        let state = AuthorityState::new(
+            config.protocol_public_key(),
+            Arc::pin(config.protocol_key_pair().copy()),
+            supported_protocol_versions,
+            store,
+            cache_traits,
+            epoch_store.clone(),
+            committee_store,
+            None,
+            None,
+            checkpoint_store.clone(),
+            &registry,
+            genesis.objects(),
+            &DBCheckpointConfig::default(),
+            config,
+            ArchiveReaderBalancer::default(),
+            None,
+            chain_id,
+            None,
+        )
+        .await;
+
+        Ok(Self {
+            state,
+            epoch_store,
+            checkpoint_store,
+            supported_protocol_versions,
+        })
+    }
+
+    /// Executes the advance epoch transaction after the last checkpoint executed by the node, and
+    /// reports the changes it makes. The next epoch starts at `epoch_start_timestamp_ms`, or at the
+    /// timestamp of the last executed checkpoint when unset.
+    pub async fn run(
+        &self,
+        epoch_start_timestamp_ms: Option<CheckpointTimestamp>,
+    ) -> anyhow::Result<ReconfigurationReport> {
+        let epoch = self.epoch_store.epoch();
+        let last_checkpoint = self
+            .checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .context("The node has not executed any checkpoint")?;
+        if last_checkpoint.epoch != epoch || last_checkpoint.end_of_epoch_data.is_some() {
+            bail!(
+                "The node already executed the last checkpoint {} of epoch {}. The database must be \
+                copied before the node reaches the end of the epoch",
+                last_checkpoint.sequence_number,
+                last_checkpoint.epoch
+            );
+        }
+
+        self.wait_for_pending_execution().await;
+        let object_store = self.state.get_object_store();
+        let system_state = get_sui_system_state(object_store.as_ref())?;
+
+        // The last executed checkpoint stands for the last checkpoint of the epoch, whose
+        // transactions are passed along with the advance epoch transaction rather than read back
+        // from the checkpoint store.
+        let contents = self
+            .checkpoint_store
+            .get_checkpoint_contents(&last_checkpoint.content_digest)?
+            .with_context(|| {
+                format!(
+                    "Missing contents of checkpoint {}",
+                    last_checkpoint.sequence_number
+                )
+            })?;
+        let digests: Vec<_> = contents.iter().map(|digests| digests.transaction).collect();
+        let transactions = self
+            .state
+            .get_transaction_cache_reader()
+            .multi_get_transaction_blocks(&digests)
+            .into_iter()
+            .zip(&digests)
+            .map(|(tx, digest)| tx.with_context(|| format!("Missing transaction {digest}")))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let end_of_epoch_observation_keys =
+            execution_time_observation_keys(transactions.iter().map(|tx| tx.transaction_data()));
+
+        info!(
+            epoch,
+            last_checkpoint = last_checkpoint.sequence_number,
+            "Executing advance epoch transaction"
+        );
+        let dry_run = self
+            .state
+            .dry_run_advance_epoch_tx(
+                &self.epoch_store,
+                &last_checkpoint.epoch_rolling_gas_cost_summary,
+                last_checkpoint.sequence_number + 1,
+                epoch_start_timestamp_ms.unwrap_or(last_checkpoint.timestamp_ms),
+                end_of_epoch_observation_keys,
+                last_checkpoint.sequence_number.saturating_sub(1),
+            )
+            .await?;
+
+        // Set up the next epoch the way reconfiguration does, from the new system state.
+        let next_epoch_start_state = dry_run.system_state.clone().into_epoch_start_state();
+        ensure!(
+            next_epoch_start_state.epoch() == epoch + 1,
+            "The advance epoch transaction moved the system state to epoch {} instead of {}",
+            next_epoch_start_state.epoch(),
+            epoch + 1
+        );
+        let next_epoch_committee = next_epoch_start_state.get_sui_committee();
+
+        let mut framework_upgrades = Vec::new();
+        for (id, version, _) in &dry_run.next_epoch_system_packages {
+            let current_version = object_store.get_object(id).map(|package| package.version());
+            if current_version != Some(*version) {
+                framework_upgrades.push(FrameworkUpgrade {
+                    id: *id,
+                    from: current_version,
+                    to: *version,
+                });
+            }
+        }
+
+        let summary = system_state.into_sui_system_state_summary();
+        let next_epoch_summary = dry_run.system_state.into_sui_system_state_summary();
+        Ok(ReconfigurationReport {
+            epoch,
+            last_checkpoint: last_checkpoint.sequence_number,
+            status: dry_run.effects.status().clone(),
+            protocol_version: self.epoch_store.protocol_version(),
+            next_epoch_protocol_version: dry_run.next_epoch_protocol_version,
+            next_epoch_protocol_version_supported: self
+                .supported_protocol_versions
+                .is_version_supported(dry_run.next_epoch_protocol_version),
+            framework_upgrades,
+            safe_mode: next_epoch_summary.safe_mode,
+            validator_changes: diff_validators(&summary, &next_epoch_summary),
+            system_state_changes: diff_system_state(&summary, &next_epoch_summary)?,
+            next_epoch_committee,
+        })
+    }
+
+    // Certificates sequenced by consensus but not executed yet are enqueued for execution when the
+    // authority state is created. They would be executed before the end of the epoch as well.
+    async fn wait_for_pending_execution(&self) {
+        let deadline = Instant::now() + PENDING_EXECUTION_TIMEOUT;
+        loop {
+            let pending = self.state.transaction_manager().inflight_queue_len();
+            if pending == 0 {
+                return;
+            }
+            if Instant::now() >= deadline {
+                warn!(
+                    pending,
+                    "Certificates are still pending execution, dry-running reconfiguration without them"
+                );
+                return;
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// Changes made to the network by the reconfiguration at the end of an epoch.
+#[derive(Debug)]
+pub struct ReconfigurationReport {
+    pub epoch: EpochId,
+    /// Last checkpoint executed by the node before the epoch change.
+    pub last_checkpoint: CheckpointSequenceNumber,
+    /// Execution status of the advance epoch transaction.
+    pub status: ExecutionStatus,
+    pub protocol_version: ProtocolVersion,
+    pub next_epoch_protocol_version: ProtocolVersion,
+    /// Whether the binary running the dry-run supports the protocol version of the next epoch.
+    pub next_epoch_protocol_version_supported: bool,
+    pub framework_upgrades: Vec<FrameworkUpgrade>,
+    /// Whether the network enters safe mode, i.e. the advance epoch call in the system package
+    /// failed.
+    pub safe_mode: bool,
+    pub validator_changes: Vec<ValidatorChange>,
+    /// Changes of the system state fields other than the active validators.
+    pub system_state_changes: Vec<FieldChange>,
+    pub next_epoch_committee: Committee,
+}
+
+/// A system package upgraded at the epoch change.
+#[derive(Debug)]
+pub struct FrameworkUpgrade {
+    pub id: ObjectID,
+    /// Version of the package before the upgrade, `None` if the package is new.
+    pub from: Option<SequenceNumber>,
+    pub to: SequenceNumber,
+}
+
+/// Change of an active validator across the epoch change.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ValidatorChange {
+    pub address: SuiAddress,
+    pub name: String,
+    /// Voting power in the current epoch, `None` if the validator joins the committee.
+    pub voting_power: Option<u64>,
+    /// Voting power in the next epoch, `None` if the validator leaves the committee.
+    pub next_epoch_voting_power: Option<u64>,
+    pub stake: Option<u64>,
+    pub next_epoch_stake: Option<u64>,
+}
+
+/// Change of a system state field, with values rendered as JSON.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Path of the field, e.g. `safeMode` or `stakeSubsidyCurrentDistributionAmount`.
+    pub path: String,
+    pub before: String,
+    pub after: String,
+}
+
+fn diff_validators(
+    summary: &SuiSystemStateSummary,
+    next_epoch_summary: &SuiSystemStateSummary,
+) -> Vec<ValidatorChange> {
+    let validators: BTreeMap<_, _> = summary
+        .active_validators
+        .iter()
+        .map(|validator| (validator.sui_address, validator))
+        .collect();
+    let next_epoch_validators: BTreeMap<_, _> = next_epoch_summary
+        .active_validators
+        .iter()
+        .map(|validator| (validator.sui_address, validator))
+        .collect();
+
+    let addresses: BTreeSet<_> = validators
+        .keys()
+        .chain(next_epoch_validators.keys())
+        .collect();
+    addresses
+        .into_iter()
+        .filter_map(|address| {
+            let validator = validators.get(address);
+            let next_epoch_validator = next_epoch_validators.get(address);
+            let change = ValidatorChange {
+                address: *address,
+                name: validator
+                    .or(next_epoch_validator)
+                    .map(|validator| validator.name.clone())
+                    .unwrap_or_default(),
+                voting_power: validator.map(|validator| validator.voting_power),
+                next_epoch_voting_power: next_epoch_validator
+                    .map(|validator| validator.voting_power),
+                stake: validator.map(|validator| validator.staking_pool_sui_balance),
+                next_epoch_stake: next_epoch_validator
+                    .map(|validator| validator.staking_pool_sui_balance),
+            };
+            (change.voting_power != change.next_epoch_voting_power
+                || change.stake != change.next_epoch_stake)
+                .then_some(change)
+        })
+        .collect()
+}
+
+fn diff_system_state(
+    summary: &SuiSystemStateSummary,
+    next_epoch_summary: &SuiSystemStateSummary,
+) -> anyhow::Result<Vec<FieldChange>> {
+    let to_json = |summary: &SuiSystemStateSummary| -> anyhow::Result<Value> {
+        let mut value = serde_json::to_value(summary)?;
+        // Active validators are compared by diff_validators.
+        if let Value::Object(fields) = &mut value {
+            fields.remove("activeValidators");
+        }
+        Ok(value)
+    };
+    let mut changes = Vec::new();
+    diff_json(
+        "",
+        &to_json(summary)?,
+        &to_json(next_epoch_summary)?,
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+// Collects the fields that differ between `before` and `after`. Objects are compared field by
+// field, other values as a whole.
+fn diff_json(path: &str, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(before_fields), Value::Object(after_fields)) => {
+            let keys: BTreeSet<_> = before_fields.keys().chain(after_fields.keys()).collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_json(
+                    &path,
+                    before_fields.get(key).unwrap_or(&Value::Null),
+                    after_fields.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        _ if before != after => changes.push(FieldChange {
+            path: path.to_string(),
+            before: before.to_string(),
+            after: after.to_string(),
+        }),
+        _ => {}
+    }
+}
+
+impl fmt::Display for ReconfigurationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Reconfiguration of epoch {} to {}, after checkpoint {}",
+            self.epoch,
+            self.epoch + 1,
+            self.last_checkpoint
+        )?;
+        writeln!(f, "Advance epoch transaction status: {:?}", self.status)?;
+        if self.safe_mode {
+            writeln!(
+                f,
+                "WARNING: the advance epoch call failed, the network enters safe mode"
+            )?;
+        }
+
+        writeln!(
+            f,
+            "\nProtocol version: {} -> {}{}",
+            self.protocol_version.as_u64(),
+            self.next_epoch_protocol_version.as_u64(),
+            if self.next_epoch_protocol_version_supported {
+                ""
+            } else {
+                " (NOT supported by this binary)"
+            }
+        )?;
+
+        writeln!(f, "\nFramework upgrades:")?;
+        if self.framework_upgrades.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for upgrade in &self.framework_upgrades {
+            match upgrade.from {
+                Some(from) => writeln!(f, "  {}: {} -> {}", upgrade.id, from, upgrade.to)?,
+                None => writeln!(f, "  {}: new package at {}", upgrade.id, upgrade.to)?,
+            }
+        }
+
+        writeln!(f, "\nValidator set changes:")?;
+        if self.validator_changes.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        let format_value =
+            |value: Option<u64>| value.map_or_else(|| "-".to_string(), |value| value.to_string());
+        for change in &self.validator_changes {
+            let kind = match (change.voting_power, change.next_epoch_voting_power) {
+                (None, Some(_)) => "joins",
+                (Some(_), None) => "leaves",
+                _ => "changes",
+            };
+            writeln!(
+                f,
+                "  {} ({}) {kind}: voting power {} -> {}, stake {} -> {}",
+                change.name,
+                change.address,
+                format_value(change.voting_power),
+                format_value(change.next_epoch_voting_power),
+                format_value(change.stake),
+                format_value(change.next_epoch_stake),
+            )?;
+        }
+        writeln!(
+            f,
+            "  next epoch committee: {} validators, total voting power {}",
+            self.next_epoch_committee.num_members(),
+            self.next_epoch_committee.total_votes()
+        )?;
+
+        writeln!(f, "\nSystem state changes:")?;
+        for change in &self.system_state_changes {
+            writeln!(
+                f,
+                "  {}: {} -> {}",
+                change.path, change.before, change.after
+            )?;
+        }
+        Ok(())
+    }
+}
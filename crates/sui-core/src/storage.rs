@@ -32,6 +32,7 @@ use sui_types::storage::AccountOwnedObjectInfo;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
+use sui_types::storage::ObjectCongestionInfo;
 use sui_types::storage::ObjectStore;
 use sui_types::storage::RpcIndexes;
 use sui_types::storage::RpcStateReader;
@@ -468,6 +469,10 @@ impl RpcStateReader for RestReadStore {
     fn indexes(&self) -> Option<&dyn RpcIndexes> {
         self.index().ok().map(|index| index as _)
     }
+
+    fn get_object_congestion_info(&self, object_id: &ObjectID) -> Option<ObjectCongestionInfo> {
+        self.state.get_object_congestion_info(*object_id)
+    }
 }
 
 impl RpcIndexes for RpcIndexStore {
@@ -0,0 +1,255 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in timing of sampled transactions through the validator pipeline.
+//!
+//! Transactions are sampled by digest, so every stage makes the same sampling decision for a
+//! transaction without coordination, and validators using the same sample rate trace the same
+//! transactions. Each stage reached by a sampled transaction is recorded along with the time
+//! elapsed since the previous one. The latency breakdowns of the most recently traced
+//! transactions are kept in memory and can be retrieved by digest from the admin interface.
+//!
+//! Traced transactions are also exported as traces through `telemetry-subscribers`, with one
+//! span per interval between stages, when `TRACE_FILTER` enables this module, e.g.
+//! `TRACE_FILTER=sui_core::transaction_latency_tracer=info`. These traces bypass the trace
+//! sample rate, since transactions are already sampled here.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use sui_config::node::TransactionLatencyTracingConfig;
+use sui_types::digests::TransactionDigest;
+use tracing::{info_span, Span};
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_latency_tracer_tests.rs"]
+mod transaction_latency_tracer_tests;
+
+// Sample rates are stored in parts per million, so that they can be updated atomically.
+const PARTS_PER_MILLION: u64 = 1_000_000;
+
+/// Stages of the validator pipeline recorded for traced transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionStage {
+    /// The transaction was signed, or voted on, by this validator.
+    Signed,
+    /// The transaction was handed to the consensus adapter, which may delay its submission
+    /// depending on the submit position of this validator.
+    ConsensusSubmissionQueued,
+    /// Consensus acknowledged the submission of the transaction.
+    SubmittedToConsensus,
+    /// The transaction was included in a consensus commit.
+    Sequenced,
+    /// The transaction was deferred to a later commit, because of shared object congestion or
+    /// pending randomness. This stage is recorded every time the transaction is deferred.
+    Deferred,
+    /// The transaction was enqueued in the transaction manager, to wait for its inputs.
+    Scheduled,
+    /// All inputs of the transaction are available, and it was sent to the execution driver.
+    ReadyForExecution,
+    /// Execution of the transaction started.
+    ExecutionStarted,
+    /// The effects of the transaction were committed.
+    Executed,
+}
+
+impl TransactionStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Signed => "signed",
+            Self::ConsensusSubmissionQueued => "consensus_submission_queued",
+            Self::SubmittedToConsensus => "submitted_to_consensus",
+            Self::Sequenced => "sequenced",
+            Self::Deferred => "deferred",
+            Self::Scheduled => "scheduled",
+            Self::ReadyForExecution => "ready_for_execution",
+            Self::ExecutionStarted => "execution_started",
+            Self::Executed => "executed",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StageTiming {
+    pub stage: TransactionStage,
+    /// Unix timestamp at which the stage was reached, in milliseconds.
+    pub timestamp_ms: u64,
+    /// Time elapsed since the previous stage, in milliseconds. Zero for the first stage.
+    pub elapsed_ms: u64,
+}
+
+/// Stages reached by a traced transaction, in the order they were recorded.
+#[derive(Clone, Debug)]
+pub struct TransactionLatencyBreakdown {
+    pub digest: TransactionDigest,
+    pub stages: Vec<StageTiming>,
+    /// Time elapsed between the first and the last recorded stages, in milliseconds.
+    pub total_ms: u64,
+}
+
+impl fmt::Display for TransactionLatencyBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "transaction {}: {}ms", self.digest, self.total_ms)?;
+        for timing in &self.stages {
+            writeln!(
+                f,
+                "  {:<28} +{:>6}ms  (at {})",
+                timing.stage.as_str(),
+                timing.elapsed_ms,
+                timing.timestamp_ms
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct TracedTransaction {
+    stages: Vec<StageTiming>,
+    first_recorded: Instant,
+    last_recorded: Instant,
+    // Root span of the exported trace, and span of the interval following the last stage.
+    // Both are closed once the transaction is executed, or evicted.
+    spans: Option<(Span, Span)>,
+}
+
+impl TracedTransaction {
+    fn breakdown(&self, digest: TransactionDigest) -> TransactionLatencyBreakdown {
+        TransactionLatencyBreakdown {
+            digest,
+            stages: self.stages.clone(),
+            total_ms: (self.last_recorded - self.first_recorded).as_millis() as u64,
+        }
+    }
+}
+
+/// Records the time at which sampled transactions reach each stage of the validator pipeline.
+pub struct TransactionLatencyTracer {
+    sample_rate_ppm: AtomicU64,
+    traced: Mutex<LruCache<TransactionDigest, TracedTransaction>>,
+}
+
+impl TransactionLatencyTracer {
+    /// Tracing is disabled when `config` is not set, until a sample rate is set.
+    pub fn new(config: Option<&TransactionLatencyTracingConfig>) -> Self {
+        let sample_rate = config.map_or(0.0, |config| config.sample_rate());
+        let max_traced_transactions = config
+            .cloned()
+            .unwrap_or_default()
+            .max_traced_transactions();
+        Self {
+            sample_rate_ppm: AtomicU64::new(Self::to_ppm(sample_rate)),
+            traced: Mutex::new(LruCache::new(max_traced_transactions)),
+        }
+    }
+
+    fn to_ppm(sample_rate: f64) -> u64 {
+        (sample_rate.clamp(0.0, 1.0) * PARTS_PER_MILLION as f64) as u64
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate_ppm.load(Ordering::Relaxed) as f64 / PARTS_PER_MILLION as f64
+    }
+
+    /// Changes the fraction of transactions traced. Setting it to 0 disables tracing, while
+    /// keeping the breakdowns of transactions traced so far.
+    pub fn set_sample_rate(&self, sample_rate: f64) {
+        self.sample_rate_ppm
+            .store(Self::to_ppm(sample_rate), Ordering::Relaxed);
+    }
+
+    fn is_sampled(&self, digest: &TransactionDigest) -> bool {
+        let sample_rate_ppm = self.sample_rate_ppm.load(Ordering::Relaxed);
+        if sample_rate_ppm == 0 {
+            return false;
+        }
+        let prefix: [u8; 8] = digest.inner()[..8].try_into().unwrap();
+        u64::from_le_bytes(prefix) % PARTS_PER_MILLION < sample_rate_ppm
+    }
+
+    /// Records that the transaction reached `stage`, if it is sampled. Stages other than
+    /// `Deferred` are only recorded the first time they are reached.
+    pub fn record(&self, digest: &TransactionDigest, stage: TransactionStage) {
+        if !self.is_sampled(digest) {
+            return;
+        }
+        let now = Instant::now();
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let mut traced = self.traced.lock();
+        let Some(transaction) = traced.get_mut(digest) else {
+            let span = info_span!(
+                parent: None,
+                "transaction_latency",
+                digest = %digest,
+                // See telemetry_subscribers::FORCE_SAMPLE_FIELD.
+                force_sample = true,
+            );
+            let stage_span = info_span!(parent: &span, "transaction_stage", stage = stage.as_str());
+            traced.put(
+                *digest,
+                TracedTransaction {
+                    stages: vec![StageTiming {
+                        stage,
+                        timestamp_ms,
+                        elapsed_ms: 0,
+                    }],
+                    first_recorded: now,
+                    last_recorded: now,
+                    spans: (stage != TransactionStage::Executed).then_some((span, stage_span)),
+                },
+            );
+            return;
+        };
+
+        if stage != TransactionStage::Deferred
+            && transaction
+                .stages
+                .iter()
+                .any(|timing| timing.stage == stage)
+        {
+            return;
+        }
+        transaction.stages.push(StageTiming {
+            stage,
+            timestamp_ms,
+            elapsed_ms: (now - transaction.last_recorded).as_millis() as u64,
+        });
+        transaction.last_recorded = now;
+
+        if stage == TransactionStage::Executed {
+            transaction.spans = None;
+        } else if let Some((span, stage_span)) = &mut transaction.spans {
+            // Replacing the span of the previous interval closes it.
+            *stage_span = info_span!(parent: &*span, "transaction_stage", stage = stage.as_str());
+        }
+    }
+
+    /// Returns the latency breakdown of a traced transaction.
+    pub fn latency_breakdown(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Option<TransactionLatencyBreakdown> {
+        self.traced
+            .lock()
+            .peek(digest)
+            .map(|transaction| transaction.breakdown(*digest))
+    }
+
+    /// Returns the latency breakdowns of up to `limit` transactions, most recently updated first.
+    pub fn recent_latency_breakdowns(&self, limit: usize) -> Vec<TransactionLatencyBreakdown> {
+        self.traced
+            .lock()
+            .iter()
+            .take(limit)
+            .map(|(digest, transaction)| transaction.breakdown(*digest))
+            .collect()
+    }
+}
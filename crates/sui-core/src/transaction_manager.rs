@@ -27,6 +27,7 @@ use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::Instant;
 use tracing::{error, info, instrument, trace, warn};
 
+use crate::transaction_latency_tracer::{TransactionLatencyTracer, TransactionStage};
 use crate::{
     authority::authority_per_epoch_store::AuthorityPerEpochStore, execution_cache::ObjectCacheRead,
 };
@@ -54,6 +55,7 @@ pub struct TransactionManager {
     transaction_cache_read: Arc<dyn TransactionCacheRead>,
     tx_ready_certificates: UnboundedSender<PendingCertificate>,
     metrics: Arc<AuthorityMetrics>,
+    latency_tracer: Arc<TransactionLatencyTracer>,
     // inner is a doubly nested lock so that we can enforce that an outer lock (for read) is held
     // before the inner lock (for read or write) can be acquired. During reconfiguration, we acquire
     // the outer lock for write, to ensure that no other threads can be running while we reconfigure.
@@ -342,11 +344,13 @@ impl TransactionManager {
         epoch_store: &AuthorityPerEpochStore,
         tx_ready_certificates: UnboundedSender<PendingCertificate>,
         metrics: Arc<AuthorityMetrics>,
+        latency_tracer: Arc<TransactionLatencyTracer>,
     ) -> TransactionManager {
         let transaction_manager = TransactionManager {
             object_cache_read,
             transaction_cache_read,
             metrics: metrics.clone(),
+            latency_tracer,
             inner: RwLock::new(RwLock::new(Inner::new(epoch_store.epoch(), metrics))),
             tx_ready_certificates,
         };
@@ -354,6 +358,10 @@ impl TransactionManager {
         transaction_manager
     }
 
+    pub(crate) fn latency_tracer(&self) -> &Arc<TransactionLatencyTracer> {
+        &self.latency_tracer
+    }
+
     /// Enqueues certificates / verified transactions into TransactionManager. Once all of the input objects are available
     /// locally for a certificate, the certified transaction will be sent to execution driver.
     ///
@@ -418,6 +426,8 @@ impl TransactionManager {
                         .inc();
                     false
                 } else {
+                    self.latency_tracer
+                        .record(&digest, TransactionStage::Scheduled);
                     true
                 }
             })
@@ -769,6 +779,10 @@ impl TransactionManager {
             .executing_certificates
             .insert(*pending_certificate.certificate.digest()));
         self.metrics.txn_ready_rate_tracker.lock().record();
+        self.latency_tracer.record(
+            pending_certificate.certificate.digest(),
+            TransactionStage::ReadyForExecution,
+        );
         let _ = self.tx_ready_certificates.send(pending_certificate);
         self.metrics.transaction_manager_num_ready.inc();
         self.metrics.execution_driver_dispatch_queue.inc();
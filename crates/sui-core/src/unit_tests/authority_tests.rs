@@ -5950,7 +5950,7 @@ async fn test_consensus_handler_per_object_congestion_control(
     } else {
         epoch_store.get_highest_pending_checkpoint_height()
     };
-    let deferred_txns = epoch_store.get_all_deferred_transactions_for_test();
+    let deferred_txns = epoch_store.get_all_deferred_transactions();
     assert_eq!(deferred_txns.len(), 1);
     assert_eq!(deferred_txns[0].1.len(), 3);
     let deferral_key = deferred_txns[0].0;
@@ -6011,7 +6011,7 @@ async fn test_consensus_handler_per_object_congestion_control(
 
     let deferred_txns = authority
         .epoch_store_for_testing()
-        .get_all_deferred_transactions_for_test();
+        .get_all_deferred_transactions();
     assert_eq!(deferred_txns.len(), 1);
     assert_eq!(deferred_txns[0].1.len(), 1);
     let deferral_key = deferred_txns[0].0;
@@ -6038,7 +6038,7 @@ async fn test_consensus_handler_per_object_congestion_control(
     assert_eq!(scheduled_txns.len(), 1);
     assert!(authority
         .epoch_store_for_testing()
-        .get_all_deferred_transactions_for_test()
+        .get_all_deferred_transactions()
         .is_empty());
 }
 
@@ -6183,7 +6183,7 @@ async fn test_consensus_handler_congestion_control_transaction_cancellation() {
     assert_eq!(scheduled_txns.len(), 3); // 3 = 2 user transactions + 1 consensus commit prologue transaction.
     assert!(authority
         .epoch_store_for_testing()
-        .get_all_deferred_transactions_for_test()
+        .get_all_deferred_transactions()
         .is_empty());
 
     // Check cancelled transaction shared locks.
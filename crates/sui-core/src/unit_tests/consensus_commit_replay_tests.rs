@@ -0,0 +1,148 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use consensus_core::{BlockAPI, CommitDigest, CommitRef, TestBlock, Transaction, VerifiedBlock};
+use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion};
+use sui_types::{
+    base_types::ObjectID, crypto::deterministic_random_account_key, object::Object,
+    transaction::CertifiedTransaction,
+};
+
+use super::*;
+use crate::{
+    authority::{test_authority_builder::TestAuthorityBuilder, AuthorityState},
+    consensus_adapter::consensus_tests::test_certificates_with_gas_objects,
+};
+
+/// A commit of one block per certificate, at `round`.
+fn make_commit(
+    state: &AuthorityState,
+    index: CommitIndex,
+    round: u32,
+    certificates: &[CertifiedTransaction],
+) -> CommittedSubDag {
+    let committee_size = state
+        .epoch_store_for_testing()
+        .epoch_start_state()
+        .get_consensus_committee()
+        .size();
+    let blocks: Vec<_> = certificates
+        .iter()
+        .enumerate()
+        .map(|(i, certificate)| {
+            let transaction =
+                ConsensusTransaction::new_certificate_message(&state.name, certificate.clone());
+            VerifiedBlock::new_for_test(
+                TestBlock::new(round, (i % committee_size) as u32)
+                    .set_transactions(vec![Transaction::new(bcs::to_bytes(&transaction).unwrap())])
+                    .build(),
+            )
+        })
+        .collect();
+    let leader = blocks[0].clone();
+    CommittedSubDag::new(
+        leader.reference(),
+        blocks.clone(),
+        vec![vec![]; blocks.len()],
+        leader.timestamp_ms(),
+        CommitRef::new(index, CommitDigest::MIN),
+        vec![],
+    )
+}
+
+#[tokio::test]
+async fn test_replay_consensus_commits() {
+    // 3 gas objects and a shared object, which all the transactions mutate.
+    let (sender, _keypair) = deterministic_random_account_key();
+    let gas_objects: Vec<Object> = (0..3)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+        .collect();
+    let shared_object = Object::shared_for_testing();
+    let mut all_objects = gas_objects.clone();
+    all_objects.push(shared_object.clone());
+
+    let network_config =
+        sui_swarm_config::network_config_builder::ConfigBuilder::new_with_temp_dir()
+            .with_objects(all_objects)
+            .build();
+    // Transactions are not deferred, so that each commit schedules all of its transactions.
+    let mut protocol_config =
+        ProtocolConfig::get_for_version(ProtocolVersion::max(), Chain::Unknown);
+    protocol_config
+        .set_per_object_congestion_control_mode_for_testing(PerObjectCongestionControlMode::None);
+    let state = TestAuthorityBuilder::new()
+        .with_network_config(&network_config, 0)
+        .with_protocol_config(protocol_config)
+        .build()
+        .await;
+
+    let epoch_store = state.epoch_store_for_testing().clone();
+    let metrics = Arc::new(AuthorityMetrics::new(&Registry::new()));
+    let backpressure_manager = BackpressureManager::new_for_tests();
+    let consensus_handler = ConsensusHandler::new(
+        epoch_store.clone(),
+        Arc::new(CheckpointServiceNoop {}),
+        state.transaction_manager().clone(),
+        state.get_object_cache_reader().clone(),
+        state.get_transaction_cache_reader().clone(),
+        Arc::new(ArcSwap::default()),
+        epoch_store.epoch_start_state().get_consensus_committee(),
+        metrics.clone(),
+        Arc::new(ConsensusThroughputCalculator::new(None, metrics)),
+        backpressure_manager.subscribe(),
+    );
+    let mut replayer = ConsensusCommitReplayer::new(epoch_store.clone(), consensus_handler);
+    assert_eq!(replayer.last_processed_commit_index(), 0);
+
+    let certificates =
+        test_certificates_with_gas_objects(&state, &gas_objects, shared_object.clone()).await;
+    let commits = vec![
+        make_commit(&state, 1, 100, &certificates[0..2]),
+        make_commit(&state, 2, 101, &certificates[2..3]),
+    ];
+
+    // WHEN replaying the commits
+    let replayed = replayer.replay(commits.clone()).await.unwrap();
+
+    // THEN each commit schedules its certificates
+    assert_eq!(replayed.len(), 2);
+    assert_eq!(replayer.last_processed_commit_index(), 2);
+    let mut assigned_versions = vec![];
+    for (replayed, certificates) in replayed
+        .iter()
+        .zip([&certificates[0..2], &certificates[2..3]])
+    {
+        let mut expected: Vec<_> = certificates.iter().map(|c| *c.digest()).collect();
+        let scheduled: Vec<_> = replayed
+            .scheduled_transactions
+            .iter()
+            .filter(|transaction| expected.contains(transaction.digest()))
+            .collect();
+        let mut digests: Vec<_> = scheduled.iter().map(|t| *t.digest()).collect();
+        digests.sort();
+        expected.sort();
+        assert_eq!(digests, expected);
+
+        // AND the shared object is assigned a new version for each of them
+        for transaction in scheduled {
+            let versions = epoch_store
+                .get_assigned_shared_object_versions(&transaction.key())
+                .unwrap();
+            let (_, version) = versions
+                .into_iter()
+                .find(|((id, _), _)| *id == shared_object.id())
+                .unwrap();
+            assigned_versions.push(version);
+        }
+    }
+    assert_eq!(assigned_versions.len(), certificates.len());
+    assert!(assigned_versions.windows(2).all(|v| v[0] < v[1]));
+
+    // Commits that have been processed cannot be replayed, nor observed as prior commits if they
+    // have not.
+    let err = replayer.replay(commits[1..].to_vec()).await.unwrap_err();
+    assert!(err.to_string().contains("already been processed"), "{err}");
+    let commit = make_commit(&state, 3, 102, &certificates[0..1]);
+    let err = replayer.observe_prior_commits(vec![commit]).unwrap_err();
+    assert!(err.to_string().contains("must be replayed"), "{err}");
+}
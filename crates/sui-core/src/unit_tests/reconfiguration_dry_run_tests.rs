@@ -0,0 +1,111 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::{
+    base_types::SuiAddress,
+    sui_system_state::sui_system_state_summary::{SuiSystemStateSummary, SuiValidatorSummary},
+};
+
+use super::{diff_system_state, diff_validators, FieldChange, ValidatorChange};
+
+fn validator(address: SuiAddress, voting_power: u64, stake: u64) -> SuiValidatorSummary {
+    SuiValidatorSummary {
+        sui_address: address,
+        name: format!("validator-{voting_power}"),
+        voting_power,
+        staking_pool_sui_balance: stake,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_diff_validators() {
+    let unchanged = SuiAddress::random_for_testing_only();
+    let changed = SuiAddress::random_for_testing_only();
+    let leaving = SuiAddress::random_for_testing_only();
+    let joining = SuiAddress::random_for_testing_only();
+
+    let summary = SuiSystemStateSummary {
+        active_validators: vec![
+            validator(unchanged, 2500, 100),
+            validator(changed, 2500, 100),
+            validator(leaving, 5000, 200),
+        ],
+        ..Default::default()
+    };
+    let next_epoch_summary = SuiSystemStateSummary {
+        active_validators: vec![
+            validator(unchanged, 2500, 100),
+            validator(changed, 3000, 120),
+            validator(joining, 4500, 180),
+        ],
+        ..Default::default()
+    };
+
+    let mut changes = diff_validators(&summary, &next_epoch_summary);
+    changes.sort_by_key(|change| change.address);
+    let mut expected = vec![
+        ValidatorChange {
+            address: changed,
+            name: "validator-2500".to_string(),
+            voting_power: Some(2500),
+            next_epoch_voting_power: Some(3000),
+            stake: Some(100),
+            next_epoch_stake: Some(120),
+        },
+        ValidatorChange {
+            address: leaving,
+            name: "validator-5000".to_string(),
+            voting_power: Some(5000),
+            next_epoch_voting_power: None,
+            stake: Some(200),
+            next_epoch_stake: None,
+        },
+        ValidatorChange {
+            address: joining,
+            name: "validator-4500".to_string(),
+            voting_power: None,
+            next_epoch_voting_power: Some(4500),
+            stake: None,
+            next_epoch_stake: Some(180),
+        },
+    ];
+    expected.sort_by_key(|change| change.address);
+    assert_eq!(changes, expected);
+}
+
+#[test]
+fn test_diff_system_state() {
+    let summary = SuiSystemStateSummary {
+        epoch: 7,
+        protocol_version: 70,
+        reference_gas_price: 750,
+        active_validators: vec![validator(SuiAddress::random_for_testing_only(), 10000, 1)],
+        ..Default::default()
+    };
+    let next_epoch_summary = SuiSystemStateSummary {
+        epoch: 8,
+        protocol_version: 70,
+        reference_gas_price: 1000,
+        safe_mode: true,
+        ..Default::default()
+    };
+
+    // Active validators are compared separately.
+    let changes = diff_system_state(&summary, &next_epoch_summary).unwrap();
+    let change = |path: &str, before: &str, after: &str| FieldChange {
+        path: path.to_string(),
+        before: before.to_string(),
+        after: after.to_string(),
+    };
+    assert_eq!(
+        changes,
+        vec![
+            change("epoch", "\"7\"", "\"8\""),
+            change("referenceGasPrice", "\"750\"", "\"1000\""),
+            change("safeMode", "false", "true"),
+        ]
+    );
+
+    assert!(diff_system_state(&summary, &summary).unwrap().is_empty());
+}
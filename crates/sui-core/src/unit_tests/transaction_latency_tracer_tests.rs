@@ -0,0 +1,171 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_config::node::TransactionLatencyTracingConfig;
+use sui_types::digests::TransactionDigest;
+
+use super::{TransactionLatencyTracer, TransactionStage};
+
+fn tracer_with_sample_rate(sample_rate: f64) -> TransactionLatencyTracer {
+    TransactionLatencyTracer::new(Some(&TransactionLatencyTracingConfig {
+        sample_rate: Some(sample_rate),
+        ..Default::default()
+    }))
+}
+
+fn recorded_stages(
+    tracer: &TransactionLatencyTracer,
+    digest: &TransactionDigest,
+) -> Vec<TransactionStage> {
+    tracer
+        .latency_breakdown(digest)
+        .unwrap()
+        .stages
+        .iter()
+        .map(|timing| timing.stage)
+        .collect()
+}
+
+#[test]
+fn test_tracing_disabled_without_config() {
+    let tracer = TransactionLatencyTracer::new(None);
+    assert_eq!(tracer.sample_rate(), 0.0);
+
+    let digest = TransactionDigest::random();
+    tracer.record(&digest, TransactionStage::Signed);
+    assert!(tracer.latency_breakdown(&digest).is_none());
+    assert!(tracer.recent_latency_breakdowns(10).is_empty());
+}
+
+#[test]
+fn test_record_stages() {
+    let tracer = tracer_with_sample_rate(1.0);
+    let digest = TransactionDigest::random();
+    let stages = [
+        TransactionStage::Signed,
+        TransactionStage::ConsensusSubmissionQueued,
+        TransactionStage::SubmittedToConsensus,
+        TransactionStage::Sequenced,
+        TransactionStage::Scheduled,
+        TransactionStage::ReadyForExecution,
+        TransactionStage::ExecutionStarted,
+        TransactionStage::Executed,
+    ];
+    for stage in stages {
+        tracer.record(&digest, stage);
+    }
+
+    let breakdown = tracer.latency_breakdown(&digest).unwrap();
+    assert_eq!(breakdown.digest, digest);
+    assert_eq!(recorded_stages(&tracer, &digest), stages);
+    assert_eq!(breakdown.stages[0].elapsed_ms, 0);
+    assert!(
+        breakdown
+            .stages
+            .iter()
+            .map(|timing| timing.elapsed_ms)
+            .sum::<u64>()
+            <= breakdown.total_ms
+    );
+    assert!(breakdown
+        .stages
+        .windows(2)
+        .all(|pair| pair[0].timestamp_ms <= pair[1].timestamp_ms));
+}
+
+#[test]
+fn test_repeated_stages() {
+    let tracer = tracer_with_sample_rate(1.0);
+    let digest = TransactionDigest::random();
+    tracer.record(&digest, TransactionStage::Sequenced);
+    tracer.record(&digest, TransactionStage::Deferred);
+    tracer.record(&digest, TransactionStage::Sequenced);
+    tracer.record(&digest, TransactionStage::Deferred);
+    tracer.record(&digest, TransactionStage::Scheduled);
+
+    // Only deferrals are recorded every time they happen.
+    assert_eq!(
+        recorded_stages(&tracer, &digest),
+        [
+            TransactionStage::Sequenced,
+            TransactionStage::Deferred,
+            TransactionStage::Deferred,
+            TransactionStage::Scheduled,
+        ]
+    );
+}
+
+#[test]
+fn test_recent_latency_breakdowns() {
+    let tracer = TransactionLatencyTracer::new(Some(&TransactionLatencyTracingConfig {
+        sample_rate: Some(1.0),
+        max_traced_transactions: Some(2.try_into().unwrap()),
+    }));
+    let digests = [
+        TransactionDigest::random(),
+        TransactionDigest::random(),
+        TransactionDigest::random(),
+    ];
+    for digest in &digests {
+        tracer.record(digest, TransactionStage::Signed);
+    }
+
+    // The oldest transaction was evicted.
+    assert!(tracer.latency_breakdown(&digests[0]).is_none());
+    let recent = tracer
+        .recent_latency_breakdowns(10)
+        .into_iter()
+        .map(|breakdown| breakdown.digest)
+        .collect::<Vec<_>>();
+    assert_eq!(recent, [digests[2], digests[1]]);
+    assert_eq!(tracer.recent_latency_breakdowns(1).len(), 1);
+}
+
+#[test]
+fn test_set_sample_rate() {
+    let tracer = TransactionLatencyTracer::new(None);
+    tracer.set_sample_rate(1.0);
+    assert_eq!(tracer.sample_rate(), 1.0);
+    let traced = TransactionDigest::random();
+    tracer.record(&traced, TransactionStage::Signed);
+
+    // Disabling tracing keeps the transactions traced so far, but stops recording stages.
+    tracer.set_sample_rate(0.0);
+    tracer.record(&traced, TransactionStage::Executed);
+    let untraced = TransactionDigest::random();
+    tracer.record(&untraced, TransactionStage::Signed);
+    assert_eq!(
+        recorded_stages(&tracer, &traced),
+        [TransactionStage::Signed]
+    );
+    assert!(tracer.latency_breakdown(&untraced).is_none());
+
+    // Sample rates are clamped.
+    tracer.set_sample_rate(2.0);
+    assert_eq!(tracer.sample_rate(), 1.0);
+}
+
+#[test]
+fn test_partial_sampling_is_deterministic() {
+    let tracer = tracer_with_sample_rate(0.5);
+    let other = tracer_with_sample_rate(0.5);
+    let digests = (0..200)
+        .map(|_| TransactionDigest::random())
+        .collect::<Vec<_>>();
+    for digest in &digests {
+        tracer.record(digest, TransactionStage::Signed);
+        other.record(digest, TransactionStage::Sequenced);
+    }
+
+    let sampled = digests
+        .iter()
+        .filter(|digest| tracer.latency_breakdown(digest).is_some())
+        .count();
+    assert!(sampled > 0 && sampled < digests.len());
+    for digest in &digests {
+        assert_eq!(
+            tracer.latency_breakdown(digest).is_some(),
+            other.latency_breakdown(digest).is_some()
+        );
+    }
+}
@@ -39,6 +39,7 @@ fn make_transaction_manager(
         &state.epoch_store_for_testing(),
         tx_ready_certificates,
         state.metrics.clone(),
+        state.transaction_latency_tracer().clone(),
     );
 
     (transaction_manager, rx_ready_certificates)
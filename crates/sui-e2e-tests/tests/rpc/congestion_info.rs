@@ -0,0 +1,157 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::proto::node::v2alpha::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::v2alpha::subscription_service_client::SubscriptionServiceClient;
+use sui_rpc_api::proto::node::v2alpha::GetCongestionInfoRequest;
+use sui_rpc_api::proto::node::v2alpha::GetCongestionInfoResponse;
+use sui_rpc_api::proto::node::v2alpha::SubscribeCongestionInfoRequest;
+use sui_rpc_api::proto::types::ObjectId as ProtoObjectId;
+use sui_sdk_types::ObjectId;
+use sui_types::SUI_CLOCK_OBJECT_ID;
+use test_cluster::TestClusterBuilder;
+use tokio_stream::StreamExt;
+
+use crate::transfer_coin;
+
+#[sim_test]
+async fn get_congestion_info() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let reference_gas_price = test_cluster.get_reference_gas_price().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // Objects no transaction was cancelled for, whether they exist or not, are not congested.
+    let object_ids = vec![
+        ObjectId::from(SUI_CLOCK_OBJECT_ID),
+        ObjectId::from(sui_types::base_types::ObjectID::random()),
+    ];
+    let GetCongestionInfoResponse {
+        objects,
+        suggested_gas_price,
+        reference_gas_price: response_reference_gas_price,
+    } = grpc_client
+        .get_congestion_info(GetCongestionInfoRequest {
+            object_ids: object_ids.iter().copied().map(Into::into).collect(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response_reference_gas_price, Some(reference_gas_price));
+    assert_eq!(suggested_gas_price, Some(reference_gas_price));
+    assert_eq!(objects.len(), object_ids.len());
+    for (object, object_id) in objects.iter().zip(&object_ids) {
+        assert_eq!(object.object_id, Some((*object_id).into()));
+        assert_eq!(object.congested, Some(false));
+        assert_eq!(object.suggested_gas_price, None);
+        assert!(object.recent_cancellations.is_empty());
+    }
+
+    // An empty request only returns the gas prices.
+    let response = grpc_client
+        .get_congestion_info(GetCongestionInfoRequest { object_ids: vec![] })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.objects.is_empty());
+    assert_eq!(response.suggested_gas_price, Some(reference_gas_price));
+}
+
+#[sim_test]
+async fn get_congestion_info_invalid_requests() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // At most 256 objects can be requested at once.
+    let object_id: ProtoObjectId = ObjectId::from(SUI_CLOCK_OBJECT_ID).into();
+    let status = grpc_client
+        .get_congestion_info(GetCongestionInfoRequest {
+            object_ids: vec![object_id.clone(); 257],
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("too many object_ids"), "{status}");
+    grpc_client
+        .get_congestion_info(GetCongestionInfoRequest {
+            object_ids: vec![object_id.clone(); 256],
+        })
+        .await
+        .unwrap();
+
+    // Object ids must be 32 bytes long.
+    let status = grpc_client
+        .get_congestion_info(GetCongestionInfoRequest {
+            object_ids: vec![
+                object_id,
+                ProtoObjectId {
+                    object_id: Some(vec![1, 2, 3].into()),
+                },
+            ],
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("invalid object_id"), "{status}");
+}
+
+#[sim_test]
+async fn subscribe_congestion_info() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let reference_gas_price = test_cluster.get_reference_gas_price().await;
+
+    let mut client = SubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // Invalid requests are rejected before subscribing.
+    let status = client
+        .subscribe_congestion_info(SubscribeCongestionInfoRequest {
+            object_ids: vec![ProtoObjectId { object_id: None }],
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let object_id = ObjectId::from(SUI_CLOCK_OBJECT_ID);
+    let mut stream = client
+        .subscribe_congestion_info(SubscribeCongestionInfoRequest {
+            object_ids: vec![object_id.into()],
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let _transaction_digest = transfer_coin(&test_cluster.wallet).await;
+
+    // An update is sent for each checkpoint.
+    let mut last = None;
+    for _ in 0..3 {
+        let response = stream.next().await.unwrap().unwrap();
+        let cursor = response.cursor.unwrap();
+        if let Some(last) = last {
+            assert_eq!(cursor, last + 1);
+        }
+        last = Some(cursor);
+
+        let congestion_info = response.congestion_info.unwrap();
+        assert_eq!(
+            congestion_info.reference_gas_price,
+            Some(reference_gas_price)
+        );
+        assert_eq!(
+            congestion_info.suggested_gas_price,
+            Some(reference_gas_price)
+        );
+        assert_eq!(congestion_info.objects.len(), 1);
+        assert_eq!(congestion_info.objects[0].object_id, Some(object_id.into()));
+        assert_eq!(congestion_info.objects[0].congested, Some(false));
+    }
+}
@@ -4,6 +4,7 @@
 mod checkpoints;
 mod coin_info;
 mod committee;
+mod congestion_info;
 mod execute;
 mod node_info;
 mod objects;
@@ -0,0 +1,31 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Schnorr signatures over secp256k1 as specified in BIP-340, e.g. as used by Bitcoin Taproot.
+module sui::schnorr_secp256k1;
+
+use std::hash::sha2_256;
+
+#[allow(unused_const)]
+/// Error that the feature is not available on this network.
+const ENotSupported: u64 = 0;
+
+/// @param signature: A 64-bytes BIP-340 signature.
+/// @param public_key: A 32-bytes x-only public key.
+/// @param msg: The 32-bytes message that we test the signature against, e.g. a Taproot sighash.
+///
+/// If the signature is a valid BIP-340 signature of the message and public key, return true.
+/// Otherwise, return false. The message is not hashed before verification, so messages that are not
+/// exactly 32 bytes long are rejected and `verify` returns false for them. Hash longer messages
+/// first, e.g. with `tagged_hash`.
+public native fun verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;
+
+/// Compute the BIP-340 tagged hash `SHA256(SHA256(tag) || SHA256(tag) || msg)`, e.g. to compute
+/// a message with the tag "TapSighash".
+public fun tagged_hash(tag: vector<u8>, msg: vector<u8>): vector<u8> {
+    let tag_hash = sha2_256(tag);
+    let mut data = tag_hash;
+    data.append(tag_hash);
+    data.append(msg);
+    sha2_256(data)
+}
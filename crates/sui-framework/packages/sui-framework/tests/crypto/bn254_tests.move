@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[allow(implicit_const_copy)]
+#[test_only]
+module sui::bn254_tests {
+    use sui::bn254;
+    use sui::group_ops;
+    use sui::random;
+    use sui::test_utils::assert_eq;
+
+    const ORDER_BYTES: vector<u8> = x"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    const ORDER_MINUS_ONE_BYTES: vector<u8> = x"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+    const LONG_SCALAR_BYTES: vector<u8> = x"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f000000000";
+    const SHORT_SCALAR_BYTES: vector<u8> = x"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f000";
+    const LONG_G1_BYTES: vector<u8> = x"010000000000000000000000000000000000000000000000000000000000000000";
+    const SHORT_G1_BYTES: vector<u8> = x"01000000000000000000000000000000000000000000000000000000000000";
+    const LONG_G2_BYTES: vector<u8> = x"edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e1911";
+    const SHORT_G2_BYTES: vector<u8> = x"edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e";
+
+    #[test]
+    fun test_scalar_ops() {
+        let zero = bn254::scalar_from_u64(0);
+        let one = bn254::scalar_from_u64(1);
+        assert!(group_ops::equal(&zero, &bn254::scalar_zero()));
+        assert!(group_ops::equal(&one, &bn254::scalar_one()));
+        assert!(group_ops::equal(&zero, &bn254::scalar_one()) == false);
+
+        let two = bn254::scalar_add(&one, &one);
+        let four = bn254::scalar_add(&two, &two);
+        assert!(group_ops::equal(&four, &bn254::scalar_from_u64(4)));
+
+        let eight = bn254::scalar_mul(&four, &two);
+        assert!(group_ops::equal(&eight, &bn254::scalar_from_u64(8)));
+
+        let zero0 = bn254::scalar_mul(&zero, &eight);
+        assert!(group_ops::equal(&zero0, &bn254::scalar_zero()));
+
+        let six = bn254::scalar_sub(&eight, &two);
+        assert!(group_ops::equal(&six, &bn254::scalar_from_u64(6)));
+
+        let minus_six = bn254::scalar_sub(&two, &eight);
+        let three = bn254::scalar_add(&minus_six, &bn254::scalar_from_u64(9));
+        assert!(group_ops::equal(&three, &bn254::scalar_from_u64(3)));
+
+        let three = bn254::scalar_div(&two, &six);
+        assert!(group_ops::equal(&three, &bn254::scalar_from_u64(3)));
+
+        let minus_three = bn254::scalar_neg(&three);
+        assert!(group_ops::equal(&bn254::scalar_add(&minus_three, &six), &bn254::scalar_from_u64(3)));
+
+        let minus_zero = bn254::scalar_neg(&zero);
+        assert!(group_ops::equal(&minus_zero, &zero));
+
+        let inv_three = bn254::scalar_inv(&three);
+        assert!(group_ops::equal(&bn254::scalar_mul(&six, &inv_three), &bn254::scalar_from_u64(2)));
+
+        let order_minus_one = bn254::scalar_from_bytes(&ORDER_MINUS_ONE_BYTES);
+        let _ = bn254::scalar_add(&order_minus_one, &order_minus_one);
+        let _ = bn254::scalar_mul(&order_minus_one, &order_minus_one);
+    }
+
+    #[test]
+    fun test_valid_scalar_from_bytes() {
+        let eight = bn254::scalar_from_u64(8);
+        let eight_from_bytes = bn254::scalar_from_bytes(group_ops::bytes(&eight));
+        assert!(group_ops::equal(&eight, &eight_from_bytes));
+
+        let zero = bn254::scalar_zero();
+        let zero_from_bytes = bn254::scalar_from_bytes(group_ops::bytes(&zero));
+        assert!(group_ops::equal(&zero, &zero_from_bytes));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_order() {
+        let _ = bn254::scalar_from_bytes(&ORDER_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_empty() {
+        let _ = bn254::scalar_from_bytes(&vector[]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_too_short() {
+        let _ = bn254::scalar_from_bytes(&SHORT_SCALAR_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_too_long() {
+        let _ = bn254::scalar_from_bytes(&LONG_SCALAR_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_scalar_inv() {
+        let a = bn254::scalar_from_u64(0);
+        let _ = bn254::scalar_inv(&a);
+    }
+
+    #[test]
+    fun test_g1_ops() {
+        let id = bn254::g1_identity();
+        let g = bn254::g1_generator();
+
+        assert!(group_ops::equal(&id, &bn254::g1_sub(&g, &g)));
+        assert!(group_ops::equal(&id, &bn254::g1_sub(&id, &id)));
+        assert!(group_ops::equal(&g, &bn254::g1_add(&id, &g)));
+        assert!(group_ops::equal(&g, &bn254::g1_add(&g, &id)));
+
+        let two_g = bn254::g1_add(&g, &g);
+        let four_g = bn254::g1_add(&two_g, &two_g);
+
+        let another_four_g = bn254::g1_mul(&bn254::scalar_from_u64(4), &g);
+        assert!(group_ops::equal(&four_g, &another_four_g));
+
+        let another_id = bn254::g1_mul(&bn254::scalar_from_u64(0), &g);
+        assert!(group_ops::equal(&id, &another_id));
+
+        let another_two_g = bn254::g1_sub(&four_g, &two_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+
+        let another_two_g = bn254::g1_div(&bn254::scalar_from_u64(2), &four_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+
+        let minus_two_g = bn254::g1_neg(&two_g);
+        let another_two_g = bn254::g1_add(&minus_two_g, &four_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+
+        let order_minus_one = bn254::scalar_from_bytes(&ORDER_MINUS_ONE_BYTES);
+        let minus_g = bn254::g1_mul(&order_minus_one, &g);
+        assert!(group_ops::equal(&minus_g, &bn254::g1_neg(&g)));
+
+        let msg1 = b"123";
+        let msg2 = b"321";
+        let hash1 = bn254::hash_to_g1(&msg1);
+        let hash2 = bn254::hash_to_g1(&msg2);
+        let hash3 = bn254::hash_to_g1(&msg1);
+        assert!(group_ops::equal(&hash1, &hash2) == false);
+        assert!(group_ops::equal(&hash1, &hash3));
+    }
+
+    #[test]
+    fun test_valid_g1_from_bytes() {
+        let g = bn254::g1_generator();
+        let g_from_bytes = bn254::g1_from_bytes(group_ops::bytes(&g));
+        assert!(group_ops::equal(&g, &g_from_bytes));
+
+        let id = bn254::g1_identity();
+        let id_from_bytes = bn254::g1_from_bytes(group_ops::bytes(&id));
+        assert!(group_ops::equal(&id, &id_from_bytes));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_empty() {
+        let _ = bn254::g1_from_bytes(&vector[]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_too_short() {
+        let _ = bn254::g1_from_bytes(&SHORT_G1_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_too_long() {
+        let _ = bn254::g1_from_bytes(&LONG_G1_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_div() {
+        let a = bn254::scalar_from_u64(0);
+        let b = bn254::g1_generator();
+        let _ = bn254::g1_div(&a, &b);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g1_empty_msg() {
+        let _ = bn254::hash_to_g1(&vector[]);
+    }
+
+    #[test]
+    fun test_g2_ops() {
+        let id = bn254::g2_identity();
+        let g = bn254::g2_generator();
+
+        assert!(group_ops::equal(&id, &bn254::g2_sub(&g, &g)));
+        assert!(group_ops::equal(&g, &bn254::g2_add(&id, &g)));
+
+        let two_g = bn254::g2_add(&g, &g);
+        let four_g = bn254::g2_add(&two_g, &two_g);
+
+        let another_four_g = bn254::g2_mul(&bn254::scalar_from_u64(4), &g);
+        assert!(group_ops::equal(&four_g, &another_four_g));
+
+        let another_id = bn254::g2_mul(&bn254::scalar_from_u64(0), &g);
+        assert!(group_ops::equal(&id, &another_id));
+
+        let another_two_g = bn254::g2_div(&bn254::scalar_from_u64(2), &four_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+
+        let minus_two_g = bn254::g2_neg(&two_g);
+        let another_two_g = bn254::g2_add(&minus_two_g, &four_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+    }
+
+    #[test]
+    fun test_valid_g2_from_bytes() {
+        let g = bn254::g2_generator();
+        let g_from_bytes = bn254::g2_from_bytes(group_ops::bytes(&g));
+        assert!(group_ops::equal(&g, &g_from_bytes));
+
+        let id = bn254::g2_identity();
+        let id_from_bytes = bn254::g2_from_bytes(group_ops::bytes(&id));
+        assert!(group_ops::equal(&id, &id_from_bytes));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g2_too_short() {
+        let _ = bn254::g2_from_bytes(&SHORT_G2_BYTES);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_invalid_g2_too_long() {
+        let _ = bn254::g2_from_bytes(&LONG_G2_BYTES);
+    }
+
+    #[test]
+    fun test_gt_ops() {
+        let id = bn254::gt_identity();
+        let g = bn254::gt_generator();
+
+        assert!(group_ops::equal(&id, &bn254::gt_sub(&g, &g)));
+        assert!(group_ops::equal(&g, &bn254::gt_add(&id, &g)));
+
+        let two_g = bn254::gt_add(&g, &g);
+        let four_g = bn254::gt_add(&two_g, &two_g);
+
+        let another_four_g = bn254::gt_mul(&bn254::scalar_from_u64(4), &g);
+        assert!(group_ops::equal(&four_g, &another_four_g));
+
+        let another_id = bn254::gt_mul(&bn254::scalar_from_u64(0), &g);
+        assert!(group_ops::equal(&id, &another_id));
+
+        let another_two_g = bn254::gt_div(&bn254::scalar_from_u64(2), &four_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+
+        let minus_two_g = bn254::gt_neg(&two_g);
+        let another_two_g = bn254::gt_add(&minus_two_g, &four_g);
+        assert!(group_ops::equal(&two_g, &another_two_g));
+    }
+
+    #[test]
+    fun test_msm_g1() {
+        let mut expected_result = bn254::g1_identity();
+        let g = bn254::g1_generator();
+        let mut scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let mut elements: vector<group_ops::Element<bn254::G1>> = vector[];
+        let mut gen = random::new_generator_for_testing();
+        let mut i = gen.generate_u8() % 32 + 1;
+        while (i > 0) {
+            let base_scalar = bn254::scalar_from_u64(gen.generate_u64());
+            let base = bn254::g1_mul(&base_scalar, &g);
+            let exponent_scalar = bn254::scalar_from_u64(gen.generate_u64());
+            let base_exp = bn254::g1_mul(&exponent_scalar, &base);
+            elements.push_back(base);
+            scalars.push_back(exponent_scalar);
+            expected_result = bn254::g1_add(&expected_result, &base_exp);
+            i = i - 1;
+        };
+        let result = bn254::g1_multi_scalar_multiplication(&scalars, &elements);
+        assert!(group_ops::equal(&result, &expected_result));
+    }
+
+    #[test]
+    fun test_msm_g2() {
+        let mut expected_result = bn254::g2_identity();
+        let g = bn254::g2_generator();
+        let mut scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let mut elements: vector<group_ops::Element<bn254::G2>> = vector[];
+        let mut gen = random::new_generator_for_testing();
+        let mut i = gen.generate_u8() % 32 + 1;
+        while (i > 0) {
+            let base_scalar = bn254::scalar_from_u64(gen.generate_u64());
+            let base = bn254::g2_mul(&base_scalar, &g);
+            let exponent_scalar = bn254::scalar_from_u64(gen.generate_u64());
+            let base_exp = bn254::g2_mul(&exponent_scalar, &base);
+            elements.push_back(base);
+            scalars.push_back(exponent_scalar);
+            expected_result = bn254::g2_add(&expected_result, &base_exp);
+            i = i - 1;
+        };
+        let result = bn254::g2_multi_scalar_multiplication(&scalars, &elements);
+        assert!(group_ops::equal(&result, &expected_result));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInvalidInput)]
+    fun test_empty_g1_msm() {
+        let scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let elements: vector<group_ops::Element<bn254::G1>> = vector[];
+        let _ = bn254::g1_multi_scalar_multiplication(&scalars, &elements);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = group_ops::EInputTooLong)]
+    fun test_msm_g1_too_long() {
+        let mut i = 1;
+        let g = bn254::g1_generator();
+        let mut scalars: vector<group_ops::Element<bn254::Scalar>> = vector[];
+        let mut elements: vector<group_ops::Element<bn254::G1>> = vector[];
+        while (i < 34) {
+            // this limit is defined in the protocol config
+            scalars.push_back(bn254::scalar_from_u64(i));
+            elements.push_back(g);
+            i = i + 1;
+        };
+        let _ = bn254::g1_multi_scalar_multiplication(&scalars, &elements);
+    }
+
+    #[test]
+    fun test_pairing() {
+        let g1 = bn254::g1_generator();
+        let g2 = bn254::g2_generator();
+        let gt = bn254::gt_generator();
+        assert_eq(bn254::pairing(&g1, &g2), gt);
+
+        let g1_3 = bn254::g1_mul(&bn254::scalar_from_u64(3), &g1);
+        let g2_5 = bn254::g2_mul(&bn254::scalar_from_u64(5), &g2);
+        let gt_15 = bn254::gt_mul(&bn254::scalar_from_u64(15), &gt);
+        assert_eq(bn254::pairing(&g1_3, &g2_5), gt_15);
+
+        assert_eq(bn254::pairing(&bn254::g1_identity(), &bn254::g2_identity()), bn254::gt_identity());
+        assert_eq(bn254::pairing(&bn254::g1_generator(), &bn254::g2_identity()), bn254::gt_identity());
+        assert_eq(bn254::pairing(&bn254::g1_identity(), &bn254::g2_generator()), bn254::gt_identity());
+    }
+}
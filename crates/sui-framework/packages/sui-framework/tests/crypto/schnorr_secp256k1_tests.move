@@ -0,0 +1,57 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::schnorr_secp256k1_tests {
+    use sui::schnorr_secp256k1;
+
+    #[test]
+    fun test_schnorr_secp256k1_valid_sig() {
+        // Test vectors 0 and 1 from https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+        let msg = x"0000000000000000000000000000000000000000000000000000000000000000";
+        let pk = x"f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9";
+        let sig = x"e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0";
+        assert!(schnorr_secp256k1::verify(&sig, &pk, &msg));
+
+        let msg = x"243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+        let pk = x"dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+        let sig = x"6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+        assert!(schnorr_secp256k1::verify(&sig, &pk, &msg));
+    }
+
+    #[test]
+    fun test_schnorr_secp256k1_invalid_sig() {
+        let msg = x"243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+        let pk = x"dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+        let invalid_sig = x"7896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+        assert!(!schnorr_secp256k1::verify(&invalid_sig, &pk, &msg));
+
+        let sig = x"6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+        let other_msg = x"343f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+        assert!(!schnorr_secp256k1::verify(&sig, &pk, &other_msg));
+    }
+
+    #[test]
+    fun test_schnorr_secp256k1_invalid_inputs() {
+        let msg = x"243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+        let pk = x"dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+        let sig = x"6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+
+        // Public key is not on the curve (test vector 5).
+        let invalid_pk = x"eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34";
+        assert!(!schnorr_secp256k1::verify(&sig, &invalid_pk, &msg));
+
+        // Wrong lengths.
+        assert!(!schnorr_secp256k1::verify(&x"", &pk, &msg));
+        assert!(!schnorr_secp256k1::verify(&sig, &x"", &msg));
+        assert!(!schnorr_secp256k1::verify(&sig, &pk, &x"243f6a88"));
+    }
+
+    #[test]
+    fun test_tagged_hash() {
+        // The next was calculated using python
+        // t = hashlib.sha256(tag).digest(); hashlib.sha256(t + t + msg).digest()
+        let expected = x"4d2794b30997311c307e5fdb33ec467ee9b96b6bcaf2111644c880804857516d";
+        assert!(schnorr_secp256k1::tagged_hash(b"TapLeaf", b"hello world!") == expected);
+    }
+}
@@ -11,7 +11,8 @@ use cached::{proc_macro::cached, SizedCache};
 use sui_json_rpc::{governance_api::ValidatorExchangeRates, SuiRpcModule};
 use sui_json_rpc_api::GovernanceReadApiServer;
 use sui_json_rpc_types::{
-    DelegatedStake, EpochInfo, StakeStatus, SuiCommittee, SuiObjectDataFilter, ValidatorApys,
+    CongestionInfo, DelegatedStake, EpochInfo, StakeStatus, SuiCommittee, SuiObjectDataFilter,
+    ValidatorApys,
 };
 use sui_open_rpc::Module;
 use sui_types::{
@@ -279,6 +280,13 @@ impl GovernanceReadApiServer for GovernanceReadApi {
         )?))
     }
 
+    async fn get_congestion_info(&self, _object_ids: Vec<ObjectID>) -> RpcResult<CongestionInfo> {
+        Err(IndexerError::NotSupportedError(
+            "congestion info is only available from a fullnode".to_owned(),
+        )
+        .into())
+    }
+
     async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
         Ok(self.get_validators_apy().await?)
     }
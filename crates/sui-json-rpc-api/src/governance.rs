@@ -4,7 +4,7 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
-use sui_json_rpc_types::{DelegatedStake, SuiCommittee, ValidatorApys};
+use sui_json_rpc_types::{CongestionInfo, DelegatedStake, SuiCommittee, ValidatorApys};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::sui_serde::BigInt;
@@ -40,6 +40,11 @@ pub trait GovernanceReadApi {
     #[method(name = "getReferenceGasPrice")]
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>>;
 
+    /// Return the congestion status of the given shared objects, along with the gas price a
+    /// transaction mutating all of them should bid to avoid being cancelled due to congestion.
+    #[method(name = "getCongestionInfo")]
+    async fn get_congestion_info(&self, object_ids: Vec<ObjectID>) -> RpcResult<CongestionInfo>;
+
     /// Return the validator APY
     #[method(name = "getValidatorsApy")]
     async fn get_validators_apy(&self) -> RpcResult<ValidatorApys>;
@@ -1,6 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::ClientError;
+use jsonrpsee::rpc_params;
+use jsonrpsee::types::error::INVALID_PARAMS_CODE;
 use shared_crypto::intent::{Intent, IntentMessage};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
@@ -10,13 +14,14 @@ use std::time::Duration;
 use sui_json::{call_args, type_args};
 use sui_json_rpc_api::{
     CoinReadApiClient, GovernanceReadApiClient, IndexerApiClient, ReadApiClient,
-    TransactionBuilderClient, WriteApiClient,
+    TransactionBuilderClient, WriteApiClient, QUERY_MAX_RESULT_LIMIT,
 };
 use sui_json_rpc_types::ObjectsPage;
 use sui_json_rpc_types::{
-    Balance, CoinPage, DelegatedStake, StakeStatus, SuiCoinMetadata, SuiExecutionStatus,
-    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffectsAPI,
-    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, TransactionBlockBytes,
+    Balance, CoinPage, CongestionInfo, DelegatedStake, StakeStatus, SuiCoinMetadata,
+    SuiExecutionStatus, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    TransactionBlockBytes,
 };
 use sui_json_rpc_types::{ObjectChange, ZkLoginIntentScope};
 use sui_macros::sim_test;
@@ -36,7 +41,7 @@ use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::signature::GenericSignature;
 use sui_types::utils::load_test_vectors;
 use sui_types::zk_login_authenticator::ZkLoginAuthenticator;
-use sui_types::{parse_sui_struct_tag, SUI_FRAMEWORK_ADDRESS};
+use sui_types::{parse_sui_struct_tag, SUI_CLOCK_OBJECT_ID, SUI_FRAMEWORK_ADDRESS};
 use test_cluster::TestClusterBuilder;
 use tokio::time::sleep;
 
@@ -682,6 +687,50 @@ async fn test_get_total_supply() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_get_congestion_info() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let reference_gas_price = cluster.get_reference_gas_price().await;
+    let http_client = cluster.rpc_client();
+
+    // Objects no transaction was cancelled for, whether they exist or not, are not congested.
+    let object_ids = vec![SUI_CLOCK_OBJECT_ID, ObjectID::random()];
+    let congestion_info = http_client.get_congestion_info(object_ids.clone()).await?;
+    assert_eq!(congestion_info.reference_gas_price, reference_gas_price);
+    assert_eq!(congestion_info.suggested_gas_price, reference_gas_price);
+    assert_eq!(congestion_info.objects.len(), object_ids.len());
+    for (object, object_id) in congestion_info.objects.iter().zip(&object_ids) {
+        assert_eq!(object.object_id, *object_id);
+        assert!(!object.congested);
+        assert_eq!(object.suggested_gas_price, None);
+    }
+
+    // The number of objects is limited.
+    let err = http_client
+        .get_congestion_info(vec![SUI_CLOCK_OBJECT_ID; *QUERY_MAX_RESULT_LIMIT + 1])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Input exceeds limit"), "{err}");
+    http_client
+        .get_congestion_info(vec![SUI_CLOCK_OBJECT_ID; *QUERY_MAX_RESULT_LIMIT])
+        .await?;
+
+    // Object ids must be valid.
+    let err = http_client
+        .request::<CongestionInfo, _>(
+            "suix_getCongestionInfo",
+            rpc_params![vec!["0x6", "not an object id"]],
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, ClientError::Call(e) if e.code() == INVALID_PARAMS_CODE),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_staking() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await;
@@ -88,3 +88,108 @@ pub struct ValidatorApy {
     pub address: SuiAddress,
     pub apy: f64,
 }
+
+/// Congestion status of a set of shared objects and the gas price a transaction mutating all of
+/// them should bid to avoid being cancelled.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CongestionInfo {
+    /// Congestion status of each requested object, in request order.
+    pub objects: Vec<ObjectCongestionInfo>,
+    /// Never lower than the reference gas price.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub suggested_gas_price: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub reference_gas_price: u64,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectCongestionInfo {
+    pub object_id: ObjectID,
+    /// Whether transactions touching this object are currently being cancelled due to congestion.
+    pub congested: bool,
+    /// Timestamp of the latest checkpoint with a cancelled transaction touching this object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub last_cancellation_timestamp_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub highest_cancelled_gas_price: Option<u64>,
+    /// Timestamp of the latest checkpoint with an executed transaction mutating this object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub last_success_timestamp_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub lowest_executed_gas_price: Option<u64>,
+    /// Gas price a transaction mutating this object should bid, if the object is congested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub suggested_gas_price: Option<u64>,
+    /// Per-checkpoint summaries of recent cancellations, oldest first.
+    pub recent_cancellations: Vec<CongestionCancellation>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CongestionCancellation {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub timestamp_ms: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub cancelled_transactions: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub highest_cancelled_gas_price: u64,
+}
+
+impl ObjectCongestionInfo {
+    /// Congestion status of an object that has not been congested recently.
+    pub fn uncongested(object_id: ObjectID) -> Self {
+        Self {
+            object_id,
+            congested: false,
+            last_cancellation_timestamp_ms: None,
+            highest_cancelled_gas_price: None,
+            last_success_timestamp_ms: None,
+            lowest_executed_gas_price: None,
+            suggested_gas_price: None,
+            recent_cancellations: vec![],
+        }
+    }
+}
+
+impl From<sui_types::storage::ObjectCongestionInfo> for ObjectCongestionInfo {
+    fn from(info: sui_types::storage::ObjectCongestionInfo) -> Self {
+        Self {
+            object_id: info.object_id,
+            congested: info.suggested_gas_price.is_some(),
+            last_cancellation_timestamp_ms: Some(info.last_cancellation_timestamp_ms),
+            highest_cancelled_gas_price: Some(info.highest_cancelled_gas_price),
+            last_success_timestamp_ms: info.last_success_timestamp_ms,
+            lowest_executed_gas_price: info.lowest_executed_gas_price,
+            suggested_gas_price: info.suggested_gas_price,
+            recent_cancellations: info
+                .recent_cancellations
+                .into_iter()
+                .map(|cancellation| CongestionCancellation {
+                    timestamp_ms: cancellation.timestamp_ms,
+                    cancelled_transactions: cancellation.cancelled_transactions,
+                    highest_cancelled_gas_price: cancellation.highest_cancelled_gas_price,
+                })
+                .collect(),
+        }
+    }
+}
@@ -34,7 +34,7 @@ use sui_types::messages_checkpoint::{
     VerifiedCheckpoint,
 };
 use sui_types::object::{Object, ObjectRead, PastObjectRead};
-use sui_types::storage::{BackingPackageStore, ObjectStore, WriteKind};
+use sui_types::storage::{BackingPackageStore, ObjectCongestionInfo, ObjectStore, WriteKind};
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::transaction::{Transaction, TransactionData, TransactionKind};
@@ -156,6 +156,7 @@ pub trait StateRead: Send + Sync {
     async fn get_staked_sui(&self, owner: SuiAddress) -> StateReadResult<Vec<StakedSui>>;
     fn get_system_state(&self) -> StateReadResult<SuiSystemState>;
     fn get_or_latest_committee(&self, epoch: Option<BigInt<u64>>) -> StateReadResult<Committee>;
+    fn get_object_congestion_info(&self, object_id: ObjectID) -> Option<ObjectCongestionInfo>;
 
     // bridge_api
     fn get_bridge(&self) -> StateReadResult<Bridge>;
@@ -403,6 +404,9 @@ impl StateRead for AuthorityState {
             .committee_store()
             .get_or_latest_committee(epoch.map(|e| *e))?)
     }
+    fn get_object_congestion_info(&self, object_id: ObjectID) -> Option<ObjectCongestionInfo> {
+        self.get_object_congestion_info(object_id)
+    }
 
     fn get_bridge(&self) -> StateReadResult<Bridge> {
         self.get_cache_reader()
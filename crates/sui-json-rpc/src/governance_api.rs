@@ -15,8 +15,12 @@ use tracing::{info, instrument};
 
 use mysten_metrics::spawn_monitored_task;
 use sui_core::authority::AuthorityState;
-use sui_json_rpc_api::{GovernanceReadApiOpenRpc, GovernanceReadApiServer, JsonRpcMetrics};
-use sui_json_rpc_types::{DelegatedStake, Stake, StakeStatus};
+use sui_json_rpc_api::{
+    GovernanceReadApiOpenRpc, GovernanceReadApiServer, JsonRpcMetrics, QUERY_MAX_RESULT_LIMIT,
+};
+use sui_json_rpc_types::{
+    CongestionInfo, DelegatedStake, ObjectCongestionInfo, Stake, StakeStatus,
+};
 use sui_json_rpc_types::{SuiCommittee, ValidatorApy, ValidatorApys};
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -256,6 +260,41 @@ impl GovernanceReadApiServer for GovernanceReadApi {
         })
     }
 
+    #[instrument(skip(self))]
+    async fn get_congestion_info(&self, object_ids: Vec<ObjectID>) -> RpcResult<CongestionInfo> {
+        with_tracing!(async move {
+            if object_ids.len() > *QUERY_MAX_RESULT_LIMIT {
+                Err(SuiRpcInputError::SizeLimitExceeded(
+                    QUERY_MAX_RESULT_LIMIT.to_string(),
+                ))?
+            }
+
+            let reference_gas_price = self
+                .state
+                .load_epoch_store_one_call_per_task()
+                .reference_gas_price();
+            let objects = object_ids
+                .into_iter()
+                .map(|object_id| {
+                    self.state
+                        .get_object_congestion_info(object_id)
+                        .map(ObjectCongestionInfo::from)
+                        .unwrap_or_else(|| ObjectCongestionInfo::uncongested(object_id))
+                })
+                .collect::<Vec<_>>();
+            let suggested_gas_price = objects
+                .iter()
+                .filter_map(|info| info.suggested_gas_price)
+                .fold(reference_gas_price, max);
+
+            Ok(CongestionInfo {
+                objects,
+                suggested_gas_price,
+                reference_gas_price,
+            })
+        })
+    }
+
     #[instrument(skip(self))]
     async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
         info!("get_validator_apy");
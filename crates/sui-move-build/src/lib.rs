@@ -60,6 +60,14 @@ use sui_types::{
 };
 use sui_verifier::verifier as sui_bytecode_verifier;
 
+pub use on_chain_dependencies::{
+    set_on_chain_package_source, with_on_chain_package_source, LocalPackageSource,
+    OnChainPackageSource, ON_CHAIN_DIGEST_MANIFEST_FIELD,
+};
+
+mod on_chain_dependencies;
+mod rust_bindings;
+
 #[cfg(test)]
 #[path = "unit_tests/build_tests.rs"]
 mod build_tests;
@@ -570,6 +578,15 @@ impl CompiledPackage {
         layout_builder.into_registry()
     }
 
+    /// Generate Rust bindings for this package: Rust types with the same BCS layout as the
+    /// package's structs and enums (and the types from dependencies that they contain), and
+    /// functions that add calls to its `public` and `entry` functions to a
+    /// `ProgrammableTransactionBuilder`. The result is meant to be written to a file and
+    /// `include!`d, e.g. from a build script.
+    pub fn generate_rust_bindings(&self) -> anyhow::Result<String> {
+        rust_bindings::generate(self)
+    }
+
     /// Checks whether this package corresponds to a built-in framework
     pub fn is_system_package(&self) -> bool {
         // System packages always have "published-at" addresses
@@ -745,6 +762,7 @@ impl PackageHooks for SuiPackageHooks {
     fn custom_package_info_fields(&self) -> Vec<String> {
         vec![
             PUBLISHED_AT_MANIFEST_FIELD.to_string(),
+            ON_CHAIN_DIGEST_MANIFEST_FIELD.to_string(),
             // TODO: remove this once version fields are removed from all manifests
             "version".to_string(),
         ]
@@ -752,10 +770,11 @@ impl PackageHooks for SuiPackageHooks {
 
     fn resolve_on_chain_dependency(
         &self,
-        _dep_name: move_symbol_pool::Symbol,
-        _info: &OnChainInfo,
+        dep_name: move_symbol_pool::Symbol,
+        info: &OnChainInfo,
+        version: Option<Symbol>,
     ) -> anyhow::Result<()> {
-        Ok(())
+        on_chain_dependencies::fetch_on_chain_dependency(dep_name, info, version)
     }
 
     fn custom_resolve_pkg_id(
@@ -770,8 +789,14 @@ impl PackageHooks for SuiPackageHooks {
         Ok(manifest.package.name)
     }
 
-    fn resolve_version(&self, _: &SourceManifest) -> anyhow::Result<Option<Symbol>> {
-        Ok(None)
+    fn resolve_version(&self, manifest: &SourceManifest) -> anyhow::Result<Option<Symbol>> {
+        // On-chain packages are immutable, so their digest identifies their version. This also
+        // records the digest in `Move.lock`.
+        Ok(manifest
+            .package
+            .custom_properties
+            .get(&Symbol::from(ON_CHAIN_DIGEST_MANIFEST_FIELD))
+            .map(|digest| Symbol::from(digest.as_str())))
     }
 }
 
@@ -0,0 +1,277 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for on-chain dependencies in package manifests, i.e. `Foo = { id = "0x..." }`.
+//!
+//! On-chain dependencies are fetched by ID from an [`OnChainPackageSource`] into `MOVE_HOME`,
+//! where they are laid out as bytecode-only packages: a generated `Move.toml` and the package's
+//! modules under `build/`. The generated manifest records the package's `published-at` address,
+//! its on-chain digest (which is written to `Move.lock` as the package's version), and declares
+//! the packages in its linkage table as on-chain dependencies in turn.
+
+use std::{
+    cell::RefCell,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Encoding, Hex};
+use move_command_line_common::env::MOVE_HOME;
+use move_compiler::command_line::DEFAULT_OUTPUT_DIR;
+use move_package::{
+    compilation::package_layout::CompiledPackageLayout,
+    resolution::url_to_file_name,
+    source_package::{layout::SourcePackageLayout, parsed_manifest::OnChainInfo},
+};
+use move_symbol_pool::Symbol;
+use once_cell::sync::Lazy;
+use sui_types::{base_types::ObjectID, is_system_package, move_package::MovePackage};
+
+use crate::PUBLISHED_AT_MANIFEST_FIELD;
+
+/// Manifest field holding the on-chain digest of a fetched package.
+pub const ON_CHAIN_DIGEST_MANIFEST_FIELD: &str = "on-chain-digest";
+
+/// Where on-chain dependencies are fetched from.
+pub trait OnChainPackageSource: Send + Sync {
+    /// Returns the package stored at `id`.
+    fn fetch_package(&self, id: ObjectID) -> anyhow::Result<MovePackage>;
+}
+
+/// Serves packages from a local directory, each stored as the BCS bytes of a `MovePackage` in a
+/// file named after its ID. Mostly useful for tests and air-gapped builds.
+pub struct LocalPackageSource {
+    dir: PathBuf,
+}
+
+impl LocalPackageSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Adds `package` to the directory.
+    pub fn write_package(&self, package: &MovePackage) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.package_path(package.id()), bcs::to_bytes(package)?)?;
+        Ok(())
+    }
+
+    fn package_path(&self, id: ObjectID) -> PathBuf {
+        self.dir.join(format!("{id}.bcs"))
+    }
+}
+
+impl OnChainPackageSource for LocalPackageSource {
+    fn fetch_package(&self, id: ObjectID) -> anyhow::Result<MovePackage> {
+        let path = self.package_path(id);
+        let bytes =
+            fs::read(&path).with_context(|| format!("Reading package {id} from {path:?}"))?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+}
+
+static ON_CHAIN_PACKAGE_SOURCE: Lazy<Mutex<Option<Arc<dyn OnChainPackageSource>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+thread_local! {
+    static SCOPED_PACKAGE_SOURCE: RefCell<Option<Arc<dyn OnChainPackageSource>>> =
+        RefCell::new(None);
+}
+
+/// Sets the source that on-chain dependencies are fetched from, for the whole process (package
+/// hooks are global as well).
+pub fn set_on_chain_package_source(source: Arc<dyn OnChainPackageSource>) {
+    *ON_CHAIN_PACKAGE_SOURCE.lock().unwrap() = Some(source);
+}
+
+/// Runs `f`, which builds a package, fetching on-chain dependencies from `source` instead of the
+/// process-wide source.
+pub fn with_on_chain_package_source<T>(
+    source: Arc<dyn OnChainPackageSource>,
+    f: impl FnOnce() -> T,
+) -> T {
+    let previous = SCOPED_PACKAGE_SOURCE.with(|scoped| scoped.replace(Some(source)));
+    let result = f();
+    SCOPED_PACKAGE_SOURCE.with(|scoped| *scoped.borrow_mut() = previous);
+    result
+}
+
+fn on_chain_package_source() -> Option<Arc<dyn OnChainPackageSource>> {
+    SCOPED_PACKAGE_SOURCE
+        .with(|scoped| scoped.borrow().clone())
+        .or_else(|| ON_CHAIN_PACKAGE_SOURCE.lock().unwrap().clone())
+}
+
+/// The directory an on-chain dependency is fetched into, which is where `move-package` looks
+/// for it.
+fn on_chain_package_path(info: &OnChainInfo) -> PathBuf {
+    Path::new(&*MOVE_HOME).join(url_to_file_name(info.id.as_str()))
+}
+
+/// Fetches the on-chain dependency `dep_name` into `MOVE_HOME`, unless it was fetched before
+/// (packages are immutable) and matches the digest recorded for it in `Move.lock`, if any. The
+/// manifest is regenerated regardless, so that the package is named after the dependency which
+/// refers to it.
+pub(crate) fn fetch_on_chain_dependency(
+    dep_name: Symbol,
+    info: &OnChainInfo,
+    locked_digest: Option<Symbol>,
+) -> anyhow::Result<()> {
+    let id = ObjectID::from_str(info.id.as_str()).map_err(|e| {
+        anyhow!(
+            "Invalid id {} for on-chain dependency '{dep_name}': {e}",
+            info.id
+        )
+    })?;
+    let path = on_chain_package_path(info);
+    let modules_dir = path
+        .join(DEFAULT_OUTPUT_DIR)
+        .join(dep_name.as_str())
+        .join(CompiledPackageLayout::CompiledModules.path());
+
+    let package_file = path.join(PACKAGE_FILE);
+    let cached_package = if package_file.exists() {
+        // A cached package that cannot be read or does not match the lock file is fetched again.
+        fs::read(&package_file)
+            .ok()
+            .and_then(|bytes| bcs::from_bytes::<MovePackage>(&bytes).ok())
+            .filter(|package| {
+                locked_digest.map_or(true, |digest| package_digest(package) == digest.as_str())
+            })
+    } else {
+        None
+    };
+    let package = if let Some(package) = cached_package {
+        package
+    } else {
+        let Some(source) = on_chain_package_source() else {
+            bail!(
+                "Cannot fetch on-chain dependency '{dep_name}' ({id}): no source to fetch \
+                 on-chain packages from is configured"
+            );
+        };
+        let package = source
+            .fetch_package(id)
+            .with_context(|| format!("Fetching on-chain dependency '{dep_name}' ({id})"))?;
+        ensure!(
+            package.id() == id,
+            "Fetched package {} instead of {id} for on-chain dependency '{dep_name}'",
+            package.id()
+        );
+        if let Some(digest) = locked_digest {
+            ensure!(
+                package_digest(&package) == digest.as_str(),
+                "On-chain dependency '{dep_name}' ({id}) has digest {}, but {digest} is recorded \
+                 in Move.lock",
+                package_digest(&package)
+            );
+        }
+
+        // Write to a temporary directory first so that an interrupted fetch is not mistaken for
+        // a complete one.
+        let tmp_path = path.with_extension("tmp");
+        if tmp_path.exists() {
+            fs::remove_dir_all(&tmp_path)?;
+        }
+        fs::create_dir_all(&tmp_path)?;
+        fs::write(tmp_path.join(PACKAGE_FILE), bcs::to_bytes(&package)?)?;
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::rename(&tmp_path, &path)?;
+        package
+    };
+
+    // Lay out the modules under the name of the dependency, since that is what the package is
+    // called in the generated manifest.
+    let build_dir = path.join(DEFAULT_OUTPUT_DIR);
+    if build_dir.exists() {
+        fs::remove_dir_all(&build_dir)?;
+    }
+    fs::create_dir_all(&modules_dir)?;
+    for (name, bytes) in package.serialized_module_map() {
+        fs::write(modules_dir.join(format!("{name}.mv")), bytes)?;
+    }
+    fs::write(
+        path.join(SourcePackageLayout::Manifest.path()),
+        generate_manifest(dep_name, &package)?,
+    )?;
+    Ok(())
+}
+
+/// Name of the file holding the fetched package in its directory.
+const PACKAGE_FILE: &str = "package.bcs";
+
+/// The digest of a package, as recorded in the generated manifest and in `Move.lock`.
+fn package_digest(package: &MovePackage) -> String {
+    Hex::encode(package.digest(/* hash_modules */ true))
+}
+
+/// The name an on-chain package is given when it is a transitive dependency.
+fn transitive_dependency_name(id: &ObjectID) -> String {
+    format!(
+        "OnChain_{}",
+        id.to_canonical_string(/* with_prefix */ false)
+    )
+}
+
+fn generate_manifest(dep_name: Symbol, package: &MovePackage) -> anyhow::Result<String> {
+    let mut manifest = String::new();
+    writeln!(manifest, "[package]")?;
+    writeln!(manifest, "name = {:?}", dep_name.as_str())?;
+    writeln!(
+        manifest,
+        "{PUBLISHED_AT_MANIFEST_FIELD} = \"{}\"",
+        package.id()
+    )?;
+    writeln!(
+        manifest,
+        "{ON_CHAIN_DIGEST_MANIFEST_FIELD} = \"{}\"",
+        package_digest(package)
+    )?;
+
+    // System packages are expected to be provided by the depending package, like for any other
+    // package.
+    writeln!(manifest, "\n[dependencies]")?;
+    for upgrade_info in package.linkage_table().values() {
+        if is_system_package(upgrade_info.upgraded_id) {
+            continue;
+        }
+        writeln!(
+            manifest,
+            "{} = {{ id = \"{}\" }}",
+            transitive_dependency_name(&upgrade_info.upgraded_id),
+            upgrade_info.upgraded_id
+        )?;
+    }
+
+    // Modules of upgraded packages are addressed by the package's original ID.
+    writeln!(manifest, "\n[addresses]")?;
+    writeln!(
+        manifest,
+        "{} = \"{}\"",
+        named_address(dep_name),
+        package.original_package_id()
+    )?;
+    Ok(manifest)
+}
+
+/// The named address of an on-chain dependency, derived from its name as in `snake_case`
+/// manifests, e.g. `DeepBook` is available as `deepbook`.
+fn named_address(dep_name: Symbol) -> String {
+    dep_name
+        .as_str()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
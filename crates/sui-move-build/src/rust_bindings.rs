@@ -0,0 +1,684 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates Rust bindings for a compiled package: a Rust type with the same BCS layout as each of
+//! the package's structs and enums (and the types from dependencies they contain), and a function
+//! per `public` or `entry` function that adds a call to it to a `ProgrammableTransactionBuilder`.
+//!
+//! The generated code expects the `anyhow`, `bcs`, `move-core-types`, `serde` and `sui-types`
+//! crates to be available.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, Context};
+use move_binary_format::{
+    file_format::Visibility,
+    normalized::{self, Bytecode, Type},
+};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+};
+use move_package::source_package::parsed_manifest::PackageName;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::CompiledPackage;
+
+const ADDRESS_TYPE: &str = "::move_core_types::account_address::AccountAddress";
+const ARGUMENT_TYPE: &str = "::sui_types::transaction::Argument";
+const IDENT_STR_TYPE: &str = "::move_core_types::identifier::IdentStr";
+const STRUCT_TAG_TYPE: &str = "::move_core_types::language_storage::StructTag";
+const TYPE_TAG_TYPE: &str = "::move_core_types::language_storage::TypeTag";
+
+/// A struct or enum, identified by its module and name.
+type DatatypeId = (ModuleId, Identifier);
+
+struct Generator {
+    /// Every module of the package and its dependencies, along with the name of the dependency it
+    /// comes from (`None` for the package's own modules).
+    modules: BTreeMap<ModuleId, (Option<PackageName>, normalized::Module)>,
+    /// The datatypes to generate Rust types for, along with which of their type parameters the
+    /// Rust type keeps. Phantom type parameters (or more generally, type parameters that do not
+    /// end up in the layout of any field) are dropped, as Rust rejects unused type parameters.
+    kept: BTreeMap<DatatypeId, Vec<bool>>,
+    /// The package's structs that it emits as events.
+    events: BTreeSet<DatatypeId>,
+    out: String,
+    indent: usize,
+}
+
+pub(crate) fn generate(package: &CompiledPackage) -> anyhow::Result<String> {
+    let mut modules = BTreeMap::new();
+    for (name, unit) in &package.package.deps_compiled_units {
+        let module = &unit.unit.module;
+        modules.insert(
+            module.self_id(),
+            (Some(*name), normalized::Module::new(module)),
+        );
+    }
+    for (name, module) in &package.bytecode_deps {
+        modules.insert(
+            module.self_id(),
+            (Some(*name), normalized::Module::new(module)),
+        );
+    }
+    let mut root = vec![];
+    for module in package.get_modules() {
+        root.push(module.self_id());
+        modules.insert(module.self_id(), (None, normalized::Module::new(module)));
+    }
+
+    let mut generator = Generator {
+        modules,
+        kept: BTreeMap::new(),
+        events: BTreeSet::new(),
+        out: String::new(),
+        indent: 0,
+    };
+    for id in &root {
+        let module = &generator.modules[id].1;
+        let names: Vec<_> = module
+            .structs
+            .keys()
+            .chain(module.enums.keys())
+            .cloned()
+            .collect();
+        generator.events.extend(emitted_events(module));
+        for name in names {
+            generator.visit(&(id.clone(), name))?;
+        }
+    }
+
+    generator.line(format!(
+        "// Rust bindings for the `{}` Move package, generated by `sui move bindgen`. Do not edit.",
+        package.package.compiled_package_info.package_name
+    ));
+    for id in &root {
+        generator.line("");
+        generator.module(id, 1)?;
+    }
+
+    let mut deps: BTreeMap<PackageName, BTreeSet<ModuleId>> = BTreeMap::new();
+    for (module, _) in generator.kept.keys() {
+        if let (Some(package), _) = &generator.modules[module] {
+            deps.entry(*package).or_default().insert(module.clone());
+        }
+    }
+    if !deps.is_empty() {
+        generator.line("");
+        generator.line("/// Types from the package's dependencies.");
+        generator.open("pub mod deps {");
+        for (i, (package, modules)) in deps.iter().enumerate() {
+            if i > 0 {
+                generator.line("");
+            }
+            generator.open(format!(
+                "pub mod {} {{",
+                rust_ident(&snake_case(package.as_str()))
+            ));
+            for (j, id) in modules.iter().enumerate() {
+                if j > 0 {
+                    generator.line("");
+                }
+                generator.module(id, 3)?;
+            }
+            generator.close("}");
+        }
+        generator.close("}");
+    }
+
+    Ok(generator.out)
+}
+
+impl Generator {
+    /// Decide which type parameters of `id` the Rust type for it keeps, visiting the datatypes
+    /// that its fields use along the way.
+    fn visit(&mut self, id: &DatatypeId) -> anyhow::Result<Vec<bool>> {
+        if let Some(kept) = self.kept.get(id) {
+            return Ok(kept.clone());
+        }
+
+        let (type_parameters, fields) = self.datatype(id)?;
+        let mut kept = vec![false; type_parameters];
+        for field in &fields {
+            self.uses(&field.type_, &mut kept)
+                .with_context(|| format!("In field {} of {}", field.name, datatype_name(id)))?;
+        }
+        self.kept.insert(id.clone(), kept.clone());
+        Ok(kept)
+    }
+
+    /// Mark the type parameters that end up in the layout of `ty`.
+    fn uses(&mut self, ty: &Type, kept: &mut [bool]) -> anyhow::Result<()> {
+        match ty {
+            Type::TypeParameter(i) => kept[*i as usize] = true,
+            Type::Vector(ty) => self.uses(ty, kept)?,
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let args_kept = match well_known(address, module.as_str(), name.as_str()) {
+                    Some((_, args_kept)) => args_kept.to_vec(),
+                    None => self.visit(&(ModuleId::new(*address, module.clone()), name.clone()))?,
+                };
+                for (arg, keep) in type_arguments.iter().zip(args_kept) {
+                    if keep {
+                        self.uses(arg, kept)?;
+                    }
+                }
+            }
+            Type::Reference(_) | Type::MutableReference(_) => {
+                bail!("References can not be stored in datatypes")
+            }
+            Type::Bool
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::U256
+            | Type::Address
+            | Type::Signer => (),
+        }
+        Ok(())
+    }
+
+    /// The number of type parameters of `id`, and the fields of all its variants if it is an
+    /// enum.
+    fn datatype(&self, id: &DatatypeId) -> anyhow::Result<(usize, Vec<normalized::Field>)> {
+        let (module, name) = id;
+        let (_, m) = self.modules.get(module).with_context(|| {
+            format!("Module {module} is not part of the package's dependencies")
+        })?;
+        if let Some(s) = m.structs.get(name) {
+            Ok((s.type_parameters.len(), s.fields.clone()))
+        } else if let Some(e) = m.enums.get(name) {
+            let fields = e.variants.iter().flat_map(|v| v.fields.clone()).collect();
+            Ok((e.type_parameters.len(), fields))
+        } else {
+            bail!("{} is not defined", datatype_name(id))
+        }
+    }
+
+    /// Emit a Rust module for the Move module `id`, nested `depth` modules deep in the generated
+    /// code. The package's own modules get all their datatypes and functions, dependencies only
+    /// get the datatypes the package needs.
+    fn module(&mut self, id: &ModuleId, depth: usize) -> anyhow::Result<()> {
+        let (package, module) = &self.modules[id];
+        let root = package.is_none();
+        let module = module.clone();
+
+        self.line("#[allow(dead_code, non_camel_case_types, non_snake_case, clippy::all)]");
+        self.open(format!("pub mod {} {{", rust_ident(module.name.as_str())));
+        self.line(format!(
+            "pub const ADDRESS: {ADDRESS_TYPE} = {ADDRESS_TYPE}::new({:?});",
+            module.address.into_bytes()
+        ));
+        self.line(format!(
+            "pub const MODULE: &{IDENT_STR_TYPE} = ::move_core_types::ident_str!({:?});",
+            module.name.as_str()
+        ));
+
+        for (name, s) in &module.structs {
+            let datatype = (id.clone(), name.clone());
+            if !self.kept.contains_key(&datatype) {
+                continue;
+            }
+            self.line("");
+            let generics = self.generics(&datatype);
+            self.line(
+                "#[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]",
+            );
+            self.open(format!(
+                "pub struct {}{generics} {{",
+                rust_ident(name.as_str())
+            ));
+            for field in &s.fields {
+                let ty = self.rust_type(&field.type_, depth);
+                self.line(format!("pub {}: {ty},", rust_ident(field.name.as_str())));
+            }
+            self.close("}");
+            self.datatype_impl(&datatype, s.type_parameters.len());
+        }
+
+        for (name, e) in &module.enums {
+            let datatype = (id.clone(), name.clone());
+            if !self.kept.contains_key(&datatype) {
+                continue;
+            }
+            self.line("");
+            let generics = self.generics(&datatype);
+            self.line(
+                "#[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]",
+            );
+            self.open(format!(
+                "pub enum {}{generics} {{",
+                rust_ident(name.as_str())
+            ));
+            for variant in &e.variants {
+                let variant_name = rust_ident(variant.name.as_str());
+                if variant.fields.is_empty() {
+                    self.line(format!("{variant_name},"));
+                    continue;
+                }
+                self.open(format!("{variant_name} {{"));
+                for field in &variant.fields {
+                    let ty = self.rust_type(&field.type_, depth);
+                    self.line(format!("{}: {ty},", rust_ident(field.name.as_str())));
+                }
+                self.close("},");
+            }
+            self.close("}");
+            self.datatype_impl(&datatype, e.type_parameters.len());
+        }
+
+        if root {
+            for (name, function) in &module.functions {
+                if function.is_entry || function.visibility == Visibility::Public {
+                    self.line("");
+                    self.function(name.as_str(), function, depth);
+                }
+            }
+        }
+
+        self.close("}");
+        Ok(())
+    }
+
+    /// Emit the `struct_tag` function of a datatype, and `from_event` if the package emits it as
+    /// an event.
+    fn datatype_impl(&mut self, id: &DatatypeId, type_parameters: usize) {
+        let generics = self.generics(id);
+        let name = id.1.as_str();
+        self.line("");
+        self.open(format!("impl{generics} {}{generics} {{", rust_ident(name)));
+        if type_parameters == 0 {
+            self.line("/// The Move type of this datatype.");
+            self.open(format!("pub fn struct_tag() -> {STRUCT_TAG_TYPE} {{"));
+        } else {
+            self.line("/// The Move type of this datatype, instantiated with `type_params`.");
+            self.open(format!(
+                "pub fn struct_tag(type_params: [{TYPE_TAG_TYPE}; {type_parameters}]) -> {STRUCT_TAG_TYPE} {{"
+            ));
+        }
+        self.open(format!("{STRUCT_TAG_TYPE} {{"));
+        self.line("address: ADDRESS,");
+        self.line("module: MODULE.to_owned(),");
+        self.line(format!(
+            "name: ::move_core_types::ident_str!({name:?}).to_owned(),"
+        ));
+        if type_parameters == 0 {
+            self.line("type_params: ::std::vec![],");
+        } else {
+            self.line("type_params: type_params.to_vec(),");
+        }
+        self.close("}");
+        self.close("}");
+
+        if self.events.contains(id) {
+            self.line("");
+            self.line("/// Decode `event` if it is an instance of this datatype.");
+            self.line(
+                "pub fn from_event(event: &::sui_types::event::Event) -> ::anyhow::Result<::std::option::Option<Self>>",
+            );
+            self.line("where");
+            self.line("    Self: ::serde::de::DeserializeOwned,");
+            self.open("{");
+            self.line("let tag = &event.type_;");
+            self.open(format!(
+                "if tag.address != ADDRESS || tag.module.as_ident_str() != MODULE || tag.name.as_str() != {name:?} {{"
+            ));
+            self.line("return Ok(None);");
+            self.close("}");
+            self.line("Ok(Some(::bcs::from_bytes(&event.contents)?))");
+            self.close("}");
+        }
+        self.close("}");
+    }
+
+    /// Emit a function that adds a call to the Move function `name` to a programmable transaction.
+    /// Arguments that can be passed as pure values are taken as Rust values, the others as
+    /// `Argument`s. A trailing `TxContext` parameter is provided by the runtime.
+    fn function(&mut self, name: &str, function: &normalized::Function, depth: usize) {
+        let mut parameters = function.parameters.as_slice();
+        if let [rest @ .., Type::Reference(ty) | Type::MutableReference(ty)] = parameters {
+            if is_tx_context(ty) {
+                parameters = rest;
+            }
+        }
+
+        self.line(format!(
+            "/// Add a call to `{}` to `builder`, returning its result.",
+            self.move_signature(name, function)
+        ));
+        self.open(format!("pub fn {}(", rust_ident(name)));
+        self.line(
+            "builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,",
+        );
+        self.line("package: ::sui_types::base_types::ObjectID,");
+        if !function.type_parameters.is_empty() {
+            self.line(format!(
+                "type_arguments: [{TYPE_TAG_TYPE}; {}],",
+                function.type_parameters.len()
+            ));
+        }
+        let mut arguments = vec![];
+        for (i, ty) in parameters.iter().enumerate() {
+            match self.pure_type(ty, depth) {
+                Some(rust_ty) => {
+                    self.line(format!("arg{i}: {rust_ty},"));
+                    arguments.push(format!("builder.pure(arg{i})?"));
+                }
+                None => {
+                    self.line(format!("arg{i}: {ARGUMENT_TYPE},"));
+                    arguments.push(format!("arg{i}"));
+                }
+            }
+        }
+        self.close_open(format!(") -> ::anyhow::Result<{ARGUMENT_TYPE}> {{"));
+        self.line(format!(
+            "let arguments = ::std::vec![{}];",
+            arguments.join(", ")
+        ));
+        self.open("Ok(builder.programmable_move_call(");
+        self.line("package,");
+        self.line("MODULE.to_owned(),");
+        self.line(format!(
+            "::move_core_types::ident_str!({name:?}).to_owned(),"
+        ));
+        if function.type_parameters.is_empty() {
+            self.line("::std::vec![],");
+        } else {
+            self.line("type_arguments.to_vec(),");
+        }
+        self.line("arguments,");
+        self.close("))");
+        self.close("}");
+    }
+
+    /// The generic parameters of the Rust type for `id`, e.g. `<T0, T2>`.
+    fn generics(&self, id: &DatatypeId) -> String {
+        let params: Vec<_> = self.kept[id]
+            .iter()
+            .enumerate()
+            .filter(|(_, kept)| **kept)
+            .map(|(i, _)| format!("T{i}"))
+            .collect();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", params.join(", "))
+        }
+    }
+
+    /// The Rust type for `ty`, used in a module nested `depth` modules deep in the generated code.
+    fn rust_type(&self, ty: &Type, depth: usize) -> String {
+        match ty {
+            Type::Bool => "bool".to_string(),
+            Type::U8 => "u8".to_string(),
+            Type::U16 => "u16".to_string(),
+            Type::U32 => "u32".to_string(),
+            Type::U64 => "u64".to_string(),
+            Type::U128 => "u128".to_string(),
+            Type::U256 => "::move_core_types::u256::U256".to_string(),
+            Type::Address | Type::Signer => "::sui_types::base_types::SuiAddress".to_string(),
+            Type::Vector(ty) => format!("::std::vec::Vec<{}>", self.rust_type(ty, depth)),
+            Type::TypeParameter(i) => format!("T{i}"),
+            Type::Reference(ty) | Type::MutableReference(ty) => self.rust_type(ty, depth),
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let (path, kept) = match well_known(address, module.as_str(), name.as_str()) {
+                    Some((path, kept)) => (path.to_string(), kept.to_vec()),
+                    None => {
+                        let id = (ModuleId::new(*address, module.clone()), name.clone());
+                        let path = format!(
+                            "{}{}::{}",
+                            "super::".repeat(depth),
+                            self.module_path(&id.0),
+                            rust_ident(name.as_str())
+                        );
+                        (path, self.kept[&id].clone())
+                    }
+                };
+                let args: Vec<_> = type_arguments
+                    .iter()
+                    .zip(kept)
+                    .filter(|(_, kept)| *kept)
+                    .map(|(ty, _)| self.rust_type(ty, depth))
+                    .collect();
+                if args.is_empty() {
+                    path
+                } else {
+                    format!("{path}<{}>", args.join(", "))
+                }
+            }
+        }
+    }
+
+    /// The Rust type to take a parameter of type `ty` as, if it can be passed as a pure value.
+    fn pure_type(&self, ty: &Type, depth: usize) -> Option<String> {
+        let pure = match ty {
+            Type::Bool
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::U256
+            | Type::Address => true,
+            Type::Vector(ty) => self.pure_type(ty, depth).is_some(),
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => match (*address, module.as_str(), name.as_str()) {
+                (MOVE_STDLIB_ADDRESS, "string" | "ascii", "String")
+                | (SUI_FRAMEWORK_ADDRESS, "object", "ID") => true,
+                (MOVE_STDLIB_ADDRESS, "option", "Option") => {
+                    self.pure_type(&type_arguments[0], depth).is_some()
+                }
+                _ => false,
+            },
+            Type::Signer
+            | Type::TypeParameter(_)
+            | Type::Reference(_)
+            | Type::MutableReference(_) => false,
+        };
+        pure.then(|| self.rust_type(ty, depth))
+    }
+
+    /// The path of the Rust module for `module`, relative to the root of the generated code.
+    fn module_path(&self, module: &ModuleId) -> String {
+        let name = rust_ident(module.name().as_str());
+        match &self.modules[module].0 {
+            None => name,
+            Some(package) => format!(
+                "deps::{}::{name}",
+                rust_ident(&snake_case(package.as_str()))
+            ),
+        }
+    }
+
+    /// The Move signature of `function`, for documentation.
+    fn move_signature(&self, name: &str, function: &normalized::Function) -> String {
+        let mut signature = String::new();
+        if function.visibility == Visibility::Public {
+            signature.push_str("public ");
+        }
+        if function.is_entry {
+            signature.push_str("entry ");
+        }
+        signature.push_str("fun ");
+        signature.push_str(name);
+        if !function.type_parameters.is_empty() {
+            let params: Vec<_> = (0..function.type_parameters.len())
+                .map(|i| format!("T{i}"))
+                .collect();
+            signature.push_str(&format!("<{}>", params.join(", ")));
+        }
+        let params: Vec<_> = function.parameters.iter().map(move_type).collect();
+        signature.push_str(&format!("({})", params.join(", ")));
+        match function.return_.as_slice() {
+            [] => (),
+            [ty] => signature.push_str(&format!(": {}", move_type(ty))),
+            tys => {
+                let tys: Vec<_> = tys.iter().map(move_type).collect();
+                signature.push_str(&format!(": ({})", tys.join(", ")));
+            }
+        }
+        signature
+    }
+
+    fn line(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if !line.is_empty() {
+            self.out.push_str(&"    ".repeat(self.indent));
+            self.out.push_str(line);
+        }
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, line: impl AsRef<str>) {
+        self.line(line);
+        self.indent += 1;
+    }
+
+    fn close(&mut self, line: impl AsRef<str>) {
+        self.indent -= 1;
+        self.line(line);
+    }
+
+    fn close_open(&mut self, line: impl AsRef<str>) {
+        self.indent -= 1;
+        self.line(line);
+        self.indent += 1;
+    }
+}
+
+/// Types that already have a Rust counterpart, with the path to it and which of their type
+/// parameters it keeps.
+fn well_known(
+    address: &AccountAddress,
+    module: &str,
+    name: &str,
+) -> Option<(&'static str, &'static [bool])> {
+    Some(match (*address, module, name) {
+        (MOVE_STDLIB_ADDRESS, "string" | "ascii", "String") => ("::std::string::String", &[]),
+        (MOVE_STDLIB_ADDRESS, "option", "Option") => ("::std::option::Option", &[true]),
+        (SUI_FRAMEWORK_ADDRESS, "object", "UID") => ("::sui_types::id::UID", &[]),
+        (SUI_FRAMEWORK_ADDRESS, "object", "ID") => ("::sui_types::id::ID", &[]),
+        (SUI_FRAMEWORK_ADDRESS, "balance", "Balance") => {
+            ("::sui_types::balance::Balance", &[false])
+        }
+        (SUI_FRAMEWORK_ADDRESS, "balance", "Supply") => ("::sui_types::balance::Supply", &[false]),
+        (SUI_FRAMEWORK_ADDRESS, "coin", "Coin") => ("::sui_types::coin::Coin", &[false]),
+        _ => return None,
+    })
+}
+
+/// The structs passed to `sui::event::emit` by the functions of `module`.
+fn emitted_events(module: &normalized::Module) -> Vec<DatatypeId> {
+    let mut events = vec![];
+    for function in module.functions.values() {
+        for bytecode in &function.code {
+            let Bytecode::CallGeneric((callee, type_arguments)) = bytecode else {
+                continue;
+            };
+            if *callee.module_id.address() != SUI_FRAMEWORK_ADDRESS
+                || callee.module_id.name().as_str() != "event"
+                || callee.function_ident.as_str() != "emit"
+            {
+                continue;
+            }
+            if let [Type::Struct {
+                address,
+                module: event_module,
+                name,
+                ..
+            }] = type_arguments.as_slice()
+            {
+                if *address == module.address && *event_module == module.name {
+                    events.push((ModuleId::new(*address, event_module.clone()), name.clone()));
+                }
+            }
+        }
+    }
+    events
+}
+
+fn is_tx_context(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Struct { address, module, name, .. }
+            if *address == SUI_FRAMEWORK_ADDRESS
+                && module.as_str() == "tx_context"
+                && name.as_str() == "TxContext"
+    )
+}
+
+fn move_type(ty: &Type) -> String {
+    match ty {
+        Type::Struct {
+            module,
+            name,
+            type_arguments,
+            ..
+        } => {
+            if type_arguments.is_empty() {
+                format!("{module}::{name}")
+            } else {
+                let args: Vec<_> = type_arguments.iter().map(move_type).collect();
+                format!("{module}::{name}<{}>", args.join(", "))
+            }
+        }
+        Type::Vector(ty) => format!("vector<{}>", move_type(ty)),
+        Type::TypeParameter(i) => format!("T{i}"),
+        Type::Reference(ty) => format!("&{}", move_type(ty)),
+        Type::MutableReference(ty) => format!("&mut {}", move_type(ty)),
+        ty => ty.to_string(),
+    }
+}
+
+fn datatype_name((module, name): &DatatypeId) -> String {
+    format!("{module}::{name}")
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            snake.push(c);
+        } else {
+            snake.push('_');
+        }
+    }
+    snake
+}
+
+/// `name` as a Rust identifier, escaping keywords.
+fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    match name {
+        // these can not be raw identifiers
+        "crate" | "self" | "Self" | "super" => format!("{name}_"),
+        _ if KEYWORDS.contains(&name) => format!("r#{name}"),
+        _ => name.to_string(),
+    }
+}
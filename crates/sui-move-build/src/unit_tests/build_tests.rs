@@ -31,6 +31,47 @@ fn generate_struct_layouts() {
     ));
 }
 
+#[test]
+fn generate_rust_bindings() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("unit_tests")
+        .join("data")
+        .join("rust_bindings");
+    let pkg = BuildConfig::new_for_testing().build(&path).unwrap();
+    let bindings = pkg.generate_rust_bindings().unwrap();
+
+    let expected = [
+        // phantom type parameters are dropped, and framework types map to their `sui_types`
+        // counterparts
+        "pub struct Shop {",
+        "pub items: super::deps::sui::table::Table,",
+        "pub revenue: ::sui_types::balance::Balance,",
+        "pub struct Listing<T0> {",
+        "pub discount: ::std::option::Option<u8>,",
+        "Reserved {",
+        "by: ::sui_types::base_types::SuiAddress,",
+        // pure arguments are taken by value, objects as `Argument`s, and `TxContext` is dropped
+        "pub fn new(",
+        "arg0: ::std::string::String,\n    ) -> ::anyhow::Result<",
+        "arg1: u64,",
+        "arg2: ::sui_types::transaction::Argument,",
+        "type_arguments: [::move_core_types::language_storage::TypeTag; 1],",
+        // only the dependency types the package uses are generated
+        "pub mod deps {",
+        "pub mod table {",
+    ];
+    for line in expected {
+        assert!(bindings.contains(line), "Missing {line:?} in:\n{bindings}");
+    }
+    assert!(!bindings.contains("pub mod coin {"), "{bindings}");
+    assert_eq!(
+        bindings.matches("pub fn from_event(").count(),
+        1,
+        "{bindings}"
+    );
+}
+
 #[test]
 fn development_mode_not_allowed() {
     let path = Path::new(env!("CARGO_MANIFEST_DIR"))
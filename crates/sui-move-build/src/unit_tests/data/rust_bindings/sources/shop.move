@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module bindings::shop;
+
+use std::string::String;
+use sui::balance::Balance;
+use sui::coin::Coin;
+use sui::event;
+use sui::sui::SUI;
+use sui::table::Table;
+
+public struct Shop has key {
+    id: UID,
+    name: String,
+    items: Table<u64, Item>,
+    revenue: Balance<SUI>,
+}
+
+public struct Item has store {
+    price: u64,
+    status: Status,
+}
+
+public struct Listing<T: store> has store {
+    item: T,
+    discount: Option<u8>,
+}
+
+public enum Status has copy, drop, store {
+    Available,
+    Reserved { by: address },
+}
+
+public struct ItemSold has copy, drop {
+    shop: ID,
+    item: u64,
+}
+
+public fun new(name: String, ctx: &mut TxContext) {
+    transfer::share_object(Shop {
+        id: object::new(ctx),
+        name,
+        items: sui::table::new(ctx),
+        revenue: sui::balance::zero(),
+    })
+}
+
+public fun buy(shop: &mut Shop, item: u64, payment: Coin<SUI>) {
+    let Item { price, .. } = shop.items.remove(item);
+    assert!(payment.value() == price);
+    shop.revenue.join(payment.into_balance());
+    event::emit(ItemSold { shop: object::id(shop), item });
+}
+
+public fun unlist<T: store>(listing: Listing<T>): T {
+    let Listing { item, .. } = listing;
+    item
+}
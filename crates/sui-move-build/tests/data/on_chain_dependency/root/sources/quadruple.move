@@ -0,0 +1,10 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module root::quadruple;
+
+use onchain::math;
+
+public fun quadruple(x: u64): u64 {
+    math::double(math::double(x))
+}
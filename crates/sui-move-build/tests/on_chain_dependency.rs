@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builds a package with an on-chain dependency. This lives in its own test binary because
+//! `MOVE_HOME`, which on-chain dependencies are fetched into, is read once per process.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use fastcrypto::encoding::{Encoding, Hex};
+use move_package::resolution::url_to_file_name;
+use move_symbol_pool::Symbol;
+use sui_move_build::{with_on_chain_package_source, BuildConfig, LocalPackageSource};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    move_package::{MovePackage, UpgradeInfo},
+};
+
+const DEP_ID: &str = "0xc0ffee";
+
+fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("on_chain_dependency")
+}
+
+/// Builds the `dep` package as if it had been published at `DEP_ID`, with `linkage_table`.
+fn publish_dep(linkage_table: BTreeMap<ObjectID, UpgradeInfo>) -> MovePackage {
+    let dep = BuildConfig::new_for_testing()
+        .build(&data_dir().join("dep"))
+        .unwrap();
+    let module_map = dep
+        .get_modules()
+        .map(|module| {
+            let mut bytes = vec![];
+            module
+                .serialize_with_version(module.version, &mut bytes)
+                .unwrap();
+            (module.name().to_string(), bytes)
+        })
+        .collect();
+    MovePackage::new(
+        ObjectID::from_str(DEP_ID).unwrap(),
+        SequenceNumber::from(1),
+        module_map,
+        u64::MAX,
+        vec![],
+        linkage_table,
+    )
+    .unwrap()
+}
+
+fn digest(package: &MovePackage) -> String {
+    Hex::encode(package.digest(/* hash_modules */ true))
+}
+
+#[test]
+fn on_chain_dependency() {
+    let move_home = tempfile::tempdir().unwrap();
+    std::env::set_var("MOVE_HOME", move_home.path());
+
+    // "Publish" the dependency by storing it in a local package source.
+    let package = publish_dep(BTreeMap::new());
+    let source_dir = tempfile::tempdir().unwrap();
+    let source = Arc::new(LocalPackageSource::new(source_dir.path()));
+    source.write_package(&package).unwrap();
+
+    // Build a copy of the root package, so that its lock file is kept next to it across builds.
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir(root.path().join("sources")).unwrap();
+    for file in ["Move.toml", "sources/quadruple.move"] {
+        fs::copy(data_dir().join("root").join(file), root.path().join(file)).unwrap();
+    }
+    let lock_file = root.path().join("Move.lock");
+    let build = || {
+        let mut build_config = BuildConfig::new_for_testing();
+        build_config.config.lock_file = Some(lock_file.clone());
+        with_on_chain_package_source(source.clone(), || build_config.build(root.path()))
+    };
+
+    let pkg = build().unwrap();
+    let on_chain = Symbol::from("OnChain");
+    assert!(pkg
+        .bytecode_deps
+        .iter()
+        .any(|(name, module)| *name == on_chain && module.name().as_str() == "math"));
+    assert_eq!(
+        pkg.dependency_ids.published.get(&on_chain),
+        Some(&package.id())
+    );
+
+    // The digest of the fetched package is recorded in the lock file.
+    let lock = fs::read_to_string(&lock_file).unwrap();
+    assert!(lock.contains(&digest(&package)));
+
+    // A cached copy of the package which does not match the lock file is fetched again.
+    let cached = move_home
+        .path()
+        .join(url_to_file_name(DEP_ID))
+        .join("package.bcs");
+    let other_package = publish_dep(BTreeMap::from([(
+        ObjectID::from_single_byte(0xaa),
+        UpgradeInfo {
+            upgraded_id: ObjectID::from_single_byte(0xaa),
+            upgraded_version: SequenceNumber::from(1),
+        },
+    )]));
+    assert_ne!(digest(&package), digest(&other_package));
+    fs::write(&cached, bcs::to_bytes(&other_package).unwrap()).unwrap();
+    build().unwrap();
+    assert_eq!(fs::read(&cached).unwrap(), bcs::to_bytes(&package).unwrap());
+
+    // So is a cached copy which cannot be read.
+    fs::write(&cached, b"not a package").unwrap();
+    build().unwrap();
+    assert_eq!(fs::read(&cached).unwrap(), bcs::to_bytes(&package).unwrap());
+
+    // A fetched package must match the lock file as well.
+    fs::write(&cached, bcs::to_bytes(&other_package).unwrap()).unwrap();
+    source.write_package(&other_package).unwrap();
+    let err = build().unwrap_err().to_string();
+    assert!(err.contains("recorded in Move.lock"), "{err}");
+    assert_eq!(fs::read_to_string(&lock_file).unwrap(), lock);
+}
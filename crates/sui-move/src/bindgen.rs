@@ -0,0 +1,49 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::manage_package::resolve_lock_file_path;
+use anyhow::Context;
+use clap::Parser;
+use move_cli::base;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use sui_move_build::{implicit_deps, BuildConfig};
+use sui_package_management::system_package_versions::latest_system_packages;
+
+/// Generate Rust bindings for a package: types matching the BCS layout of its structs, enums and
+/// events, and functions that add calls to its `public` and `entry` functions to a
+/// `ProgrammableTransactionBuilder`.
+#[derive(Parser)]
+#[group(id = "sui-move-bindgen")]
+pub struct Bindgen {
+    /// Where to write the bindings. Printed to stdout if not set.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+impl Bindgen {
+    pub fn execute(self, path: Option<&Path>, build_config: MoveBuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let mut config = resolve_lock_file_path(build_config, Some(&rerooted_path))?;
+        config.implicit_dependencies = implicit_deps(latest_system_packages());
+        let pkg = BuildConfig {
+            config,
+            run_bytecode_verifier: true,
+            print_diags_to_stderr: true,
+            chain_id: None,
+        }
+        .build(&rerooted_path)?;
+
+        let bindings = pkg.generate_rust_bindings()?;
+        match self.output {
+            Some(path) => fs::write(&path, bindings)
+                .with_context(|| format!("Unable to write {}", path.display()))?,
+            None => write!(io::stdout(), "{bindings}")?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,505 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::manage_package::resolve_lock_file_path;
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use move_cli::base;
+use move_core_types::account_address::AccountAddress;
+use move_model_2::{
+    compiled::{self, Type},
+    model::Datatype,
+    source_model::Model,
+    QualifiedMemberId,
+};
+use move_package::BuildConfig;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use sui_move_build::implicit_deps;
+use sui_package_management::system_package_versions::latest_system_packages;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+/// Print the call graph of a package, along with the datatypes used by each function, its
+/// visibility, whether it takes a `&mut TxContext` and whether it transfers, shares, freezes or
+/// receives objects. With `--packages`, print the dependencies between packages instead.
+#[derive(Parser)]
+#[group(id = "sui-move-call-graph")]
+pub struct CallGraph {
+    /// The format to print the call graph in.
+    #[clap(long, value_enum, default_value = "dot")]
+    pub format: CallGraphFormat,
+    /// Where to write the call graph. Printed to stdout if not set.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+    /// Include all functions of the package's dependencies, rather than only the ones called by
+    /// the package.
+    #[clap(long)]
+    pub include_deps: bool,
+    /// Print the dependencies between the package and its dependencies, rather than the call
+    /// graph of their functions.
+    #[clap(long, conflicts_with = "include_deps")]
+    pub packages: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CallGraphFormat {
+    /// Graphviz DOT
+    Dot,
+    Json,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectOperation {
+    Transfer,
+    Share,
+    Freeze,
+    Receive,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackageCallGraph {
+    pub package: String,
+    /// Functions, keyed by their fully qualified name.
+    pub functions: BTreeMap<String, FunctionSummary>,
+    /// Structs and enums used by the functions in the graph, keyed by their fully qualified name.
+    pub datatypes: BTreeMap<String, DatatypeSummary>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FunctionSummary {
+    /// Whether the function is defined in the root package, rather than in a dependency.
+    pub root: bool,
+    pub visibility: &'static str,
+    pub entry: bool,
+    pub mut_tx_context: bool,
+    /// Object operations performed by calling the `sui::transfer` module directly.
+    pub object_operations: BTreeSet<ObjectOperation>,
+    /// Object operations performed by the function, or by any function it calls (transitively).
+    pub reachable_object_operations: BTreeSet<ObjectOperation>,
+    pub calls: BTreeSet<String>,
+    pub datatypes: BTreeSet<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackageGraph {
+    pub package: String,
+    /// The packages in the model, keyed by their named address (or their address if they do not
+    /// have one).
+    pub packages: BTreeMap<String, PackageSummary>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackageSummary {
+    /// Whether this is the root package, rather than one of its dependencies.
+    pub root: bool,
+    pub address: String,
+    pub modules: BTreeSet<String>,
+    /// Packages whose modules are used directly by the modules of this package.
+    pub dependencies: BTreeSet<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DatatypeSummary {
+    /// Whether the datatype is defined in the root package, rather than in a dependency.
+    pub root: bool,
+    pub abilities: Vec<String>,
+    /// Functions in the graph using the datatype.
+    pub used_by: BTreeSet<String>,
+}
+
+impl CallGraph {
+    pub fn execute(self, path: Option<&Path>, build_config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let mut build_config = resolve_lock_file_path(build_config, Some(&rerooted_path))?;
+        build_config.implicit_dependencies = implicit_deps(latest_system_packages());
+
+        let model = build_config.move_model_for_package(&rerooted_path, &mut io::stderr())?;
+        let rendered = if self.packages {
+            let graph = PackageGraph::new(&model)?;
+            match self.format {
+                CallGraphFormat::Dot => graph.to_dot(),
+                CallGraphFormat::Json => serde_json::to_string_pretty(&graph)?,
+            }
+        } else {
+            let graph = PackageCallGraph::new(&model, self.include_deps)?;
+            match self.format {
+                CallGraphFormat::Dot => graph.to_dot(),
+                CallGraphFormat::Json => serde_json::to_string_pretty(&graph)?,
+            }
+        };
+
+        match self.output {
+            Some(path) => fs::write(&path, rendered)
+                .with_context(|| format!("Unable to write {}", path.display()))?,
+            None => writeln!(io::stdout(), "{rendered}")?,
+        }
+        Ok(())
+    }
+}
+
+impl PackageCallGraph {
+    pub fn new(model: &Model, include_deps: bool) -> anyhow::Result<Self> {
+        let root_package = model
+            .root_package_name()
+            .context("The model does not have a root package")?;
+        let is_root = |id: &QualifiedMemberId| {
+            model
+                .maybe_module(id.0)
+                .is_some_and(|m| m.info().package == Some(root_package))
+        };
+
+        // Macros have no compiled representation, and are not part of the call graph.
+        let compiled: BTreeMap<QualifiedMemberId, &compiled::Function> = model
+            .modules()
+            .flat_map(|m| m.functions().collect::<Vec<_>>())
+            .filter_map(|f| Some(((f.module().id(), f.name()), f.maybe_compiled()?)))
+            .collect();
+
+        let direct_ops: BTreeMap<_, _> = compiled
+            .iter()
+            .map(|(id, fun)| (*id, direct_object_operations(fun)))
+            .collect();
+        let reachable_ops = reachable_object_operations(&compiled, &direct_ops);
+
+        // The functions of the root package, and the functions they call.
+        let nodes: BTreeSet<QualifiedMemberId> = if include_deps {
+            compiled.keys().copied().collect()
+        } else {
+            compiled
+                .iter()
+                .filter(|(id, _)| is_root(id))
+                .flat_map(|(id, fun)| std::iter::once(*id).chain(fun.calls.iter().copied()))
+                .filter(|id| compiled.contains_key(id))
+                .collect()
+        };
+
+        let mut functions = BTreeMap::new();
+        let mut datatypes: BTreeMap<String, DatatypeSummary> = BTreeMap::new();
+        for id in &nodes {
+            let fun = compiled[id];
+            let name = member_name(model, id);
+
+            let used: BTreeSet<String> = fun
+                .used_datatypes()
+                .iter()
+                .map(|dt| {
+                    let dt_name = member_name(model, dt);
+                    datatypes
+                        .entry(dt_name.clone())
+                        .or_insert_with(|| DatatypeSummary {
+                            root: is_root(dt),
+                            abilities: datatype_abilities(model, dt),
+                            used_by: BTreeSet::new(),
+                        })
+                        .used_by
+                        .insert(name.clone());
+                    dt_name
+                })
+                .collect();
+
+            let visibility = if fun.is_public() {
+                "public"
+            } else if fun.is_package() {
+                "package"
+            } else {
+                "private"
+            };
+
+            functions.insert(
+                name,
+                FunctionSummary {
+                    root: is_root(id),
+                    visibility,
+                    entry: fun.is_entry(),
+                    mut_tx_context: fun.parameters.iter().any(is_mut_tx_context),
+                    object_operations: direct_ops[id].clone(),
+                    reachable_object_operations: reachable_ops[id].clone(),
+                    calls: fun
+                        .calls
+                        .iter()
+                        .filter(|callee| nodes.contains(*callee))
+                        .map(|callee| member_name(model, callee))
+                        .collect(),
+                    datatypes: used,
+                },
+            );
+        }
+
+        Ok(Self {
+            package: root_package.to_string(),
+            functions,
+            datatypes,
+        })
+    }
+
+    /// Render the graph in Graphviz DOT format, with one cluster per module. Entry functions are
+    /// drawn with a thick border, public functions are filled and functions from dependencies are
+    /// dashed. Only the datatypes of the root package are drawn, to keep the graph readable.
+    pub fn to_dot(&self) -> String {
+        let mut modules: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+        for name in self
+            .functions
+            .keys()
+            .chain(self.datatypes.keys())
+            .map(String::as_str)
+        {
+            let (module, member) = name.rsplit_once("::").unwrap_or(("", name));
+            modules.entry(module).or_default().push((name, member));
+        }
+
+        let mut out = String::new();
+        writeln!(out, "digraph {} {{", quote(&self.package)).unwrap();
+        writeln!(out, "    rankdir=LR;").unwrap();
+        writeln!(out, "    node [fontname=\"monospace\"];").unwrap();
+
+        for (i, (module, members)) in modules.iter().enumerate() {
+            let mut cluster = String::new();
+            for (name, member) in members {
+                if let Some(fun) = self.functions.get(*name) {
+                    let mut label = member.to_string();
+                    let mut tags = vec![fun.visibility.to_string()];
+                    if fun.entry {
+                        tags.push("entry".to_string());
+                    }
+                    if fun.mut_tx_context {
+                        tags.push("&mut TxContext".to_string());
+                    }
+                    tags.extend(fun.object_operations.iter().map(|op| format!("{op:?}")));
+                    write!(label, "\\n[{}]", tags.join(", ")).unwrap();
+
+                    let mut style = vec![];
+                    if fun.visibility == "public" {
+                        style.push("filled");
+                    }
+                    if !fun.root {
+                        style.push("dashed");
+                    }
+                    writeln!(
+                        cluster,
+                        "        {} [shape=box, label={}, style={}, fillcolor=\"lightblue\", penwidth={}];",
+                        quote(name),
+                        quote(&label),
+                        quote(&style.join(",")),
+                        if fun.entry { 3 } else { 1 },
+                    )
+                    .unwrap();
+                } else if self.datatypes[*name].root {
+                    let abilities = &self.datatypes[*name].abilities;
+                    let label = if abilities.is_empty() {
+                        member.to_string()
+                    } else {
+                        format!("{member}\\nhas {}", abilities.join(", "))
+                    };
+                    writeln!(
+                        cluster,
+                        "        {} [shape=ellipse, label={}];",
+                        quote(name),
+                        quote(&label),
+                    )
+                    .unwrap();
+                }
+            }
+
+            if !cluster.is_empty() {
+                writeln!(out, "    subgraph cluster_{i} {{").unwrap();
+                writeln!(out, "        label={};", quote(module)).unwrap();
+                out.push_str(&cluster);
+                writeln!(out, "    }}").unwrap();
+            }
+        }
+
+        for (name, fun) in &self.functions {
+            for callee in &fun.calls {
+                writeln!(out, "    {} -> {};", quote(name), quote(callee)).unwrap();
+            }
+            for datatype in &fun.datatypes {
+                if self.datatypes[datatype].root {
+                    writeln!(
+                        out,
+                        "    {} -> {} [style=dotted, arrowhead=none];",
+                        quote(name),
+                        quote(datatype)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+impl PackageGraph {
+    pub fn new(model: &Model) -> anyhow::Result<Self> {
+        let root_package = model
+            .root_package_name()
+            .context("The model does not have a root package")?;
+
+        let mut packages = BTreeMap::new();
+        for package in model.packages() {
+            let mut summary = PackageSummary {
+                root: false,
+                address: display_address(&package.address()),
+                modules: BTreeSet::new(),
+                dependencies: BTreeSet::new(),
+            };
+            for module in package.modules() {
+                summary.root |= module.info().package == Some(root_package);
+                summary.modules.insert(module.name().to_string());
+                summary.dependencies.extend(
+                    module
+                        .deps()
+                        .iter()
+                        .filter(|((addr, _), is_immediate)| {
+                            **is_immediate && *addr != package.address()
+                        })
+                        .map(|((addr, _), _)| package_name(model, addr)),
+                );
+            }
+            packages.insert(package_name(model, &package.address()), summary);
+        }
+
+        Ok(Self {
+            package: root_package.to_string(),
+            packages,
+        })
+    }
+
+    /// Render the graph in Graphviz DOT format, with the root package filled.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph {} {{", quote(&self.package)).unwrap();
+        writeln!(out, "    rankdir=LR;").unwrap();
+        writeln!(out, "    node [fontname=\"monospace\", shape=box];").unwrap();
+        for (name, package) in &self.packages {
+            writeln!(
+                out,
+                "    {} [label={}, style={}, fillcolor=\"lightblue\"];",
+                quote(name),
+                quote(&format!("{name}\\n{}", package.address)),
+                quote(if package.root { "filled" } else { "" }),
+            )
+            .unwrap();
+        }
+        for (name, package) in &self.packages {
+            for dep in &package.dependencies {
+                writeln!(out, "    {} -> {};", quote(name), quote(dep)).unwrap();
+            }
+        }
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+/// The object operations performed by `fun` through direct calls to `sui::transfer`.
+fn direct_object_operations(fun: &compiled::Function) -> BTreeSet<ObjectOperation> {
+    fun.calls
+        .iter()
+        .filter(|((addr, module), _)| {
+            *addr == SUI_FRAMEWORK_ADDRESS && module.as_str() == "transfer"
+        })
+        .filter_map(|(_, name)| transfer_operation(name.as_str()))
+        .collect()
+}
+
+/// The object operation performed by the function of `sui::transfer` called `name`, if any. This
+/// covers every function of the module, including the natives other framework modules call.
+fn transfer_operation(name: &str) -> Option<ObjectOperation> {
+    match name {
+        "transfer" | "public_transfer" | "transfer_impl" => Some(ObjectOperation::Transfer),
+        "share_object" | "public_share_object" | "share_object_impl" => {
+            Some(ObjectOperation::Share)
+        }
+        "freeze_object" | "public_freeze_object" | "freeze_object_impl" => {
+            Some(ObjectOperation::Freeze)
+        }
+        "receive" | "public_receive" | "receive_impl" => Some(ObjectOperation::Receive),
+        _ => None,
+    }
+}
+
+/// Propagate object operations from callees to callers, until reaching a fixpoint (functions may
+/// be recursive).
+fn reachable_object_operations(
+    compiled: &BTreeMap<QualifiedMemberId, &compiled::Function>,
+    direct: &BTreeMap<QualifiedMemberId, BTreeSet<ObjectOperation>>,
+) -> BTreeMap<QualifiedMemberId, BTreeSet<ObjectOperation>> {
+    let mut reachable = direct.clone();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (id, fun) in compiled {
+            let from_callees: BTreeSet<_> = fun
+                .calls
+                .iter()
+                .filter_map(|callee| reachable.get(callee))
+                .flatten()
+                .copied()
+                .collect();
+            let ops = reachable.get_mut(id).expect("Every function has an entry");
+            let before = ops.len();
+            ops.extend(from_callees);
+            changed |= ops.len() != before;
+        }
+    }
+    reachable
+}
+
+fn is_mut_tx_context(ty: &Type) -> bool {
+    let Type::Reference(/* is_mut */ true, inner) = ty else {
+        return false;
+    };
+    let Type::Datatype(inst) = inner.as_ref() else {
+        return false;
+    };
+    let ((addr, module), name) = inst.0;
+    addr == SUI_FRAMEWORK_ADDRESS && module.as_str() == "tx_context" && name.as_str() == "TxContext"
+}
+
+fn datatype_abilities(model: &Model, id: &QualifiedMemberId) -> Vec<String> {
+    let Some(datatype) = model
+        .maybe_module(id.0)
+        .and_then(|m| m.maybe_datatype(id.1))
+    else {
+        return vec![];
+    };
+    let abilities = match datatype {
+        Datatype::Struct(s) => s.compiled().abilities,
+        Datatype::Enum(e) => e.compiled().abilities,
+    };
+    abilities.into_iter().map(|a| a.to_string()).collect()
+}
+
+/// The fully qualified name of a module member, using the named address of its package if there is
+/// one.
+fn member_name(model: &Model, ((addr, module), member): &QualifiedMemberId) -> String {
+    format!("{}::{module}::{member}", package_name(model, addr))
+}
+
+/// The named address of the package at `addr`, or the address itself if it does not have one.
+fn package_name(model: &Model, addr: &AccountAddress) -> String {
+    model
+        .maybe_package(addr)
+        .and_then(|p| p.name())
+        .map_or_else(|| display_address(addr), |name| name.to_string())
+}
+
+fn display_address(addr: &AccountAddress) -> String {
+    format!("0x{}", addr.short_str_lossless())
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+#[path = "unit_tests/call_graph_tests.rs"]
+mod call_graph_tests;
@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context;
+use clap::Parser;
+use move_binary_format::CompiledModule;
+use move_decompiler::decompiler::Decompiler;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[group(id = "sui-move-decompile")]
+pub struct Decompile {
+    /// Path to a .mv file to decompile
+    #[clap(name = "module_path")]
+    module_path: PathBuf,
+}
+
+impl Decompile {
+    pub fn execute(self) -> anyhow::Result<()> {
+        let bytes = std::fs::read(&self.module_path)
+            .with_context(|| format!("Failed to read {}", self.module_path.display()))?;
+        // this deserialized a module to the max version of the bytecode but it's OK here because
+        // it's not run as part of the deterministic replicated state machine.
+        let module = CompiledModule::deserialize_with_defaults(&bytes)?;
+        print!("{}", Decompiler::new(&module).decompile()?);
+        Ok(())
+    }
+}
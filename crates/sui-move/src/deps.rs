@@ -0,0 +1,159 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::manage_package::resolve_lock_file_path;
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use move_cli::base;
+use move_package::{
+    package_hooks::PackageIdentifier,
+    resolution::{
+        dependency_graph::{DependencyGraph, DependencyMode},
+        resolved_git_revision,
+    },
+    source_package::parsed_manifest::{DependencyKind, GitInfo, OnChainInfo, PackageName},
+    BuildConfig,
+};
+use std::{collections::BTreeSet, io, path::Path};
+use sui_move_build::implicit_deps;
+use sui_package_management::system_package_versions::latest_system_packages;
+
+/// Inspect and update the dependencies of a package, as recorded in its Move.lock.
+#[derive(Parser)]
+#[group(id = "sui-move-deps")]
+pub struct Deps {
+    #[clap(subcommand)]
+    pub cmd: DepsCommand,
+}
+
+#[derive(Subcommand)]
+pub enum DepsCommand {
+    /// Print the resolved dependency graph of the package, along with the source of each
+    /// dependency and the commit that git dependencies are resolved to. Git dependencies that have
+    /// already been downloaded are not fetched again.
+    Tree,
+    /// Update git dependencies to the latest commit on their branch and rewrite Move.lock.
+    /// Dependencies pinned to an exact commit or a tag are left as they are, and nothing is
+    /// changed if all dependencies are already up to date.
+    Update {
+        /// The name of the dependency to update. All git dependencies are updated if this is not
+        /// set.
+        name: Option<String>,
+    },
+}
+
+impl Deps {
+    pub fn execute(self, path: Option<&Path>, build_config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let mut build_config = resolve_lock_file_path(build_config, Some(&rerooted_path))?;
+        build_config.implicit_dependencies = implicit_deps(latest_system_packages());
+
+        match self.cmd {
+            DepsCommand::Tree => {
+                let graph =
+                    build_config.dependency_graph_for_package(&rerooted_path, &mut io::stderr())?;
+                print_tree(&graph);
+            }
+
+            DepsCommand::Update { name } => {
+                let names: Vec<_> = name.iter().map(|n| PackageName::from(n.as_str())).collect();
+                let updated =
+                    build_config.update_dependencies(&rerooted_path, &names, &mut io::stderr())?;
+
+                if updated.is_empty() {
+                    println!("Dependencies are already up to date");
+                }
+
+                for dep in updated {
+                    println!(
+                        "{} {} {}: {} -> {}",
+                        "UPDATED".bold().green(),
+                        dep.id,
+                        describe_source(&dep.kind),
+                        dep.old_revision.as_deref().unwrap_or("(new)"),
+                        dep.new_revision.as_deref().unwrap_or("(unknown)"),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn print_tree(graph: &DependencyGraph) {
+    println!("{}", graph.root_package_name);
+    let mut expanded = BTreeSet::new();
+    print_dependencies(graph, graph.root_package_id, "", &mut expanded);
+}
+
+/// Print the dependencies of `id`, and recursively their dependencies, in the style of
+/// `cargo tree`. Packages whose dependencies have already been printed are marked with `(*)`.
+fn print_dependencies(
+    graph: &DependencyGraph,
+    id: PackageIdentifier,
+    prefix: &str,
+    expanded: &mut BTreeSet<PackageIdentifier>,
+) {
+    let mut deps: Vec<_> = graph.package_graph.edges(id).collect();
+    deps.sort_by_key(|(_, pkg, dep)| (dep.dep_name, *pkg));
+
+    let count = deps.len();
+    for (i, (_, pkg, dep)) in deps.into_iter().enumerate() {
+        let (branch, indent) = if i + 1 == count {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        let mut line = format!("{prefix}{branch}{}", dep.dep_name);
+        if dep.dep_name != pkg {
+            line.push_str(&format!(" (package {pkg})"));
+        }
+
+        if let Some(package) = graph.package_table.get(&pkg) {
+            line.push_str(&format!(" {}", describe_source(&package.kind)));
+            if let Some(version) = &package.version {
+                line.push_str(&format!(" v{version}"));
+            }
+            if let Some(revision) = resolved_git_revision(&package.kind) {
+                line.push_str(&format!(" @ {revision}"));
+            }
+        }
+
+        if dep.mode == DependencyMode::DevOnly {
+            line.push_str(" [dev]");
+        }
+
+        let first_visit = expanded.insert(pkg);
+        if !first_visit && graph.package_graph.edges(pkg).next().is_some() {
+            line.push_str(" (*)");
+        }
+
+        println!("{line}");
+        if first_visit {
+            print_dependencies(graph, pkg, &format!("{prefix}{indent}"), expanded);
+        }
+    }
+}
+
+fn describe_source(kind: &DependencyKind) -> String {
+    match kind {
+        DependencyKind::Local(path) => format!("(local: {})", path.display()),
+        DependencyKind::Git(GitInfo {
+            git_url,
+            git_rev,
+            subdir,
+        }) => {
+            if subdir.as_os_str().is_empty() {
+                format!("(git: {git_url}, rev: {git_rev})")
+            } else {
+                format!(
+                    "(git: {git_url}, rev: {git_rev}, subdir: {})",
+                    subdir.display()
+                )
+            }
+        }
+        DependencyKind::OnChain(OnChainInfo { id }) => format!("(on-chain: {id})"),
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Context};
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use move_vm_profiler::{
+    analysis::{compare_function_gas, FunctionGas},
+    GasProfiler,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Inspect the gas profiles written by `sui move test --gas-profile`.
+#[derive(Parser)]
+#[group(id = "sui-move-gas-profile")]
+pub struct GasProfile {
+    #[clap(subcommand)]
+    pub cmd: GasProfileCommand,
+}
+
+#[derive(Subcommand)]
+pub enum GasProfileCommand {
+    /// Compare the gas used by two profiles, or by two directories of profiles (matching profiles
+    /// by file name), reporting the change in total gas per test and the functions whose gas usage
+    /// changed the most.
+    Compare {
+        /// The baseline profile, or directory of profiles.
+        base: PathBuf,
+        /// The profile, or directory of profiles, to compare against the baseline.
+        new: PathBuf,
+        /// The number of functions to report per test, ordered by the largest change in gas used
+        /// by the function itself.
+        #[clap(long, default_value = "10")]
+        top: usize,
+        /// Fail if the total gas used by any test increased by more than this percentage.
+        #[clap(long, value_name = "PERCENT")]
+        max_increase: Option<f64>,
+    },
+    /// Convert a profile to the folded-stack format used by flamegraph tools, such as
+    /// `inferno-flamegraph` or `flamegraph.pl`.
+    Flamegraph {
+        /// The profile to convert.
+        profile: PathBuf,
+        /// Where to write the folded stacks. Printed to stdout if not set.
+        #[clap(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+impl GasProfile {
+    pub fn execute(self) -> anyhow::Result<()> {
+        match self.cmd {
+            GasProfileCommand::Compare {
+                base,
+                new,
+                top,
+                max_increase,
+            } => compare(&base, &new, top, max_increase),
+
+            GasProfileCommand::Flamegraph { profile, output } => {
+                let profile = read_profile(&profile)?;
+                match output {
+                    Some(path) => {
+                        let file = fs::File::create(&path)
+                            .with_context(|| format!("Unable to create {}", path.display()))?;
+                        profile.write_folded(&mut BufWriter::new(file))?;
+                    }
+                    None => profile.write_folded(&mut io::stdout().lock())?,
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn compare(base: &Path, new: &Path, top: usize, max_increase: Option<f64>) -> anyhow::Result<()> {
+    let pairs = match (base.is_dir(), new.is_dir()) {
+        (false, false) => vec![(
+            new.display().to_string(),
+            Some(base.to_path_buf()),
+            Some(new.to_path_buf()),
+        )],
+        (true, true) => {
+            let mut profiles: BTreeMap<String, (Option<PathBuf>, Option<PathBuf>)> =
+                BTreeMap::new();
+            for path in profile_files(base)? {
+                profiles.entry(file_name(&path)).or_default().0 = Some(path);
+            }
+            for path in profile_files(new)? {
+                profiles.entry(file_name(&path)).or_default().1 = Some(path);
+            }
+            profiles
+                .into_iter()
+                .map(|(name, (base, new))| (name, base, new))
+                .collect()
+        }
+        _ => bail!("Profiles to compare must both be files, or both be directories"),
+    };
+
+    let mut regressions = vec![];
+    let mut stdout = io::stdout().lock();
+    for (name, base, new) in pairs {
+        let (base, new) = match (base, new) {
+            (Some(base), Some(new)) => (read_profile(&base)?, read_profile(&new)?),
+            (Some(_), None) => {
+                writeln!(stdout, "{} {name}", "REMOVED".bold().yellow())?;
+                continue;
+            }
+            (None, Some(_)) => {
+                writeln!(stdout, "{} {name}", "ADDED".bold().yellow())?;
+                continue;
+            }
+            (None, None) => unreachable!("Every profile comes from one of the directories"),
+        };
+
+        let (base_total, new_total) = (base.total_gas(), new.total_gas());
+        let deltas = compare_function_gas(&base.function_gas(), &new.function_gas());
+        if base_total == new_total && deltas.is_empty() {
+            continue;
+        }
+
+        let change = new_total as i128 - base_total as i128;
+        let percent = if change == 0 {
+            0.0
+        } else if base_total == 0 {
+            f64::INFINITY
+        } else {
+            100.0 * change as f64 / base_total as f64
+        };
+
+        let summary = format!("{name}: {base_total} -> {new_total} ({change:+}, {percent:+.2}%)");
+        if change > 0 {
+            writeln!(stdout, "{}", summary.bold().red())?;
+        } else {
+            writeln!(stdout, "{}", summary.bold().green())?;
+        }
+
+        for delta in deltas.iter().take(top) {
+            let calls = |gas: Option<FunctionGas>| gas.map_or(0, |g| g.calls);
+            writeln!(
+                stdout,
+                "    {:+12} exclusive {:+12} inclusive  {} (calls: {} -> {})",
+                delta.exclusive_delta(),
+                delta.inclusive_delta(),
+                delta.function,
+                calls(delta.base),
+                calls(delta.new),
+            )?;
+        }
+
+        if max_increase.is_some_and(|max| percent > max) {
+            regressions.push(name);
+        }
+    }
+
+    if !regressions.is_empty() {
+        bail!(
+            "Gas usage increased by more than {}% in: {}",
+            max_increase.unwrap_or_default(),
+            regressions.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+fn read_profile(path: &Path) -> anyhow::Result<GasProfiler> {
+    GasProfiler::from_path(path)
+        .with_context(|| format!("Unable to read gas profile {}", path.display()))
+}
+
+/// The gas profiles (JSON files) directly inside `dir`.
+fn profile_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+}
@@ -7,9 +7,14 @@ use move_package::BuildConfig;
 use std::path::Path;
 use sui_move_build::{set_sui_flavor, SuiPackageHooks};
 
+pub mod bindgen;
 pub mod build;
+pub mod call_graph;
 pub mod coverage;
+pub mod decompile;
+pub mod deps;
 pub mod disassemble;
+pub mod gas_profile;
 pub mod manage_package;
 pub mod migrate;
 pub mod new;
@@ -17,9 +22,14 @@ pub mod unit_test;
 
 #[derive(Parser)]
 pub enum Command {
+    Bindgen(bindgen::Bindgen),
     Build(build::Build),
+    CallGraph(call_graph::CallGraph),
     Coverage(coverage::Coverage),
+    Decompile(decompile::Decompile),
+    Deps(deps::Deps),
     Disassemble(disassemble::Disassemble),
+    GasProfile(gas_profile::GasProfile),
     ManagePackage(manage_package::ManagePackage),
     Migrate(migrate::Migrate),
     New(new::New),
@@ -43,9 +53,14 @@ pub fn execute_move_command(
     }
     move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
     match command {
+        Command::Bindgen(c) => c.execute(package_path, build_config),
         Command::Build(c) => c.execute(package_path, build_config),
+        Command::CallGraph(c) => c.execute(package_path, build_config),
         Command::Coverage(c) => c.execute(package_path, build_config),
+        Command::Decompile(c) => c.execute(),
+        Command::Deps(c) => c.execute(package_path, build_config),
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        Command::GasProfile(c) => c.execute(),
         Command::ManagePackage(c) => c.execute(package_path, build_config),
         Command::Migrate(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
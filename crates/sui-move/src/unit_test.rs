@@ -42,6 +42,12 @@ impl Test {
         path: Option<&Path>,
         build_config: BuildConfig,
     ) -> anyhow::Result<UnitTestResult> {
+        if self.test.mutate {
+            let rerooted_path = base::reroot_path(path)?;
+            let unit_test_config = self.test.unit_test_config();
+            return run_move_mutation_tests(&rerooted_path, build_config, unit_test_config);
+        }
+
         let compute_coverage = self.test.compute_coverage;
         if !cfg!(debug_assertions) && compute_coverage {
             return Err(anyhow::anyhow!(
@@ -115,6 +121,38 @@ pub fn run_move_unit_tests(
     })
 }
 
+/// Run mutation tests for the package at `path`, reporting mutants that survive its unit tests.
+/// Returns an error if the unit tests do not pass on the unmodified package, and a failure if any
+/// mutant survives.
+pub fn run_move_mutation_tests(
+    path: &Path,
+    mut build_config: BuildConfig,
+    config: UnitTestingConfig,
+) -> anyhow::Result<UnitTestResult> {
+    // bind the extension hook if it has not yet been done
+    Lazy::force(&SET_EXTENSION_HOOK);
+
+    build_config.implicit_dependencies = implicit_deps(latest_system_packages());
+
+    let (results, _) = move_cli::base::test::run_move_mutation_tests(
+        path,
+        build_config,
+        config,
+        sui_move_natives::all_natives(
+            /* silent */ true,
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+        ),
+        Some(initial_cost_schedule_for_unit_tests()),
+        &mut std::io::stdout(),
+    )?;
+
+    Ok(if results.survived.is_empty() {
+        UnitTestResult::Success
+    } else {
+        UnitTestResult::Failure
+    })
+}
+
 fn new_testing_object_and_natives_cost_runtime(ext: &mut NativeContextExtensions) {
     // Use a throwaway metrics registry for testing.
     let registry = prometheus::Registry::new();
@@ -0,0 +1,192 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeSet, fs, io, path::PathBuf};
+
+use move_model_2::source_model::Model;
+use move_package::BuildConfig;
+use sui_move_build::set_sui_flavor;
+
+use super::{transfer_operation, ObjectOperation, PackageCallGraph, PackageGraph};
+
+fn sui_move_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn model() -> Model {
+    let install_dir = tempfile::tempdir().unwrap();
+    let mut config = BuildConfig {
+        install_dir: Some(install_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    set_sui_flavor(&mut config);
+    config
+        .move_model_for_package(
+            &sui_move_dir().join("tests").join("data").join("call_graph"),
+            &mut io::sink(),
+        )
+        .unwrap()
+}
+
+fn ops<const N: usize>(ops: [ObjectOperation; N]) -> BTreeSet<ObjectOperation> {
+    BTreeSet::from(ops)
+}
+
+fn names<const N: usize>(names: [&str; N]) -> BTreeSet<String> {
+    names.into_iter().map(String::from).collect()
+}
+
+#[test]
+fn test_call_graph() {
+    let graph = PackageCallGraph::new(&model(), /* include_deps */ false).unwrap();
+    assert_eq!(graph.package, "CallGraph");
+
+    let new = &graph.functions["call_graph::counter::new"];
+    assert!(new.root);
+    assert_eq!(new.visibility, "public");
+    assert!(!new.entry);
+    assert!(new.mut_tx_context);
+    assert!(new.calls.contains("sui::object::new"));
+    assert!(new.datatypes.contains("call_graph::counter::Counter"));
+    assert!(new.reachable_object_operations.is_empty());
+
+    let increment = &graph.functions["call_graph::counter::increment"];
+    assert!(!increment.mut_tx_context);
+    assert!(increment.calls.is_empty());
+
+    let send = &graph.functions["call_graph::counter::send"];
+    assert_eq!(send.visibility, "package");
+    assert_eq!(send.calls, names(["sui::transfer::transfer"]));
+    assert_eq!(send.object_operations, ops([ObjectOperation::Transfer]));
+
+    // Operations performed by callees are only reachable.
+    let create = &graph.functions["call_graph::counter::create"];
+    assert_eq!(create.visibility, "private");
+    assert!(create.entry);
+    assert_eq!(
+        create.calls,
+        names(["call_graph::counter::new", "call_graph::counter::share"])
+    );
+    assert!(create.object_operations.is_empty());
+    assert_eq!(
+        create.reachable_object_operations,
+        ops([ObjectOperation::Share])
+    );
+
+    let init = &graph.functions["call_graph::admin::init"];
+    assert_eq!(init.object_operations, ops([ObjectOperation::Transfer]));
+
+    let create_for = &graph.functions["call_graph::admin::create_for"];
+    assert!(create_for.object_operations.is_empty());
+    assert_eq!(
+        create_for.reachable_object_operations,
+        ops([ObjectOperation::Transfer])
+    );
+
+    let frozen = &graph.functions["call_graph::admin::frozen"];
+    assert_eq!(frozen.object_operations, ops([ObjectOperation::Freeze]));
+    assert!(frozen.datatypes.contains("call_graph::admin::AdminCap"));
+
+    // Only the functions of dependencies called by the package are included.
+    let share_object = &graph.functions["sui::transfer::share_object"];
+    assert!(!share_object.root);
+    assert_eq!(
+        share_object.reachable_object_operations,
+        ops([ObjectOperation::Share])
+    );
+    assert!(!graph.functions.contains_key("sui::coin::mint"));
+
+    let counter = &graph.datatypes["call_graph::counter::Counter"];
+    assert!(counter.root);
+    assert_eq!(counter.abilities, vec!["key".to_string()]);
+    assert!(counter.used_by.contains("call_graph::counter::new"));
+    assert!(counter.used_by.contains("call_graph::admin::frozen"));
+    assert!(!graph.datatypes["sui::tx_context::TxContext"].root);
+}
+
+#[test]
+fn test_call_graph_include_deps() {
+    let graph = PackageCallGraph::new(&model(), /* include_deps */ true).unwrap();
+    let mint = &graph.functions["sui::coin::mint"];
+    assert!(!mint.root);
+    assert!(mint.mut_tx_context);
+    assert!(graph.functions.contains_key("call_graph::counter::new"));
+}
+
+#[test]
+fn test_call_graph_dot() {
+    let dot = PackageCallGraph::new(&model(), /* include_deps */ false)
+        .unwrap()
+        .to_dot();
+    assert!(dot.starts_with("digraph \"CallGraph\" {"), "{dot}");
+    assert!(dot.contains("label=\"call_graph::counter\";"), "{dot}");
+    assert!(
+        dot.contains("\"call_graph::counter::create\" -> \"call_graph::counter::share\";"),
+        "{dot}"
+    );
+    assert!(
+        dot.contains("label=\"create\\n[private, entry, &mut TxContext]\""),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(
+            "\"call_graph::counter::new\" -> \"call_graph::counter::Counter\" \
+             [style=dotted, arrowhead=none];"
+        ),
+        "{dot}"
+    );
+    // Datatypes of dependencies are not drawn.
+    assert!(!dot.contains("\"sui::tx_context::TxContext\" ["), "{dot}");
+}
+
+#[test]
+fn test_package_graph() {
+    let graph = PackageGraph::new(&model()).unwrap();
+    assert_eq!(graph.package, "CallGraph");
+
+    let root = &graph.packages["call_graph"];
+    assert!(root.root);
+    assert_eq!(root.address, "0x0");
+    assert_eq!(root.modules, names(["admin", "counter"]));
+    assert_eq!(root.dependencies, names(["sui"]));
+
+    let sui = &graph.packages["sui"];
+    assert!(!sui.root);
+    assert_eq!(sui.address, "0x2");
+    assert!(sui.modules.contains("transfer"));
+    assert_eq!(sui.dependencies, names(["std"]));
+    assert!(graph.packages["std"].dependencies.is_empty());
+
+    let dot = graph.to_dot();
+    assert!(dot.contains("\"call_graph\" -> \"sui\";"), "{dot}");
+    assert!(dot.contains("\"sui\" -> \"std\";"), "{dot}");
+    assert!(!dot.contains("\"call_graph\" -> \"std\";"), "{dot}");
+}
+
+#[test]
+fn test_transfer_operations_are_complete() {
+    // Functions of `sui::transfer` that do not move objects around.
+    let no_operation = ["receiving_object_id", "make_receiver", "receiving_id"];
+
+    let transfer = fs::read_to_string(
+        sui_move_dir().join("../sui-framework/packages/sui-framework/sources/transfer.move"),
+    )
+    .unwrap();
+    let functions: Vec<_> = transfer
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once("fun ")?;
+            let end = rest.find(|c: char| !c.is_alphanumeric() && c != '_')?;
+            Some(&rest[..end])
+        })
+        .collect();
+    assert!(functions.contains(&"public_share_object"));
+
+    for function in functions {
+        assert_eq!(
+            transfer_operation(function).is_none(),
+            no_operation.contains(&function),
+            "sui::transfer::{function} is not classified",
+        );
+    }
+}
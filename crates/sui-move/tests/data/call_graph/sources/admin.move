@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module call_graph::admin;
+
+use call_graph::counter;
+
+public struct AdminCap has key, store {
+    id: UID,
+}
+
+fun init(ctx: &mut TxContext) {
+    transfer::public_transfer(AdminCap { id: object::new(ctx) }, ctx.sender())
+}
+
+public fun create_for(_: &AdminCap, recipient: address, ctx: &mut TxContext) {
+    let mut counter = counter::new(ctx);
+    counter.increment();
+    counter.send(recipient)
+}
+
+public fun frozen(_: &AdminCap, ctx: &mut TxContext) {
+    transfer::freeze_object(counter::new(ctx))
+}
@@ -0,0 +1,29 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module call_graph::counter;
+
+public struct Counter has key {
+    id: UID,
+    value: u64,
+}
+
+public fun new(ctx: &mut TxContext): Counter {
+    Counter { id: object::new(ctx), value: 0 }
+}
+
+public fun increment(counter: &mut Counter) {
+    counter.value = counter.value + 1;
+}
+
+public(package) fun send(counter: Counter, recipient: address) {
+    transfer::transfer(counter, recipient)
+}
+
+entry fun create(ctx: &mut TxContext) {
+    share(new(ctx))
+}
+
+fun share(counter: Counter) {
+    transfer::share_object(counter)
+}
@@ -5,6 +5,7 @@ use super::{
     metrics::Metrics, server::Server, Discovery, DiscoveryEventLoop, DiscoveryServer, State,
 };
 use crate::discovery::TrustedPeerChangeEvent;
+use crate::peer_reputation::PeerReputation;
 use anemo::codegen::InboundRequestLayer;
 use anemo_tower::rate_limit;
 use fastcrypto::traits::KeyPair;
@@ -24,6 +25,7 @@ use tokio::{
 pub struct Builder {
     config: Option<P2pConfig>,
     metrics: Option<Metrics>,
+    peer_reputation: Option<PeerReputation>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
 }
 
@@ -33,6 +35,7 @@ impl Builder {
         Self {
             config: None,
             metrics: None,
+            peer_reputation: None,
             trusted_peer_change_rx,
         }
     }
@@ -47,6 +50,12 @@ impl Builder {
         self
     }
 
+    /// Sets the peer reputation tracker used to avoid dialing and querying banned peers.
+    pub fn peer_reputation(mut self, peer_reputation: PeerReputation) -> Self {
+        self.peer_reputation = Some(peer_reputation);
+        self
+    }
+
     pub fn build(self) -> (UnstartedDiscovery, DiscoveryServer<impl Discovery>) {
         let discovery_config = self
             .config
@@ -72,10 +81,12 @@ impl Builder {
         let Builder {
             config,
             metrics,
+            peer_reputation,
             trusted_peer_change_rx,
         } = self;
         let config = config.unwrap();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
+        let peer_reputation = peer_reputation.unwrap_or_default();
         let (sender, receiver) = oneshot::channel();
 
         let handle = Handle {
@@ -102,6 +113,7 @@ impl Builder {
                 state,
                 trusted_peer_change_rx,
                 metrics,
+                peer_reputation,
             },
             server,
         )
@@ -116,6 +128,7 @@ pub struct UnstartedDiscovery {
     pub(super) state: Arc<RwLock<State>>,
     pub(super) trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
     pub(super) metrics: Metrics,
+    pub(super) peer_reputation: PeerReputation,
 }
 
 impl UnstartedDiscovery {
@@ -131,6 +144,7 @@ impl UnstartedDiscovery {
             state,
             trusted_peer_change_rx,
             metrics,
+            peer_reputation,
         } = self;
 
         let discovery_config = config.discovery.clone().unwrap_or_default();
@@ -160,6 +174,7 @@ impl UnstartedDiscovery {
                 state,
                 trusted_peer_change_rx,
                 metrics,
+                peer_reputation,
             },
             handle,
         )
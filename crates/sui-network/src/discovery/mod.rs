@@ -27,6 +27,8 @@ use tokio::{
 };
 use tracing::{debug, info, trace};
 
+use crate::peer_reputation::PeerReputation;
+
 const TIMEOUT: Duration = Duration::from_secs(1);
 const ONE_DAY_MILLISECONDS: u64 = 24 * 60 * 60 * 1_000;
 const MAX_ADDRESS_LENGTH: usize = 300;
@@ -124,6 +126,7 @@ struct DiscoveryEventLoop {
     state: Arc<RwLock<State>>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
     metrics: Metrics,
+    peer_reputation: PeerReputation,
 }
 
 impl DiscoveryEventLoop {
@@ -297,6 +300,7 @@ impl DiscoveryEventLoop {
                 self.state.clone(),
                 self.metrics.clone(),
                 self.allowlisted_peers.clone(),
+                self.peer_reputation.clone(),
             ));
 
         // Cull old peers older than a day
@@ -325,6 +329,7 @@ impl DiscoveryEventLoop {
                 !info.addresses.is_empty() // Peer has addresses we can dial
                 && !state.connected_peers.contains_key(peer_id) // We're not already connected
                 && !self.pending_dials.contains_key(peer_id) // There is no pending dial to this node
+                && !self.peer_reputation.is_banned(peer_id) // The peer isn't temporarily banned
             })
             .collect::<Vec<_>>();
 
@@ -456,12 +461,14 @@ async fn query_connected_peers_for_their_known_peers(
     state: Arc<RwLock<State>>,
     metrics: Metrics,
     allowlisted_peers: Arc<HashMap<PeerId, Option<Multiaddr>>>,
+    peer_reputation: PeerReputation,
 ) {
     use rand::seq::IteratorRandom;
 
     let peers_to_query = network
         .peers()
         .into_iter()
+        .filter(|id| !peer_reputation.is_banned(id))
         .flat_map(|id| network.peer(id))
         .choose_multiple(&mut rand::thread_rng(), config.peers_to_query());
 
@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anemo::PeerId;
+use prometheus::{
+    register_gauge_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, GaugeVec, IntCounter, IntGauge, Registry,
+};
+use std::sync::Arc;
+use tap::Pipe;
+
+#[derive(Clone)]
+pub(super) struct Metrics(Option<Arc<Inner>>);
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Metrics").finish()
+    }
+}
+
+impl Metrics {
+    pub fn enabled(registry: &Registry) -> Self {
+        Metrics(Some(Inner::new(registry)))
+    }
+
+    pub fn disabled() -> Self {
+        Metrics(None)
+    }
+
+    pub fn inc_peer_bans(&self) {
+        if let Some(inner) = &self.0 {
+            inner.peer_bans.inc();
+        }
+    }
+
+    pub fn set_banned_peers(&self, count: usize) {
+        if let Some(inner) = &self.0 {
+            inner.banned_peers.set(count as i64);
+        }
+    }
+
+    pub fn set_peer_score(&self, peer_id: &PeerId, score: f64) {
+        if let Some(inner) = &self.0 {
+            inner
+                .peer_score
+                .with_label_values(&[&peer_id.to_string()])
+                .set(score);
+        }
+    }
+
+    pub fn remove_peer_score(&self, peer_id: &PeerId) {
+        if let Some(inner) = &self.0 {
+            let _ = inner
+                .peer_score
+                .remove_label_values(&[&peer_id.to_string()]);
+        }
+    }
+}
+
+struct Inner {
+    peer_bans: IntCounter,
+    banned_peers: IntGauge,
+    peer_score: GaugeVec,
+}
+
+impl Inner {
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        Self {
+            peer_bans: register_int_counter_with_registry!(
+                "peer_reputation_bans",
+                "Number of times a peer was temporarily banned for persistent failures or invalid responses",
+                registry
+            )
+            .unwrap(),
+            banned_peers: register_int_gauge_with_registry!(
+                "peer_reputation_banned_peers",
+                "Number of peers that are currently banned",
+                registry
+            )
+            .unwrap(),
+            peer_score: register_gauge_vec_with_registry!(
+                "peer_reputation_score",
+                "Reputation score of a peer, from 0 (worst) to 100 (best)",
+                &["peer_id"],
+                registry
+            )
+            .unwrap(),
+        }
+        .pipe(Arc::new)
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer reputation tracking shared by the p2p subsystems.
+//!
+//! Every peer is scored from 0 to 100 based on the latency of its responses, the rate of failed or
+//! timed out requests, and how far its advertised checkpoint height lags behind the highest one
+//! known. State sync prefers high-scoring peers when downloading checkpoints, and peers that keep
+//! failing or serving invalid data are temporarily banned: state sync stops downloading from them
+//! and discovery stops dialing them.
+
+use anemo::PeerId;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use sui_config::p2p::PeerReputationConfig;
+use tracing::{debug, warn};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+use self::metrics::Metrics;
+
+const MAX_SCORE: f64 = 100.0;
+/// Maximum penalty for slow responses, reached asymptotically as latency grows.
+const LATENCY_PENALTY: f64 = 40.0;
+/// Latency at which half of the maximum latency penalty applies.
+const REFERENCE_LATENCY: Duration = Duration::from_secs(1);
+/// Maximum penalty for failed requests, applied when every recent request failed.
+const FAILURE_PENALTY: f64 = 40.0;
+/// Maximum penalty for lagging behind, reached asymptotically as the lag grows.
+const HEIGHT_LAG_PENALTY: f64 = 20.0;
+/// Height lag, in checkpoints, at which half of the maximum height lag penalty applies.
+const REFERENCE_HEIGHT_LAG: f64 = 100.0;
+/// Weight of the most recent sample in the latency and failure rate moving averages.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// A cheaply cloneable handle to the reputation of our peers.
+#[derive(Clone, Debug)]
+pub struct PeerReputation {
+    config: Arc<PeerReputationConfig>,
+    peers: Arc<Mutex<HashMap<PeerId, PeerRecord>>>,
+    metrics: Metrics,
+}
+
+/// A point-in-time view of the reputation of a peer.
+#[derive(Clone, Debug)]
+pub struct PeerScore {
+    pub peer_id: PeerId,
+    pub score: f64,
+    /// Moving average of the latency of successful responses, if any were received.
+    pub latency: Option<Duration>,
+    /// Moving average of the fraction of requests that failed, from 0 to 1.
+    pub failure_rate: f64,
+    pub successes: u64,
+    pub failures: u64,
+    pub invalid_responses: u64,
+    /// Number of checkpoints the peer is behind the highest checkpoint known from all peers.
+    pub height_lag: u64,
+    /// Number of times the peer was banned.
+    pub bans: u32,
+    /// Remaining time of the current ban, if the peer is banned.
+    pub banned_for: Option<Duration>,
+}
+
+#[derive(Debug, Default)]
+struct PeerRecord {
+    latency: Option<Duration>,
+    failure_rate: f64,
+    successes: u64,
+    failures: u64,
+    invalid_responses: u64,
+    consecutive_failures: u32,
+    invalid_responses_since_ban: u32,
+    height_lag: u64,
+    bans: u32,
+    banned_until: Option<Instant>,
+}
+
+impl PeerRecord {
+    fn score(&self) -> f64 {
+        let latency_penalty = self.latency.map_or(0.0, |latency| {
+            let latency = latency.as_secs_f64();
+            LATENCY_PENALTY * latency / (latency + REFERENCE_LATENCY.as_secs_f64())
+        });
+        let failure_penalty = FAILURE_PENALTY * self.failure_rate;
+        let height_lag = self.height_lag as f64;
+        let height_lag_penalty =
+            HEIGHT_LAG_PENALTY * height_lag / (height_lag + REFERENCE_HEIGHT_LAG);
+
+        (MAX_SCORE - latency_penalty - failure_penalty - height_lag_penalty).clamp(0.0, MAX_SCORE)
+    }
+
+    fn banned_for(&self, now: Instant) -> Option<Duration> {
+        self.banned_until
+            .and_then(|banned_until| banned_until.checked_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    fn record_outcome(&mut self, failed: bool) {
+        let sample = if failed { 1.0 } else { 0.0 };
+        self.failure_rate = EWMA_ALPHA * sample + (1.0 - EWMA_ALPHA) * self.failure_rate;
+    }
+}
+
+impl PeerReputation {
+    pub fn new(config: PeerReputationConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            peers: Default::default(),
+            metrics: Metrics::disabled(),
+        }
+    }
+
+    pub fn with_metrics(mut self, registry: &prometheus::Registry) -> Self {
+        self.metrics = Metrics::enabled(registry);
+        self
+    }
+
+    /// Records a successful response from `peer_id` which took `latency` to arrive.
+    pub fn record_success(&self, peer_id: PeerId, latency: Duration) {
+        let mut peers = self.peers.lock().unwrap();
+        let record = peers.entry(peer_id).or_default();
+        record.successes += 1;
+        record.consecutive_failures = 0;
+        record.record_outcome(false);
+        record.latency = Some(match record.latency {
+            Some(average) => average.mul_f64(1.0 - EWMA_ALPHA) + latency.mul_f64(EWMA_ALPHA),
+            None => latency,
+        });
+    }
+
+    /// Records a request to `peer_id` that failed or timed out.
+    pub fn record_failure(&self, peer_id: PeerId) {
+        let mut peers = self.peers.lock().unwrap();
+        let record = peers.entry(peer_id).or_default();
+        record.failures += 1;
+        record.consecutive_failures += 1;
+        record.record_outcome(true);
+        if record.consecutive_failures >= self.config.max_consecutive_failures() {
+            self.ban(peer_id, record, "consecutive failures");
+        }
+    }
+
+    /// Records a response from `peer_id` that failed verification.
+    pub fn record_invalid_response(&self, peer_id: PeerId) {
+        let mut peers = self.peers.lock().unwrap();
+        let record = peers.entry(peer_id).or_default();
+        record.invalid_responses += 1;
+        record.invalid_responses_since_ban += 1;
+        record.record_outcome(true);
+        if record.invalid_responses_since_ban >= self.config.max_invalid_responses() {
+            self.ban(peer_id, record, "invalid responses");
+        }
+    }
+
+    /// Records how many checkpoints `peer_id` is behind the highest checkpoint known from all
+    /// peers.
+    pub fn record_height_lag(&self, peer_id: PeerId, height_lag: u64) {
+        self.peers
+            .lock()
+            .unwrap()
+            .entry(peer_id)
+            .or_default()
+            .height_lag = height_lag;
+    }
+
+    /// Returns the score of `peer_id`. Peers we know nothing about have the maximum score.
+    pub fn score(&self, peer_id: &PeerId) -> f64 {
+        self.peers
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .map_or(MAX_SCORE, PeerRecord::score)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.peers
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .and_then(|record| record.banned_for(Instant::now()))
+            .is_some()
+    }
+
+    /// Drops the reputation of a disconnected peer, unless it is banned, so that the ban still
+    /// applies if the peer reconnects.
+    pub fn forget_peer(&self, peer_id: &PeerId) {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().unwrap();
+        if peers
+            .get(peer_id)
+            .is_some_and(|record| record.banned_for(now).is_none())
+        {
+            peers.remove(peer_id);
+            self.metrics.remove_peer_score(peer_id);
+        }
+    }
+
+    /// Returns the reputation of all tracked peers, highest score first.
+    pub fn scores(&self) -> Vec<PeerScore> {
+        let now = Instant::now();
+        let mut scores: Vec<_> = self
+            .peers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(peer_id, record)| PeerScore {
+                peer_id: *peer_id,
+                score: record.score(),
+                latency: record.latency,
+                failure_rate: record.failure_rate,
+                successes: record.successes,
+                failures: record.failures,
+                invalid_responses: record.invalid_responses,
+                height_lag: record.height_lag,
+                bans: record.bans,
+                banned_for: record.banned_for(now),
+            })
+            .collect();
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scores
+    }
+
+    /// Updates the score and ban metrics. Expected to be called periodically.
+    pub fn report_metrics(&self) {
+        let scores = self.scores();
+        for score in &scores {
+            self.metrics.set_peer_score(&score.peer_id, score.score);
+        }
+        self.metrics.set_banned_peers(
+            scores
+                .iter()
+                .filter(|score| score.banned_for.is_some())
+                .count(),
+        );
+    }
+
+    fn ban(&self, peer_id: PeerId, record: &mut PeerRecord, reason: &str) {
+        let now = Instant::now();
+        if record.banned_for(now).is_some() {
+            debug!("Peer {peer_id} is already banned");
+            return;
+        }
+
+        let duration = self
+            .config
+            .ban_duration()
+            .saturating_mul(2u32.saturating_pow(record.bans))
+            .min(self.config.max_ban_duration());
+        warn!(
+            "Banning peer {} for {duration:?} due to {reason}",
+            peer_id.short_display(4)
+        );
+        record.bans += 1;
+        record.banned_until = Some(now + duration);
+        record.consecutive_failures = 0;
+        record.invalid_responses_since_ban = 0;
+        self.metrics.inc_peer_bans();
+    }
+}
+
+impl Default for PeerReputation {
+    fn default() -> Self {
+        Self::new(PeerReputationConfig::default())
+    }
+}
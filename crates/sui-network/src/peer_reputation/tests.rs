@@ -0,0 +1,114 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn peer(byte: u8) -> PeerId {
+    PeerId([byte; 32])
+}
+
+#[test]
+fn unknown_peer_has_max_score() {
+    let reputation = PeerReputation::default();
+    assert_eq!(reputation.score(&peer(1)), MAX_SCORE);
+    assert!(!reputation.is_banned(&peer(1)));
+    assert!(reputation.scores().is_empty());
+}
+
+#[test]
+fn score_orders_peers_by_quality() {
+    let reputation = PeerReputation::default();
+    let (fast, slow, flaky, lagging) = (peer(1), peer(2), peer(3), peer(4));
+
+    for _ in 0..10 {
+        reputation.record_success(fast, Duration::from_millis(20));
+        reputation.record_success(slow, Duration::from_secs(2));
+        reputation.record_success(flaky, Duration::from_millis(20));
+        reputation.record_success(lagging, Duration::from_millis(20));
+    }
+    reputation.record_failure(flaky);
+    reputation.record_failure(flaky);
+    reputation.record_height_lag(lagging, 1_000);
+
+    let fast_score = reputation.score(&fast);
+    assert!(fast_score > reputation.score(&slow));
+    assert!(fast_score > reputation.score(&flaky));
+    assert!(fast_score > reputation.score(&lagging));
+
+    let scores = reputation.scores();
+    assert_eq!(scores.len(), 4);
+    assert_eq!(scores[0].peer_id, fast);
+    assert!(scores.windows(2).all(|pair| pair[0].score >= pair[1].score));
+}
+
+#[test]
+fn ban_after_consecutive_failures() {
+    let reputation = PeerReputation::new(PeerReputationConfig {
+        max_consecutive_failures: Some(3),
+        ..Default::default()
+    });
+    let peer_id = peer(1);
+
+    reputation.record_failure(peer_id);
+    reputation.record_failure(peer_id);
+    // A success in between resets the consecutive failure count.
+    reputation.record_success(peer_id, Duration::from_millis(20));
+    reputation.record_failure(peer_id);
+    reputation.record_failure(peer_id);
+    assert!(!reputation.is_banned(&peer_id));
+
+    reputation.record_failure(peer_id);
+    assert!(reputation.is_banned(&peer_id));
+
+    let score = &reputation.scores()[0];
+    assert_eq!(score.failures, 5);
+    assert_eq!(score.bans, 1);
+    assert!(score.banned_for.is_some());
+
+    // Banned peers are kept when they disconnect.
+    reputation.forget_peer(&peer_id);
+    assert!(reputation.is_banned(&peer_id));
+}
+
+#[test]
+fn ban_after_invalid_responses() {
+    let reputation = PeerReputation::new(PeerReputationConfig {
+        max_invalid_responses: Some(2),
+        ..Default::default()
+    });
+    let peer_id = peer(1);
+
+    reputation.record_invalid_response(peer_id);
+    assert!(!reputation.is_banned(&peer_id));
+    reputation.record_invalid_response(peer_id);
+    assert!(reputation.is_banned(&peer_id));
+}
+
+#[test]
+fn ban_expires_and_doubles() {
+    let reputation = PeerReputation::new(PeerReputationConfig {
+        max_consecutive_failures: Some(1),
+        ban_duration_ms: Some(50),
+        max_ban_duration_ms: Some(150),
+        ..Default::default()
+    });
+    let peer_id = peer(1);
+
+    reputation.record_failure(peer_id);
+    assert!(reputation.is_banned(&peer_id));
+    std::thread::sleep(Duration::from_millis(60));
+    assert!(!reputation.is_banned(&peer_id));
+
+    // The second ban lasts twice as long, the third is capped at the maximum.
+    reputation.record_failure(peer_id);
+    assert!(reputation.scores()[0].banned_for.unwrap() > Duration::from_millis(50));
+    std::thread::sleep(Duration::from_millis(110));
+    reputation.record_failure(peer_id);
+    assert!(reputation.scores()[0].banned_for.unwrap() <= Duration::from_millis(150));
+    assert_eq!(reputation.scores()[0].bans, 3);
+
+    // Peers that are not banned are dropped when they disconnect.
+    std::thread::sleep(Duration::from_millis(160));
+    reputation.forget_peer(&peer_id);
+    assert!(reputation.scores().is_empty());
+}
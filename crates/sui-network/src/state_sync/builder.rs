@@ -15,12 +15,15 @@ use tokio::{
     sync::{broadcast, mpsc},
     task::JoinSet,
 };
+use tracing::warn;
 
 use super::{
+    ingestion::CheckpointIngestionReader,
     metrics::Metrics,
     server::{CheckpointContentsDownloadLimitLayer, Server},
     Handle, PeerHeights, StateSync, StateSyncEventLoop, StateSyncMessage, StateSyncServer,
 };
+use crate::peer_reputation::PeerReputation;
 use sui_types::storage::WriteStore;
 
 pub struct Builder<S> {
@@ -28,6 +31,7 @@ pub struct Builder<S> {
     config: Option<StateSyncConfig>,
     metrics: Option<Metrics>,
     archive_readers: Option<ArchiveReaderBalancer>,
+    peer_reputation: Option<PeerReputation>,
 }
 
 impl Builder<()> {
@@ -38,6 +42,7 @@ impl Builder<()> {
             config: None,
             metrics: None,
             archive_readers: None,
+            peer_reputation: None,
         }
     }
 }
@@ -49,6 +54,7 @@ impl<S> Builder<S> {
             config: self.config,
             metrics: self.metrics,
             archive_readers: self.archive_readers,
+            peer_reputation: self.peer_reputation,
         }
     }
 
@@ -66,6 +72,13 @@ impl<S> Builder<S> {
         self.archive_readers = Some(archive_readers);
         self
     }
+
+    /// Sets the peer reputation tracker used to select peers to sync from. It can be shared with
+    /// other subsystems, e.g. discovery.
+    pub fn peer_reputation(mut self, peer_reputation: PeerReputation) -> Self {
+        self.peer_reputation = Some(peer_reputation);
+        self
+    }
 }
 
 impl<S> Builder<S>
@@ -126,11 +139,26 @@ where
             config,
             metrics,
             archive_readers,
+            peer_reputation,
         } = self;
         let store = store.unwrap();
         let config = config.unwrap_or_default();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
         let archive_readers = archive_readers.unwrap_or_default();
+        let peer_reputation = peer_reputation.unwrap_or_default();
+        let checkpoint_ingestion_reader =
+            config
+                .checkpoint_ingestion_store
+                .as_ref()
+                .and_then(|store_config| {
+                    CheckpointIngestionReader::new(
+                        store_config,
+                        config.checkpoint_ingestion_download_concurrency(),
+                    )
+                    .map_err(|e| warn!("Failed to create checkpoint ingestion reader: {e:?}"))
+                    .ok()
+                    .map(Arc::new)
+                });
 
         let (sender, mailbox) = mpsc::channel(config.mailbox_capacity());
         let (checkpoint_event_sender, _receiver) =
@@ -139,6 +167,7 @@ where
         let handle = Handle {
             sender,
             checkpoint_event_sender: checkpoint_event_sender.clone(),
+            peer_reputation: peer_reputation.clone(),
         };
         let peer_heights = PeerHeights {
             peers: HashMap::new(),
@@ -164,9 +193,11 @@ where
                 store,
                 download_limit_layer: None,
                 peer_heights,
+                peer_reputation,
                 checkpoint_event_sender,
                 metrics,
                 archive_readers,
+                checkpoint_ingestion_reader,
             },
             server,
         )
@@ -180,9 +211,11 @@ pub struct UnstartedStateSync<S> {
     pub(super) download_limit_layer: Option<CheckpointContentsDownloadLimitLayer>,
     pub(super) store: S,
     pub(super) peer_heights: Arc<RwLock<PeerHeights>>,
+    pub(super) peer_reputation: PeerReputation,
     pub(super) checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     pub(super) metrics: Metrics,
     pub(super) archive_readers: ArchiveReaderBalancer,
+    pub(super) checkpoint_ingestion_reader: Option<Arc<CheckpointIngestionReader>>,
 }
 
 impl<S> UnstartedStateSync<S>
@@ -197,9 +230,11 @@ where
             download_limit_layer,
             store,
             peer_heights,
+            peer_reputation,
             checkpoint_event_sender,
             metrics,
             archive_readers,
+            checkpoint_ingestion_reader,
         } = self;
 
         (
@@ -213,11 +248,14 @@ where
                 download_limit_layer,
                 store,
                 peer_heights,
+                peer_reputation,
                 checkpoint_event_sender,
                 network,
                 metrics,
                 archive_readers,
                 sync_checkpoint_from_archive_task: None,
+                checkpoint_ingestion_reader,
+                sync_checkpoint_from_ingestion_store_task: None,
             },
             handle,
         )
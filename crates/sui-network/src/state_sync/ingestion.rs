@@ -0,0 +1,142 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context, Result};
+use futures::StreamExt;
+use object_store::path::Path;
+use std::ops::Range;
+use std::sync::Arc;
+use sui_config::object_storage_config::ObjectStoreConfig;
+use sui_storage::blob::Blob;
+use sui_storage::object_store::http::HttpDownloaderBuilder;
+use sui_storage::object_store::ObjectStoreGetExt;
+use sui_storage::verify_checkpoint;
+use sui_types::base_types::ExecutionData;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::{
+    CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
+    VerifiedCheckpointContents,
+};
+use sui_types::storage::WriteStore;
+use tracing::{debug, warn};
+
+/// Reads checkpoints from a store of per-checkpoint `CheckpointData` blobs, in the format written
+/// by `sui-data-ingestion`. The store can be a remote bucket or a local directory.
+pub(super) struct CheckpointIngestionReader {
+    remote_store: Arc<dyn ObjectStoreGetExt>,
+    concurrency: usize,
+}
+
+impl CheckpointIngestionReader {
+    pub fn new(config: &ObjectStoreConfig, concurrency: usize) -> Result<Self> {
+        let remote_store = if config.no_sign_request {
+            config.make_http()?
+        } else {
+            config.make().map(Arc::new)?
+        };
+        Ok(Self {
+            remote_store,
+            concurrency: concurrency.max(1),
+        })
+    }
+
+    pub fn remote_store_identifier(&self) -> String {
+        self.remote_store.to_string()
+    }
+
+    async fn fetch(&self, sequence_number: CheckpointSequenceNumber) -> Result<CheckpointData> {
+        let path = Path::from(format!("{sequence_number}.chk"));
+        let bytes = self.remote_store.get_bytes(&path).await?;
+        Blob::from_bytes::<CheckpointData>(&bytes)
+    }
+
+    /// Syncs the checkpoints in `checkpoint_range` into `store`. Checkpoints are downloaded in
+    /// parallel and inserted in order, stopping at the first checkpoint that is not available in
+    /// the ingestion store yet or fails verification. Returns the number of synced checkpoints.
+    pub async fn sync<S>(&self, store: &S, checkpoint_range: Range<CheckpointSequenceNumber>) -> u64
+    where
+        S: WriteStore,
+    {
+        let mut checkpoints =
+            futures::stream::iter(checkpoint_range)
+                .map(|sequence_number| async move {
+                    (sequence_number, self.fetch(sequence_number).await)
+                })
+                .buffered(self.concurrency);
+
+        let mut synced = 0;
+        while let Some((sequence_number, checkpoint_data)) = checkpoints.next().await {
+            let checkpoint_data = match checkpoint_data {
+                Ok(checkpoint_data) => checkpoint_data,
+                Err(err) => {
+                    debug!(
+                        "Checkpoint {sequence_number} is not available in ingestion store: {err:?}"
+                    );
+                    break;
+                }
+            };
+            if let Err(err) = insert_checkpoint_data(store, checkpoint_data) {
+                warn!("Failed to sync checkpoint {sequence_number} from ingestion store: {err:?}");
+                break;
+            }
+            synced += 1;
+        }
+        synced
+    }
+}
+
+/// Verifies and inserts the summary and contents of a checkpoint, and bumps the highest synced
+/// checkpoint watermark. The previous checkpoint must already be synced.
+fn insert_checkpoint_data<S>(store: &S, checkpoint_data: CheckpointData) -> Result<()>
+where
+    S: WriteStore,
+{
+    let CheckpointData {
+        checkpoint_summary,
+        checkpoint_contents,
+        transactions,
+    } = checkpoint_data;
+    let sequence_number = checkpoint_summary.sequence_number;
+
+    let checkpoint = match store.get_checkpoint_by_sequence_number(sequence_number) {
+        Some(checkpoint) => checkpoint,
+        None => {
+            let previous_sequence_number = sequence_number
+                .checked_sub(1)
+                .context("Checkpoint seq num underflow")?;
+            let previous_checkpoint = store
+                .get_checkpoint_by_sequence_number(previous_sequence_number)
+                .with_context(|| {
+                    format!("Missing previous checkpoint {previous_sequence_number} in store")
+                })?;
+            let checkpoint: VerifiedCheckpoint =
+                verify_checkpoint(&previous_checkpoint, store, checkpoint_summary)
+                    .map_err(|_| anyhow!("Checkpoint verification failed"))?;
+            store
+                .insert_checkpoint(&checkpoint)
+                .map_err(|e| anyhow!("Failed to insert checkpoint: {e}"))?;
+            store
+                .update_highest_verified_checkpoint(&checkpoint)
+                .map_err(|e| anyhow!("Failed to update watermark: {e}"))?;
+            checkpoint
+        }
+    };
+
+    let contents = FullCheckpointContents::from_contents_and_execution_data(
+        checkpoint_contents,
+        transactions
+            .into_iter()
+            .map(|tx| ExecutionData::new(tx.transaction, tx.effects)),
+    );
+    contents.verify_digests(checkpoint.content_digest)?;
+    store
+        .insert_checkpoint_contents(
+            &checkpoint,
+            VerifiedCheckpointContents::new_unchecked(contents),
+        )
+        .map_err(|e| anyhow!("Failed to insert content: {e}"))?;
+    store
+        .update_highest_synced_checkpoint(&checkpoint)
+        .map_err(|e| anyhow!("Failed to update watermark: {e}"))?;
+    Ok(())
+}
@@ -3,8 +3,8 @@
 
 use mysten_metrics::histogram::Histogram as MystenHistogram;
 use prometheus::{
-    register_histogram_with_registry, register_int_gauge_with_registry, Histogram, IntGauge,
-    Registry,
+    register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
 };
 use std::sync::Arc;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -48,6 +48,12 @@ impl Metrics {
         }
     }
 
+    pub fn inc_checkpoints_synced_from_ingestion_store(&self, count: u64) {
+        if let Some(inner) = &self.0 {
+            inner.checkpoints_synced_from_ingestion_store.inc_by(count);
+        }
+    }
+
     pub fn checkpoint_summary_age_metrics(&self) -> Option<(&Histogram, &MystenHistogram)> {
         if let Some(inner) = &self.0 {
             return Some((
@@ -63,6 +69,7 @@ struct Inner {
     highest_known_checkpoint: IntGauge,
     highest_verified_checkpoint: IntGauge,
     highest_synced_checkpoint: IntGauge,
+    checkpoints_synced_from_ingestion_store: IntCounter,
     checkpoint_summary_age: Histogram,
     // TODO: delete once users are migrated to non-Mysten histogram.
     checkpoint_summary_age_ms: MystenHistogram,
@@ -92,6 +99,13 @@ impl Inner {
             )
             .unwrap(),
 
+            checkpoints_synced_from_ingestion_store: register_int_counter_with_registry!(
+                "checkpoints_synced_from_ingestion_store",
+                "Number of checkpoints synced from the checkpoint ingestion store",
+                registry
+            )
+            .unwrap(),
+
             checkpoint_summary_age: register_histogram_with_registry!(
                 "checkpoint_summary_age",
                 "Age of checkpoints summaries when they arrive and are verified.",
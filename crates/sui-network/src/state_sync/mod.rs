@@ -78,6 +78,7 @@ mod generated {
     include!(concat!(env!("OUT_DIR"), "/sui.StateSync.rs"));
 }
 mod builder;
+mod ingestion;
 mod metrics;
 mod server;
 #[cfg(test)]
@@ -93,7 +94,11 @@ pub use server::GetCheckpointSummaryRequest;
 use sui_archival::reader::ArchiveReaderBalancer;
 use sui_storage::verify_checkpoint;
 
-use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
+use self::{
+    ingestion::CheckpointIngestionReader, metrics::Metrics,
+    server::CheckpointContentsDownloadLimitLayer,
+};
+use crate::peer_reputation::{PeerReputation, PeerScore};
 
 /// A handle to the StateSync subsystem.
 ///
@@ -103,6 +108,7 @@ use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
 pub struct Handle {
     sender: mpsc::Sender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    peer_reputation: PeerReputation,
 }
 
 impl Handle {
@@ -125,6 +131,11 @@ impl Handle {
     pub fn subscribe_to_synced_checkpoints(&self) -> broadcast::Receiver<VerifiedCheckpoint> {
         self.checkpoint_event_sender.subscribe()
     }
+
+    /// Returns the reputation of the peers we sync from, highest score first.
+    pub fn peer_scores(&self) -> Vec<PeerScore> {
+        self.peer_reputation.scores()
+    }
 }
 
 struct PeerHeights {
@@ -271,7 +282,8 @@ impl PeerHeights {
     }
 }
 
-// PeerBalancer is an Iterator that selects peers based on RTT with some added randomness.
+// PeerBalancer is an Iterator that selects peers based on their reputation score and RTT with
+// some added randomness.
 #[derive(Clone)]
 struct PeerBalancer {
     peers: VecDeque<(anemo::Peer, PeerStateSyncInfo)>,
@@ -289,24 +301,34 @@ impl PeerBalancer {
     pub fn new(
         network: &anemo::Network,
         peer_heights: Arc<RwLock<PeerHeights>>,
+        peer_reputation: &PeerReputation,
         request_type: PeerCheckpointRequestType,
     ) -> Self {
-        let mut peers: Vec<_> = peer_heights
+        let (mut peers, banned_peers): (Vec<_>, Vec<_>) = peer_heights
             .read()
             .unwrap()
             .peers_on_same_chain()
             // Filter out any peers who we aren't connected with.
-            .filter_map(|(peer_id, info)| {
-                network
-                    .peer(*peer_id)
-                    .map(|peer| (peer.connection_rtt(), peer, *info))
+            .filter_map(|(peer_id, info)| network.peer(*peer_id).map(|peer| (peer, *info)))
+            .partition(|(peer, _)| !peer_reputation.is_banned(&peer.peer_id()));
+        // Banned peers are only used as a last resort, if there is no one else to sync from.
+        if peers.is_empty() {
+            peers = banned_peers;
+        }
+        let mut peers: Vec<_> = peers
+            .into_iter()
+            .map(|(peer, info)| {
+                let score = peer_reputation.score(&peer.peer_id());
+                (score, peer.connection_rtt(), peer, info)
             })
             .collect();
-        peers.sort_by(|(rtt_a, _, _), (rtt_b, _, _)| rtt_a.cmp(rtt_b));
+        peers.sort_by(|(score_a, rtt_a, _, _), (score_b, rtt_b, _, _)| {
+            score_b.total_cmp(score_a).then_with(|| rtt_a.cmp(rtt_b))
+        });
         Self {
             peers: peers
                 .into_iter()
-                .map(|(_, peer, info)| (peer, info))
+                .map(|(_, _, peer, info)| (peer, info))
                 .collect(),
             requested_checkpoint: None,
             request_type,
@@ -373,12 +395,16 @@ struct StateSyncEventLoop<S> {
 
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_reputation: PeerReputation,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     network: anemo::Network,
     metrics: Metrics,
 
     archive_readers: ArchiveReaderBalancer,
     sync_checkpoint_from_archive_task: Option<AbortHandle>,
+
+    checkpoint_ingestion_reader: Option<Arc<CheckpointIngestionReader>>,
+    sync_checkpoint_from_ingestion_store_task: Option<AbortHandle>,
 }
 
 impl<S> StateSyncEventLoop<S>
@@ -420,6 +446,7 @@ where
             self.network.clone(),
             self.store.clone(),
             self.peer_heights.clone(),
+            self.peer_reputation.clone(),
             self.weak_sender.clone(),
             self.checkpoint_event_sender.clone(),
             self.config.checkpoint_content_download_concurrency(),
@@ -446,6 +473,20 @@ where
         let task_handle = self.tasks.spawn(task);
         self.sync_checkpoint_from_archive_task = Some(task_handle);
 
+        // Start checkpoint content sync loop from the ingestion store, if one is configured.
+        if let Some(reader) = self.checkpoint_ingestion_reader.clone() {
+            let task = sync_checkpoint_contents_from_ingestion_store(
+                self.network.clone(),
+                reader,
+                self.store.clone(),
+                self.peer_heights.clone(),
+                self.config.checkpoint_ingestion_bootstrap_threshold(),
+                self.metrics.clone(),
+            );
+            let task_handle = self.tasks.spawn(task);
+            self.sync_checkpoint_from_ingestion_store_task = Some(task_handle);
+        }
+
         // Start main loop.
         loop {
             tokio::select! {
@@ -490,6 +531,10 @@ where
                     if matches!(&self.sync_checkpoint_from_archive_task, Some(t) if t.is_finished()) {
                         panic!("sync_checkpoint_from_archive task unexpectedly terminated")
                     }
+
+                    if matches!(&self.sync_checkpoint_from_ingestion_store_task, Some(t) if t.is_finished()) {
+                        panic!("sync_checkpoint_from_ingestion_store task unexpectedly terminated")
+                    }
                 },
             }
 
@@ -614,6 +659,7 @@ where
             }
             Ok(PeerEvent::LostPeer(peer_id, _)) => {
                 self.peer_heights.write().unwrap().peers.remove(&peer_id);
+                self.peer_reputation.forget_peer(&peer_id);
             }
 
             Err(RecvError::Closed) => {
@@ -647,11 +693,14 @@ where
         let task = query_peers_for_their_latest_checkpoint(
             self.network.clone(),
             self.peer_heights.clone(),
+            self.peer_reputation.clone(),
             self.weak_sender.clone(),
             self.config.timeout(),
         );
         self.tasks.spawn(task);
 
+        self.peer_reputation.report_metrics();
+
         if let Some(layer) = self.download_limit_layer.as_ref() {
             layer.maybe_prune_map();
         }
@@ -685,6 +734,7 @@ where
                 self.network.clone(),
                 self.store.clone(),
                 self.peer_heights.clone(),
+                self.peer_reputation.clone(),
                 self.metrics.clone(),
                 self.config.pinned_checkpoints.clone(),
                 self.config.checkpoint_header_download_concurrency(),
@@ -891,6 +941,7 @@ async fn query_peer_for_latest_info(
 async fn query_peers_for_their_latest_checkpoint(
     network: anemo::Network,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_reputation: PeerReputation,
     sender: mpsc::WeakSender<StateSyncMessage>,
     timeout: Duration,
 ) {
@@ -923,6 +974,16 @@ async fn query_peers_for_their_latest_checkpoint(
 
     let checkpoints = futures::future::join_all(futs).await.into_iter().flatten();
 
+    // Update how far behind each peer is, now that we have their latest heights.
+    {
+        let peer_heights = peer_heights.read().unwrap();
+        if let Some(highest) = peer_heights.highest_known_checkpoint_sequence_number() {
+            for (peer_id, info) in peer_heights.peers_on_same_chain() {
+                peer_reputation.record_height_lag(*peer_id, highest.saturating_sub(info.height));
+            }
+        }
+    }
+
     let highest_checkpoint = checkpoints.max_by_key(|checkpoint| *checkpoint.sequence_number());
 
     let our_highest_checkpoint = peer_heights
@@ -952,6 +1013,7 @@ async fn sync_to_checkpoint<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_reputation: PeerReputation,
     metrics: Metrics,
     pinned_checkpoints: Vec<(CheckpointSequenceNumber, CheckpointDigest)>,
     checkpoint_header_download_concurrency: usize,
@@ -977,6 +1039,7 @@ where
     let peer_balancer = PeerBalancer::new(
         &network,
         peer_heights.clone(),
+        &peer_reputation,
         PeerCheckpointRequestType::Summary,
     );
     // range of the next sequence_numbers to fetch
@@ -986,6 +1049,7 @@ where
             let peers = peer_balancer.clone().with_checkpoint(next);
             let peer_heights = peer_heights.clone();
             let pinned_checkpoints = &pinned_checkpoints;
+            let peer_reputation = &peer_reputation;
             async move {
                 if let Some(checkpoint) = peer_heights
                     .read()
@@ -998,15 +1062,20 @@ where
                 // Iterate through peers trying each one in turn until we're able to
                 // successfully get the target checkpoint
                 for mut peer in peers {
+                    let peer_id = peer.inner().peer_id();
                     let request = Request::new(GetCheckpointSummaryRequest::BySequenceNumber(next))
                         .with_timeout(timeout);
-                    if let Some(checkpoint) = peer
-                        .get_checkpoint_summary(request)
-                        .await
-                        .tap_err(|e| trace!("{e:?}"))
-                        .ok()
-                        .and_then(Response::into_inner)
-                        .tap_none(|| trace!("peer unable to help sync"))
+                    let now = tokio::time::Instant::now();
+                    let response = match peer.get_checkpoint_summary(request).await {
+                        Ok(response) => response.into_inner(),
+                        Err(e) => {
+                            trace!("{e:?}");
+                            peer_reputation.record_failure(peer_id);
+                            continue;
+                        }
+                    };
+                    if let Some(checkpoint) =
+                        response.tap_none(|| trace!("peer unable to help sync"))
                     {
                         // peer didn't give us a checkpoint with the height that we requested
                         if *checkpoint.sequence_number() != next {
@@ -1014,6 +1083,7 @@ where
                                 "peer returned checkpoint with wrong sequence number: expected {next}, got {}",
                                 checkpoint.sequence_number()
                             );
+                            peer_reputation.record_invalid_response(peer_id);
                             continue;
                         }
 
@@ -1029,16 +1099,18 @@ where
                                     pinned_checkpoints[pinned_digest_index].1,
                                     checkpoint_digest
                                 );
+                                peer_reputation.record_invalid_response(peer_id);
                                 continue;
                             }
                         }
+                        peer_reputation.record_success(peer_id, now.elapsed());
 
                         // Insert in our store in the event that things fail and we need to retry
                         peer_heights
                             .write()
                             .unwrap()
                             .insert_checkpoint(checkpoint.clone());
-                        return (Some(checkpoint), next, Some(peer.inner().peer_id()));
+                        return (Some(checkpoint), next, Some(peer_id));
                     }
                 }
                 (None, next, None)
@@ -1079,6 +1151,7 @@ where
                     // Mark peer as not on the same chain as us
                     if let Some(peer_id) = maybe_peer_id {
                         peer_heights.mark_peer_as_not_on_same_chain(peer_id);
+                        peer_reputation.record_invalid_response(peer_id);
                     }
 
                     return Err(anyhow::anyhow!(
@@ -1178,10 +1251,74 @@ async fn sync_checkpoint_contents_from_archive<S>(
     }
 }
 
+/// Syncs checkpoints from the ingestion store when peers no longer have the contents this node
+/// needs, or when this node is so far behind that downloading large ranges from the store in
+/// parallel is faster than syncing them one by one from peers.
+async fn sync_checkpoint_contents_from_ingestion_store<S>(
+    network: anemo::Network,
+    reader: Arc<CheckpointIngestionReader>,
+    store: S,
+    peer_heights: Arc<RwLock<PeerHeights>>,
+    bootstrap_threshold: u64,
+    metrics: Metrics,
+) where
+    S: WriteStore + Clone + Send + Sync + 'static,
+{
+    loop {
+        let (lowest_checkpoint_on_peers, highest_known_checkpoint) = {
+            let peer_heights = peer_heights.read().unwrap();
+            let lowest = peer_heights
+                .peers_on_same_chain()
+                // Filter out any peers who we aren't connected with.
+                .filter(|(peer_id, _info)| network.peer(**peer_id).is_some())
+                .map(|(_peer_id, info)| info.lowest)
+                .min();
+            (
+                lowest,
+                peer_heights.highest_known_checkpoint_sequence_number(),
+            )
+        };
+        let highest_synced = store
+            .get_highest_synced_checkpoint()
+            .expect("store operation should not fail")
+            .sequence_number;
+
+        let behind_lowest_on_peers =
+            matches!(lowest_checkpoint_on_peers, Some(lowest) if highest_synced < lowest);
+        let bootstrapping = matches!(
+            highest_known_checkpoint,
+            Some(highest) if highest.saturating_sub(highest_synced) > bootstrap_threshold
+        );
+        debug!("Syncing checkpoint contents from ingestion store: behind_lowest_on_peers: {behind_lowest_on_peers}, bootstrapping: {bootstrapping}, highest_synced: {highest_synced}");
+        if behind_lowest_on_peers || bootstrapping {
+            let start = highest_synced
+                .checked_add(1)
+                .expect("Checkpoint seq num overflow");
+            let end = lowest_checkpoint_on_peers
+                .into_iter()
+                .chain(highest_known_checkpoint.map(|highest| highest.saturating_add(1)))
+                .max()
+                .unwrap_or(start);
+            let synced = reader.sync(&store, start..end).await;
+            metrics.inc_checkpoints_synced_from_ingestion_store(synced);
+            info!(
+                "Synced {synced} checkpoints from ingestion store {} in range {start}..{end}",
+                reader.remote_store_identifier()
+            );
+            if synced > 0 {
+                // Continue right away with the next range.
+                continue;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
 async fn sync_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_reputation: PeerReputation,
     sender: mpsc::WeakSender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     checkpoint_content_download_concurrency: usize,
@@ -1245,6 +1382,7 @@ async fn sync_checkpoint_contents<S>(
                             network.clone(),
                             &store,
                             peer_heights.clone(),
+                            peer_reputation.clone(),
                             timeout,
                             checkpoint,
                         ));
@@ -1277,6 +1415,7 @@ async fn sync_checkpoint_contents<S>(
                 network.clone(),
                 &store,
                 peer_heights.clone(),
+                peer_reputation.clone(),
                 timeout,
                 next_checkpoint,
             ));
@@ -1299,6 +1438,7 @@ async fn sync_one_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_reputation: PeerReputation,
     timeout: Duration,
     checkpoint: VerifiedCheckpoint,
 ) -> Result<VerifiedCheckpoint, VerifiedCheckpoint>
@@ -1323,11 +1463,13 @@ where
     let peers = PeerBalancer::new(
         &network,
         peer_heights.clone(),
+        &peer_reputation,
         PeerCheckpointRequestType::Content,
     )
     .with_checkpoint(*checkpoint.sequence_number());
     let now = tokio::time::Instant::now();
-    let Some(_contents) = get_full_checkpoint_contents(peers, &store, &checkpoint, timeout).await
+    let Some(_contents) =
+        get_full_checkpoint_contents(peers, &peer_reputation, &store, &checkpoint, timeout).await
     else {
         // Delay completion in case of error so we don't hammer the network with retries.
         let duration = peer_heights
@@ -1348,6 +1490,7 @@ where
 #[instrument(level = "debug", skip_all)]
 async fn get_full_checkpoint_contents<S>(
     peers: PeerBalancer,
+    peer_reputation: &PeerReputation,
     store: S,
     checkpoint: &VerifiedCheckpoint,
     timeout: Duration,
@@ -1367,27 +1510,28 @@ where
     // Iterate through our selected peers trying each one in turn until we're able to
     // successfully get the target checkpoint
     for mut peer in peers {
-        debug!(
-            ?timeout,
-            "requesting checkpoint contents from {}",
-            peer.inner().peer_id(),
-        );
+        let peer_id = peer.inner().peer_id();
+        debug!(?timeout, "requesting checkpoint contents from {peer_id}");
         let request = Request::new(digest).with_timeout(timeout);
-        if let Some(contents) = peer
-            .get_checkpoint_contents(request)
-            .await
-            .tap_err(|e| trace!("{e:?}"))
-            .ok()
-            .and_then(Response::into_inner)
-            .tap_none(|| trace!("peer unable to help sync"))
-        {
+        let now = tokio::time::Instant::now();
+        let response = match peer.get_checkpoint_contents(request).await {
+            Ok(response) => response.into_inner(),
+            Err(e) => {
+                trace!("{e:?}");
+                peer_reputation.record_failure(peer_id);
+                continue;
+            }
+        };
+        if let Some(contents) = response.tap_none(|| trace!("peer unable to help sync")) {
             if contents.verify_digests(digest).is_ok() {
+                peer_reputation.record_success(peer_id, now.elapsed());
                 let verified_contents = VerifiedCheckpointContents::new_unchecked(contents.clone());
                 store
                     .insert_checkpoint_contents(checkpoint, verified_contents)
                     .expect("store operation should not fail");
                 return Some(contents);
             }
+            peer_reputation.record_invalid_response(peer_id);
         }
     }
     debug!("no peers had checkpoint contents");
@@ -3,8 +3,8 @@
 
 use crate::{
     state_sync::{
-        Builder, GetCheckpointSummaryRequest, PeerStateSyncInfo, StateSync, StateSyncMessage,
-        UnstartedStateSync,
+        ingestion::CheckpointIngestionReader, Builder, GetCheckpointSummaryRequest,
+        PeerStateSyncInfo, StateSync, StateSyncMessage, UnstartedStateSync,
     },
     utils::build_network,
 };
@@ -12,15 +12,19 @@ use anemo::{PeerId, Request};
 use anyhow::anyhow;
 use prometheus::Registry;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::{collections::HashMap, time::Duration};
 use sui_archival::reader::ArchiveReaderBalancer;
 use sui_archival::writer::ArchiveWriter;
 use sui_config::node::ArchiveReaderConfig;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use sui_config::p2p::StateSyncConfig;
+use sui_storage::blob::{Blob, BlobEncoding};
 use sui_storage::{FileCompression, StorageFormat};
 use sui_swarm_config::test_utils::{empty_contents, CommitteeFixture};
 use sui_types::{
-    messages_checkpoint::CheckpointDigest,
+    full_checkpoint_content::CheckpointData,
+    messages_checkpoint::{CheckpointDigest, VerifiedCheckpoint},
     storage::{ReadStore, SharedInMemoryStore, WriteStore},
 };
 use tempfile::tempdir;
@@ -446,6 +450,197 @@ async fn test_state_sync_using_archive() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Writes `checkpoints`, which have no transactions, to a local directory in the format of the
+/// checkpoint ingestion store, and returns the config of the store.
+fn write_checkpoint_ingestion_store(
+    path: &Path,
+    checkpoints: &[VerifiedCheckpoint],
+) -> anyhow::Result<ObjectStoreConfig> {
+    for checkpoint in checkpoints {
+        let checkpoint_data = CheckpointData {
+            checkpoint_summary: checkpoint.inner().to_owned(),
+            checkpoint_contents: empty_contents().into_checkpoint_contents(),
+            transactions: vec![],
+        };
+        std::fs::write(
+            path.join(format!("{}.chk", checkpoint.sequence_number)),
+            Blob::encode(&checkpoint_data, BlobEncoding::Bcs)?.to_bytes(),
+        )?;
+    }
+    Ok(ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(path.to_path_buf()),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn sync_from_checkpoint_ingestion_store() -> anyhow::Result<()> {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, _, _, _) = committee.make_empty_checkpoints(20, None);
+    // The ingestion store only has the first 10 checkpoints
+    let temp_dir = tempdir()?;
+    let store_config =
+        write_checkpoint_ingestion_store(temp_dir.path(), &ordered_checkpoints[0..10])?;
+    let reader = CheckpointIngestionReader::new(&store_config, 4)?;
+
+    let store = SharedInMemoryStore::default();
+    store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+
+    // Syncing stops at the first checkpoint missing from the ingestion store
+    assert_eq!(reader.sync(&store, 1..20).await, 9);
+    for checkpoint in &ordered_checkpoints[1..10] {
+        assert_eq!(
+            store.get_checkpoint_by_sequence_number(checkpoint.sequence_number),
+            Some(checkpoint.clone())
+        );
+        assert!(store
+            .get_full_checkpoint_contents_by_sequence_number(checkpoint.sequence_number)
+            .is_some());
+    }
+    {
+        let store = store.inner();
+        assert_eq!(
+            store.get_highest_verified_checkpoint(),
+            Some(&ordered_checkpoints[9])
+        );
+        assert_eq!(
+            store.get_highest_synced_checkpoint(),
+            Some(&ordered_checkpoints[9])
+        );
+    }
+
+    // Checkpoints after a missing one are not synced either
+    assert_eq!(reader.sync(&store, 10..20).await, 0);
+    assert_eq!(
+        store.inner().get_highest_synced_checkpoint(),
+        Some(&ordered_checkpoints[9])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_state_sync_using_checkpoint_ingestion_store() -> anyhow::Result<()> {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    // build mock data
+    let (ordered_checkpoints, _, sequence_number_to_digest, checkpoints) =
+        committee.make_empty_checkpoints(100, None);
+    // Only the first 50 checkpoints are in the ingestion store, so the node has to fall back to
+    // its peers for the latest checkpoints, and to the ingestion store for the older ones its
+    // peers have pruned
+    let temp_dir = tempdir()?;
+    let store_config =
+        write_checkpoint_ingestion_store(temp_dir.path(), &ordered_checkpoints[0..50])?;
+    // We will delete all checkpoints older than this checkpoint on Node 2
+    let oldest_checkpoint_to_keep: u64 = 10;
+
+    // Build and connect two nodes where Node 1 will be given access to the ingestion store
+    let config = StateSyncConfig {
+        checkpoint_ingestion_store: Some(store_config),
+        ..Default::default()
+    };
+    let (builder, server) = Builder::new()
+        .store(SharedInMemoryStore::default())
+        .config(config)
+        .build();
+    let network_1 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_1, _handle_1) = builder.build(network_1.clone());
+    let (builder, server) = Builder::new().store(SharedInMemoryStore::default()).build();
+    let network_2 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_2, _handle_2) = builder.build(network_2.clone());
+    network_1.connect(network_2.local_addr()).await.unwrap();
+
+    // Init the root committee in both nodes
+    event_loop_1.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+    event_loop_2.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+
+    // Node 2 has all the data, except for the contents of the first 10 checkpoints
+    {
+        let mut store = event_loop_2.store.inner_mut();
+        for checkpoint in ordered_checkpoints.clone() {
+            store.insert_checkpoint(&checkpoint);
+            store.insert_checkpoint_contents(&checkpoint, empty_contents());
+            store.update_highest_synced_checkpoint(&checkpoint);
+        }
+        for checkpoint in &ordered_checkpoints[0..(oldest_checkpoint_to_keep as usize)] {
+            store.delete_checkpoint_content_test_only(checkpoint.sequence_number)?;
+        }
+        assert_eq!(
+            store.get_lowest_available_checkpoint(),
+            oldest_checkpoint_to_keep
+        );
+    }
+
+    // Node 1 will know that Node 2 has the data starting checkpoint 10
+    event_loop_1.peer_heights.write().unwrap().peers.insert(
+        network_2.peer_id(),
+        PeerStateSyncInfo {
+            genesis_checkpoint_digest: *ordered_checkpoints[0].digest(),
+            on_same_chain_as_us: true,
+            height: *ordered_checkpoints.last().unwrap().sequence_number(),
+            lowest: oldest_checkpoint_to_keep,
+        },
+    );
+
+    // Get handle to node 1 store
+    let store_1 = event_loop_1.store.clone();
+
+    // Start both event loops
+    tokio::spawn(event_loop_1.start());
+    tokio::spawn(event_loop_2.start());
+
+    let total_time = Instant::now();
+    loop {
+        {
+            let store = store_1.inner();
+            if let Some(highest_synced_checkpoint) = store.get_highest_synced_checkpoint() {
+                if highest_synced_checkpoint.sequence_number
+                    == ordered_checkpoints.last().unwrap().sequence_number
+                {
+                    // Node 1 is fully synced to the latest checkpoint on Node 2
+                    let expected = checkpoints
+                        .iter()
+                        .map(|(key, value)| (key, value.data()))
+                        .collect::<HashMap<_, _>>();
+                    let actual = store
+                        .checkpoints()
+                        .iter()
+                        .map(|(key, value)| (key, value.data()))
+                        .collect::<HashMap<_, _>>();
+                    assert_eq!(actual, expected);
+                    assert_eq!(
+                        store.checkpoint_sequence_number_to_digest(),
+                        &sequence_number_to_digest
+                    );
+                    break;
+                }
+            }
+        }
+        if total_time.elapsed() > Duration::from_secs(120) {
+            return Err(anyhow!("Test timed out"));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    for checkpoint in &ordered_checkpoints[1..] {
+        assert!(store_1
+            .get_full_checkpoint_contents_by_sequence_number(checkpoint.sequence_number)
+            .is_some());
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn sync_with_checkpoints_being_inserted() {
     telemetry_subscribers::init_for_testing();
@@ -16,9 +16,11 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     str::FromStr,
 };
+use sui_core::authority::authority_store_pruner::PruningConfigUpdate;
 use sui_types::{
     base_types::AuthorityName,
     crypto::{RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
+    digests::TransactionDigest,
     error::SuiError,
 };
 use telemetry_subscribers::TracingHandle;
@@ -68,6 +70,47 @@ use tracing::info;
 // Inject a full signature from another node, bypassing validity checks.
 //
 //  $ curl 'http://127.0.0.1:1337/randomness-inject-full-sig?round=123&sigs=base64encodedsig'
+//
+// View pruner progress, watermarks and the live pruning config.
+//
+//  $ curl 'http://127.0.0.1:1337/pruner-status'
+//
+// Change pruning retention and batch sizes without restarting the node. All params are optional.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/pruner-config?num_epochs_to_retain_for_checkpoints=2&max_checkpoints_in_batch=50'
+//
+// Pause and resume pruning.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/pause-pruning'
+//  $ curl -X POST 'http://127.0.0.1:1337/resume-pruning'
+//
+// Trigger a manual compaction of a comma separated list of column families.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/compact?column_families=objects,effects'
+//
+// View the reputation scores and bans of the peers state sync downloads checkpoints from.
+//
+//  $ curl 'http://127.0.0.1:1337/peer-scores'
+//
+// View the latency breakdown of a traced transaction, or of the 20 most recently traced ones.
+//
+//  $ curl 'http://127.0.0.1:1337/transaction-latency?digest=base58encodeddigest'
+//  $ curl 'http://127.0.0.1:1337/transaction-latency?limit=20'
+//
+// Trace 1% of transactions through the validator pipeline. Set to 0 to stop tracing.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/transaction-latency-sampling?sample_rate=0.01'
+//
+// View the objects accessed most often through the execution cache and the object store, with
+// their ownership and the packages of the transactions writing them.
+//
+//  $ curl 'http://127.0.0.1:1337/hot-objects'
+//  $ curl 'http://127.0.0.1:1337/hot-objects?limit=20'
+//
+// Sample 10% of object accesses, or clear the hot object counts.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/object-access-sampling?sample_rate=0.1'
+//  $ curl -X POST 'http://127.0.0.1:1337/reset-hot-objects'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -80,6 +123,17 @@ const NODE_CONFIG: &str = "/node-config";
 const RANDOMNESS_PARTIAL_SIGS_ROUTE: &str = "/randomness-partial-sigs";
 const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-sigs";
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
+const PRUNER_STATUS_ROUTE: &str = "/pruner-status";
+const PRUNER_CONFIG_ROUTE: &str = "/pruner-config";
+const PAUSE_PRUNING_ROUTE: &str = "/pause-pruning";
+const RESUME_PRUNING_ROUTE: &str = "/resume-pruning";
+const COMPACT_ROUTE: &str = "/compact";
+const PEER_SCORES_ROUTE: &str = "/peer-scores";
+const TRANSACTION_LATENCY_ROUTE: &str = "/transaction-latency";
+const TRANSACTION_LATENCY_SAMPLING_ROUTE: &str = "/transaction-latency-sampling";
+const HOT_OBJECTS_ROUTE: &str = "/hot-objects";
+const OBJECT_ACCESS_SAMPLING_ROUTE: &str = "/object-access-sampling";
+const RESET_HOT_OBJECTS_ROUTE: &str = "/reset-hot-objects";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -119,6 +173,23 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
             RANDOMNESS_INJECT_FULL_SIG_ROUTE,
             post(randomness_inject_full_sig),
         )
+        .route(PRUNER_STATUS_ROUTE, get(pruner_status))
+        .route(PRUNER_CONFIG_ROUTE, post(update_pruner_config))
+        .route(PAUSE_PRUNING_ROUTE, post(pause_pruning))
+        .route(RESUME_PRUNING_ROUTE, post(resume_pruning))
+        .route(COMPACT_ROUTE, post(compact))
+        .route(PEER_SCORES_ROUTE, get(peer_scores))
+        .route(TRANSACTION_LATENCY_ROUTE, get(transaction_latency))
+        .route(
+            TRANSACTION_LATENCY_SAMPLING_ROUTE,
+            post(set_transaction_latency_sampling),
+        )
+        .route(HOT_OBJECTS_ROUTE, get(hot_objects))
+        .route(
+            OBJECT_ACCESS_SAMPLING_ROUTE,
+            post(set_object_access_sampling),
+        )
+        .route(RESET_HOT_OBJECTS_ROUTE, post(reset_hot_objects))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -443,3 +514,231 @@ async fn randomness_inject_full_sig(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
     }
 }
+
+async fn pruner_status(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.node.state().pruner_handle().status() {
+        Ok(status) => (StatusCode::OK, format!("{:#?}\n", status)),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct PrunerConfig {
+    num_epochs_to_retain: Option<u64>,
+    num_epochs_to_retain_for_checkpoints: Option<u64>,
+    max_checkpoints_in_batch: Option<usize>,
+    max_transactions_in_batch: Option<usize>,
+    periodic_compaction_threshold_days: Option<usize>,
+    smooth: Option<bool>,
+}
+
+async fn update_pruner_config(
+    State(state): State<Arc<AppState>>,
+    args: Query<PrunerConfig>,
+) -> (StatusCode, String) {
+    let Query(PrunerConfig {
+        num_epochs_to_retain,
+        num_epochs_to_retain_for_checkpoints,
+        max_checkpoints_in_batch,
+        max_transactions_in_batch,
+        periodic_compaction_threshold_days,
+        smooth,
+    }) = args;
+
+    let update = PruningConfigUpdate {
+        num_epochs_to_retain,
+        num_epochs_to_retain_for_checkpoints,
+        max_checkpoints_in_batch,
+        max_transactions_in_batch,
+        periodic_compaction_threshold_days,
+        smooth,
+    };
+    match state.node.state().pruner_handle().update_config(update) {
+        Ok(config) => (
+            StatusCode::OK,
+            format!("pruning config updated to {:#?}\n", config),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn pause_pruning(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    state.node.state().pruner_handle().pause();
+    (StatusCode::OK, "pruning paused\n".to_string())
+}
+
+async fn resume_pruning(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    state.node.state().pruner_handle().resume();
+    (StatusCode::OK, "pruning resumed\n".to_string())
+}
+
+#[derive(Deserialize)]
+struct Compact {
+    column_families: String,
+}
+
+async fn compact(State(state): State<Arc<AppState>>, args: Query<Compact>) -> (StatusCode, String) {
+    let Query(Compact { column_families }) = args;
+
+    let column_families: Vec<String> = column_families
+        .split(',')
+        .map(|cf| cf.trim().to_string())
+        .filter(|cf| !cf.is_empty())
+        .collect();
+    if column_families.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "no column families specified\n".to_string(),
+        );
+    }
+
+    match state
+        .node
+        .state()
+        .pruner_handle()
+        .compact(column_families.clone())
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            format!("compacted column families {:?}\n", column_families),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn peer_scores(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let scores = state.node.state_sync_handle().peer_scores();
+    (StatusCode::OK, format!("{:#?}\n", scores))
+}
+
+#[derive(Deserialize)]
+struct TransactionLatency {
+    digest: Option<String>,
+    limit: Option<usize>,
+}
+
+async fn transaction_latency(
+    State(state): State<Arc<AppState>>,
+    args: Query<TransactionLatency>,
+) -> (StatusCode, String) {
+    let Query(TransactionLatency { digest, limit }) = args;
+    let node_state = state.node.state();
+    let tracer = node_state.transaction_latency_tracer();
+
+    let Some(digest) = digest else {
+        let breakdowns = tracer.recent_latency_breakdowns(limit.unwrap_or(20));
+        return (
+            StatusCode::OK,
+            format!(
+                "sample rate {}, {} transactions\n{}",
+                tracer.sample_rate(),
+                breakdowns.len(),
+                breakdowns
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<String>()
+            ),
+        );
+    };
+
+    let digest = match TransactionDigest::from_str(&digest) {
+        Ok(digest) => digest,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    match tracer.latency_breakdown(&digest) {
+        Some(breakdown) => (StatusCode::OK, breakdown.to_string()),
+        None => (
+            StatusCode::NOT_FOUND,
+            format!("transaction {digest} was not traced\n"),
+        ),
+    }
+}
+
+#[derive(Deserialize)]
+struct TransactionLatencySampling {
+    sample_rate: f64,
+}
+
+async fn set_transaction_latency_sampling(
+    State(state): State<Arc<AppState>>,
+    args: Query<TransactionLatencySampling>,
+) -> (StatusCode, String) {
+    let Query(TransactionLatencySampling { sample_rate }) = args;
+    if !(0.0..=1.0).contains(&sample_rate) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "sample_rate must be between 0 and 1\n".to_string(),
+        );
+    }
+
+    state
+        .node
+        .state()
+        .transaction_latency_tracer()
+        .set_sample_rate(sample_rate);
+    (
+        StatusCode::OK,
+        format!("transaction latency sample rate set to {sample_rate}\n"),
+    )
+}
+
+#[derive(Deserialize)]
+struct HotObjects {
+    limit: Option<usize>,
+}
+
+async fn hot_objects(
+    State(state): State<Arc<AppState>>,
+    args: Query<HotObjects>,
+) -> (StatusCode, String) {
+    let Query(HotObjects { limit }) = args;
+    let node_state = state.node.state();
+    let stats = node_state.object_access_stats();
+    let hot_objects = stats.hot_objects(limit.unwrap_or(stats.report_size()));
+    (
+        StatusCode::OK,
+        format!(
+            "sample rate {}, {} objects\n{}",
+            stats.sample_rate(),
+            hot_objects.len(),
+            hot_objects
+                .iter()
+                .map(ToString::to_string)
+                .collect::<String>()
+        ),
+    )
+}
+
+#[derive(Deserialize)]
+struct ObjectAccessSampling {
+    sample_rate: f64,
+}
+
+async fn set_object_access_sampling(
+    State(state): State<Arc<AppState>>,
+    args: Query<ObjectAccessSampling>,
+) -> (StatusCode, String) {
+    let Query(ObjectAccessSampling { sample_rate }) = args;
+    if !(0.0..=1.0).contains(&sample_rate) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "sample_rate must be between 0 and 1\n".to_string(),
+        );
+    }
+
+    state
+        .node
+        .state()
+        .object_access_stats()
+        .set_sample_rate(sample_rate);
+    (
+        StatusCode::OK,
+        format!("object access sample rate set to {sample_rate}\n"),
+    )
+}
+
+async fn reset_hot_objects(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    state.node.state().object_access_stats().reset();
+    (StatusCode::OK, "hot object counts cleared\n".to_string())
+}
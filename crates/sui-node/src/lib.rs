@@ -124,6 +124,7 @@ use sui_macros::{fail_point_async, replay_log};
 use sui_network::api::ValidatorServer;
 use sui_network::discovery;
 use sui_network::discovery::TrustedPeerChangeEvent;
+use sui_network::peer_reputation::PeerReputation;
 use sui_network::state_sync;
 use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_snapshot::uploader::StateSnapshotUploader;
@@ -1089,15 +1090,26 @@ impl SuiNode {
         randomness_tx: mpsc::Sender<(EpochId, RandomnessRound, Vec<u8>)>,
         prometheus_registry: &Registry,
     ) -> Result<P2pComponents> {
+        let peer_reputation = PeerReputation::new(
+            config
+                .p2p_config
+                .peer_reputation
+                .clone()
+                .unwrap_or_default(),
+        )
+        .with_metrics(prometheus_registry);
+
         let (state_sync, state_sync_server) = state_sync::Builder::new()
             .config(config.p2p_config.state_sync.clone().unwrap_or_default())
             .store(state_sync_store)
             .archive_readers(archive_readers)
+            .peer_reputation(peer_reputation.clone())
             .with_metrics(prometheus_registry)
             .build();
 
         let (discovery, discovery_server) = discovery::Builder::new(trusted_peer_change_rx)
             .config(config.p2p_config.clone())
+            .peer_reputation(peer_reputation)
             .build();
 
         let discovery_config = config.p2p_config.discovery.clone().unwrap_or_default();
@@ -1262,6 +1274,8 @@ impl SuiNode {
             client.clone(),
             checkpoint_store.clone(),
         ));
+        consensus_adapter
+            .set_transaction_latency_tracer(state.transaction_latency_tracer().clone());
         let consensus_manager =
             ConsensusManager::new(&config, consensus_config, registry_service, client);
 
@@ -1648,6 +1662,10 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn state_sync_handle(&self) -> state_sync::Handle {
+        self.state_sync_handle.clone()
+    }
+
     // Only used for testing because of how epoch store is loaded.
     pub fn reference_gas_price_for_testing(&self) -> Result<u64, anyhow::Error> {
         self.state.reference_gas_price_for_testing()
@@ -11,6 +11,7 @@ use tracing::{error, info};
 
 use mysten_common::sync::async_once_cell::AsyncOnceCell;
 use sui_config::node::RunWithRange;
+use sui_config::node_config_validation::Severity;
 use sui_config::{Config, NodeConfig};
 use sui_core::runtime::SuiRuntimes;
 use sui_node::metrics;
@@ -40,6 +41,12 @@ struct Args {
 
     #[clap(long, group = "exclusive")]
     run_with_range_checkpoint: Option<CheckpointSequenceNumber>,
+
+    #[clap(
+        long,
+        help = "Check the config for semantic problems and exit without starting the node"
+    )]
+    validate_config: bool,
 }
 
 fn main() {
@@ -52,6 +59,17 @@ fn main() {
     // ProtocolConfig::poison_get_for_min_version();
     let args = Args::parse();
     let mut config = NodeConfig::load(&args.config_path).unwrap();
+    if args.validate_config {
+        let issues = config.validate();
+        for issue in &issues {
+            println!("{issue}");
+        }
+        if issues.iter().any(|issue| issue.severity == Severity::Error) {
+            std::process::exit(1);
+        }
+        println!("{} is valid", args.config_path.display());
+        return;
+    }
     assert!(
         config.supported_protocol_versions.is_none(),
         "supported_protocol_versions cannot be read from the config file"
@@ -36,7 +36,10 @@ use sui_json_rpc_types::{
     SuiTransactionBlockResponseQuery, TransactionBlockBytes, TransactionBlocksPage,
     TransactionFilter, TransferObjectParams,
 };
-use sui_json_rpc_types::{SuiTypeTag, ValidatorApy, ValidatorApys};
+use sui_json_rpc_types::{
+    CongestionCancellation, CongestionInfo, ObjectCongestionInfo, SuiTypeTag, ValidatorApy,
+    ValidatorApys,
+};
 use sui_open_rpc::ExamplePairing;
 use sui_protocol_config::Chain;
 use sui_protocol_config::ProtocolConfig;
@@ -137,6 +140,7 @@ impl RpcExampleProvider {
             self.suix_resolve_name_service_address(),
             self.suix_resolve_name_service_names(),
             self.sui_try_multi_get_past_objects(),
+            self.suix_get_congestion_info(),
         ]
         .into_iter()
         .map(|example| (example.function_name, example.examples))
@@ -1558,4 +1562,51 @@ impl RpcExampleProvider {
             )],
         )
     }
+
+    fn suix_get_congestion_info(&mut self) -> Examples {
+        let pool = ObjectID::from_str(
+            "0xf948981b806057580f91622417534f491da5f61aeaf33d0ed8e69fd5691c95ce",
+        )
+        .unwrap();
+        let other = ObjectID::from_str(
+            "0x4405b50d791fd3346754e8171aaab6bc2ed26c2c46efdd033c14b30ae507ac33",
+        )
+        .unwrap();
+        let result = CongestionInfo {
+            objects: vec![
+                ObjectCongestionInfo {
+                    object_id: pool,
+                    congested: true,
+                    last_cancellation_timestamp_ms: Some(1734537600000),
+                    highest_cancelled_gas_price: Some(1500),
+                    last_success_timestamp_ms: Some(1734537600000),
+                    lowest_executed_gas_price: Some(1600),
+                    suggested_gas_price: Some(1600),
+                    recent_cancellations: vec![
+                        CongestionCancellation {
+                            timestamp_ms: 1734537599750,
+                            cancelled_transactions: 3,
+                            highest_cancelled_gas_price: 1200,
+                        },
+                        CongestionCancellation {
+                            timestamp_ms: 1734537600000,
+                            cancelled_transactions: 5,
+                            highest_cancelled_gas_price: 1500,
+                        },
+                    ],
+                },
+                ObjectCongestionInfo::uncongested(other),
+            ],
+            suggested_gas_price: 1600,
+            reference_gas_price: 750,
+        };
+        Examples::new(
+            "suix_getCongestionInfo",
+            vec![ExamplePairing::new(
+                "Gets the congestion status of shared objects and the gas price to bid for a transaction mutating them.",
+                vec![("object_ids", json!(vec![pool, other]))],
+                json!(result),
+            )],
+        )
+    }
 }
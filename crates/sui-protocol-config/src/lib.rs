@@ -18,7 +18,7 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 79;
+const MAX_PROTOCOL_VERSION: u64 = 80;
 
 // Record history of protocol version allocations here:
 //
@@ -227,6 +227,8 @@ const MAX_PROTOCOL_VERSION: u64 = 79;
 // Version 78: Make `TxContext` Move API native
 //             Enable execution time estimate mode for congestion control on testnet.
 // Version 79: Enable median based commit timestamp in consensus on testnet.
+// Version 80: Add BN254 group ops native functions in devnet.
+//             Add BIP-340 Schnorr signature verification native function in devnet.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -653,6 +655,18 @@ struct FeatureFlags {
     // weighted by stake median timestamp of the leader's ancestors.
     #[serde(skip_serializing_if = "is_false")]
     consensus_median_based_commit_timestamp: bool,
+
+    // Enable BN254 group ops native functions.
+    #[serde(skip_serializing_if = "is_false")]
+    enable_bn254_group_ops: bool,
+
+    // Enable BIP-340 Schnorr signature verification native function.
+    #[serde(skip_serializing_if = "is_false")]
+    enable_schnorr_secp256k1: bool,
+
+    // Strategy used by consensus to score authorities and build the leader schedule.
+    #[serde(skip_serializing_if = "ConsensusLeaderScoringStrategy::is_distributed_votes")]
+    consensus_leader_scoring_strategy: ConsensusLeaderScoringStrategy,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -728,6 +742,29 @@ impl ConsensusChoice {
     }
 }
 
+// Strategies used by consensus to score authorities for leader schedule changes.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum ConsensusLeaderScoringStrategy {
+    /// An authority scores the stake of all the blocks that include its votes for leaders.
+    #[default]
+    DistributedVotes,
+    /// An authority scores one point for each of its votes for leaders that is included by a
+    /// quorum of blocks.
+    CertifiedVotes,
+    /// Like `DistributedVotes`, but each vote is discounted by how long after the leader it was
+    /// proposed.
+    LatencyWeighted,
+    /// Leaders are elected in a deterministic stake-weighted round robin, without swapping low
+    /// scoring authorities out of the schedule. Scores are still computed from distributed votes.
+    StakeWeightedRoundRobin,
+}
+
+impl ConsensusLeaderScoringStrategy {
+    pub fn is_distributed_votes(&self) -> bool {
+        matches!(self, ConsensusLeaderScoringStrategy::DistributedVotes)
+    }
+}
+
 // Configuration options for consensus network.
 #[derive(Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ConsensusNetwork {
@@ -1271,6 +1308,34 @@ pub struct ProtocolConfig {
     group_ops_bls12381_uncompressed_g1_sum_base_cost: Option<u64>,
     group_ops_bls12381_uncompressed_g1_sum_cost_per_term: Option<u64>,
     group_ops_bls12381_uncompressed_g1_sum_max_terms: Option<u64>,
+    group_ops_bn254_decode_scalar_cost: Option<u64>,
+    group_ops_bn254_decode_g1_cost: Option<u64>,
+    group_ops_bn254_decode_g2_cost: Option<u64>,
+    group_ops_bn254_decode_gt_cost: Option<u64>,
+    group_ops_bn254_scalar_add_cost: Option<u64>,
+    group_ops_bn254_g1_add_cost: Option<u64>,
+    group_ops_bn254_g2_add_cost: Option<u64>,
+    group_ops_bn254_gt_add_cost: Option<u64>,
+    group_ops_bn254_scalar_sub_cost: Option<u64>,
+    group_ops_bn254_g1_sub_cost: Option<u64>,
+    group_ops_bn254_g2_sub_cost: Option<u64>,
+    group_ops_bn254_gt_sub_cost: Option<u64>,
+    group_ops_bn254_scalar_mul_cost: Option<u64>,
+    group_ops_bn254_g1_mul_cost: Option<u64>,
+    group_ops_bn254_g2_mul_cost: Option<u64>,
+    group_ops_bn254_gt_mul_cost: Option<u64>,
+    group_ops_bn254_scalar_div_cost: Option<u64>,
+    group_ops_bn254_g1_div_cost: Option<u64>,
+    group_ops_bn254_g2_div_cost: Option<u64>,
+    group_ops_bn254_gt_div_cost: Option<u64>,
+    group_ops_bn254_g1_hash_to_base_cost: Option<u64>,
+    group_ops_bn254_g1_hash_to_cost_per_byte: Option<u64>,
+    group_ops_bn254_g1_msm_base_cost: Option<u64>,
+    group_ops_bn254_g2_msm_base_cost: Option<u64>,
+    group_ops_bn254_g1_msm_base_cost_per_input: Option<u64>,
+    group_ops_bn254_g2_msm_base_cost_per_input: Option<u64>,
+    group_ops_bn254_msm_max_len: Option<u32>,
+    group_ops_bn254_pairing_cost: Option<u64>,
 
     // hmac::hmac_sha3_256
     hmac_hmac_sha3_256_cost_base: Option<u64>,
@@ -1291,6 +1356,9 @@ pub struct ProtocolConfig {
     nitro_attestation_verify_base_cost: Option<u64>,
     nitro_attestation_verify_cost_per_cert: Option<u64>,
 
+    // schnorr_secp256k1::verify
+    schnorr_secp256k1_verify_cost_base: Option<u64>,
+
     // Stdlib costs
     bcs_per_byte_serialized_cost: Option<u64>,
     bcs_legacy_min_output_size_cost: Option<u64>,
@@ -1866,6 +1934,18 @@ impl ProtocolConfig {
         res
     }
 
+    pub fn consensus_leader_scoring_strategy(&self) -> ConsensusLeaderScoringStrategy {
+        self.feature_flags.consensus_leader_scoring_strategy
+    }
+
+    pub fn enable_bn254_group_ops(&self) -> bool {
+        self.feature_flags.enable_bn254_group_ops
+    }
+
+    pub fn enable_schnorr_secp256k1(&self) -> bool {
+        self.feature_flags.enable_schnorr_secp256k1
+    }
+
     pub fn convert_type_argument_error(&self) -> bool {
         self.feature_flags.convert_type_argument_error
     }
@@ -2338,6 +2418,34 @@ impl ProtocolConfig {
             group_ops_bls12381_uncompressed_g1_sum_base_cost: None,
             group_ops_bls12381_uncompressed_g1_sum_cost_per_term: None,
             group_ops_bls12381_uncompressed_g1_sum_max_terms: None,
+            group_ops_bn254_decode_scalar_cost: None,
+            group_ops_bn254_decode_g1_cost: None,
+            group_ops_bn254_decode_g2_cost: None,
+            group_ops_bn254_decode_gt_cost: None,
+            group_ops_bn254_scalar_add_cost: None,
+            group_ops_bn254_g1_add_cost: None,
+            group_ops_bn254_g2_add_cost: None,
+            group_ops_bn254_gt_add_cost: None,
+            group_ops_bn254_scalar_sub_cost: None,
+            group_ops_bn254_g1_sub_cost: None,
+            group_ops_bn254_g2_sub_cost: None,
+            group_ops_bn254_gt_sub_cost: None,
+            group_ops_bn254_scalar_mul_cost: None,
+            group_ops_bn254_g1_mul_cost: None,
+            group_ops_bn254_g2_mul_cost: None,
+            group_ops_bn254_gt_mul_cost: None,
+            group_ops_bn254_scalar_div_cost: None,
+            group_ops_bn254_g1_div_cost: None,
+            group_ops_bn254_g2_div_cost: None,
+            group_ops_bn254_gt_div_cost: None,
+            group_ops_bn254_g1_hash_to_base_cost: None,
+            group_ops_bn254_g1_hash_to_cost_per_byte: None,
+            group_ops_bn254_g1_msm_base_cost: None,
+            group_ops_bn254_g2_msm_base_cost: None,
+            group_ops_bn254_g1_msm_base_cost_per_input: None,
+            group_ops_bn254_g2_msm_base_cost_per_input: None,
+            group_ops_bn254_msm_max_len: None,
+            group_ops_bn254_pairing_cost: None,
 
             // zklogin::check_zklogin_id
             check_zklogin_id_cost_base: None,
@@ -2353,6 +2461,9 @@ impl ProtocolConfig {
             nitro_attestation_verify_base_cost: None,
             nitro_attestation_verify_cost_per_cert: None,
 
+            // schnorr_secp256k1::verify
+            schnorr_secp256k1_verify_cost_base: None,
+
             bcs_per_byte_serialized_cost: None,
             bcs_legacy_min_output_size_cost: None,
             bcs_failure_cost: None,
@@ -3414,6 +3525,57 @@ impl ProtocolConfig {
                         cfg.feature_flags.consensus_median_based_commit_timestamp = true;
                     }
                 }
+                80 => {
+                    // Enable BN254 group ops for devnet
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.enable_bn254_group_ops = true;
+                    }
+
+                    // Derived from the ratio of the bn254 to the bls12381 timings measured by
+                    // sui-execution/latest/sui-move-natives/benches/group_ops_bench.rs,
+                    // applied to the existing bls12381 costs.
+                    cfg.group_ops_bn254_decode_scalar_cost = Some(7);
+                    cfg.group_ops_bn254_decode_g1_cost = Some(1370);
+                    cfg.group_ops_bn254_decode_g2_cost = Some(2680);
+                    cfg.group_ops_bn254_decode_gt_cost = Some(2390);
+
+                    cfg.group_ops_bn254_scalar_add_cost = Some(10);
+                    cfg.group_ops_bn254_g1_add_cost = Some(610);
+                    cfg.group_ops_bn254_g2_add_cost = Some(1470);
+                    cfg.group_ops_bn254_gt_add_cost = Some(150);
+
+                    cfg.group_ops_bn254_scalar_sub_cost = Some(10);
+                    cfg.group_ops_bn254_g1_sub_cost = Some(605);
+                    cfg.group_ops_bn254_g2_sub_cost = Some(1460);
+                    cfg.group_ops_bn254_gt_sub_cost = Some(410);
+
+                    cfg.group_ops_bn254_scalar_mul_cost = Some(11);
+                    cfg.group_ops_bn254_g1_mul_cost = Some(2660);
+                    cfg.group_ops_bn254_g2_mul_cost = Some(5480);
+                    cfg.group_ops_bn254_gt_mul_cost = Some(17690);
+
+                    cfg.group_ops_bn254_scalar_div_cost = Some(91);
+                    cfg.group_ops_bn254_g1_div_cost = Some(2760);
+                    cfg.group_ops_bn254_g2_div_cost = Some(5580);
+                    cfg.group_ops_bn254_gt_div_cost = Some(17910);
+
+                    cfg.group_ops_bn254_g1_hash_to_base_cost = Some(2450);
+                    cfg.group_ops_bn254_g1_hash_to_cost_per_byte = Some(2);
+
+                    cfg.group_ops_bn254_g1_msm_base_cost = Some(36210);
+                    cfg.group_ops_bn254_g2_msm_base_cost = Some(77430);
+                    cfg.group_ops_bn254_g1_msm_base_cost_per_input = Some(690);
+                    cfg.group_ops_bn254_g2_msm_base_cost_per_input = Some(1810);
+                    cfg.group_ops_bn254_msm_max_len = Some(32);
+
+                    cfg.group_ops_bn254_pairing_cost = Some(19120);
+
+                    // Enable BIP-340 Schnorr signature verification for devnet
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.enable_schnorr_secp256k1 = true;
+                    }
+                    cfg.schnorr_secp256k1_verify_cost_base = Some(1470);
+                }
                 // Use this template when making changes:
                 //
                 //     // modify an existing constant.
@@ -3601,6 +3763,13 @@ impl ProtocolConfig {
     pub fn set_consensus_median_based_commit_timestamp_for_testing(&mut self, val: bool) {
         self.feature_flags.consensus_median_based_commit_timestamp = val;
     }
+
+    pub fn set_consensus_leader_scoring_strategy_for_testing(
+        &mut self,
+        val: ConsensusLeaderScoringStrategy,
+    ) {
+        self.feature_flags.consensus_leader_scoring_strategy = val;
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
@@ -6,8 +6,9 @@ use axum::{Extension, Json};
 use axum_extra::extract::WithRejection;
 use futures::{future::join_all, StreamExt};
 
+use move_core_types::language_storage::TypeTag;
 use sui_sdk::rpc_types::StakeStatus;
-use sui_sdk::{SuiClient, SUI_COIN_TYPE};
+use sui_sdk::SuiClient;
 use sui_types::base_types::SuiAddress;
 use tracing::info;
 
@@ -16,7 +17,8 @@ use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
     Amount, Coin, Currencies, Currency, SubAccountType, SubBalance,
 };
-use crate::{OnlineServerContext, SuiEnv};
+use crate::{OnlineServerContext, SuiEnv, SUI};
+use std::str::FromStr;
 use std::time::Duration;
 use sui_sdk::error::SuiRpcResult;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -92,7 +94,10 @@ async fn get_balances(
         let mut amounts = Vec::new();
         for (currency, balance_result) in balances {
             match balance_result {
-                Ok(value) => amounts.push(Amount::new(value, Some(currency))),
+                Ok(value) => {
+                    let currency = resolve_currency(ctx, &currency).await?;
+                    amounts.push(Amount::new(value, Some(currency)))
+                }
                 Err(_e) => {
                     return Err(Error::InvalidInput(format!(
                         "{:?}",
@@ -109,6 +114,17 @@ async fn get_balances(
     }
 }
 
+/// Returns the currency with symbol and decimals taken from the on-chain `CoinMetadata`
+/// of its coin type, rather than the values supplied by the caller.
+async fn resolve_currency(
+    ctx: &OnlineServerContext,
+    currency: &Currency,
+) -> Result<Currency, Error> {
+    let type_tag = TypeTag::from_str(&currency.metadata.coin_type)
+        .map_err(|e| Error::InvalidInput(format!("{}: {e}", currency.metadata.coin_type)))?;
+    ctx.coin_metadata_cache.get_currency(&type_tag).await
+}
+
 async fn get_account_balances(
     ctx: &OnlineServerContext,
     address: SuiAddress,
@@ -192,16 +208,27 @@ pub async fn coins(
     WithRejection(Json(request), _): WithRejection<Json<AccountCoinsRequest>, Error>,
 ) -> Result<AccountCoinsResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
-    let coins = context
-        .client
-        .coin_read_api()
-        .get_coins_stream(
-            request.account_identifier.address,
-            Some(SUI_COIN_TYPE.to_string()),
-        )
-        .map(Coin::from)
-        .collect()
-        .await;
+    // Without an explicit list of currencies, only SUI coins are returned.
+    let currencies = if request.currencies.0.is_empty() {
+        vec![SUI.clone()]
+    } else {
+        request.currencies.0.clone()
+    };
+    let mut coins = vec![];
+    for currency in &currencies {
+        let currency = resolve_currency(&context, currency).await?;
+        let currency_coins: Vec<Coin> = context
+            .client
+            .coin_read_api()
+            .get_coins_stream(
+                request.account_identifier.address,
+                Some(currency.metadata.coin_type.clone()),
+            )
+            .map(|coin| Coin::new(coin, currency.clone()))
+            .collect()
+            .await;
+        coins.extend(currency_coins);
+    }
 
     Ok(AccountCoinsResponse {
         block_identifier: context.blocks().current_block_identifier().await?,
@@ -12,7 +12,7 @@ use futures::StreamExt;
 
 use shared_crypto::intent::{Intent, IntentMessage};
 use sui_json_rpc_types::{
-    StakeStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
+    Coin, StakeStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
     SuiTransactionBlockResponseOptions,
 };
 use sui_sdk::rpc_types::SuiExecutionStatus;
@@ -27,16 +27,20 @@ use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
 
 use crate::errors::Error;
 use crate::types::{
-    Amount, ConstructionCombineRequest, ConstructionCombineResponse, ConstructionDeriveRequest,
-    ConstructionDeriveResponse, ConstructionHashRequest, ConstructionMetadata,
-    ConstructionMetadataRequest, ConstructionMetadataResponse, ConstructionParseRequest,
-    ConstructionParseResponse, ConstructionPayloadsRequest, ConstructionPayloadsResponse,
-    ConstructionPreprocessRequest, ConstructionPreprocessResponse, ConstructionSubmitRequest,
-    InternalOperation, MetadataOptions, SignatureType, SigningPayload, TransactionIdentifier,
-    TransactionIdentifierResponse,
+    AccountIdentifier, Amount, ConstructionCombineRequest, ConstructionCombineResponse,
+    ConstructionDeriveRequest, ConstructionDeriveResponse, ConstructionHashRequest,
+    ConstructionMetadata, ConstructionMetadataRequest, ConstructionMetadataResponse,
+    ConstructionParseRequest, ConstructionParseResponse, ConstructionPayloadsRequest,
+    ConstructionPayloadsResponse, ConstructionPreprocessRequest, ConstructionPreprocessResponse,
+    ConstructionSubmitRequest, InternalOperation, MetadataOptions, SignatureType, SigningPayload,
+    TransactionIdentifier, TransactionIdentifierResponse,
 };
 use crate::{OnlineServerContext, SuiEnv};
 
+#[cfg(test)]
+#[path = "unit_tests/construction_tests.rs"]
+mod construction_tests;
+
 // This module implements the [Rosetta Construction API](https://www.rosetta-api.org/docs/ConstructionApi.html)
 
 /// Derive returns the AccountIdentifier associated with a public key.
@@ -64,12 +68,13 @@ pub async fn payloads(
 ) -> Result<ConstructionPayloadsResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
     let metadata = request.metadata.ok_or(Error::MissingMetadata)?;
-    let address = metadata.sender;
 
     let data = request
         .operations
         .into_internal()?
         .try_into_data(metadata)?;
+    // The sender and, for sponsored transactions, the gas owner sign the same digest.
+    let signers = data.signers();
     let intent_msg = IntentMessage::new(Intent::sui_transaction(), data);
     let intent_msg_bytes = bcs::to_bytes(&intent_msg)?;
 
@@ -79,11 +84,14 @@ pub async fn payloads(
 
     Ok(ConstructionPayloadsResponse {
         unsigned_transaction: Hex::from_bytes(&intent_msg_bytes),
-        payloads: vec![SigningPayload {
-            account_identifier: address.into(),
-            hex_bytes: Hex::encode(digest),
-            signature_type: Some(SignatureType::Ed25519),
-        }],
+        payloads: signers
+            .into_iter()
+            .map(|address| SigningPayload {
+                account_identifier: address.into(),
+                hex_bytes: Hex::encode(digest),
+                signature_type: Some(SignatureType::Ed25519),
+            })
+            .collect(),
     })
 }
 
@@ -98,24 +106,39 @@ pub async fn combine(
     env.check_network_identifier(&request.network_identifier)?;
     let unsigned_tx = request.unsigned_transaction.to_vec()?;
     let intent_msg: IntentMessage<TransactionData> = bcs::from_bytes(&unsigned_tx)?;
-    let sig = request
-        .signatures
-        .first()
-        .ok_or_else(|| Error::MissingInput("Signature".to_string()))?;
-    let sig_bytes = sig.hex_bytes.to_vec()?;
-    let pub_key = sig.public_key.hex_bytes.to_vec()?;
-    let flag = vec![match sig.signature_type {
-        SignatureType::Ed25519 => SignatureScheme::ED25519,
-        SignatureType::Ecdsa => SignatureScheme::Secp256k1,
+    if request.signatures.is_empty() {
+        return Err(Error::MissingInput("Signature".to_string()));
     }
-    .flag()];
-
-    let signed_tx = Transaction::from_generic_sig_data(
-        intent_msg.value,
-        vec![GenericSignature::from_bytes(
-            &[&*flag, &*sig_bytes, &*pub_key].concat(),
-        )?],
-    );
+    // Sponsored transactions carry one signature for the sender and one for the sponsor.
+    let signers = intent_msg.value.signers();
+    let single_signer = signers.len() == 1;
+    let signatures = signers
+        .into_iter()
+        .map(|signer| {
+            let sig = request
+                .signatures
+                .iter()
+                .find(|sig| sig.signing_payload.account_identifier.address == signer)
+                .or_else(|| {
+                    // Keep accepting any signature for transactions with a single signer,
+                    // regardless of its payload's account.
+                    single_signer.then(|| &request.signatures[0])
+                })
+                .ok_or_else(|| Error::MissingInput(format!("Signature for {signer}")))?;
+            let sig_bytes = sig.hex_bytes.to_vec()?;
+            let pub_key = sig.public_key.hex_bytes.to_vec()?;
+            let flag = vec![match sig.signature_type {
+                SignatureType::Ed25519 => SignatureScheme::ED25519,
+                SignatureType::Ecdsa => SignatureScheme::Secp256k1,
+            }
+            .flag()];
+            Ok(GenericSignature::from_bytes(
+                &[&*flag, &*sig_bytes, &*pub_key].concat(),
+            )?)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let signed_tx = Transaction::from_generic_sig_data(intent_msg.value, signatures);
     // TODO: this will likely fail with zklogin authenticator, since we do not know the current epoch.
     // As long as coinbase doesn't need to use zklogin for custodial wallets this is okay.
     let place_holder_epoch = 0;
@@ -197,13 +220,22 @@ pub async fn preprocess(
 
     let internal_operation = request.operations.into_internal()?;
     let sender = internal_operation.sender();
-    let budget = request.metadata.and_then(|m| m.budget);
+    let (budget, sponsor) = request
+        .metadata
+        .map(|m| (m.budget, m.sponsor))
+        .unwrap_or_default();
+    let sponsor = sponsor.filter(|sponsor| *sponsor != sender);
+    let required_public_keys = std::iter::once(sender)
+        .chain(sponsor)
+        .map(AccountIdentifier::from)
+        .collect();
     Ok(ConstructionPreprocessResponse {
         options: Some(MetadataOptions {
             internal_operation,
             budget,
+            sponsor,
         }),
-        required_public_keys: vec![sender.into()],
+        required_public_keys,
     })
 }
 
@@ -238,6 +270,10 @@ pub async fn metadata(
     let option = request.options.ok_or(Error::MissingMetadata)?;
     let budget = option.budget;
     let sender = option.internal_operation.sender();
+    let sponsor = option.sponsor;
+    let gas_owner = sponsor.unwrap_or(sender);
+    // Value of the sender's own SUI coins in `objects`, only used for sponsored transactions.
+    let mut sender_coin_value = 0u128;
     let currency = match &option.internal_operation {
         InternalOperation::PayCoin { currency, .. } => Some(currency.clone()),
         _ => None,
@@ -256,7 +292,16 @@ pub async fn metadata(
     let (total_required_amount, objects) = match &option.internal_operation {
         InternalOperation::PaySui { amounts, .. } => {
             let amount = amounts.iter().sum::<u64>();
-            (Some(amount), vec![])
+            if sponsor.is_some() {
+                let coins = select_sender_coins(&context, sender, Some(amount)).await?;
+                sender_coin_value = coins.iter().map(|coin| coin.balance as u128).sum();
+                (
+                    Some(0),
+                    coins.iter().map(|coin| coin.object_ref()).collect(),
+                )
+            } else {
+                (Some(amount), vec![])
+            }
         }
         InternalOperation::PayCoin { amounts, .. } => {
             let amount = amounts.iter().sum::<u64>();
@@ -272,7 +317,18 @@ pub async fn metadata(
                 .collect();
             (Some(0), coin_objs) // amount is 0 for gas coin
         }
-        InternalOperation::Stake { amount, .. } => (*amount, vec![]),
+        InternalOperation::Stake { amount, .. } => {
+            if sponsor.is_some() {
+                let coins = select_sender_coins(&context, sender, *amount).await?;
+                sender_coin_value = coins.iter().map(|coin| coin.balance as u128).sum();
+                (
+                    Some(0),
+                    coins.iter().map(|coin| coin.object_ref()).collect(),
+                )
+            } else {
+                (*amount, vec![])
+            }
+        }
         InternalOperation::WithdrawStake { sender, stake_ids } => {
             let stake_ids = if stake_ids.is_empty() {
                 // unstake all
@@ -321,18 +377,27 @@ pub async fn metadata(
         None => {
             // Dry run the transaction to get the gas used, amount doesn't really matter here when using mock coins.
             // get gas estimation from dry-run, this will also return any tx error.
+            let total_coin_value = if sponsor.is_some() {
+                sender_coin_value as i128
+            } else {
+                // Mock coin have 1B SUI
+                1_000_000_000 * 1_000_000_000
+            };
             let data = option
                 .internal_operation
                 .try_into_data(ConstructionMetadata {
                     sender,
                     coins: vec![],
                     objects: objects.clone(),
-                    // Mock coin have 1B SUI
-                    total_coin_value: 1_000_000_000 * 1_000_000_000,
+                    total_coin_value,
                     gas_price,
                     // MAX BUDGET
                     budget: 50_000_000_000,
                     currency: currency.clone(),
+                    // The dry run mock gas coin is always owned by the sender, so the sender
+                    // stands in for the sponsor. This keeps the sponsored transaction shape,
+                    // which is what determines the gas cost.
+                    sponsor: sponsor.map(|_| sender),
                 })?;
 
             let dry_run = context
@@ -355,7 +420,7 @@ pub async fn metadata(
         context
             .client
             .coin_read_api()
-            .select_coins(sender, None, total_amount.into(), vec![])
+            .select_coins(gas_owner, None, total_amount.into(), vec![])
             .await
             .ok()
     } else {
@@ -369,12 +434,16 @@ pub async fn metadata(
         context
             .client
             .coin_read_api()
-            .get_coins_stream(sender, None)
+            .get_coins_stream(gas_owner, None)
             .collect::<Vec<_>>()
             .await
     };
 
-    let total_coin_value = coins.iter().fold(0, |sum, coin| sum + coin.balance);
+    let total_coin_value = if sponsor.is_some() {
+        sender_coin_value
+    } else {
+        coins.iter().fold(0, |sum, coin| sum + coin.balance as u128)
+    };
 
     let coins = coins
         .into_iter()
@@ -386,10 +455,11 @@ pub async fn metadata(
             sender,
             coins,
             objects,
-            total_coin_value: total_coin_value.into(),
+            total_coin_value: total_coin_value as i128,
             gas_price,
             budget,
             currency,
+            sponsor,
         },
         suggested_fee: vec![Amount::new(budget as i128, None)],
     })
@@ -414,7 +484,7 @@ pub async fn parse(
         intent.value
     };
     let account_identifier_signers = if request.signed {
-        vec![data.sender().into()]
+        data.signers().into_iter().map(|s| s.into()).collect()
     } else {
         vec![]
     };
@@ -425,3 +495,35 @@ pub async fn parse(
         metadata: None,
     })
 }
+
+/// Selects the sender's SUI coins funding a sponsored transfer or stake, or all of them when
+/// `amount` is None.
+async fn select_sender_coins(
+    context: &OnlineServerContext,
+    sender: SuiAddress,
+    amount: Option<u64>,
+) -> Result<Vec<Coin>, Error> {
+    let coins = match amount {
+        Some(amount) => {
+            context
+                .client
+                .coin_read_api()
+                .select_coins(sender, None, amount.into(), vec![])
+                .await?
+        }
+        None => {
+            context
+                .client
+                .coin_read_api()
+                .get_coins_stream(sender, None)
+                .collect::<Vec<_>>()
+                .await
+        }
+    };
+    if coins.is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "No SUI coins found for {sender}"
+        )));
+    }
+    Ok(coins)
+}
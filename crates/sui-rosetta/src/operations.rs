@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Not;
 use std::str::FromStr;
 use std::vec;
@@ -15,7 +15,7 @@ use serde::Serialize;
 
 use sui_json_rpc_types::SuiProgrammableMoveCall;
 use sui_json_rpc_types::SuiProgrammableTransactionBlock;
-use sui_json_rpc_types::{BalanceChange, SuiArgument};
+use sui_json_rpc_types::{BalanceChange, SuiArgument, SuiEvent};
 use sui_json_rpc_types::{SuiCallArg, SuiCommand};
 use sui_sdk::rpc_types::{
     SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
@@ -407,6 +407,9 @@ impl Operations {
                 SuiCommand::SplitCoins(coin, amounts) => {
                     split_coins(inputs, &known_results, *coin, amounts)
                 }
+                // Merging coins doesn't change any balance, it only happens before paying or
+                // staking from more than one input coin.
+                SuiCommand::MergeCoins(_, _) => Some(vec![]),
                 SuiCommand::TransferObjects(objs, addr) => transfer_object(
                     &mut aggregated_recipients,
                     inputs,
@@ -581,7 +584,6 @@ impl Operations {
         let tx = response
             .transaction
             .ok_or_else(|| anyhow!("Response input should not be empty"))?;
-        let sender = *tx.data.sender();
         let effect = response
             .effects
             .ok_or_else(|| anyhow!("Response effects should not be empty"))?;
@@ -611,42 +613,22 @@ impl Operations {
                     balances
                 });
 
-        let mut principal_amounts = 0;
-        let mut reward_amounts = 0;
         // Extract balance change from unstake events
-
-        if let Some(events) = response.events {
-            for event in events.data {
-                if is_unstake_event(&event.type_) {
-                    let principal_amount = event
-                        .parsed_json
-                        .pointer("/principal_amount")
-                        .and_then(|v| v.as_str())
-                        .and_then(|v| i128::from_str(v).ok());
-                    let reward_amount = event
-                        .parsed_json
-                        .pointer("/reward_amount")
-                        .and_then(|v| v.as_str())
-                        .and_then(|v| i128::from_str(v).ok());
-                    if let (Some(principal_amount), Some(reward_amount)) =
-                        (principal_amount, reward_amount)
-                    {
-                        principal_amounts += principal_amount;
-                        reward_amounts += reward_amount;
-                    }
-                }
-            }
-        }
-        let staking_balance = if principal_amounts != 0 {
-            *accounted_balances.entry((sender, SUI.clone())).or_default() -= principal_amounts;
-            *accounted_balances.entry((sender, SUI.clone())).or_default() -= reward_amounts;
-            vec![
-                Operation::stake_principle(status, sender, principal_amounts),
-                Operation::stake_reward(status, sender, reward_amounts),
-            ]
-        } else {
-            vec![]
-        };
+        let unstakes = response
+            .events
+            .map(|events| unstake_amounts(&events.data))
+            .unwrap_or_default();
+        let staking_balance = unstakes
+            .into_iter()
+            .flat_map(|(staker, (principal_amount, reward_amount))| {
+                *accounted_balances.entry((staker, SUI.clone())).or_default() -=
+                    principal_amount + reward_amount;
+                [
+                    Operation::stake_principle(status, staker, principal_amount),
+                    Operation::stake_reward(status, staker, reward_amount),
+                ]
+            })
+            .collect::<Vec<_>>();
 
         let mut balance_changes = vec![];
 
@@ -722,6 +704,38 @@ impl Operations {
     }
 }
 
+/// Sums the principal and reward withdrawn by each `UnstakingRequestEvent`, keyed by the staker
+/// that receives them. Events without a staker address are attributed to the event sender.
+fn unstake_amounts(events: &[SuiEvent]) -> BTreeMap<SuiAddress, (i128, i128)> {
+    let amount = |event: &SuiEvent, pointer| {
+        event
+            .parsed_json
+            .pointer(pointer)
+            .and_then(|v| v.as_str())
+            .and_then(|v| i128::from_str(v).ok())
+    };
+    events
+        .iter()
+        .filter(|event| is_unstake_event(&event.type_))
+        .fold(BTreeMap::new(), |mut amounts, event| {
+            if let (Some(principal_amount), Some(reward_amount)) = (
+                amount(event, "/principal_amount"),
+                amount(event, "/reward_amount"),
+            ) {
+                let staker = event
+                    .parsed_json
+                    .pointer("/staker_address")
+                    .and_then(|v| v.as_str())
+                    .and_then(|v| SuiAddress::from_str(v).ok())
+                    .unwrap_or(event.sender);
+                let (principal, reward) = amounts.entry(staker).or_insert((0, 0));
+                *principal += principal_amount;
+                *reward += reward_amount;
+            }
+            amounts
+        })
+}
+
 fn is_unstake_event(tag: &StructTag) -> bool {
     tag.address == SUI_SYSTEM_ADDRESS
         && tag.module.as_ident_str() == ident_str!("validator")
@@ -245,6 +245,8 @@ pub struct AccountCoinsRequest {
     pub network_identifier: NetworkIdentifier,
     pub account_identifier: AccountIdentifier,
     pub include_mempool: bool,
+    #[serde(default, deserialize_with = "deserialize_or_default_currencies")]
+    pub currencies: Currencies,
 }
 #[derive(Serialize)]
 pub struct AccountCoinsResponse {
@@ -262,8 +264,8 @@ pub struct Coin {
     pub amount: Amount,
 }
 
-impl From<sui_sdk::rpc_types::Coin> for Coin {
-    fn from(coin: sui_sdk::rpc_types::Coin) -> Self {
+impl Coin {
+    pub fn new(coin: sui_sdk::rpc_types::Coin, currency: Currency) -> Self {
         Self {
             coin_identifier: CoinIdentifier {
                 identifier: CoinID {
@@ -273,13 +275,19 @@ impl From<sui_sdk::rpc_types::Coin> for Coin {
             },
             amount: Amount {
                 value: coin.balance as i128,
-                currency: SUI.clone(),
+                currency,
                 metadata: None,
             },
         }
     }
 }
 
+impl From<sui_sdk::rpc_types::Coin> for Coin {
+    fn from(coin: sui_sdk::rpc_types::Coin) -> Self {
+        Self::new(coin, SUI.clone())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct CoinIdentifier {
     pub identifier: CoinID,
@@ -615,6 +623,10 @@ pub struct ConstructionPreprocessRequest {
 pub struct PreprocessMetadata {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub budget: Option<u64>,
+    /// Address paying for gas. When set, the transaction is sponsored and has to be signed by
+    /// both the sender and the sponsor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<SuiAddress>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -630,6 +642,8 @@ pub struct MetadataOptions {
     pub internal_operation: InternalOperation,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub budget: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<SuiAddress>,
 }
 
 impl IntoResponse for ConstructionPreprocessResponse {
@@ -669,6 +683,10 @@ pub struct ConstructionMetadata {
     pub gas_price: u64,
     pub budget: u64,
     pub currency: Option<Currency>,
+    /// Owner of `coins` for sponsored transactions. `objects` then holds the sender's coins
+    /// used to fund SUI transfers and stakes, since the gas coins belong to the sponsor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<SuiAddress>,
 }
 
 impl IntoResponse for ConstructionMetadataResponse {
@@ -955,7 +973,11 @@ impl InternalOperation {
                 ..
             } => {
                 let mut builder = ProgrammableTransactionBuilder::new();
-                builder.pay_sui(recipients, amounts)?;
+                if metadata.sponsor.is_some() {
+                    builder.pay(metadata.objects.clone(), recipients, amounts)?;
+                } else {
+                    builder.pay_sui(recipients, amounts)?;
+                }
                 builder.finish()
             }
             Self::PayCoin {
@@ -989,13 +1011,24 @@ impl InternalOperation {
                     let state = builder.input(CallArg::SUI_SYSTEM_MUT)?;
                     (validator, state, amount)
                 } else {
-                    let amount =
-                        builder.pure(metadata.total_coin_value as u64 - metadata.budget)?;
+                    // For sponsored transactions the budget is paid from the sponsor's coins, so
+                    // the sender's whole balance can be staked.
+                    let amount = if metadata.sponsor.is_some() {
+                        metadata.total_coin_value as u64
+                    } else {
+                        metadata.total_coin_value as u64 - metadata.budget
+                    };
+                    let amount = builder.pure(amount)?;
                     let state = builder.input(CallArg::SUI_SYSTEM_MUT)?;
                     let validator = builder.input(CallArg::Pure(bcs::to_bytes(&validator)?))?;
                     (validator, state, amount)
                 };
-                let coin = builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+                let source = if metadata.sponsor.is_some() {
+                    merge_coins(&mut builder, metadata.objects.clone())?
+                } else {
+                    Argument::GasCoin
+                };
+                let coin = builder.command(Command::SplitCoins(source, vec![amount]));
 
                 let arguments = vec![system_state, coin, validator];
 
@@ -1037,12 +1070,41 @@ impl InternalOperation {
             }
         };
 
-        Ok(TransactionData::new_programmable(
-            metadata.sender,
-            metadata.coins,
-            pt,
-            metadata.budget,
-            metadata.gas_price,
-        ))
+        Ok(match metadata.sponsor {
+            Some(sponsor) => TransactionData::new_programmable_allow_sponsor(
+                metadata.sender,
+                metadata.coins,
+                pt,
+                metadata.budget,
+                metadata.gas_price,
+                sponsor,
+            ),
+            None => TransactionData::new_programmable(
+                metadata.sender,
+                metadata.coins,
+                pt,
+                metadata.budget,
+                metadata.gas_price,
+            ),
+        })
+    }
+}
+
+/// Merges `coins` into the first one and returns it as an argument.
+fn merge_coins(
+    builder: &mut ProgrammableTransactionBuilder,
+    coins: Vec<ObjectRef>,
+) -> Result<Argument, Error> {
+    let mut coins = coins.into_iter();
+    let coin = coins
+        .next()
+        .ok_or_else(|| Error::MissingInput("Sender coins".to_string()))?;
+    let coin = builder.obj(ObjectArg::ImmOrOwnedObject(coin))?;
+    let merge_args = coins
+        .map(|c| builder.obj(ObjectArg::ImmOrOwnedObject(c)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !merge_args.is_empty() {
+        builder.command(Command::MergeCoins(coin, merge_args));
     }
+    Ok(coin)
 }
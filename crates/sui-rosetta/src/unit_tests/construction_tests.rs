@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::marker::PhantomData;
+
+use sui_types::base_types::random_object_ref;
+use sui_types::crypto::{get_key_pair, AccountKeyPair, Signature, SuiSignature};
+
+use super::*;
+use crate::types::{CurveType, NetworkIdentifier, PublicKey, Signature as RosettaSignature};
+
+/// Signs `intent_msg` with `keypair`, in the format of the signatures of a Rosetta request.
+fn sign(
+    intent_msg: &IntentMessage<TransactionData>,
+    signer: SuiAddress,
+    keypair: &AccountKeyPair,
+) -> RosettaSignature {
+    let signature = Signature::new_secure(intent_msg, keypair);
+    RosettaSignature {
+        signing_payload: SigningPayload {
+            account_identifier: signer.into(),
+            hex_bytes: String::new(),
+            signature_type: Some(SignatureType::Ed25519),
+        },
+        public_key: PublicKey {
+            hex_bytes: Hex::from_bytes(signature.public_key_bytes()),
+            curve_type: CurveType::Edwards25519,
+        },
+        signature_type: SignatureType::Ed25519,
+        hex_bytes: Hex::from_bytes(signature.signature_bytes()),
+    }
+}
+
+async fn combine_signatures(
+    intent_msg: &IntentMessage<TransactionData>,
+    signatures: Vec<RosettaSignature>,
+) -> Result<ConstructionCombineResponse, Error> {
+    let request = ConstructionCombineRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: "sui".to_string(),
+            network: SuiEnv::LocalNet,
+        },
+        unsigned_transaction: Hex::from_bytes(&bcs::to_bytes(intent_msg).unwrap()),
+        signatures,
+    };
+    combine(
+        Extension(SuiEnv::LocalNet),
+        WithRejection(Json(request), PhantomData),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_combine_sponsored_transaction() {
+    let (sender, sender_keypair): (_, AccountKeyPair) = get_key_pair();
+    let (sponsor, sponsor_keypair): (_, AccountKeyPair) = get_key_pair();
+    let data = TransactionData::new_transfer_sui_allow_sponsor(
+        SuiAddress::ZERO,
+        sender,
+        Some(1),
+        random_object_ref(),
+        1000,
+        1,
+        sponsor,
+    );
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), data);
+    let sender_signature = sign(&intent_msg, sender, &sender_keypair);
+
+    // The signature of the sender is not used for the sponsor.
+    let err = combine_signatures(&intent_msg, vec![sender_signature])
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::MissingInput(input) if input.contains(&sponsor.to_string())),
+        "{err:?}"
+    );
+
+    let signatures = vec![
+        sign(&intent_msg, sponsor, &sponsor_keypair),
+        sign(&intent_msg, sender, &sender_keypair),
+    ];
+    let response = combine_signatures(&intent_msg, signatures).await.unwrap();
+    let signed_tx: Transaction =
+        bcs::from_bytes(&response.signed_transaction.to_vec().unwrap()).unwrap();
+    assert_eq!(signed_tx.data().tx_signatures().len(), 2);
+}
@@ -1,13 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
 use move_core_types::annotated_value::MoveTypeLayout;
-use sui_json_rpc_types::SuiCallArg;
+use move_core_types::language_storage::StructTag;
+use serde_json::json;
+use sui_json_rpc_types::{SuiCallArg, SuiEvent};
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use sui_types::transaction::{CallArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
+use sui_types::transaction::{
+    CallArg, TransactionData, TransactionDataAPI, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+};
 
-use crate::operations::Operations;
+use crate::operations::{unstake_amounts, Operations};
 use crate::types::{ConstructionMetadata, OperationType};
 use crate::SUI;
 
@@ -49,6 +55,7 @@ async fn test_operation_data_parsing_pay_sui() -> Result<(), anyhow::Error> {
         gas_price,
         budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
         currency: None,
+        sponsor: None,
     };
     let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
     assert_eq!(data, parsed_data);
@@ -105,12 +112,118 @@ async fn test_operation_data_parsing_pay_coin() -> Result<(), anyhow::Error> {
         gas_price,
         budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
         currency: Some(SUI.clone()),
+        sponsor: None,
+    };
+    let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
+    assert_eq!(data, parsed_data);
+
+    Ok(())
+}
+#[tokio::test]
+async fn test_operation_data_parsing_sponsored_pay_sui() -> Result<(), anyhow::Error> {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    // Two sender coins, so that they get merged before paying.
+    let coins = vec![
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        ),
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        ),
+    ];
+
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay(
+                coins.clone(),
+                vec![SuiAddress::random_for_testing_only()],
+                vec![10000],
+            )
+            .unwrap();
+        builder.finish()
+    };
+    let gas_price = 10;
+    let data = TransactionData::new_programmable_allow_sponsor(
+        sender,
+        vec![gas],
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        gas_price,
+        sponsor,
+    );
+
+    let ops: Operations = data.clone().try_into()?;
+    ops.0
+        .iter()
+        .for_each(|op| assert_eq!(op.type_, OperationType::PaySui));
+    let metadata = ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: coins,
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+        currency: None,
+        sponsor: Some(sponsor),
     };
     let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
     assert_eq!(data, parsed_data);
+    assert_eq!(
+        vec![sender, sponsor],
+        parsed_data.signers().into_iter().collect::<Vec<_>>()
+    );
 
     Ok(())
 }
+
+#[test]
+fn test_unstake_amounts() {
+    let staker = SuiAddress::random_for_testing_only();
+    let sender = SuiAddress::random_for_testing_only();
+    let unstake_event = |staker: Option<SuiAddress>, principal: u64, reward: u64| {
+        let mut parsed_json = json!({
+            "principal_amount": principal.to_string(),
+            "reward_amount": reward.to_string(),
+        });
+        if let Some(staker) = staker {
+            parsed_json["staker_address"] = json!(staker.to_string());
+        }
+        SuiEvent {
+            sender,
+            type_: StructTag::from_str("0x3::validator::UnstakingRequestEvent").unwrap(),
+            parsed_json,
+            ..SuiEvent::random_for_testing()
+        }
+    };
+
+    let events = vec![
+        unstake_event(Some(staker), 1000, 10),
+        unstake_event(Some(staker), 2000, 25),
+        unstake_event(None, 500, 5),
+        // Not an unstake event.
+        SuiEvent {
+            parsed_json: json!({"principal_amount": "1", "reward_amount": "1"}),
+            ..SuiEvent::random_for_testing()
+        },
+    ];
+    let amounts = unstake_amounts(&events);
+    assert_eq!(amounts.len(), 2);
+    assert_eq!(amounts[&staker], (3000, 35));
+    assert_eq!(amounts[&sender], (500, 5));
+}
+
 #[tokio::test]
 async fn test_sui_json() {
     let arg1 = CallArg::Pure(bcs::to_bytes(&1000000u64).unwrap());
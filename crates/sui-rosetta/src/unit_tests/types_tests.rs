@@ -103,6 +103,7 @@ async fn test_metadata_total_coin_value_js_conversion_for_large_balance() {
         gas_price: 0,
         budget: 0,
         currency: None,
+        sponsor: None,
     };
     let prod_metadata_json = serde_json::to_string(&prod_metadata).unwrap();
 
@@ -11,12 +11,13 @@ use crate::field_mask::FieldMaskUtil;
 use crate::proto::node::v2::node_service_client::NodeServiceClient;
 use crate::proto::node::v2::{
     EffectsFinality, ExecuteTransactionResponse, GetCheckpointResponse, GetFullCheckpointResponse,
-    GetObjectResponse,
+    GetObjectResponse, GetTransactionResponse,
 };
 use crate::proto::types::Bcs;
 use crate::proto::TryFromProtoError;
 use prost_types::FieldMask;
 use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::TransactionDigest;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
@@ -146,6 +147,26 @@ impl Client {
             .map_err(|e| status_from_error_with_metadata(e, metadata))
     }
 
+    /// Returns the sequence number of the checkpoint that includes the given transaction, if
+    /// the transaction has been checkpointed.
+    pub async fn get_transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<Option<CheckpointSequenceNumber>> {
+        let request = crate::proto::node::v2::GetTransactionRequest {
+            digest: Some(sui_sdk_types::TransactionDigest::from(digest).into()),
+            read_mask: FieldMask::from_paths(["checkpoint"]).pipe(Some),
+        };
+
+        let GetTransactionResponse { checkpoint, .. } = self
+            .raw_client()
+            .get_transaction(request)
+            .await?
+            .into_inner();
+
+        Ok(checkpoint)
+    }
+
     pub async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
         self.get_object_internal(object_id, None).await
     }
@@ -11,8 +11,6 @@ use tonic::{
 };
 use tower::{Service, ServiceExt};
 
-use crate::subscription::SubscriptionServiceHandle;
-
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 #[derive(Default)]
@@ -138,10 +136,11 @@ impl crate::proto::node::v2::node_service_server::NodeService for crate::RpcServ
 }
 
 use crate::proto::node::v2alpha::SubscribeCheckpointsResponse;
+use crate::proto::node::v2alpha::SubscribeCongestionInfoResponse;
 
 #[tonic::async_trait]
 impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionService
-    for SubscriptionServiceHandle
+    for crate::RpcService
 {
     /// Server streaming response type for the SubscribeCheckpoints method.
     type SubscribeCheckpointsStream = Pin<
@@ -151,17 +150,21 @@ impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionServi
         >,
     >;
 
+    /// Server streaming response type for the SubscribeCongestionInfo method.
+    type SubscribeCongestionInfoStream = Pin<
+        Box<
+            dyn tokio_stream::Stream<Item = Result<SubscribeCongestionInfoResponse, tonic::Status>>
+                + Send,
+        >,
+    >;
+
     async fn subscribe_checkpoints(
         &self,
         request: tonic::Request<crate::proto::node::v2alpha::SubscribeCheckpointsRequest>,
     ) -> Result<tonic::Response<Self::SubscribeCheckpointsStream>, tonic::Status> {
         let read_mask = request.into_inner().read_mask.unwrap_or_default();
 
-        let Some(mut receiver) = self.register_subscription().await else {
-            return Err(tonic::Status::unavailable(
-                "too many existing subscriptions",
-            ));
-        };
+        let mut receiver = self.register_checkpoint_subscription().await?;
 
         let response = Box::pin(async_stream::stream! {
             while let Some(checkpoint) = receiver.recv().await {
@@ -182,6 +185,66 @@ impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionServi
 
         Ok(tonic::Response::new(response))
     }
+
+    async fn subscribe_congestion_info(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::SubscribeCongestionInfoRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeCongestionInfoStream>, tonic::Status> {
+        let object_ids =
+            crate::service::congestion_info::parse_object_ids(&request.into_inner().object_ids)?;
+
+        let mut receiver = self.register_checkpoint_subscription().await?;
+
+        let service = self.clone();
+        let response = Box::pin(async_stream::stream! {
+            // The congestion tracker has already processed a checkpoint by the time it is
+            // broadcast to subscribers, so querying it here reflects that checkpoint.
+            while let Some(checkpoint) = receiver.recv().await {
+                let Some(cursor) = checkpoint.sequence_number else {
+                    yield Err(tonic::Status::internal("unable to determine cursor"));
+                    break;
+                };
+
+                let congestion_info = match service.congestion_info_for_objects(&object_ids) {
+                    Ok(congestion_info) => congestion_info,
+                    Err(e) => {
+                        yield Err(e.into());
+                        break;
+                    }
+                };
+                let response = SubscribeCongestionInfoResponse {
+                    cursor: Some(cursor),
+                    congestion_info: Some(congestion_info),
+                };
+
+                yield Ok(response);
+            }
+        });
+
+        Ok(tonic::Response::new(response))
+    }
+}
+
+impl crate::RpcService {
+    async fn register_checkpoint_subscription(
+        &self,
+    ) -> Result<
+        tokio::sync::mpsc::Receiver<
+            std::sync::Arc<crate::proto::node::v2::GetFullCheckpointResponse>,
+        >,
+        tonic::Status,
+    > {
+        let Some(subscription_service_handle) = self.subscription_service_handle.as_ref() else {
+            return Err(tonic::Status::unimplemented(
+                "subscriptions are not enabled on this node",
+            ));
+        };
+
+        subscription_service_handle
+            .register_subscription()
+            .await
+            .ok_or_else(|| tonic::Status::unavailable("too many existing subscriptions"))
+    }
 }
 
 // Go through all of the fields of the checkpoint and apply the provided 'options'.
@@ -345,6 +408,18 @@ impl crate::proto::node::v2alpha::node_service_server::NodeService for crate::Rp
             .map_err(Into::into)
     }
 
+    async fn get_congestion_info(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::GetCongestionInfoRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2alpha::GetCongestionInfoResponse>,
+        tonic::Status,
+    > {
+        self.get_congestion_info(request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
     async fn simulate_transaction(
         &self,
         request: tonic::Request<crate::proto::node::v2alpha::SimulateTransactionRequest>,
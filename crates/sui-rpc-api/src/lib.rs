@@ -151,9 +151,8 @@ impl RpcService {
                 .add_service(node_service)
                 .add_service(node_service_alpha);
 
-            if let Some(subscription_service_handle) = self.subscription_service_handle.clone() {
-                services = services
-                    .add_service(SubscriptionServiceServer::new(subscription_service_handle));
+            if self.subscription_service_handle.is_some() {
+                services = services.add_service(SubscriptionServiceServer::new(self.clone()));
             }
 
             services.into_router()
@@ -150,6 +150,75 @@ pub struct GetGasInfoResponse {
     #[prost(uint64, optional, tag = "1")]
     pub reference_gas_price: ::core::option::Option<u64>,
 }
+/// Request message for `NodeService.GetCongestionInfo`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCongestionInfoRequest {
+    /// The shared objects to request congestion information about
+    #[prost(message, repeated, tag = "1")]
+    pub object_ids: ::prost::alloc::vec::Vec<super::super::types::ObjectId>,
+}
+/// Response message for `NodeService.GetCongestionInfo`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCongestionInfoResponse {
+    /// Congestion information for each of the requested objects, in request
+    /// order.
+    #[prost(message, repeated, tag = "1")]
+    pub objects: ::prost::alloc::vec::Vec<ObjectCongestionInfo>,
+    /// Suggested gas price, denominated in MIST, for a transaction that mutates
+    /// all of the requested objects. This is never lower than the reference gas
+    /// price.
+    #[prost(uint64, optional, tag = "2")]
+    pub suggested_gas_price: ::core::option::Option<u64>,
+    /// Reference gas price denominated in MIST
+    #[prost(uint64, optional, tag = "3")]
+    pub reference_gas_price: ::core::option::Option<u64>,
+}
+/// Congestion status of a single shared object, derived from transactions that
+/// were cancelled or executed in recently executed checkpoints.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ObjectCongestionInfo {
+    #[prost(message, optional, tag = "1")]
+    pub object_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// Whether transactions touching this object are currently being cancelled
+    /// due to congestion.
+    #[prost(bool, optional, tag = "2")]
+    pub congested: ::core::option::Option<bool>,
+    /// Timestamp of the latest checkpoint in which a transaction touching this
+    /// object was cancelled.
+    #[prost(uint64, optional, tag = "3")]
+    pub last_cancellation_timestamp_ms: ::core::option::Option<u64>,
+    /// Highest gas price of the transactions cancelled in that checkpoint.
+    #[prost(uint64, optional, tag = "4")]
+    pub highest_cancelled_gas_price: ::core::option::Option<u64>,
+    /// Timestamp of the latest checkpoint in which a transaction mutating this
+    /// object executed.
+    #[prost(uint64, optional, tag = "5")]
+    pub last_success_timestamp_ms: ::core::option::Option<u64>,
+    /// Lowest gas price of the transactions executed in that checkpoint.
+    #[prost(uint64, optional, tag = "6")]
+    pub lowest_executed_gas_price: ::core::option::Option<u64>,
+    /// Gas price a transaction mutating this object should bid to avoid being
+    /// cancelled. Unset if the object is not congested.
+    #[prost(uint64, optional, tag = "7")]
+    pub suggested_gas_price: ::core::option::Option<u64>,
+    /// Per-checkpoint summaries of recent cancellations, oldest first.
+    #[prost(message, repeated, tag = "8")]
+    pub recent_cancellations: ::prost::alloc::vec::Vec<CongestionCancellation>,
+}
+/// Summary of the transactions touching an object that were cancelled due to
+/// congestion in a single checkpoint.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CongestionCancellation {
+    /// Timestamp of the checkpoint.
+    #[prost(uint64, optional, tag = "1")]
+    pub timestamp_ms: ::core::option::Option<u64>,
+    /// Number of cancelled transactions.
+    #[prost(uint64, optional, tag = "2")]
+    pub cancelled_transactions: ::core::option::Option<u64>,
+    /// Highest gas price of the cancelled transactions.
+    #[prost(uint64, optional, tag = "3")]
+    pub highest_cancelled_gas_price: ::core::option::Option<u64>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SimulateTransactionRequest {
     #[prost(message, optional, tag = "2")]
@@ -443,6 +512,34 @@ pub mod node_service_client {
                 .insert(GrpcMethod::new("sui.node.v2alpha.NodeService", "GetGasInfo"));
             self.inner.unary(req, path, codec).await
         }
+        /// Query the congestion status of a set of shared objects along with the gas
+        /// price a transaction mutating them should bid to avoid being cancelled.
+        pub async fn get_congestion_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCongestionInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCongestionInfoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.NodeService/GetCongestionInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2alpha.NodeService", "GetCongestionInfo"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn simulate_transaction(
             &mut self,
             request: impl tonic::IntoRequest<super::SimulateTransactionRequest>,
@@ -550,6 +647,15 @@ pub mod node_service_server {
             tonic::Response<super::GetGasInfoResponse>,
             tonic::Status,
         >;
+        /// Query the congestion status of a set of shared objects along with the gas
+        /// price a transaction mutating them should bid to avoid being cancelled.
+        async fn get_congestion_info(
+            &self,
+            request: tonic::Request<super::GetCongestionInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCongestionInfoResponse>,
+            tonic::Status,
+        >;
         async fn simulate_transaction(
             &self,
             request: tonic::Request<super::SimulateTransactionRequest>,
@@ -870,6 +976,52 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/sui.node.v2alpha.NodeService/GetCongestionInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCongestionInfoSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetCongestionInfoRequest>
+                    for GetCongestionInfoSvc<T> {
+                        type Response = super::GetCongestionInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCongestionInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_congestion_info(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetCongestionInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/sui.node.v2alpha.NodeService/SimulateTransaction" => {
                     #[allow(non_camel_case_types)]
                     struct SimulateTransactionSvc<T: NodeService>(pub Arc<T>);
@@ -1019,6 +1171,24 @@ pub struct SubscribeCheckpointsResponse {
     #[prost(message, optional, tag = "2")]
     pub checkpoint: ::core::option::Option<super::v2::GetFullCheckpointResponse>,
 }
+/// Request message for SubscriptionService.SubscribeCongestionInfo
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeCongestionInfoRequest {
+    /// The shared objects to receive congestion updates for
+    #[prost(message, repeated, tag = "1")]
+    pub object_ids: ::prost::alloc::vec::Vec<super::super::types::ObjectId>,
+}
+/// Response message for SubscriptionService.SubscribeCongestionInfo
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeCongestionInfoResponse {
+    /// Required. The checkpoint sequence number and value of the current cursor
+    /// into the checkpoint stream
+    #[prost(uint64, optional, tag = "1")]
+    pub cursor: ::core::option::Option<u64>,
+    /// Congestion information for the requested objects as of this checkpoint
+    #[prost(message, optional, tag = "2")]
+    pub congestion_info: ::core::option::Option<GetCongestionInfoResponse>,
+}
 /// Generated client implementations.
 pub mod subscription_service_client {
     #![allow(
@@ -1154,6 +1324,42 @@ pub mod subscription_service_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Subscribe to congestion updates for a set of shared objects.
+        ///
+        /// A response is sent for every checkpoint executed by the server once the
+        /// subscription is initialized, containing the congestion information for the
+        /// requested objects as of that checkpoint.
+        pub async fn subscribe_congestion_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeCongestionInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::SubscribeCongestionInfoResponse>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.SubscriptionService/SubscribeCongestionInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.node.v2alpha.SubscriptionService",
+                        "SubscribeCongestionInfo",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -1197,6 +1403,27 @@ pub mod subscription_service_server {
             tonic::Response<Self::SubscribeCheckpointsStream>,
             tonic::Status,
         >;
+        /// Server streaming response type for the SubscribeCongestionInfo method.
+        type SubscribeCongestionInfoStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::SubscribeCongestionInfoResponse,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Subscribe to congestion updates for a set of shared objects.
+        ///
+        /// A response is sent for every checkpoint executed by the server once the
+        /// subscription is initialized, containing the congestion information for the
+        /// requested objects as of that checkpoint.
+        async fn subscribe_congestion_info(
+            &self,
+            request: tonic::Request<super::SubscribeCongestionInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeCongestionInfoStream>,
+            tonic::Status,
+        >;
     }
     /// Service for subscribing to data from a Sui Fullnode
     #[derive(Debug)]
@@ -1326,6 +1553,59 @@ pub mod subscription_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/sui.node.v2alpha.SubscriptionService/SubscribeCongestionInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeCongestionInfoSvc<T: SubscriptionService>(pub Arc<T>);
+                    impl<
+                        T: SubscriptionService,
+                    > tonic::server::ServerStreamingService<
+                        super::SubscribeCongestionInfoRequest,
+                    > for SubscribeCongestionInfoSvc<T> {
+                        type Response = super::SubscribeCongestionInfoResponse;
+                        type ResponseStream = T::SubscribeCongestionInfoStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::SubscribeCongestionInfoRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SubscriptionService>::subscribe_congestion_info(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeCongestionInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
@@ -0,0 +1,108 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::node::v2alpha::CongestionCancellation;
+use crate::proto::node::v2alpha::GetCongestionInfoRequest;
+use crate::proto::node::v2alpha::GetCongestionInfoResponse;
+use crate::proto::node::v2alpha::ObjectCongestionInfo;
+use crate::proto::types::ObjectId as ProtoObjectId;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcService;
+use sui_sdk_types::ObjectId;
+
+/// Maximum number of objects that can be requested in a single congestion query.
+pub(crate) const MAX_CONGESTION_INFO_OBJECTS: usize = 256;
+
+impl RpcService {
+    pub fn get_congestion_info(
+        &self,
+        request: GetCongestionInfoRequest,
+    ) -> Result<GetCongestionInfoResponse> {
+        let object_ids = parse_object_ids(&request.object_ids)?;
+        self.congestion_info_for_objects(&object_ids)
+    }
+
+    pub(crate) fn congestion_info_for_objects(
+        &self,
+        object_ids: &[ObjectId],
+    ) -> Result<GetCongestionInfoResponse> {
+        let reference_gas_price = self.reader.get_system_state_summary()?.reference_gas_price;
+
+        let objects = object_ids
+            .iter()
+            .map(|object_id| {
+                self.reader
+                    .inner()
+                    .get_object_congestion_info(&(*object_id).into())
+                    .map(object_congestion_info_to_proto)
+                    .unwrap_or_else(|| ObjectCongestionInfo {
+                        object_id: Some((*object_id).into()),
+                        congested: Some(false),
+                        ..Default::default()
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        // A transaction mutating all of the requested objects needs to outbid the most congested
+        // of them, and can never bid below the reference gas price.
+        let suggested_gas_price = objects
+            .iter()
+            .filter_map(|info| info.suggested_gas_price)
+            .fold(reference_gas_price, std::cmp::max);
+
+        Ok(GetCongestionInfoResponse {
+            objects,
+            suggested_gas_price: Some(suggested_gas_price),
+            reference_gas_price: Some(reference_gas_price),
+        })
+    }
+}
+
+pub(crate) fn parse_object_ids(object_ids: &[ProtoObjectId]) -> Result<Vec<ObjectId>> {
+    if object_ids.len() > MAX_CONGESTION_INFO_OBJECTS {
+        return Err(FieldViolation::new("object_ids")
+            .with_description(format!(
+                "too many object_ids requested, maximum is {MAX_CONGESTION_INFO_OBJECTS}"
+            ))
+            .with_reason(ErrorReason::FieldInvalid)
+            .into());
+    }
+
+    object_ids
+        .iter()
+        .enumerate()
+        .map(|(i, object_id)| {
+            ObjectId::try_from(object_id).map_err(|e| {
+                FieldViolation::new_at("object_ids", i)
+                    .with_description(format!("invalid object_id: {e}"))
+                    .with_reason(ErrorReason::FieldInvalid)
+                    .into()
+            })
+        })
+        .collect()
+}
+
+fn object_congestion_info_to_proto(
+    info: sui_types::storage::ObjectCongestionInfo,
+) -> ObjectCongestionInfo {
+    ObjectCongestionInfo {
+        object_id: Some(ObjectId::from(info.object_id).into()),
+        congested: Some(info.suggested_gas_price.is_some()),
+        last_cancellation_timestamp_ms: Some(info.last_cancellation_timestamp_ms),
+        highest_cancelled_gas_price: Some(info.highest_cancelled_gas_price),
+        last_success_timestamp_ms: info.last_success_timestamp_ms,
+        lowest_executed_gas_price: info.lowest_executed_gas_price,
+        suggested_gas_price: info.suggested_gas_price,
+        recent_cancellations: info
+            .recent_cancellations
+            .into_iter()
+            .map(|cancellation| CongestionCancellation {
+                timestamp_ms: Some(cancellation.timestamp_ms),
+                cancelled_transactions: Some(cancellation.cancelled_transactions),
+                highest_cancelled_gas_price: Some(cancellation.highest_cancelled_gas_price),
+            })
+            .collect(),
+    }
+}
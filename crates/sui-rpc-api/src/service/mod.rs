@@ -5,6 +5,7 @@ mod accounts;
 pub(crate) mod checkpoints;
 mod coin_info;
 mod committee;
+pub(crate) mod congestion_info;
 mod gas_info;
 pub(crate) mod health;
 mod info;
@@ -90,12 +90,13 @@ use sui_json_rpc_api::{
 };
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
-    SuiObjectResponseQuery,
+    DryRunTransactionBlockResponse, ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery,
 };
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::transaction::TransactionData;
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi};
 use crate::error::{Error, SuiRpcResult};
@@ -620,4 +621,11 @@ impl DataReader for ReadApi {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, anyhow::Error> {
+        Ok(self.dry_run_transaction_block(tx).await?)
+    }
 }
@@ -253,6 +253,7 @@ impl ValidatorConfigBuilder {
             verifier_signing_config: VerifierSigningConfig::default(),
             enable_db_write_stall: None,
             execution_time_observer_config: self.execution_time_observer_config,
+            transaction_latency_tracing_config: None,
             chain_override_for_testing: self.chain_override,
         }
     }
@@ -561,6 +562,7 @@ impl FullnodeConfigBuilder {
             verifier_signing_config: VerifierSigningConfig::default(),
             enable_db_write_stall: None,
             execution_time_observer_config: None,
+            transaction_latency_tracing_config: None,
             chain_override_for_testing: self.chain_override,
         }
     }
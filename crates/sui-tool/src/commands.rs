@@ -4,10 +4,11 @@
 use crate::{
     check_completed_snapshot,
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
-    get_latest_available_epoch, get_object, get_transaction_block, make_clients,
-    restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum, ConciseObjectOutput,
-    GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
+    download_db_snapshot, download_formal_snapshot, dry_run_reconfiguration,
+    dump_checkpoints_from_archive, get_latest_available_epoch, get_object, get_transaction_block,
+    make_clients, replay_consensus_commits, restore_from_db_checkpoint, verify_archive,
+    verify_archive_by_checksum, ConciseObjectOutput, GroupedObjectOutput, SnapshotVerifyMode,
+    VerboseObjectOutput,
 };
 use anyhow::Result;
 use futures::{future::join_all, StreamExt};
@@ -27,9 +28,12 @@ use sui_types::{
 use clap::*;
 use fastcrypto::encoding::Encoding;
 use sui_archival::{read_manifest_as_json, write_manifest_from_json};
+use sui_config::node_config_validation::{diff_node_configs, Severity};
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
-use sui_config::Config;
+use sui_config::{Config, NodeConfig};
 use sui_core::authority_aggregator::AuthorityAggregatorBuilder;
+use sui_swarm_config::network_config_builder::ConfigBuilder;
+use sui_swarm_config::node_config_builder::FullnodeConfigBuilder;
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
@@ -404,6 +408,75 @@ pub enum ToolCommand {
         cmd: ReplayToolCommand,
     },
 
+    /// Check a node config for semantic problems, such as conflicting ports, invalid keys or
+    /// inconsistent pruning settings. Exits with an error if any error-level issue is found.
+    #[command(name = "validate-config")]
+    ValidateConfig {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+    },
+
+    /// Print the differences between two node configs. If no other config is given, the config
+    /// is compared against the defaults generated by sui-swarm-config for the same node type,
+    /// ignoring fields which are specific to each node such as keys, paths and addresses.
+    #[command(name = "diff-config")]
+    DiffConfig {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+
+        #[arg(long = "other")]
+        other: Option<PathBuf>,
+    },
+
+    /// Replay the consensus commits stored by a validator through a fresh consensus handler, to
+    /// investigate divergences in post-consensus processing such as checkpoint forks. Scheduled
+    /// transactions, shared object version assignments and deferrals are logged for each commit.
+    /// Only the commits following the last one processed by the node can be replayed: to replay
+    /// commits the node already processed, such as the divergent commits of a fork, point
+    /// `--config-path` to a node database restored to a point before them (e.g. with `restore-db`
+    /// from a database checkpoint), and `--consensus-db-path` to a consensus database containing
+    /// them. The node and consensus databases are copied to the working directory first, and
+    /// should not be in use by a running node.
+    #[command(name = "replay-consensus-commits")]
+    ReplayConsensusCommits {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+
+        /// Consensus database of the epoch to replay. Defaults to the database of the epoch the
+        /// node would recover to, under the consensus db path of the config.
+        #[arg(long = "consensus-db-path")]
+        consensus_db_path: Option<PathBuf>,
+
+        /// Last commit to replay. Defaults to the last commit in the consensus database.
+        #[arg(long = "end-commit")]
+        end_commit: Option<u32>,
+
+        /// Directory the databases are copied to. Must not exist.
+        #[arg(long = "working-dir")]
+        working_dir: PathBuf,
+    },
+
+    /// Execute the end of epoch transaction offline on top of the last executed checkpoint of an
+    /// epoch, and report the resulting system state changes, validator set changes, next epoch
+    /// protocol version and framework upgrades. The system packages and supported protocol
+    /// versions of this binary are used, so a candidate release can be checked against a
+    /// validator's database before the epoch ends. The node database is copied to the working
+    /// directory first, and should not be in use by a running node.
+    #[command(name = "dry-run-reconfiguration")]
+    DryRunReconfiguration {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+
+        /// Timestamp of the first checkpoint of the next epoch. Defaults to the timestamp of the
+        /// last executed checkpoint.
+        #[arg(long = "epoch-start-timestamp-ms")]
+        epoch_start_timestamp_ms: Option<u64>,
+
+        /// Directory the node database is copied to. Must not exist.
+        #[arg(long = "working-dir")]
+        working_dir: PathBuf,
+    },
+
     /// Ask all validators to sign a transaction through AuthorityAggregator.
     #[command(name = "sign-transaction")]
     SignTransaction {
@@ -1076,6 +1149,24 @@ impl ToolCommand {
                 dump_checkpoints_from_archive(object_store_config, start, end, max_content_length)
                     .await?;
             }
+            ToolCommand::ReplayConsensusCommits {
+                config_path,
+                consensus_db_path,
+                end_commit,
+                working_dir,
+            } => {
+                let config = NodeConfig::load(&config_path)?;
+                replay_consensus_commits(config, consensus_db_path, end_commit, &working_dir)
+                    .await?;
+            }
+            ToolCommand::DryRunReconfiguration {
+                config_path,
+                epoch_start_timestamp_ms,
+                working_dir,
+            } => {
+                let config = NodeConfig::load(&config_path)?;
+                dry_run_reconfiguration(config, epoch_start_timestamp_ms, &working_dir).await?;
+            }
             ToolCommand::SignTransaction {
                 genesis,
                 sender_signed_data,
@@ -1091,6 +1182,50 @@ impl ToolCommand {
                 let result = agg.process_transaction(transaction, None).await;
                 println!("{:?}", result);
             }
+            ToolCommand::ValidateConfig { config_path } => {
+                let config = NodeConfig::load(&config_path)?;
+                let issues = config.validate();
+                for issue in &issues {
+                    println!("{issue}");
+                }
+                let errors = issues
+                    .iter()
+                    .filter(|issue| issue.severity == Severity::Error)
+                    .count();
+                if errors > 0 {
+                    anyhow::bail!("{} has {errors} error(s)", config_path.display());
+                }
+                println!(
+                    "{} is valid ({} warning(s))",
+                    config_path.display(),
+                    issues.len()
+                );
+            }
+            ToolCommand::DiffConfig { config_path, other } => {
+                let config = NodeConfig::load(&config_path)?;
+                let differences = match other {
+                    Some(other) => diff_node_configs(&config, &NodeConfig::load(other)?)?,
+                    None => {
+                        let network_config = ConfigBuilder::new_with_temp_dir().build();
+                        let default_config = if config.consensus_config.is_some() {
+                            network_config.validator_configs()[0].clone()
+                        } else {
+                            FullnodeConfigBuilder::new()
+                                .build(&mut rand::rngs::OsRng, &network_config)
+                        };
+                        diff_node_configs(&default_config, &config)?
+                            .into_iter()
+                            .filter(|difference| !difference.is_instance_specific())
+                            .collect()
+                    }
+                };
+                for difference in &differences {
+                    println!("{difference}");
+                }
+                if differences.is_empty() {
+                    println!("No differences");
+                }
+            }
         };
         Ok(())
     }
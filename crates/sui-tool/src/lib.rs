@@ -39,6 +39,7 @@ use tokio::time::Instant;
 
 use anyhow::anyhow;
 use clap::ValueEnum;
+use consensus_core::{CommitIndex, CommitReader};
 use eyre::ContextCompat;
 use fastcrypto::hash::MultisetHash;
 use futures::{StreamExt, TryStreamExt};
@@ -52,7 +53,9 @@ use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
+use sui_core::consensus_commit_replay::ConsensusCommitReplayer;
 use sui_core::epoch::committee_store::CommitteeStore;
+use sui_core::reconfiguration_dry_run::ReconfigurationDryRun;
 use sui_core::storage::RocksDbStore;
 use sui_snapshot::reader::StateSnapshotReaderV1;
 use sui_snapshot::setup_db_state;
@@ -575,6 +578,113 @@ pub async fn restore_from_db_checkpoint(
     Ok(())
 }
 
+/// Replays the consensus commits following the last one processed by the node, up to
+/// `end_commit`, through a fresh consensus handler running against a copy of the node database,
+/// made under `working_dir`. The consensus database is copied as well, so neither database of the
+/// node is modified.
+pub async fn replay_consensus_commits(
+    mut config: NodeConfig,
+    consensus_db_path: Option<PathBuf>,
+    end_commit: Option<CommitIndex>,
+    working_dir: &Path,
+) -> Result<(), anyhow::Error> {
+    if working_dir.exists() {
+        return Err(anyhow!(
+            "Working directory {} already exists",
+            working_dir.display()
+        ));
+    }
+    let live_db_path = config.db_path();
+    config.db_path = working_dir.to_path_buf();
+    info!(
+        "Copying node database from {} to {}",
+        live_db_path.display(),
+        config.db_path().display()
+    );
+    copy_dir_all(&live_db_path, config.db_path(), vec![])?;
+
+    let mut replayer = ConsensusCommitReplayer::open(&config).await?;
+    let consensus_db_path = match consensus_db_path {
+        Some(path) => path,
+        None => config
+            .consensus_config()
+            .ok_or_else(|| anyhow!("Node config has no consensus config"))?
+            .db_path()
+            .join(replayer.epoch().to_string()),
+    };
+    let consensus_db_copy = working_dir.join("consensus");
+    info!(
+        "Copying consensus database from {} to {}",
+        consensus_db_path.display(),
+        consensus_db_copy.display()
+    );
+    copy_dir_all(&consensus_db_path, &consensus_db_copy, vec![])?;
+    let reader = CommitReader::open(
+        consensus_db_copy
+            .to_str()
+            .ok_or_else(|| anyhow!("Invalid consensus database path"))?,
+    );
+
+    let last_commit = reader
+        .last_commit_index()
+        .ok_or_else(|| anyhow!("No commits found in {}", consensus_db_path.display()))?;
+    let end_commit = end_commit.unwrap_or(last_commit).min(last_commit);
+    let last_processed_commit = replayer.last_processed_commit_index();
+    if end_commit <= last_processed_commit {
+        return Err(anyhow!(
+            "Commits up to {end_commit} cannot be replayed as the node has already processed commits up to {last_processed_commit}. Replaying them requires a node database restored to a point before they were processed, e.g. with `restore-db` from a database checkpoint"
+        ));
+    }
+
+    // Observe the commits a restarting node would observe again, to rebuild the same handler
+    // state.
+    let prior_commits =
+        reader.read_committed_sub_dags(replayer.first_prior_commit_index()..=last_processed_commit);
+    replayer.observe_prior_commits(prior_commits)?;
+
+    let start_commit = last_processed_commit + 1;
+    info!(
+        "Replaying commits {start_commit}..={end_commit} of epoch {}",
+        replayer.epoch()
+    );
+    // Commits are loaded in batches to bound memory usage.
+    const BATCH_SIZE: CommitIndex = 100;
+    let mut batch_start = start_commit;
+    while batch_start <= end_commit {
+        let batch_end = batch_start.saturating_add(BATCH_SIZE - 1).min(end_commit);
+        let commits = reader.read_committed_sub_dags(batch_start..=batch_end);
+        replayer.replay(commits).await?;
+        batch_start = batch_end + 1;
+    }
+    Ok(())
+}
+
+pub async fn dry_run_reconfiguration(
+    mut config: NodeConfig,
+    epoch_start_timestamp_ms: Option<u64>,
+    working_dir: &Path,
+) -> Result<(), anyhow::Error> {
+    if working_dir.exists() {
+        return Err(anyhow!(
+            "Working directory {} already exists",
+            working_dir.display()
+        ));
+    }
+    let live_db_path = config.db_path();
+    config.db_path = working_dir.to_path_buf();
+    info!(
+        "Copying node database from {} to {}",
+        live_db_path.display(),
+        config.db_path().display()
+    );
+    copy_dir_all(&live_db_path, config.db_path(), vec![])?;
+
+    let dry_run = ReconfigurationDryRun::open(config).await?;
+    let report = dry_run.run(epoch_start_timestamp_ms).await?;
+    println!("{report}");
+    Ok(())
+}
+
 fn start_summary_sync(
     perpetual_db: Arc<AuthorityPerpetualTables>,
     committee_store: Arc<CommitteeStore>,
@@ -17,12 +17,13 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use sui_json::{is_receiving_argument, resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
-    SuiTypeTag,
+    DryRunTransactionBlockResponse, RPCTransactionRequestParams, SuiData, SuiObjectDataOptions,
+    SuiObjectResponse, SuiRawData, SuiTypeTag,
 };
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, ObjectType, SuiAddress};
 use sui_types::error::UserInputError;
+use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::move_package::MovePackage;
@@ -34,6 +35,10 @@ use sui_types::transaction::{
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
+pub use transaction_block_builder::{CommandResult, TransactionBlockBuilder, TxArg};
+
+mod transaction_block_builder;
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -49,6 +54,38 @@ pub trait DataReader {
     ) -> Result<SuiObjectResponse, anyhow::Error>;
 
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    /// Dry runs the transaction, which is used to estimate gas budgets. Readers that cannot
+    /// execute transactions keep the default implementation, which returns an error.
+    async fn dry_run_transaction_block(
+        &self,
+        _tx: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, anyhow::Error> {
+        bail!("Dry run is not supported by this data reader")
+    }
+}
+
+/// Gas overhead added on top of the dry run gas cost when estimating a gas budget, in units of
+/// the gas price.
+pub const GAS_SAFE_OVERHEAD: u64 = 1000;
+
+/// Estimates the gas budget of a transaction from the gas cost of its dry run, as the maximum
+/// between A and B, where:
+///
+/// A = computation cost + GAS_SAFE_OVERHEAD * reference gas price
+/// B = computation cost + storage cost - storage rebate + GAS_SAFE_OVERHEAD * reference gas price
+///
+/// This gas estimate is computed exactly as in the TypeScript SDK
+/// <https://github.com/MystenLabs/sui/blob/3c4369270605f78a243842098b7029daf8d883d9/sdk/typescript/src/transactions/TransactionBlock.ts#L845-L858>
+pub fn estimate_gas_budget_from_gas_cost(
+    gas_cost_summary: &GasCostSummary,
+    reference_gas_price: u64,
+) -> u64 {
+    let safe_overhead = GAS_SAFE_OVERHEAD * reference_gas_price;
+    let computation_cost_with_overhead = gas_cost_summary.computation_cost + safe_overhead;
+
+    let gas_usage = gas_cost_summary.net_gas_usage() + safe_overhead as i64;
+    computation_cost_with_overhead.max(if gas_usage < 0 { 0 } else { gas_usage as u64 })
 }
 
 #[derive(Clone)]
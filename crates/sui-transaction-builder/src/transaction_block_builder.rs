@@ -0,0 +1,479 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, ensure};
+use futures::future::join_all;
+use move_binary_format::binary_config::BinaryConfig;
+use move_binary_format::file_format::SignatureToken;
+use move_binary_format::CompiledModule;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+use sui_json::is_receiving_argument;
+use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI};
+use sui_protocol_config::ProtocolConfig;
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TxContext, TxContextKind};
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::{Object, Owner};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+};
+
+use crate::{estimate_gas_budget_from_gas_cost, DataReader, TransactionBuilder};
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_block_builder_tests.rs"]
+mod transaction_block_builder_tests;
+
+/// An argument to a command of a [`TransactionBlockBuilder`].
+#[derive(Clone, Debug)]
+pub enum TxArg {
+    /// An object input. It is resolved to an owned, shared or receiving input when the command
+    /// is added, from the current owner of the object and how the command uses it.
+    Object(ObjectID),
+    /// The BCS bytes of a pure value, see [`TxArg::pure`].
+    Pure(Vec<u8>),
+    /// The gas coin, the result of a previous command, or an already added input.
+    Argument(Argument),
+}
+
+impl TxArg {
+    pub fn pure<T: Serialize>(value: &T) -> anyhow::Result<Self> {
+        Ok(Self::Pure(bcs::to_bytes(value)?))
+    }
+}
+
+impl From<ObjectID> for TxArg {
+    fn from(id: ObjectID) -> Self {
+        Self::Object(id)
+    }
+}
+
+impl From<Argument> for TxArg {
+    fn from(arg: Argument) -> Self {
+        Self::Argument(arg)
+    }
+}
+
+impl From<CommandResult> for TxArg {
+    fn from(result: CommandResult) -> Self {
+        Self::Argument(result.arg())
+    }
+}
+
+/// The symbolic result of a command, which can be passed to later commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandResult(u16);
+
+impl CommandResult {
+    /// The whole result, for commands returning a single value.
+    pub fn arg(&self) -> Argument {
+        Argument::Result(self.0)
+    }
+
+    /// The `i`-th value returned by the command.
+    pub fn nested(&self, i: u16) -> Argument {
+        Argument::NestedResult(self.0, i)
+    }
+}
+
+/// A high level builder for programmable transaction blocks. Objects are referred to by id and
+/// resolved through the `DataReader`, gas coins are selected (and merged by the protocol when
+/// several are needed) from the gas owner's coins, and the gas budget is estimated with a dry
+/// run unless it is set explicitly.
+pub struct TransactionBlockBuilder {
+    reader: Arc<dyn DataReader + Sync + Send>,
+    sender: SuiAddress,
+    sponsor: Option<SuiAddress>,
+    gas_payment: Vec<ObjectID>,
+    gas_budget: Option<u64>,
+    gas_price: Option<u64>,
+    builder: ProgrammableTransactionBuilder,
+    objects: BTreeMap<ObjectID, Object>,
+    modules: BTreeMap<(ObjectID, Identifier), CompiledModule>,
+}
+
+impl TransactionBuilder {
+    /// Starts building a programmable transaction block sent by `sender`.
+    pub fn transaction_block(&self, sender: SuiAddress) -> TransactionBlockBuilder {
+        TransactionBlockBuilder::new(self.0.clone(), sender)
+    }
+}
+
+impl TransactionBlockBuilder {
+    pub fn new(reader: Arc<dyn DataReader + Sync + Send>, sender: SuiAddress) -> Self {
+        Self {
+            reader,
+            sender,
+            sponsor: None,
+            gas_payment: vec![],
+            gas_budget: None,
+            gas_price: None,
+            builder: ProgrammableTransactionBuilder::new(),
+            objects: BTreeMap::new(),
+            modules: BTreeMap::new(),
+        }
+    }
+
+    /// Pays for gas with the coins of `sponsor` instead of the sender's.
+    pub fn with_sponsor(mut self, sponsor: SuiAddress) -> Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+
+    /// Pays for gas with these coins instead of selecting them automatically.
+    pub fn with_gas_payment(mut self, gas_payment: Vec<ObjectID>) -> Self {
+        self.gas_payment = gas_payment;
+        self
+    }
+
+    /// Uses this gas budget instead of estimating it with a dry run.
+    pub fn with_gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = Some(gas_budget);
+        self
+    }
+
+    /// Uses this gas price instead of the reference gas price.
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    pub fn gas(&self) -> Argument {
+        Argument::GasCoin
+    }
+
+    pub fn pure<T: Serialize>(&mut self, value: T) -> anyhow::Result<Argument> {
+        self.builder.pure(value)
+    }
+
+    /// Adds an object input to be used by a non Move call command. Owned and immutable objects
+    /// are passed by reference at their latest version, shared objects mutably.
+    pub async fn object(&mut self, id: ObjectID) -> anyhow::Result<Argument> {
+        let obj_arg = self.object_arg(id, None).await?;
+        self.builder.obj(obj_arg)
+    }
+
+    pub async fn move_call(
+        &mut self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<TxArg>,
+    ) -> anyhow::Result<CommandResult> {
+        let module = Identifier::new(module)?;
+        let function = Identifier::new(function)?;
+        let parameters = self
+            .function_parameters(package, &module, &function)
+            .await?;
+        ensure!(
+            parameters.len() == arguments.len(),
+            "{package}::{module}::{function} expects {} arguments, got {}",
+            parameters.len(),
+            arguments.len()
+        );
+
+        let view = self.modules[&(package, module.clone())].clone();
+        let mut args = vec![];
+        for (argument, parameter) in arguments.into_iter().zip(parameters) {
+            args.push(match argument {
+                TxArg::Object(id) => {
+                    let obj_arg = self.object_arg(id, Some((&view, &parameter))).await?;
+                    self.builder.obj(obj_arg)?
+                }
+                other => self.argument(other).await?,
+            });
+        }
+        Ok(self.command(Command::move_call(
+            package,
+            module,
+            function,
+            type_arguments,
+            args,
+        )))
+    }
+
+    pub async fn transfer_objects(
+        &mut self,
+        objects: Vec<TxArg>,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<()> {
+        let objects = self.arguments(objects).await?;
+        let recipient = self.builder.pure(recipient)?;
+        self.builder
+            .command(Command::TransferObjects(objects, recipient));
+        Ok(())
+    }
+
+    /// Splits `amounts` off `coin`, returning one argument per new coin.
+    pub async fn split_coins(
+        &mut self,
+        coin: TxArg,
+        amounts: Vec<u64>,
+    ) -> anyhow::Result<Vec<Argument>> {
+        let coin = self.argument(coin).await?;
+        let amount_args = amounts
+            .iter()
+            .map(|amount| self.builder.pure(*amount))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let result = self.command(Command::SplitCoins(coin, amount_args));
+        Ok((0..amounts.len() as u16)
+            .map(|i| result.nested(i))
+            .collect())
+    }
+
+    pub async fn merge_coins(&mut self, target: TxArg, coins: Vec<TxArg>) -> anyhow::Result<()> {
+        let target = self.argument(target).await?;
+        let coins = self.arguments(coins).await?;
+        self.builder.command(Command::MergeCoins(target, coins));
+        Ok(())
+    }
+
+    pub async fn make_move_vec(
+        &mut self,
+        type_: Option<TypeTag>,
+        elements: Vec<TxArg>,
+    ) -> anyhow::Result<CommandResult> {
+        let elements = self.arguments(elements).await?;
+        Ok(self.command(Command::MakeMoveVec(type_, elements)))
+    }
+
+    /// Returns the transaction kind, without selecting gas.
+    pub fn finish(self) -> TransactionKind {
+        TransactionKind::ProgrammableTransaction(self.builder.finish())
+    }
+
+    /// Builds the transaction data, estimating the gas budget and selecting the gas coins if
+    /// they were not provided.
+    pub async fn build(self) -> anyhow::Result<TransactionData> {
+        let reader = self.reader.clone();
+        let sender = self.sender;
+        let gas_owner = self.sponsor.unwrap_or(sender);
+        let gas_payment = self.gas_payment.clone();
+        let gas_budget = self.gas_budget;
+        let gas_price = match self.gas_price {
+            Some(gas_price) => gas_price,
+            None => reader.get_reference_gas_price().await?,
+        };
+        let kind = self.finish();
+
+        let builder = TransactionBuilder::new(reader.clone());
+        let gas_payment = if gas_payment.is_empty() {
+            vec![]
+        } else {
+            builder.input_refs(&gas_payment).await?
+        };
+        let gas_budget = match gas_budget {
+            Some(gas_budget) => gas_budget,
+            None => {
+                estimate_gas_budget(
+                    reader.as_ref(),
+                    TransactionData::new_with_gas_coins_allow_sponsor(
+                        kind.clone(),
+                        sender,
+                        gas_payment.clone(),
+                        ProtocolConfig::get_for_min_version().max_tx_gas(),
+                        gas_price,
+                        gas_owner,
+                    ),
+                    gas_price,
+                )
+                .await?
+            }
+        };
+        let gas_payment = if gas_payment.is_empty() {
+            select_gas_coins(reader.as_ref(), gas_owner, gas_budget, &kind).await?
+        } else {
+            gas_payment
+        };
+
+        Ok(TransactionData::new_with_gas_coins_allow_sponsor(
+            kind,
+            sender,
+            gas_payment,
+            gas_budget,
+            gas_price,
+            gas_owner,
+        ))
+    }
+
+    fn command(&mut self, command: Command) -> CommandResult {
+        match self.builder.command(command) {
+            Argument::Result(i) => CommandResult(i),
+            _ => unreachable!("commands always return Argument::Result"),
+        }
+    }
+
+    async fn argument(&mut self, argument: TxArg) -> anyhow::Result<Argument> {
+        match argument {
+            TxArg::Object(id) => self.object(id).await,
+            TxArg::Pure(bytes) => self.builder.input(CallArg::Pure(bytes)),
+            TxArg::Argument(arg) => Ok(arg),
+        }
+    }
+
+    async fn arguments(&mut self, arguments: Vec<TxArg>) -> anyhow::Result<Vec<Argument>> {
+        let mut args = vec![];
+        for argument in arguments {
+            args.push(self.argument(argument).await?);
+        }
+        Ok(args)
+    }
+
+    /// Resolves how an object is passed as an input. `usage` is the Move call parameter the
+    /// object is passed to, if any.
+    async fn object_arg(
+        &mut self,
+        id: ObjectID,
+        usage: Option<(&CompiledModule, &SignatureToken)>,
+    ) -> anyhow::Result<ObjectArg> {
+        let obj = self.get_object(id).await?;
+        let obj_ref = obj.compute_object_reference();
+        if let Some((view, arg_type)) = usage {
+            if is_receiving_argument(view, arg_type) {
+                return Ok(ObjectArg::Receiving(obj_ref));
+            }
+        }
+        // Is mutable if passed by mutable reference or by value
+        let mutable = usage.map_or(true, |(_, arg_type)| {
+            matches!(arg_type, SignatureToken::MutableReference(_)) || !arg_type.is_reference()
+        });
+        Ok(match obj.owner {
+            Owner::Shared {
+                initial_shared_version,
+            }
+            | Owner::ConsensusV2 {
+                start_version: initial_shared_version,
+                authenticator: _,
+            } => ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutable,
+            },
+            Owner::AddressOwner(_) | Owner::Immutable => ObjectArg::ImmOrOwnedObject(obj_ref),
+            Owner::ObjectOwner(parent) => {
+                bail!("Object {id} is owned by object {parent} and cannot be used as an input")
+            }
+        })
+    }
+
+    async fn get_object(&mut self, id: ObjectID) -> anyhow::Result<&Object> {
+        if !self.objects.contains_key(&id) {
+            let obj: Object = self
+                .reader
+                .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
+                .await?
+                .into_object()?
+                .try_into()?;
+            self.objects.insert(id, obj);
+        }
+        Ok(&self.objects[&id])
+    }
+
+    /// Returns the parameters of a Move function, without the trailing `TxContext`.
+    async fn function_parameters(
+        &mut self,
+        package: ObjectID,
+        module: &Identifier,
+        function: &Identifier,
+    ) -> anyhow::Result<Vec<SignatureToken>> {
+        let key = (package, module.clone());
+        if !self.modules.contains_key(&key) {
+            let compiled_module = self
+                .get_object(package)
+                .await?
+                .data
+                .try_as_package()
+                .ok_or_else(|| anyhow!("Object [{package}] is not a package"))?
+                .deserialize_module(module, &BinaryConfig::standard())?;
+            self.modules.insert(key.clone(), compiled_module);
+        }
+        let view = &self.modules[&key];
+
+        let (_, def) = view
+            .find_function_def_by_name(function.as_str())
+            .ok_or_else(|| anyhow!("Function {package}::{module}::{function} does not exist"))?;
+        let handle = view.function_handle_at(def.function);
+        let mut parameters = view.signature_at(handle.parameters).0.clone();
+        if parameters
+            .last()
+            .is_some_and(|last| TxContext::kind(view, last) != TxContextKind::None)
+        {
+            parameters.pop();
+        }
+        Ok(parameters)
+    }
+}
+
+async fn estimate_gas_budget(
+    reader: &(dyn DataReader + Sync + Send),
+    tx: TransactionData,
+    gas_price: u64,
+) -> anyhow::Result<u64> {
+    let response = reader.dry_run_transaction_block(tx).await.map_err(|e| {
+        anyhow!("Could not determine the gas budget, provide one explicitly. Error: {e}")
+    })?;
+    if let SuiExecutionStatus::Failure { error } = response.effects.status() {
+        bail!(
+            "Dry run failed, could not determine the gas budget. Error: {error}{}",
+            response
+                .execution_error_source
+                .map(|source| format!(" ({source})"))
+                .unwrap_or_default()
+        );
+    }
+    Ok(estimate_gas_budget_from_gas_cost(
+        response.effects.gas_cost_summary(),
+        gas_price,
+    ))
+}
+
+/// Selects the largest coins of `gas_owner` which are not inputs of the transaction, until they
+/// cover `gas_budget`.
+async fn select_gas_coins(
+    reader: &(dyn DataReader + Sync + Send),
+    gas_owner: SuiAddress,
+    gas_budget: u64,
+    kind: &TransactionKind,
+) -> anyhow::Result<Vec<ObjectRef>> {
+    let input_objects: BTreeSet<_> = kind
+        .input_objects()?
+        .iter()
+        .map(InputObjectKind::object_id)
+        .collect();
+    let coin_infos = reader
+        .get_owned_objects(gas_owner, GasCoin::type_())
+        .await?
+        .into_iter()
+        .filter(|info| !input_objects.contains(&info.object_id));
+
+    let responses = join_all(coin_infos.map(|info| {
+        reader.get_object_with_options(info.object_id, SuiObjectDataOptions::bcs_lossless())
+    }))
+    .await;
+    let mut coins = vec![];
+    for response in responses {
+        let obj: Object = response?.into_object()?.try_into()?;
+        let coin = GasCoin::try_from(&obj)?;
+        coins.push((coin.value(), obj.compute_object_reference()));
+    }
+    coins.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let max_gas_payment_objects =
+        ProtocolConfig::get_for_min_version().max_gas_payment_objects() as usize;
+    let mut total = 0u64;
+    let mut selected = vec![];
+    for (value, obj_ref) in coins.into_iter().take(max_gas_payment_objects) {
+        total = total.saturating_add(value);
+        selected.push(obj_ref);
+        if total >= gas_budget {
+            return Ok(selected);
+        }
+    }
+    bail!("Cannot find gas coins for address {gas_owner} with a total amount sufficient for the required gas budget {gas_budget}")
+}
@@ -0,0 +1,218 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
+use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::{Object, ObjectRead, Owner};
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, TransactionDataAPI, TransactionKind,
+};
+
+use super::{TransactionBlockBuilder, TxArg};
+use crate::DataReader;
+
+const GAS_PRICE: u64 = 1000;
+
+/// A `DataReader` serving a fixed set of objects.
+struct TestReader {
+    objects: BTreeMap<ObjectID, Object>,
+}
+
+impl TestReader {
+    fn new(objects: impl IntoIterator<Item = Object>) -> Arc<Self> {
+        Arc::new(Self {
+            objects: objects.into_iter().map(|obj| (obj.id(), obj)).collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl DataReader for TestReader {
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        object_type: StructTag,
+    ) -> Result<Vec<ObjectInfo>, anyhow::Error> {
+        Ok(self
+            .objects
+            .values()
+            .filter(|obj| {
+                obj.owner == Owner::AddressOwner(address)
+                    && obj.struct_tag().as_ref() == Some(&object_type)
+            })
+            .map(|obj| ObjectInfo::new(&obj.compute_object_reference(), obj))
+            .collect())
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, anyhow::Error> {
+        let read = match self.objects.get(&object_id) {
+            Some(obj) => ObjectRead::Exists(
+                obj.compute_object_reference(),
+                obj.clone(),
+                Some(GasCoin::layout()),
+            ),
+            None => ObjectRead::NotExists(object_id),
+        };
+        (read, options).try_into()
+    }
+
+    async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+        Ok(GAS_PRICE)
+    }
+}
+
+fn inputs(kind: TransactionKind) -> Vec<CallArg> {
+    match kind {
+        TransactionKind::ProgrammableTransaction(pt) => pt.inputs,
+        _ => unreachable!(),
+    }
+}
+
+fn commands(kind: TransactionKind) -> Vec<Command> {
+    match kind {
+        TransactionKind::ProgrammableTransaction(pt) => pt.commands,
+        _ => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn test_object_inputs() {
+    let sender = SuiAddress::random_for_testing_only();
+    let owned = Object::with_owner_for_testing(sender);
+    let shared = Object::shared_for_testing();
+    let immutable = Object::immutable_for_testing();
+    let reader = TestReader::new([owned.clone(), shared.clone(), immutable.clone()]);
+
+    let mut builder = TransactionBlockBuilder::new(reader, sender);
+    builder.object(owned.id()).await.unwrap();
+    builder.object(shared.id()).await.unwrap();
+    builder.object(immutable.id()).await.unwrap();
+
+    let Owner::Shared {
+        initial_shared_version,
+    } = shared.owner
+    else {
+        unreachable!()
+    };
+    assert_eq!(
+        inputs(builder.finish()),
+        vec![
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                owned.compute_object_reference()
+            )),
+            CallArg::Object(ObjectArg::SharedObject {
+                id: shared.id(),
+                initial_shared_version,
+                mutable: true,
+            }),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                immutable.compute_object_reference()
+            )),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_object_owned_input_is_rejected() {
+    let parent = ObjectID::random();
+    let child = Object::with_object_owner_for_testing(ObjectID::random(), parent);
+    let reader = TestReader::new([child.clone()]);
+
+    let mut builder = TransactionBlockBuilder::new(reader, SuiAddress::ZERO);
+    let err = builder.object(child.id()).await.unwrap_err();
+    assert!(err.to_string().contains(&parent.to_string()), "{err}");
+    assert!(builder.object(ObjectID::random()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_pure_arguments() {
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let reader = TestReader::new([]);
+
+    let mut builder = TransactionBlockBuilder::new(reader, sender);
+    let value = builder.pure(7u64).unwrap();
+    assert_eq!(value, Argument::Input(0));
+    let coins = builder
+        .split_coins(TxArg::Argument(builder.gas()), vec![10, 20])
+        .await
+        .unwrap();
+    assert_eq!(
+        coins,
+        vec![Argument::NestedResult(0, 0), Argument::NestedResult(0, 1)]
+    );
+    builder
+        .transfer_objects(coins.into_iter().map(TxArg::from).collect(), recipient)
+        .await
+        .unwrap();
+    let vector = builder
+        .make_move_vec(None, vec![TxArg::pure(&1u64).unwrap(), value.into()])
+        .await
+        .unwrap();
+    assert_eq!(vector.arg(), Argument::Result(2));
+
+    let kind = builder.finish();
+    assert_eq!(
+        inputs(kind.clone()),
+        vec![
+            CallArg::Pure(bcs::to_bytes(&7u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&10u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&20u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&recipient).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&1u64).unwrap()),
+        ]
+    );
+    assert_eq!(
+        commands(kind),
+        vec![
+            Command::SplitCoins(
+                Argument::GasCoin,
+                vec![Argument::Input(1), Argument::Input(2)]
+            ),
+            Command::TransferObjects(
+                vec![Argument::NestedResult(0, 0), Argument::NestedResult(0, 1)],
+                Argument::Input(3)
+            ),
+            Command::MakeMoveVec(None, vec![Argument::Input(4), Argument::Input(0)]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_build_selects_gas_coins() {
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+    let small_coin = Object::new_gas_with_balance_and_owner_for_testing(1_000, sponsor);
+    let large_coin = Object::new_gas_with_balance_and_owner_for_testing(1_000_000, sponsor);
+    let sender_coin = Object::new_gas_with_balance_and_owner_for_testing(1_000_000, sender);
+    let reader = TestReader::new([small_coin, large_coin.clone(), sender_coin.clone()]);
+
+    let mut builder = TransactionBlockBuilder::new(reader, sender)
+        .with_sponsor(sponsor)
+        .with_gas_budget(500_000);
+    builder
+        .transfer_objects(vec![sender_coin.id().into()], sponsor)
+        .await
+        .unwrap();
+    let data = builder.build().await.unwrap();
+
+    assert_eq!(data.sender(), sender);
+    assert_eq!(data.gas_owner(), sponsor);
+    assert_eq!(data.gas_budget(), 500_000);
+    assert_eq!(data.gas_price(), GAS_PRICE);
+    assert_eq!(data.gas(), &[large_coin.compute_object_reference()]);
+
+    // The budget cannot be estimated without a dry run.
+    let builder = TransactionBlockBuilder::new(TestReader::new([]), sender);
+    assert!(builder.build().await.is_err());
+}
@@ -651,6 +651,13 @@ impl RpcStateReader for PersistedStoreInnerReadOnlyWrapper {
     fn indexes(&self) -> Option<&dyn sui_types::storage::RpcIndexes> {
         None
     }
+
+    fn get_object_congestion_info(
+        &self,
+        _object_id: &ObjectID,
+    ) -> Option<sui_types::storage::ObjectCongestionInfo> {
+        None
+    }
 }
 
 impl PersistedStoreInnerReadOnlyWrapper {
@@ -26,8 +26,10 @@ use move_core_types::language_storage::ModuleId;
 pub use object_store_trait::ObjectStore;
 pub use read_store::AccountOwnedObjectInfo;
 pub use read_store::CoinInfo;
+pub use read_store::CongestionCancellation;
 pub use read_store::DynamicFieldIndexInfo;
 pub use read_store::DynamicFieldKey;
+pub use read_store::ObjectCongestionInfo;
 pub use read_store::ReadStore;
 pub use read_store::RpcIndexes;
 pub use read_store::RpcStateReader;
@@ -610,6 +610,12 @@ pub trait RpcStateReader: ObjectStore + ReadStore + Send + Sync {
 
     // Get a handle to an instance of the RpcIndexes
     fn indexes(&self) -> Option<&dyn RpcIndexes>;
+
+    /// Congestion status of a shared object as observed in recently executed checkpoints.
+    ///
+    /// Returns `None` if the object has not seen any cancellations recently or if congestion is
+    /// not tracked by this store.
+    fn get_object_congestion_info(&self, object_id: &ObjectID) -> Option<ObjectCongestionInfo>;
 }
 
 pub type DynamicFieldIteratorItem =
@@ -678,3 +684,31 @@ pub struct CoinInfo {
     pub coin_metadata_object_id: Option<ObjectID>,
     pub treasury_object_id: Option<ObjectID>,
 }
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct ObjectCongestionInfo {
+    pub object_id: ObjectID,
+    /// Timestamp of the latest checkpoint in which a transaction touching this object was
+    /// cancelled due to congestion.
+    pub last_cancellation_timestamp_ms: u64,
+    /// Highest gas price of the transactions cancelled in that checkpoint.
+    pub highest_cancelled_gas_price: u64,
+    /// Timestamp of the latest checkpoint in which a transaction mutating this object executed
+    /// after the object was first seen congested.
+    pub last_success_timestamp_ms: Option<u64>,
+    /// Lowest gas price of the transactions that executed in that checkpoint.
+    pub lowest_executed_gas_price: Option<u64>,
+    /// Gas price a transaction mutating this object should bid to avoid cancellation, if the
+    /// object is still considered congested.
+    pub suggested_gas_price: Option<u64>,
+    /// Per-checkpoint cancellation summaries, oldest first.
+    pub recent_cancellations: Vec<CongestionCancellation>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct CongestionCancellation {
+    pub timestamp_ms: u64,
+    /// Number of transactions touching the object that were cancelled in the checkpoint.
+    pub cancelled_transactions: u64,
+    pub highest_cancelled_gas_price: u64,
+}
@@ -71,7 +71,6 @@ use sui_types::{
     crypto::{EmptySignInfo, SignatureScheme},
     digests::TransactionDigest,
     error::SuiError,
-    gas::GasCostSummary,
     gas_coin::GasCoin,
     message_envelope::Envelope,
     metrics::BytecodeVerifierMetrics,
@@ -107,8 +106,7 @@ mod profiler_tests;
 
 static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
-/// Only to be used within CLI
-pub const GAS_SAFE_OVERHEAD: u64 = 1000;
+pub use sui_transaction_builder::{estimate_gas_budget_from_gas_cost, GAS_SAFE_OVERHEAD};
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
@@ -2908,17 +2906,6 @@ pub async fn estimate_gas_budget(
     }
 }
 
-pub fn estimate_gas_budget_from_gas_cost(
-    gas_cost_summary: &GasCostSummary,
-    reference_gas_price: u64,
-) -> u64 {
-    let safe_overhead = GAS_SAFE_OVERHEAD * reference_gas_price;
-    let computation_cost_with_overhead = gas_cost_summary.computation_cost + safe_overhead;
-
-    let gas_usage = gas_cost_summary.net_gas_usage() + safe_overhead as i64;
-    computation_cost_with_overhead.max(if gas_usage < 0 { 0 } else { gas_usage as u64 })
-}
-
 /// Queries the protocol config for the maximum gas allowed in a transaction.
 pub async fn max_gas_budget(client: &SuiClient) -> Result<u64, anyhow::Error> {
     let cfg = client.read_api().get_protocol_config(None).await?;
@@ -0,0 +1,51 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_move_build::OnChainPackageSource;
+use sui_sdk::SuiClientBuilder;
+use sui_types::{base_types::ObjectID, move_package::MovePackage, object::Object};
+
+/// Fetches on-chain dependencies from the fullnode of a client environment.
+pub struct RpcPackageSource {
+    rpc_url: String,
+}
+
+impl RpcPackageSource {
+    pub fn new(rpc_url: String) -> Self {
+        Self { rpc_url }
+    }
+
+    async fn fetch(&self, id: ObjectID) -> anyhow::Result<MovePackage> {
+        let client = SuiClientBuilder::default().build(&self.rpc_url).await?;
+        let object: Object = client
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?
+            .try_into()?;
+        object
+            .data
+            .try_as_package()
+            .cloned()
+            .ok_or_else(|| anyhow!("Object {id} is not a package"))
+    }
+}
+
+impl OnChainPackageSource for RpcPackageSource {
+    fn fetch_package(&self, id: ObjectID) -> anyhow::Result<MovePackage> {
+        // Package resolution is synchronous but may run inside the CLI's runtime, so the request
+        // is made from a separate thread with its own runtime.
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?
+                    .block_on(self.fetch(id))
+            })
+            .join()
+            .map_err(|_| anyhow!("Fetching package {id} panicked"))?
+        })
+    }
+}
@@ -5,6 +5,7 @@ use crate::client_commands::{pkg_tree_shake, SuiClientCommands};
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
+use crate::on_chain_packages::RpcPackageSource;
 use crate::validator_commands::SuiValidatorCommand;
 use anyhow::{anyhow, bail, ensure, Context};
 use clap::*;
@@ -49,7 +50,7 @@ use sui_move::manage_package::resolve_lock_file_path;
 use sui_move::{self, execute_move_command};
 use sui_move_build::{
     check_invalid_dependencies, check_unpublished_dependencies, implicit_deps,
-    BuildConfig as SuiBuildConfig, SuiPackageHooks,
+    set_on_chain_package_source, BuildConfig as SuiBuildConfig, SuiPackageHooks,
 };
 use sui_package_management::system_package_versions::latest_system_packages;
 use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
@@ -489,6 +490,17 @@ impl SuiCommand {
                 cmd,
                 config: client_config,
             } => {
+                // On-chain dependencies are fetched from the active environment, if any.
+                let config_path = match &client_config {
+                    Some(path) => Some(path.clone()),
+                    None => sui_config_dir().ok().map(|dir| dir.join(SUI_CLIENT_CONFIG)),
+                };
+                if let Some(env) = config_path
+                    .and_then(|path| PersistedConfig::<SuiClientConfig>::read(&path).ok())
+                    .and_then(|config| config.get_active_env().ok().cloned())
+                {
+                    set_on_chain_package_source(Arc::new(RpcPackageSource::new(env.rpc)));
+                }
                 match cmd {
                     sui_move::Command::Build(build) if build.dump_bytecode_as_base64 => {
                         // `sui move build` does not ordinarily require a network connection.
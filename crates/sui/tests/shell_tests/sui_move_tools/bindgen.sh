@@ -0,0 +1,5 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# generates Rust bindings for a package, printing the modules, types and functions generated
+sui move bindgen -p example 2> /dev/null | grep -E '^ *pub (mod|struct|enum|fn) '
@@ -0,0 +1,6 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# prints the calls and datatypes used by the functions of a package, and the packages it depends on
+sui move call-graph -p example 2> /dev/null | grep '^    "example::.* -> '
+sui move call-graph -p example --packages 2> /dev/null | grep '"example"'
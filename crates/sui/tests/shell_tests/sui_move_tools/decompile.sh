@@ -0,0 +1,7 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# decompiles a module built by `sui move build`, printing its declarations
+sui move build -p example > /dev/null 2>&1
+sui move decompile example/build/example/bytecode_modules/counter.mv \
+  | grep -E '^(module|    (public |entry )*(struct|fun)) '
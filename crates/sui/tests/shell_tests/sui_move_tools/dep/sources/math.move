@@ -0,0 +1,8 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module dep::math;
+
+public fun double(x: u64): u64 {
+    x * 2
+}
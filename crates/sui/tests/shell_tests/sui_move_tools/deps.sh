@@ -0,0 +1,7 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# prints the dependency tree of a package (leaving out the system packages, which are git
+# dependencies), then checks that there is nothing to update
+sui move deps tree -p example 2> /dev/null | grep -v "git:"
+sui move deps update -p example 2> /dev/null
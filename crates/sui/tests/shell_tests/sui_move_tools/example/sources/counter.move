@@ -0,0 +1,23 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module example::counter;
+
+use dep::math;
+
+public struct Counter has key {
+    id: UID,
+    value: u64,
+}
+
+entry fun create(ctx: &mut TxContext) {
+    transfer::share_object(Counter { id: object::new(ctx), value: 1 })
+}
+
+public fun double(counter: &mut Counter) {
+    counter.value = math::double(counter.value)
+}
+
+public fun value(counter: &Counter): u64 {
+    counter.value
+}
@@ -0,0 +1,8 @@
+# Copyright (c) Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+
+# compares two directories of gas profiles, and converts a profile to folded stacks
+sui move gas-profile compare profiles/base profiles/new
+sui move gas-profile compare profiles/base profiles/new --max-increase 10 > /dev/null 2>&1 \
+  || echo "gas usage increased by more than 10%"
+sui move gas-profile flamegraph profiles/new/test_a.json
@@ -37,6 +37,11 @@ pub mod span_latency_prom;
 /// Alias for a type-erased error type.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
+/// Name of the span field which, when set to `true` on a root span, exports its trace
+/// regardless of the configured sample rate. This is meant for callers that already sample
+/// what they trace. The span still has to be enabled by `TRACE_FILTER`.
+pub const FORCE_SAMPLE_FIELD: &str = "force_sample";
+
 /// Configuration for different logging/tracing options
 /// ===
 /// - json_log_output: Output JSON logs to stdout only.
@@ -515,8 +520,15 @@ impl ShouldSample for SamplingFilter {
         attributes: &[KeyValue],
         links: &[Link],
     ) -> SamplingResult {
-        let sample_rate = self.sample_rate.load(Ordering::Relaxed);
-        let sampler = Sampler::TraceIdRatioBased(sample_rate);
+        let force_sample = attributes.iter().any(|attribute| {
+            attribute.key.as_str() == FORCE_SAMPLE_FIELD
+                && attribute.value == opentelemetry::Value::Bool(true)
+        });
+        let sampler = if force_sample {
+            Sampler::AlwaysOn
+        } else {
+            Sampler::TraceIdRatioBased(self.sample_rate.load(Ordering::Relaxed))
+        };
 
         sampler.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
     }
@@ -590,6 +602,22 @@ mod tests {
         panic!("This should cause error logs to be printed out!");
     }
 
+    #[test]
+    fn test_sampling_filter_force_sample() {
+        let sampler = SamplingFilter::new(0.0001);
+        let should_sample = |attributes: &[KeyValue]| {
+            // This trace id is above the sampling threshold of the ratio based sampler.
+            let trace_id = TraceId::from_u128(u128::MAX);
+            let result =
+                sampler.should_sample(None, trace_id, "span", &SpanKind::Internal, attributes, &[]);
+            result.decision == opentelemetry::trace::SamplingDecision::RecordAndSample
+        };
+
+        assert!(!should_sample(&[]));
+        assert!(!should_sample(&[KeyValue::new(FORCE_SAMPLE_FIELD, false)]));
+        assert!(should_sample(&[KeyValue::new(FORCE_SAMPLE_FIELD, true)]));
+    }
+
     // Both the following tests should be able to "race" to initialize logging without causing a
     // panic
     #[test]
@@ -8,16 +8,24 @@ use clap::*;
 use move_binary_format::CompiledModule;
 use move_command_line_common::files::MOVE_COVERAGE_MAP_EXTENSION;
 use move_compiler::{
+    cfgir::ast as G,
     diagnostics::{self, Diagnostics},
     shared::{NumberFormat, NumericalAddress},
     unit_test::{plan_builder::construct_test_plan, TestPlan},
     PASS_CFGIR,
 };
+use move_core_types::language_storage::ModuleId;
 use move_coverage::coverage_map::{output_map_to_file, CoverageMap};
 use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
-use move_unit_test::UnitTestingConfig;
+use move_symbol_pool::Symbol;
+use move_unit_test::{mutation::MutationTestResults, UnitTestingConfig};
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{io::Write, path::Path, process::ExitStatus};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    path::Path,
+    process::ExitStatus,
+};
 // if windows
 #[cfg(target_family = "windows")]
 use std::os::windows::process::ExitStatusExt;
@@ -72,6 +80,17 @@ pub struct Test {
     // Enable tracing for tests
     #[clap(long = "trace-execution", value_name = "PATH")]
     pub trace_execution: Option<Option<String>>,
+
+    /// Write a gas profile for each test to the given directory (`gas_profiles` by default). The
+    /// profiles can be compared with those of another run, or viewed as a flamegraph.
+    #[clap(long = "gas-profile", value_name = "PATH")]
+    pub gas_profile: Option<Option<String>>,
+
+    /// Run mutation testing: re-run the tests against versions of the package's code with a single
+    /// operator, constant or condition changed, and report the changes that no test catches. Fails
+    /// if any such change survives.
+    #[clap(long = "mutate", conflicts_with_all = ["compute_coverage", "list"])]
+    pub mutate: bool,
 }
 
 impl Test {
@@ -83,6 +102,23 @@ impl Test {
         cost_table: Option<CostTable>,
     ) -> anyhow::Result<()> {
         let rerooted_path = reroot_path(path)?;
+        if self.mutate {
+            let (results, _) = run_move_mutation_tests(
+                &rerooted_path,
+                config,
+                self.unit_test_config(),
+                natives,
+                cost_table,
+                &mut std::io::stdout(),
+            )?;
+
+            // Return a non-zero exit code if any mutant survived
+            if !results.survived.is_empty() {
+                std::process::exit(1)
+            }
+            return Ok(());
+        }
+
         let compute_coverage = self.compute_coverage;
         // save disassembly if trace execution is enabled
         let save_disassembly = self.trace_execution.is_some();
@@ -116,6 +152,8 @@ impl Test {
             seed,
             rand_num_iters,
             trace_execution,
+            gas_profile,
+            mutate: _,
         } = self;
         UnitTestingConfig {
             gas_limit,
@@ -127,6 +165,7 @@ impl Test {
             seed,
             rand_num_iters,
             trace_execution,
+            gas_profile,
             ..UnitTestingConfig::default_with_bound(None)
         }
     }
@@ -141,7 +180,7 @@ pub enum UnitTestResult {
 
 pub fn run_move_unit_tests<W: Write + Send>(
     pkg_path: &Path,
-    mut build_config: move_package::BuildConfig,
+    build_config: move_package::BuildConfig,
     mut unit_test_config: UnitTestingConfig,
     natives: Vec<NativeFunctionRecord>,
     cost_table: Option<CostTable>,
@@ -149,6 +188,104 @@ pub fn run_move_unit_tests<W: Write + Send>(
     save_disassembly: bool,
     writer: &mut W,
 ) -> Result<(UnitTestResult, Option<Diagnostics>)> {
+    let PackageTestPlan {
+        test_plan,
+        no_tests,
+        warning_diags,
+        ..
+    } = build_test_plan(
+        pkg_path,
+        build_config,
+        &mut unit_test_config,
+        save_disassembly,
+        writer,
+    )?;
+
+    let trace_path = pkg_path.join(".trace");
+    let coverage_map_path = pkg_path
+        .join(".coverage_map")
+        .with_extension(MOVE_COVERAGE_MAP_EXTENSION);
+    let cleanup_trace = || {
+        if compute_coverage && trace_path.exists() {
+            std::fs::remove_file(&trace_path).unwrap();
+        }
+    };
+
+    cleanup_trace();
+
+    // If we need to compute test coverage set the VM tracking environment variable since we will
+    // need this trace to construct the coverage information.
+    if compute_coverage {
+        std::env::set_var("MOVE_VM_TRACE", &trace_path);
+    }
+
+    // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
+    // the trace files.
+    if !unit_test_config
+        .run_and_report_unit_tests(test_plan, Some(natives), cost_table, writer)?
+        .1
+    {
+        cleanup_trace();
+        return Ok((UnitTestResult::Failure, warning_diags));
+    }
+
+    // Compute the coverage map. This will be used by other commands after this.
+    if compute_coverage && !no_tests {
+        let coverage_map = CoverageMap::from_trace_file(trace_path);
+        output_map_to_file(coverage_map_path, &coverage_map).unwrap();
+    }
+    Ok((UnitTestResult::Success, warning_diags))
+}
+
+/// Run the unit tests of the package at `pkg_path` against mutated versions of its (non-test)
+/// functions, and report the mutants that none of the tests catch.
+pub fn run_move_mutation_tests<W: Write + Send>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    writer: &mut W,
+) -> Result<(MutationTestResults, Option<Diagnostics>)> {
+    let PackageTestPlan {
+        test_plan,
+        warning_diags,
+        mutation_targets,
+        ..
+    } = build_test_plan(
+        pkg_path,
+        build_config,
+        &mut unit_test_config,
+        /* save_disassembly */ false,
+        writer,
+    )?;
+
+    let results = unit_test_config.run_mutation_tests(
+        test_plan,
+        &mutation_targets,
+        Some(natives),
+        cost_table,
+        writer,
+    )?;
+    Ok((results, warning_diags))
+}
+
+struct PackageTestPlan {
+    test_plan: TestPlan,
+    no_tests: bool,
+    warning_diags: Option<Diagnostics>,
+    /// The functions of the root package that are not test or test-only code, by module.
+    mutation_targets: BTreeMap<ModuleId, BTreeSet<Symbol>>,
+}
+
+/// Build the package at `pkg_path` in test mode and construct the plan for running its unit tests.
+fn build_test_plan<W: Write>(
+    pkg_path: &Path,
+    mut build_config: move_package::BuildConfig,
+    unit_test_config: &mut UnitTestingConfig,
+    save_disassembly: bool,
+    writer: &mut W,
+) -> Result<PackageTestPlan> {
     let mut test_plan = None;
     build_config.test_mode = true;
     build_config.dev_mode = true;
@@ -197,6 +334,7 @@ pub fn run_move_unit_tests<W: Write + Send>(
     // then save it, before resuming the rest of the compilation and returning the results and
     // control back to the Move package system.
     let mut warning_diags = None;
+    let mut non_test_functions = BTreeMap::new();
     build_plan.compile_with_driver(writer, |compiler| {
         let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>().unwrap();
         let compiler =
@@ -204,6 +342,7 @@ pub fn run_move_unit_tests<W: Write + Send>(
         let (compiler, cfgir) = compiler.into_ast();
         let compilation_env = compiler.compilation_env();
         let built_test_plan = construct_test_plan(compilation_env, Some(root_package), &cfgir);
+        non_test_functions = root_non_test_functions(root_package, &cfgir);
         let mapped_files = compilation_env.mapped_files().clone();
 
         let compilation_result = compiler.at_cfgir(cfgir).build();
@@ -225,40 +364,45 @@ pub fn run_move_unit_tests<W: Write + Send>(
     let no_tests = test_plan.is_empty();
     let test_plan = TestPlan::new(test_plan, mapped_files, units, bytecode_deps_modules);
 
-    let trace_path = pkg_path.join(".trace");
-    let coverage_map_path = pkg_path
-        .join(".coverage_map")
-        .with_extension(MOVE_COVERAGE_MAP_EXTENSION);
-    let cleanup_trace = || {
-        if compute_coverage && trace_path.exists() {
-            std::fs::remove_file(&trace_path).unwrap();
-        }
-    };
-
-    cleanup_trace();
-
-    // If we need to compute test coverage set the VM tracking environment variable since we will
-    // need this trace to construct the coverage information.
-    if compute_coverage {
-        std::env::set_var("MOVE_VM_TRACE", &trace_path);
-    }
+    let mutation_targets = test_plan
+        .module_info
+        .iter()
+        .filter(|(_, unit)| unit.package_name == Some(root_package))
+        .filter_map(|(module_id, unit)| {
+            let functions = non_test_functions.get(&unit.name)?;
+            Some((module_id.clone(), functions.clone()))
+        })
+        .collect();
 
-    // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
-    // the trace files.
-    if !unit_test_config
-        .run_and_report_unit_tests(test_plan, Some(natives), cost_table, writer)?
-        .1
-    {
-        cleanup_trace();
-        return Ok((UnitTestResult::Failure, warning_diags));
-    }
+    Ok(PackageTestPlan {
+        test_plan,
+        no_tests,
+        warning_diags,
+        mutation_targets,
+    })
+}
 
-    // Compute the coverage map. This will be used by other commands after this.
-    if compute_coverage && !no_tests {
-        let coverage_map = CoverageMap::from_trace_file(trace_path);
-        output_map_to_file(coverage_map_path, &coverage_map).unwrap();
-    }
-    Ok((UnitTestResult::Success, warning_diags))
+/// The functions in each (non-test-only) module of `root_package` that are not tests or test-only
+/// functions, keyed by module name.
+fn root_non_test_functions(
+    root_package: Symbol,
+    prog: &G::Program,
+) -> BTreeMap<Symbol, BTreeSet<Symbol>> {
+    prog.modules
+        .key_cloned_iter()
+        .filter(|(_, mdef)| {
+            mdef.package_name == Some(root_package) && !mdef.attributes.is_test_or_test_only()
+        })
+        .map(|(mident, mdef)| {
+            let functions = mdef
+                .functions
+                .iter()
+                .filter(|(_, _, fdef)| !fdef.attributes.is_test_or_test_only())
+                .map(|(_, name, _)| *name)
+                .collect();
+            (mident.value.module.0.value, functions)
+        })
+        .collect()
 }
 
 impl From<UnitTestResult> for ExitStatus {
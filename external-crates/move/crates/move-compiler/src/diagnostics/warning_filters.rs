@@ -103,6 +103,8 @@ unsafe impl Sync for WarningFilters {}
 /// Used to filter out diagnostics, specifically used for warning suppression
 pub struct WarningFiltersBuilder {
     filters: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
+    /// Warnings that are reported as errors, unless they are filtered
+    denied: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
     for_dependency: bool, // if false, the filters are used for source code
 }
 
@@ -195,6 +197,23 @@ impl WarningFiltersScope {
         }
     }
 
+    /// Returns true if the warning should be reported as an error in this scope
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        let mut scope = &self.0;
+        loop {
+            match scope {
+                WarningFiltersScope_::Empty => return false,
+                WarningFiltersScope_::Static(filters) => return filters.is_denied(diag),
+                WarningFiltersScope_::Node(node) => {
+                    if node.filters.is_denied(diag) {
+                        return true;
+                    }
+                    scope = &node.prev;
+                }
+            }
+        }
+    }
+
     pub fn is_filtered_for_dependency(&self) -> bool {
         let mut scope = &self.0;
         loop {
@@ -247,6 +266,10 @@ impl WarningFilters {
         self.borrow().is_filtered(diag)
     }
 
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        self.borrow().is_denied(diag)
+    }
+
     pub fn for_dependency(&self) -> bool {
         self.borrow().for_dependency()
     }
@@ -260,6 +283,7 @@ impl WarningFiltersBuilder {
     pub const fn new_for_source() -> Self {
         Self {
             filters: BTreeMap::new(),
+            denied: BTreeMap::new(),
             for_dependency: false,
         }
     }
@@ -267,6 +291,7 @@ impl WarningFiltersBuilder {
     pub const fn new_for_dependency() -> Self {
         Self {
             filters: BTreeMap::new(),
+            denied: BTreeMap::new(),
             for_dependency: true,
         }
     }
@@ -282,6 +307,15 @@ impl WarningFiltersBuilder {
             .is_some_and(|filters| filters.is_filtered_by_info(info))
     }
 
+    /// Returns true if the warning should be reported as an error. Filtered warnings are not
+    /// reported at all, so `is_filtered` takes precedence.
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        let prefix = diag.info.external_prefix();
+        self.denied
+            .get(&prefix)
+            .is_some_and(|filters| filters.is_filtered_by_info(&diag.info))
+    }
+
     pub fn union(&mut self, other: &Self) {
         for (prefix, filters) in &other.filters {
             self.filters
@@ -289,6 +323,12 @@ impl WarningFiltersBuilder {
                 .or_insert_with(UnprefixedWarningFilters::new)
                 .union(filters);
         }
+        for (prefix, filters) in &other.denied {
+            self.denied
+                .entry(*prefix)
+                .or_insert_with(UnprefixedWarningFilters::new)
+                .union(filters);
+        }
         // if there is a dependency code filter on the stack, it means we are filtering dependent
         // code and this information must be preserved when stacking up additional filters (which
         // involves union of the current filter with the new one)
@@ -296,9 +336,21 @@ impl WarningFiltersBuilder {
     }
 
     pub fn add(&mut self, filter: WarningFilter) {
+        Self::add_to(&mut self.filters, filter)
+    }
+
+    /// Report the warnings matching `filter` as errors, unless they are also filtered.
+    pub fn deny(&mut self, filter: WarningFilter) {
+        Self::add_to(&mut self.denied, filter)
+    }
+
+    fn add_to(
+        filters: &mut BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
+        filter: WarningFilter,
+    ) {
         let (prefix, category, code, name) = match filter {
             WarningFilter::All(prefix) => {
-                self.filters.insert(prefix, UnprefixedWarningFilters::All);
+                filters.insert(prefix, UnprefixedWarningFilters::All);
                 return;
             }
             WarningFilter::Category {
@@ -313,7 +365,7 @@ impl WarningFiltersBuilder {
                 name,
             } => (prefix, category, Some(code), name),
         };
-        self.filters
+        filters
             .entry(prefix)
             .or_insert(UnprefixedWarningFilters::Empty)
             .add(category, code, name)
@@ -325,6 +377,7 @@ impl WarningFiltersBuilder {
                 None,
                 UnprefixedWarningFilters::unused_warnings_filter_for_test(),
             )]),
+            denied: BTreeMap::new(),
             for_dependency: false,
         }
     }
@@ -1,6 +1,8 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::{Arc, RwLock};
+
 use move_symbol_pool::Symbol;
 
 use crate::{
@@ -36,6 +38,17 @@ pub enum LintLevel {
     All,
 }
 
+/// How a lint is reported for a package, as set in the `[lints]` section of its manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    /// Do not report the lint
+    Allow,
+    /// Report the lint as a warning
+    Warn,
+    /// Report the lint as an error
+    Deny,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum LinterDiagnosticCategory {
@@ -44,6 +57,9 @@ pub enum LinterDiagnosticCategory {
     Suspicious,
     Deprecated,
     Style,
+    /// First category available to lint packs registered with `register_lint_pack`. Each pack
+    /// should use its own category, below `Sui`.
+    Custom = 50,
     Sui = 99,
 }
 
@@ -181,6 +197,48 @@ lints!(
 pub const ALLOW_ATTR_CATEGORY: &str = "lint";
 pub const LINT_WARNING_PREFIX: &str = "Lint ";
 
+/// A set of lints run by the package system, alongside the built-in linters. Custom packs can be
+/// registered with `register_lint_pack` (e.g. by a custom build of the CLI), and are run on the
+/// typed AST or CFGIR in the same way as the built-in linters.
+///
+/// The lints of a pack must report diagnostics with the `LINT_WARNING_PREFIX` prefix, and a
+/// category of `LinterDiagnosticCategory::Custom` or above that is not used by other packs.
+pub trait LintPack: Send + Sync {
+    /// The filters for the lints that run at `level`. The names of these filters are used to
+    /// suppress the lints with `#[allow(lint(...))]`, and to configure them in a package manifest.
+    fn filters(&self, level: LintLevel) -> Vec<WarningFilter>;
+
+    /// The visitors implementing the lints that run at `level`.
+    fn visitors(&self, level: LintLevel) -> Vec<Visitor>;
+}
+
+static LINT_PACKS: RwLock<Vec<Arc<dyn LintPack>>> = RwLock::new(Vec::new());
+
+/// Register a lint pack to be run whenever a package is built.
+pub fn register_lint_pack(pack: impl LintPack + 'static) {
+    LINT_PACKS.write().unwrap().push(Arc::new(pack))
+}
+
+pub fn registered_lint_packs() -> Vec<Arc<dyn LintPack>> {
+    LINT_PACKS.read().unwrap().clone()
+}
+
+/// The built-in linters for Move code, available in all flavors.
+pub struct MoveLints;
+
+impl LintPack for MoveLints {
+    fn filters(&self, level: LintLevel) -> Vec<WarningFilter> {
+        match level {
+            LintLevel::None | LintLevel::Default => vec![],
+            LintLevel::All => known_filters().1,
+        }
+    }
+
+    fn visitors(&self, level: LintLevel) -> Vec<Visitor> {
+        linter_visitors(level)
+    }
+}
+
 pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
     (
         Some(ALLOW_ATTR_CATEGORY.into()),
@@ -7,7 +7,9 @@ use crate::{
     diagnostics::warning_filters::WarningFilter,
     expansion::ast as E,
     hlir::ast::{BaseType_, SingleType, SingleType_},
-    linters::{LintLevel, LinterDiagnosticCategory, ALLOW_ATTR_CATEGORY, LINT_WARNING_PREFIX},
+    linters::{
+        LintLevel, LintPack, LinterDiagnosticCategory, ALLOW_ATTR_CATEGORY, LINT_WARNING_PREFIX,
+    },
     typing::visitor::TypingVisitor,
 };
 use move_ir_types::location::Loc;
@@ -184,6 +186,34 @@ pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
     }
 }
 
+/// The Sui linters, run by the package system for packages in Sui mode.
+pub struct SuiLints;
+
+impl LintPack for SuiLints {
+    fn filters(&self, level: LintLevel) -> Vec<WarningFilter> {
+        // lints only run with `LintLevel::All`
+        let not_default = [
+            FREEZING_CAPABILITY_FILTER_NAME,
+            PREFER_MUTABLE_TX_CONTEXT_FILTER_NAME,
+        ];
+        known_filters()
+            .1
+            .into_iter()
+            .filter(|filter| match (filter, level) {
+                (WarningFilter::All(_), _) | (_, LintLevel::None) => false,
+                (_, LintLevel::Default) => filter
+                    .to_str()
+                    .is_some_and(|name| !not_default.contains(&name)),
+                (_, LintLevel::All) => true,
+            })
+            .collect()
+    }
+
+    fn visitors(&self, level: LintLevel) -> Vec<Visitor> {
+        linter_visitors(level)
+    }
+}
+
 /// Returns abilities of a given type, if any.
 pub fn type_abilities(sp!(_, st_): &SingleType) -> Option<E::AbilitySet> {
     let sp!(_, bt_) = match st_ {
@@ -0,0 +1,518 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The structured program recovered from a function's bytecode, and its rendering as Move source.
+//! Names of locals, types and functions are resolved while the program is built, so rendering does
+//! not need the module.
+
+use move_abstract_interpreter::control_flow_graph::BlockId;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Write},
+};
+
+const INDENT: &str = "    ";
+
+#[derive(Debug, Clone)]
+pub(crate) enum Exp {
+    /// A local.
+    Name(String),
+    /// A temporary, which is never assigned to again.
+    Temp(String),
+    /// A literal or constant.
+    Value(String),
+    IfElse(Box<Exp>, Box<Exp>, Box<Exp>),
+    Call {
+        function: String,
+        type_args: Vec<String>,
+        args: Vec<Exp>,
+    },
+    Unary(&'static str, Box<Exp>),
+    Binary(&'static str, Box<Exp>, Box<Exp>),
+    Cast(Box<Exp>, &'static str),
+    Borrow {
+        mut_: bool,
+        exp: Box<Exp>,
+    },
+    BorrowField {
+        mut_: bool,
+        exp: Box<Exp>,
+        field: String,
+    },
+    Deref(Box<Exp>),
+    /// Packs a struct or an enum variant. Variants declared without fields are packed without
+    /// braces.
+    Pack {
+        name: String,
+        fields: Vec<(String, Exp)>,
+        braces: bool,
+    },
+    Vector {
+        type_: String,
+        elems: Vec<Exp>,
+    },
+    /// Extracts the fields of a value (or reference) known to be the given variant, as
+    /// `match (exp) { E::V { f: x0 } => x0, _ => abort 0 }`.
+    UnpackVariant {
+        variant: String,
+        fields: Vec<String>,
+        braces: bool,
+        exp: Box<Exp>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Stmt {
+    /// Binds temporaries holding values that could not be assigned to locals directly.
+    Let(Vec<String>, Exp),
+    /// Assigns to locals, where `_` discards the value.
+    Assign(Vec<String>, Exp),
+    /// Unpacks a struct, assigning each field to a local (or `_`), or binding it to a temporary.
+    Unpack {
+        bind: bool,
+        name: String,
+        fields: Vec<(String, String)>,
+        exp: Exp,
+    },
+    Exp(Exp),
+    WriteRef(Exp, Exp),
+    Return(Vec<Exp>),
+    Abort(Exp),
+    Assert(Exp, Exp),
+    If(Exp, Vec<Stmt>, Vec<Stmt>),
+    /// A loop, identified by the block of its header.
+    Loop(BlockId, Vec<Stmt>),
+    While(BlockId, Exp, Vec<Stmt>),
+    Break(BlockId),
+    Continue(BlockId),
+    /// Switches on a reference to an enum. Each arm lists the patterns of the variants that share
+    /// its body.
+    Match(Exp, Vec<(Vec<String>, Vec<Stmt>)>),
+}
+
+impl Exp {
+    pub(crate) fn call(function: String, type_args: Vec<String>, args: Vec<Exp>) -> Self {
+        Exp::Call {
+            function,
+            type_args,
+            args,
+        }
+    }
+
+    /// The negation of a condition, simplifying negated comparisons.
+    pub(crate) fn negate(self) -> Self {
+        match self {
+            Exp::Unary("!", e) => *e,
+            Exp::Binary(op, l, r) => {
+                let negated = match op {
+                    "==" => "!=",
+                    "!=" => "==",
+                    "<" => ">=",
+                    ">=" => "<",
+                    ">" => "<=",
+                    "<=" => ">",
+                    _ => return Exp::Unary("!", Box::new(Exp::Binary(op, l, r))),
+                };
+                Exp::Binary(negated, l, r)
+            }
+            e => Exp::Unary("!", Box::new(e)),
+        }
+    }
+
+    fn is_atomic(&self) -> bool {
+        !matches!(
+            self,
+            Exp::Unary(..)
+                | Exp::Binary(..)
+                | Exp::Borrow { .. }
+                | Exp::BorrowField { .. }
+                | Exp::Deref(..)
+                | Exp::IfElse(..)
+                | Exp::UnpackVariant { .. }
+        )
+    }
+
+    fn render_atomic(&self, f: &mut String) -> fmt::Result {
+        if self.is_atomic() {
+            self.render(f)
+        } else {
+            f.push('(');
+            self.render(f)?;
+            f.push(')');
+            Ok(())
+        }
+    }
+
+    /// Renders the place borrowed by a reference, so that field accesses through references to
+    /// locals or other fields read as paths (`x.f.g`).
+    fn render_place(&self, f: &mut String) -> fmt::Result {
+        match self {
+            Exp::Borrow { exp, .. } => exp.render_atomic(f),
+            Exp::BorrowField { exp, field, .. } => {
+                exp.render_place(f)?;
+                write!(f, ".{field}")
+            }
+            e => e.render_atomic(f),
+        }
+    }
+
+    fn render(&self, f: &mut String) -> fmt::Result {
+        match self {
+            Exp::Name(s) | Exp::Temp(s) | Exp::Value(s) => f.write_str(s),
+            Exp::IfElse(cond, t, e) => {
+                f.write_str("if (")?;
+                cond.render(f)?;
+                f.write_str(") ")?;
+                t.render_atomic(f)?;
+                f.write_str(" else ")?;
+                e.render_atomic(f)
+            }
+            Exp::Call {
+                function,
+                type_args,
+                args,
+            } => {
+                f.write_str(function)?;
+                fmt_type_args(f, type_args)?;
+                f.push('(');
+                fmt_comma_list(f, args, |f, arg| arg.render(f))?;
+                f.push(')');
+                Ok(())
+            }
+            Exp::Unary(op, e) => {
+                f.write_str(op)?;
+                e.render_atomic(f)
+            }
+            Exp::Binary(op, l, r) => {
+                l.render_atomic(f)?;
+                write!(f, " {op} ")?;
+                r.render_atomic(f)
+            }
+            Exp::Cast(e, ty) => {
+                f.push('(');
+                e.render_atomic(f)?;
+                write!(f, " as {ty})")
+            }
+            Exp::Borrow { mut_, exp } => {
+                f.write_str(if *mut_ { "&mut " } else { "&" })?;
+                exp.render_atomic(f)
+            }
+            Exp::BorrowField { mut_, .. } => {
+                f.write_str(if *mut_ { "&mut " } else { "&" })?;
+                self.render_place(f)
+            }
+            Exp::Deref(e) => match &**e {
+                Exp::Borrow { .. } | Exp::BorrowField { .. } => e.render_place(f),
+                e => {
+                    f.push('*');
+                    e.render_atomic(f)
+                }
+            },
+            Exp::Pack {
+                name,
+                fields,
+                braces,
+            } => {
+                f.write_str(name)?;
+                if *braces {
+                    f.write_str(" { ")?;
+                    fmt_comma_list(f, fields, |f, (name, exp)| {
+                        write!(f, "{name}: ")?;
+                        exp.render(f)
+                    })?;
+                    f.write_str(" }")?;
+                }
+                Ok(())
+            }
+            Exp::Vector { type_, elems } => {
+                write!(f, "vector<{type_}>[")?;
+                fmt_comma_list(f, elems, |f, elem| elem.render(f))?;
+                f.push(']');
+                Ok(())
+            }
+            Exp::UnpackVariant {
+                variant,
+                fields,
+                braces,
+                exp,
+            } => {
+                f.write_str("match (")?;
+                exp.render(f)?;
+                write!(f, ") {{ {variant}")?;
+                let binders: Vec<_> = (0..fields.len()).map(|i| format!("x{i}")).collect();
+                if *braces {
+                    f.write_str(" { ")?;
+                    fmt_comma_list(f, fields.iter().zip(&binders), |f, (field, binder)| {
+                        write!(f, "{field}: {binder}")
+                    })?;
+                    f.write_str(" }")?;
+                }
+                f.write_str(" => ")?;
+                fmt_tuple(f, &binders, |f, binder| f.write_str(binder))?;
+                f.write_str(", _ => abort 0 }")
+            }
+        }
+    }
+}
+
+/// Renders a function body, with its local declarations, at the given indentation.
+pub(crate) fn fmt_body(
+    f: &mut String,
+    declarations: &[String],
+    body: &[Stmt],
+    indent: usize,
+) -> fmt::Result {
+    let mut printer = Printer {
+        labels: BTreeSet::new(),
+        loops: vec![],
+        indent,
+    };
+    printer.collect_labels(body);
+    for declaration in declarations {
+        printer.line(f)?;
+        write!(f, "{declaration};")?;
+    }
+    // the last returned value is the value of the body
+    let (last, rest) = match body.split_last() {
+        Some((Stmt::Return(values), rest)) if !values.is_empty() => (Some(values), rest),
+        _ => (None, body),
+    };
+    printer.stmts(f, rest)?;
+    if let Some(values) = last {
+        printer.line(f)?;
+        fmt_tuple(f, values, |f, v| v.render(f))?;
+    }
+    Ok(())
+}
+
+struct Printer {
+    /// Loops that are the target of a `break` or `continue` from a nested loop
+    labels: BTreeSet<BlockId>,
+    loops: Vec<BlockId>,
+    indent: usize,
+}
+
+impl Printer {
+    fn collect_labels(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::If(_, t, e) => {
+                    self.collect_labels(t);
+                    self.collect_labels(e);
+                }
+                Stmt::Loop(id, body) | Stmt::While(id, _, body) => {
+                    self.loops.push(*id);
+                    self.collect_labels(body);
+                    self.loops.pop();
+                }
+                Stmt::Break(id) | Stmt::Continue(id) => {
+                    if self.loops.last() != Some(id) {
+                        self.labels.insert(*id);
+                    }
+                }
+                Stmt::Match(_, arms) => {
+                    for (_, body) in arms {
+                        self.collect_labels(body);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn line(&self, f: &mut String) -> fmt::Result {
+        f.push('\n');
+        for _ in 0..self.indent {
+            f.write_str(INDENT)?;
+        }
+        Ok(())
+    }
+
+    fn block(&mut self, f: &mut String, stmts: &[Stmt]) -> fmt::Result {
+        f.push('{');
+        self.indent += 1;
+        self.stmts(f, stmts)?;
+        self.indent -= 1;
+        if !stmts.is_empty() {
+            self.line(f)?;
+        }
+        f.push('}');
+        Ok(())
+    }
+
+    fn stmts(&mut self, f: &mut String, stmts: &[Stmt]) -> fmt::Result {
+        for stmt in stmts {
+            self.line(f)?;
+            self.stmt(f, stmt)?;
+        }
+        Ok(())
+    }
+
+    fn label(&self, f: &mut String, id: BlockId) -> fmt::Result {
+        if self.loops.last() != Some(&id) {
+            write!(f, " 'l{id}")?;
+        }
+        Ok(())
+    }
+
+    fn stmt(&mut self, f: &mut String, stmt: &Stmt) -> fmt::Result {
+        match stmt {
+            Stmt::Let(names, e) => {
+                f.write_str("let ")?;
+                fmt_tuple(f, names, |f, name| f.write_str(name))?;
+                f.write_str(" = ")?;
+                e.render(f)?;
+            }
+            Stmt::Assign(names, e) => {
+                fmt_tuple(f, names, |f, name| f.write_str(name))?;
+                f.write_str(" = ")?;
+                e.render(f)?;
+            }
+            Stmt::Unpack {
+                bind,
+                name,
+                fields,
+                exp,
+            } => {
+                if *bind {
+                    f.write_str("let ")?;
+                }
+                write!(f, "{name} {{ ")?;
+                fmt_comma_list(f, fields, |f, (field, local)| write!(f, "{field}: {local}"))?;
+                f.write_str(" } = ")?;
+                exp.render(f)?;
+            }
+            Stmt::Exp(e) => e.render(f)?,
+            Stmt::WriteRef(r, e) => {
+                match r {
+                    Exp::Borrow { .. } | Exp::BorrowField { .. } => r.render_place(f)?,
+                    r => {
+                        f.push('*');
+                        r.render_atomic(f)?;
+                    }
+                }
+                f.write_str(" = ")?;
+                e.render(f)?;
+            }
+            Stmt::Return(values) => {
+                f.write_str("return")?;
+                if !values.is_empty() {
+                    f.push(' ');
+                    fmt_tuple(f, values, |f, v| v.render(f))?;
+                }
+            }
+            Stmt::Abort(e) => {
+                f.write_str("abort ")?;
+                e.render(f)?;
+            }
+            Stmt::Assert(cond, code) => {
+                f.write_str("assert!(")?;
+                cond.render(f)?;
+                f.write_str(", ")?;
+                code.render(f)?;
+                f.push(')');
+            }
+            Stmt::If(cond, t, e) => {
+                f.write_str("if (")?;
+                cond.render(f)?;
+                f.write_str(") ")?;
+                self.block(f, t)?;
+                if !e.is_empty() {
+                    f.write_str(" else ")?;
+                    match e.as_slice() {
+                        [nested @ Stmt::If(..)] => self.stmt(f, nested)?,
+                        _ => self.block(f, e)?,
+                    }
+                    // the nested `if` already ended the statement
+                    if matches!(e.as_slice(), [Stmt::If(..)]) {
+                        return Ok(());
+                    }
+                }
+            }
+            Stmt::Loop(id, body) => {
+                if self.labels.contains(id) {
+                    write!(f, "'l{id}: ")?;
+                }
+                f.write_str("loop ")?;
+                self.loops.push(*id);
+                self.block(f, body)?;
+                self.loops.pop();
+            }
+            Stmt::While(id, cond, body) => {
+                if self.labels.contains(id) {
+                    write!(f, "'l{id}: ")?;
+                }
+                f.write_str("while (")?;
+                cond.render(f)?;
+                f.write_str(") ")?;
+                self.loops.push(*id);
+                self.block(f, body)?;
+                self.loops.pop();
+            }
+            Stmt::Break(id) => {
+                f.write_str("break")?;
+                self.label(f, *id)?;
+            }
+            Stmt::Continue(id) => {
+                f.write_str("continue")?;
+                self.label(f, *id)?;
+            }
+            Stmt::Match(subject, arms) => {
+                f.write_str("match (")?;
+                subject.render(f)?;
+                f.write_str(") {")?;
+                self.indent += 1;
+                for (patterns, body) in arms {
+                    self.line(f)?;
+                    f.write_str(&patterns.join(" | "))?;
+                    f.write_str(" => ")?;
+                    self.block(f, body)?;
+                    f.push(',');
+                }
+                self.indent -= 1;
+                self.line(f)?;
+                f.push('}');
+            }
+        }
+        f.push(';');
+        Ok(())
+    }
+}
+
+fn fmt_type_args(f: &mut String, type_args: &[String]) -> fmt::Result {
+    if !type_args.is_empty() {
+        write!(f, "<{}>", type_args.join(", "))?;
+    }
+    Ok(())
+}
+
+fn fmt_comma_list<T>(
+    f: &mut String,
+    items: impl IntoIterator<Item = T>,
+    mut fmt_item: impl FnMut(&mut String, T) -> fmt::Result,
+) -> fmt::Result {
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        fmt_item(f, item)?;
+    }
+    Ok(())
+}
+
+/// Renders a single item as-is, and any other number of items as a tuple.
+fn fmt_tuple<T>(
+    f: &mut String,
+    items: &[T],
+    mut fmt_item: impl FnMut(&mut String, &T) -> fmt::Result,
+) -> fmt::Result {
+    match items {
+        [item] => fmt_item(f, item),
+        items => {
+            f.push('(');
+            fmt_comma_list(f, items, fmt_item)?;
+            f.push(')');
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,1001 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recovers structured code from the bytecode of a function.
+//!
+//! Each basic block is executed symbolically, turning the values on the operand stack into
+//! expressions, and the instructions that consume them into statements. The blocks are then
+//! arranged into loops, conditionals and matches, following the loops found by the control-flow
+//! graph: a loop's body is its natural loop, extended with the paths that leave it through a
+//! `break`, and a conditional ends where its branches meet again, at the immediate post-dominator
+//! of its block within the innermost enclosing loop.
+
+use crate::{
+    ast::{Exp, Stmt},
+    decompiler::{identifier, local_name, Decompiler},
+    simplify,
+};
+use anyhow::{bail, ensure, Result};
+use move_abstract_interpreter::control_flow_graph::{
+    BlockId, ControlFlowGraph, VMControlFlowGraph,
+};
+use move_binary_format::file_format::{
+    Bytecode, CodeUnit, EnumDefinitionIndex, FieldHandleIndex, JumpTableInner, LocalIndex,
+    SignatureIndex, StructDefInstantiationIndex, StructDefinitionIndex, StructFieldInformation,
+    VariantTag,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Stands for the end of a region: returns, aborts, and jumps out of a loop body or back to its
+/// header.
+const EXIT: BlockId = BlockId::MAX;
+
+pub(crate) struct Body {
+    /// Declarations of the locals that are not parameters
+    pub declarations: Vec<String>,
+    pub stmts: Vec<Stmt>,
+    /// Parameters that are assigned to, or borrowed mutably
+    pub mutable_params: BTreeSet<usize>,
+}
+
+struct Loop {
+    body: BTreeSet<BlockId>,
+    /// Where a `break` jumps to, if the loop can be left other than by returning or aborting
+    exit: Option<BlockId>,
+}
+
+enum Terminator {
+    /// The block returns or aborts
+    Exit,
+    Jump(BlockId),
+    /// Branches on a condition, to the blocks taken when it is true and false
+    Branch(Exp, BlockId, BlockId),
+    /// Switches on a reference to an enum, with the variant patterns leading to each block
+    Switch(Exp, Vec<(Vec<String>, BlockId)>),
+}
+
+struct FunctionDecompiler<'a, 'm> {
+    decompiler: &'a Decompiler<'m>,
+    code: &'a CodeUnit,
+    cfg: VMControlFlowGraph,
+    num_params: usize,
+    preds: BTreeMap<BlockId, Vec<BlockId>>,
+    /// The loops of the function, by header
+    loops: BTreeMap<BlockId, Loop>,
+    /// The header of the innermost loop containing each block in a loop
+    innermost_loop: BTreeMap<BlockId, BlockId>,
+    /// Immediate post-dominators within each loop body (or the whole function, for `None`)
+    ipdoms: BTreeMap<Option<BlockId>, BTreeMap<BlockId, BlockId>>,
+    visited: BTreeSet<BlockId>,
+    next_temp: usize,
+}
+
+pub(crate) fn decompile(
+    decompiler: &Decompiler,
+    num_params: usize,
+    code: &CodeUnit,
+) -> Result<Body> {
+    let cfg = VMControlFlowGraph::new(&code.code, &code.jump_tables);
+    let mut preds: BTreeMap<BlockId, Vec<BlockId>> = BTreeMap::new();
+    for block in cfg.blocks() {
+        for succ in cfg.successors(block) {
+            preds.entry(*succ).or_default().push(block);
+        }
+    }
+
+    let mut function = FunctionDecompiler {
+        decompiler,
+        code,
+        cfg,
+        num_params,
+        preds,
+        loops: BTreeMap::new(),
+        innermost_loop: BTreeMap::new(),
+        ipdoms: BTreeMap::new(),
+        visited: BTreeSet::new(),
+        next_temp: 0,
+    };
+    function.find_loops();
+    function.ipdoms = std::iter::once(None)
+        .chain(function.loops.keys().copied().map(Some))
+        .map(|region| (region, function.ipdoms_in(region)))
+        .collect();
+    let entry = function.cfg.entry_block_id();
+    let mut stmts = function.region(entry, None, &mut vec![], false)?;
+    simplify::function_body(&mut stmts);
+
+    let mut used = BTreeSet::new();
+    let mut mutable_params = BTreeSet::new();
+    for instr in &code.code {
+        match instr {
+            Bytecode::StLoc(idx) | Bytecode::MutBorrowLoc(idx) => {
+                used.insert(*idx as usize);
+                if (*idx as usize) < num_params {
+                    mutable_params.insert(*idx as usize);
+                }
+            }
+            Bytecode::CopyLoc(idx) | Bytecode::MoveLoc(idx) | Bytecode::ImmBorrowLoc(idx) => {
+                used.insert(*idx as usize);
+            }
+            _ => (),
+        }
+    }
+    let declarations = decompiler
+        .module
+        .signature_at(code.locals)
+        .0
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| (i + num_params, ty))
+        .filter(|(idx, _)| used.contains(idx))
+        .map(|(idx, ty)| {
+            format!(
+                "let mut {}: {}",
+                local_name(idx, num_params),
+                decompiler.type_(ty)
+            )
+        })
+        .collect();
+
+    Ok(Body {
+        declarations,
+        stmts,
+        mutable_params,
+    })
+}
+
+impl FunctionDecompiler<'_, '_> {
+    // Structuring
+
+    fn find_loops(&mut self) {
+        let cfg = &self.cfg;
+        let mut natural_loops: Vec<(BlockId, BTreeSet<BlockId>)> = vec![];
+        for header in cfg.blocks() {
+            if !cfg.is_loop_head(header) {
+                continue;
+            }
+            let mut body = BTreeSet::from([header]);
+            let mut worklist: Vec<BlockId> = self
+                .preds_of(header)
+                .iter()
+                .copied()
+                .filter(|pred| cfg.is_back_edge(*pred, header))
+                .collect();
+            while let Some(block) = worklist.pop() {
+                if body.insert(block) {
+                    worklist.extend(self.preds_of(block));
+                }
+            }
+            natural_loops.push((header, body));
+        }
+
+        // process outer loops first, so that the exits of inner loops can be told apart from
+        // jumps out of the loops around them
+        natural_loops.sort_by_key(|(_, body)| std::cmp::Reverse(body.len()));
+        for (header, mut body) in natural_loops {
+            let enclosing: Vec<BlockId> = self
+                .loops
+                .iter()
+                .filter(|(_, l)| l.body.contains(&header))
+                .map(|(h, _)| *h)
+                .collect();
+            let leaves_loop = |this: &Self, block: BlockId, body: &BTreeSet<BlockId>| {
+                !body.contains(&block)
+                    && !enclosing.contains(&block)
+                    && enclosing
+                        .iter()
+                        .all(|outer| this.loops[outer].body.contains(&block))
+            };
+
+            // The paths that leave the loop through a `break` are not part of the natural loop,
+            // as they do not lead back to the header. They are laid out within the loop, and are
+            // only reachable from it.
+            loop {
+                let last = *body.iter().next_back().unwrap();
+                let break_paths: Vec<BlockId> = self
+                    .exits(&body)
+                    .into_iter()
+                    .filter(|block| {
+                        leaves_loop(self, *block, &body)
+                            && *block < last
+                            && self.preds_of(*block).iter().all(|pred| body.contains(pred))
+                    })
+                    .collect();
+                if break_paths.is_empty() {
+                    break;
+                }
+                body.extend(break_paths);
+            }
+
+            let exit = self
+                .exits(&body)
+                .into_iter()
+                .find(|block| leaves_loop(self, *block, &body));
+            for block in &body {
+                self.innermost_loop.insert(*block, header);
+            }
+            self.loops.insert(header, Loop { body, exit });
+        }
+    }
+
+    fn preds_of(&self, block: BlockId) -> &[BlockId] {
+        self.preds.get(&block).map_or(&[], |preds| preds.as_slice())
+    }
+
+    /// The blocks outside of `body` that it jumps to, in order.
+    fn exits(&self, body: &BTreeSet<BlockId>) -> BTreeSet<BlockId> {
+        body.iter()
+            .flat_map(|block| self.cfg.successors(*block))
+            .filter(|succ| !body.contains(succ))
+            .copied()
+            .collect()
+    }
+
+    /// Where the branches from `block` to `targets` meet again, if they do. This is the immediate
+    /// post-dominator of `block`, unless some branches return, abort or leave the loop, in which
+    /// case it is the first block that the others can all reach.
+    fn merge(&self, block: BlockId, targets: &[BlockId]) -> Option<BlockId> {
+        let region = self.innermost_loop.get(&block).copied();
+        if let Some(ipdom) = self.ipdoms[&region].get(&block) {
+            if *ipdom != EXIT {
+                return Some(*ipdom);
+            }
+        }
+
+        let mut reached_by: BTreeMap<BlockId, usize> = BTreeMap::new();
+        for target in targets {
+            for reached in self.reachable_in(region, *target) {
+                *reached_by.entry(reached).or_default() += 1;
+            }
+        }
+        reached_by
+            .into_iter()
+            .find(|(_, count)| *count > 1)
+            .map(|(block, _)| block)
+    }
+
+    /// The blocks reachable from `start` without leaving the body of the loop with the given
+    /// header (or the function, for `None`), or going back to its header.
+    fn reachable_in(&self, header: Option<BlockId>, start: BlockId) -> BTreeSet<BlockId> {
+        let in_region = |block: &BlockId| {
+            Some(*block) != header
+                && header.is_none_or(|header| self.loops[&header].body.contains(block))
+        };
+        let mut reached = BTreeSet::new();
+        let mut worklist = vec![start];
+        while let Some(block) = worklist.pop() {
+            if in_region(&block) && reached.insert(block) {
+                worklist.extend(self.cfg.successors(block));
+            }
+        }
+        reached
+    }
+
+    /// The immediate post-dominators of the blocks in the body of the loop with the given header,
+    /// or of the whole function.
+    fn ipdoms_in(&self, header: Option<BlockId>) -> BTreeMap<BlockId, BlockId> {
+        let nodes: BTreeSet<BlockId> = match header {
+            Some(header) => self.loops[&header].body.clone(),
+            None => self.cfg.blocks().into_iter().collect(),
+        };
+        let succs = |block: BlockId| -> Vec<BlockId> {
+            let succs: Vec<_> = self
+                .cfg
+                .successors(block)
+                .iter()
+                .map(|succ| {
+                    if nodes.contains(succ) && Some(*succ) != header {
+                        *succ
+                    } else {
+                        EXIT
+                    }
+                })
+                .collect();
+            if succs.is_empty() {
+                vec![EXIT]
+            } else {
+                succs
+            }
+        };
+
+        let all: BTreeSet<BlockId> = nodes.iter().copied().chain([EXIT]).collect();
+        let mut pdoms: BTreeMap<BlockId, BTreeSet<BlockId>> =
+            nodes.iter().map(|block| (*block, all.clone())).collect();
+        pdoms.insert(EXIT, BTreeSet::from([EXIT]));
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in nodes.iter().rev() {
+                let mut pdom = succs(*block)
+                    .into_iter()
+                    .map(|succ| pdoms[&succ].clone())
+                    .reduce(|a, b| a.intersection(&b).copied().collect())
+                    .unwrap_or_default();
+                pdom.insert(*block);
+                if pdom != pdoms[block] {
+                    pdoms.insert(*block, pdom);
+                    changed = true;
+                }
+            }
+        }
+
+        let mut ipdoms = BTreeMap::new();
+        for block in &nodes {
+            let strict = pdoms[block].len() - 1;
+            if let Some(ipdom) = pdoms[block]
+                .iter()
+                .find(|pdom| *pdom != block && pdoms[*pdom].len() == strict)
+            {
+                ipdoms.insert(*block, *ipdom);
+            }
+        }
+        ipdoms
+    }
+
+    /// Structures the code starting at `start`, until it reaches `stop` or jumps out of the
+    /// enclosing `loops` (given by header and exit). When `entering` a loop, `start` is its
+    /// header.
+    fn region(
+        &mut self,
+        start: BlockId,
+        stop: Option<BlockId>,
+        loops: &mut Vec<(BlockId, Option<BlockId>)>,
+        mut entering: bool,
+    ) -> Result<Vec<Stmt>> {
+        let mut stmts = vec![];
+        let mut cur = start;
+        loop {
+            if Some(cur) == stop {
+                return Ok(stmts);
+            }
+            if !entering {
+                if let Some((header, _)) = loops.iter().rev().find(|(header, _)| *header == cur) {
+                    stmts.push(Stmt::Continue(*header));
+                    return Ok(stmts);
+                }
+                if let Some((header, _)) = loops.iter().rev().find(|(_, exit)| *exit == Some(cur)) {
+                    stmts.push(Stmt::Break(*header));
+                    return Ok(stmts);
+                }
+                if let Some(exit) = self.loops.get(&cur).map(|l| l.exit) {
+                    loops.push((cur, exit));
+                    let body = self.region(cur, None, loops, true);
+                    loops.pop();
+                    stmts.push(Stmt::Loop(cur, body?));
+                    match exit {
+                        Some(exit) => {
+                            cur = exit;
+                            continue;
+                        }
+                        None => return Ok(stmts),
+                    }
+                }
+            }
+            entering = false;
+
+            ensure!(
+                self.visited.insert(cur) || self.can_repeat(cur),
+                "unstructured control flow at offset {cur}"
+            );
+            match self.block(cur, &mut stmts)? {
+                Terminator::Exit => return Ok(stmts),
+                Terminator::Jump(target) => cur = target,
+                // when the branches do not meet again, at least one of them always returns,
+                // aborts or leaves the loop, and the other continues to where this region stops
+                Terminator::Branch(cond, on_true, on_false) => {
+                    let merge = self.merge(cur, &[on_true, on_false]);
+                    let then = self.region(on_true, merge.or(stop), loops, false)?;
+                    let else_ = self.region(on_false, merge.or(stop), loops, false)?;
+                    stmts.push(Stmt::If(cond, then, else_));
+                    match merge {
+                        Some(merge) => cur = merge,
+                        None => return Ok(stmts),
+                    }
+                }
+                Terminator::Switch(subject, targets) => {
+                    let arm_targets: Vec<_> = targets.iter().map(|(_, target)| *target).collect();
+                    let merge = self.merge(cur, &arm_targets);
+                    let mut arms = vec![];
+                    for (patterns, target) in targets {
+                        let body = self.region(target, merge.or(stop), loops, false)?;
+                        arms.push((patterns, body));
+                    }
+                    stmts.push(Stmt::Match(subject, arms));
+                    match merge {
+                        Some(merge) => cur = merge,
+                        None => return Ok(stmts),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether the code from `block` on can be repeated wherever it is reached from. This is the
+    /// case for code that ends the function without looping, which is reached from several places
+    /// when returning early from a block, e.g. in the code expanded from macros.
+    fn can_repeat(&self, block: BlockId) -> bool {
+        !self.innermost_loop.contains_key(&block)
+            && self
+                .reachable_in(None, block)
+                .iter()
+                .all(|reached| !self.loops.contains_key(reached))
+    }
+
+    // Basic blocks
+
+    /// Adds the statements of `block` to `stmts`, returning how the block ends.
+    fn block(&mut self, block: BlockId, stmts: &mut Vec<Stmt>) -> Result<Terminator> {
+        use Bytecode as B;
+        let module = self.decompiler.module;
+        let code = &self.code.code;
+        let end = self.cfg.block_end(block) as usize;
+        let mut stack: Vec<Exp> = vec![];
+        let mut pc = self.cfg.block_start(block) as usize;
+        while pc <= end {
+            match &code[pc] {
+                B::Nop => (),
+                B::Pop => {
+                    let e = pop(&mut stack)?;
+                    self.emit(&mut stack, stmts, Stmt::Exp(e));
+                }
+                B::Ret => {
+                    stmts.push(Stmt::Return(std::mem::take(&mut stack)));
+                    return Ok(Terminator::Exit);
+                }
+                B::Abort => {
+                    let code = pop(&mut stack)?;
+                    self.emit(&mut stack, stmts, Stmt::Abort(code));
+                    return Ok(Terminator::Exit);
+                }
+                B::Branch(target) => {
+                    ensure_empty(&stack, pc)?;
+                    return Ok(Terminator::Jump(*target));
+                }
+                B::BrTrue(target) => {
+                    let cond = pop(&mut stack)?;
+                    ensure_empty(&stack, pc)?;
+                    return Ok(Terminator::Branch(cond, *target, end as BlockId + 1));
+                }
+                B::BrFalse(target) => {
+                    let cond = pop(&mut stack)?;
+                    ensure_empty(&stack, pc)?;
+                    return Ok(Terminator::Branch(cond, end as BlockId + 1, *target));
+                }
+                B::VariantSwitch(idx) => {
+                    let subject = pop(&mut stack)?;
+                    ensure_empty(&stack, pc)?;
+                    let table = &self.code.jump_tables[idx.0 as usize];
+                    let JumpTableInner::Full(offsets) = &table.jump_table;
+                    let mut targets: Vec<(Vec<String>, BlockId)> = vec![];
+                    for (tag, offset) in offsets.iter().enumerate() {
+                        let pattern = self.variant_pattern(table.head_enum, tag as VariantTag);
+                        match targets.iter_mut().find(|(_, target)| target == offset) {
+                            Some((patterns, _)) => patterns.push(pattern),
+                            None => targets.push((vec![pattern], *offset)),
+                        }
+                    }
+                    return Ok(Terminator::Switch(subject, targets));
+                }
+
+                B::LdU8(n) => stack.push(Exp::Value(format!("{n}u8"))),
+                B::LdU16(n) => stack.push(Exp::Value(format!("{n}u16"))),
+                B::LdU32(n) => stack.push(Exp::Value(format!("{n}u32"))),
+                B::LdU64(n) => stack.push(Exp::Value(n.to_string())),
+                B::LdU128(n) => stack.push(Exp::Value(format!("{n}u128"))),
+                B::LdU256(n) => stack.push(Exp::Value(format!("{n}u256"))),
+                B::LdTrue => stack.push(Exp::Value("true".to_string())),
+                B::LdFalse => stack.push(Exp::Value("false".to_string())),
+                B::LdConst(idx) => stack.push(Exp::Value(format!("C{}", idx.0))),
+
+                B::CastU8 => cast(&mut stack, "u8")?,
+                B::CastU16 => cast(&mut stack, "u16")?,
+                B::CastU32 => cast(&mut stack, "u32")?,
+                B::CastU64 => cast(&mut stack, "u64")?,
+                B::CastU128 => cast(&mut stack, "u128")?,
+                B::CastU256 => cast(&mut stack, "u256")?,
+
+                B::CopyLoc(idx) | B::MoveLoc(idx) => stack.push(self.local(*idx)),
+                B::StLoc(idx) => {
+                    let e = pop(&mut stack)?;
+                    let local = local_name(*idx as usize, self.num_params);
+                    self.emit(&mut stack, stmts, Stmt::Assign(vec![local], e));
+                }
+                B::MutBorrowLoc(idx) | B::ImmBorrowLoc(idx) => stack.push(Exp::Borrow {
+                    mut_: matches!(&code[pc], B::MutBorrowLoc(_)),
+                    exp: Box::new(self.local(*idx)),
+                }),
+
+                B::Call(idx) => {
+                    let handle = module.function_handle_at(*idx);
+                    let function = self.decompiler.function_name(*idx);
+                    let num_args = module.signature_at(handle.parameters).len();
+                    let num_rets = module.signature_at(handle.return_).len();
+                    let args = pop_n(&mut stack, num_args)?;
+                    let call = Exp::call(function, vec![], args);
+                    pc = self.results(call, num_rets, pc, end, &mut stack, stmts);
+                }
+                B::CallGeneric(idx) => {
+                    let inst = module.function_instantiation_at(*idx);
+                    let handle = module.function_handle_at(inst.handle);
+                    let function = self.decompiler.function_name(inst.handle);
+                    let type_args = self.decompiler.signature_types(inst.type_parameters);
+                    let num_args = module.signature_at(handle.parameters).len();
+                    let num_rets = module.signature_at(handle.return_).len();
+                    let args = pop_n(&mut stack, num_args)?;
+                    let call = Exp::call(function, type_args, args);
+                    pc = self.results(call, num_rets, pc, end, &mut stack, stmts);
+                }
+
+                B::Pack(idx) => {
+                    let name = self.struct_name(*idx, vec![]);
+                    let fields = self.field_names(*idx);
+                    let values = pop_n(&mut stack, fields.len())?;
+                    stack.push(Exp::Pack {
+                        name,
+                        fields: fields.into_iter().zip(values).collect(),
+                        braces: true,
+                    });
+                }
+                B::PackGeneric(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    let type_args = self.decompiler.signature_types(inst.type_parameters);
+                    let name = self.struct_name(inst.def, type_args);
+                    let fields = self.field_names(inst.def);
+                    let values = pop_n(&mut stack, fields.len())?;
+                    stack.push(Exp::Pack {
+                        name,
+                        fields: fields.into_iter().zip(values).collect(),
+                        braces: true,
+                    });
+                }
+                B::Unpack(idx) => {
+                    let name = self.struct_name(*idx, vec![]);
+                    pc = self.unpack(name, *idx, pc, end, &mut stack, stmts)?;
+                }
+                B::UnpackGeneric(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    let type_args = self.decompiler.signature_types(inst.type_parameters);
+                    let name = self.struct_name(inst.def, type_args);
+                    pc = self.unpack(name, inst.def, pc, end, &mut stack, stmts)?;
+                }
+
+                B::PackVariant(idx) => {
+                    let handle = module.variant_handle_at(*idx);
+                    self.pack_variant(handle.enum_def, handle.variant, &mut stack)?;
+                }
+                B::PackVariantGeneric(idx) => {
+                    let handle = module.variant_instantiation_handle_at(*idx);
+                    let def = module.enum_instantiation_at(handle.enum_def).def;
+                    self.pack_variant(def, handle.variant, &mut stack)?;
+                }
+                B::UnpackVariant(idx)
+                | B::UnpackVariantImmRef(idx)
+                | B::UnpackVariantMutRef(idx) => {
+                    let handle = module.variant_handle_at(*idx);
+                    pc = self.unpack_variant(
+                        handle.enum_def,
+                        handle.variant,
+                        pc,
+                        end,
+                        &mut stack,
+                        stmts,
+                    )?;
+                }
+                B::UnpackVariantGeneric(idx)
+                | B::UnpackVariantGenericImmRef(idx)
+                | B::UnpackVariantGenericMutRef(idx) => {
+                    let handle = module.variant_instantiation_handle_at(*idx);
+                    let def = module.enum_instantiation_at(handle.enum_def).def;
+                    pc = self.unpack_variant(def, handle.variant, pc, end, &mut stack, stmts)?;
+                }
+
+                B::ReadRef => {
+                    let e = pop(&mut stack)?;
+                    stack.push(Exp::Deref(Box::new(e)));
+                }
+                B::WriteRef => {
+                    let reference = pop(&mut stack)?;
+                    let value = pop(&mut stack)?;
+                    self.emit(&mut stack, stmts, Stmt::WriteRef(reference, value));
+                }
+                // references are frozen implicitly in source
+                B::FreezeRef => (),
+                B::MutBorrowField(idx) | B::ImmBorrowField(idx) => {
+                    let field = self.field_name(*idx);
+                    let exp = Box::new(pop(&mut stack)?);
+                    stack.push(Exp::BorrowField {
+                        mut_: matches!(&code[pc], B::MutBorrowField(_)),
+                        exp,
+                        field,
+                    });
+                }
+                B::MutBorrowFieldGeneric(idx) | B::ImmBorrowFieldGeneric(idx) => {
+                    let field = self.field_name(module.field_instantiation_at(*idx).handle);
+                    let exp = Box::new(pop(&mut stack)?);
+                    stack.push(Exp::BorrowField {
+                        mut_: matches!(&code[pc], B::MutBorrowFieldGeneric(_)),
+                        exp,
+                        field,
+                    });
+                }
+
+                B::Add => binary(&mut stack, "+")?,
+                B::Sub => binary(&mut stack, "-")?,
+                B::Mul => binary(&mut stack, "*")?,
+                B::Mod => binary(&mut stack, "%")?,
+                B::Div => binary(&mut stack, "/")?,
+                B::BitOr => binary(&mut stack, "|")?,
+                B::BitAnd => binary(&mut stack, "&")?,
+                B::Xor => binary(&mut stack, "^")?,
+                B::Shl => binary(&mut stack, "<<")?,
+                B::Shr => binary(&mut stack, ">>")?,
+                B::Or => binary(&mut stack, "||")?,
+                B::And => binary(&mut stack, "&&")?,
+                B::Eq => binary(&mut stack, "==")?,
+                B::Neq => binary(&mut stack, "!=")?,
+                B::Lt => binary(&mut stack, "<")?,
+                B::Gt => binary(&mut stack, ">")?,
+                B::Le => binary(&mut stack, "<=")?,
+                B::Ge => binary(&mut stack, ">=")?,
+                B::Not => {
+                    let e = pop(&mut stack)?;
+                    stack.push(e.negate());
+                }
+
+                B::VecPack(idx, n) => {
+                    let type_ = self.vector_element_type(*idx);
+                    let elems = pop_n(&mut stack, *n as usize)?;
+                    stack.push(Exp::Vector { type_, elems });
+                }
+                B::VecLen(idx) => self.vector_call("length", *idx, 1, &mut stack)?,
+                B::VecImmBorrow(idx) => self.vector_call("borrow", *idx, 2, &mut stack)?,
+                B::VecMutBorrow(idx) => self.vector_call("borrow_mut", *idx, 2, &mut stack)?,
+                B::VecPopBack(idx) => self.vector_call("pop_back", *idx, 1, &mut stack)?,
+                B::VecPushBack(idx) => {
+                    self.vector_call("push_back", *idx, 2, &mut stack)?;
+                    let call = pop(&mut stack)?;
+                    self.emit(&mut stack, stmts, Stmt::Exp(call));
+                }
+                B::VecSwap(idx) => {
+                    self.vector_call("swap", *idx, 3, &mut stack)?;
+                    let call = pop(&mut stack)?;
+                    self.emit(&mut stack, stmts, Stmt::Exp(call));
+                }
+                B::VecUnpack(idx, 0) => {
+                    self.vector_call("destroy_empty", *idx, 1, &mut stack)?;
+                    let call = pop(&mut stack)?;
+                    self.emit(&mut stack, stmts, Stmt::Exp(call));
+                }
+                B::VecUnpack(_, _) => bail!("unsupported unpacking of a vector at offset {pc}"),
+
+                B::ExistsDeprecated(idx) => self.global("exists", *idx, vec![], &mut stack)?,
+                B::MoveFromDeprecated(idx) => self.global("move_from", *idx, vec![], &mut stack)?,
+                B::ImmBorrowGlobalDeprecated(idx) => {
+                    self.global("borrow_global", *idx, vec![], &mut stack)?
+                }
+                B::MutBorrowGlobalDeprecated(idx) => {
+                    self.global("borrow_global_mut", *idx, vec![], &mut stack)?
+                }
+                B::ExistsGenericDeprecated(idx) => {
+                    let (def, type_args) = self.struct_instantiation(*idx);
+                    self.global("exists", def, type_args, &mut stack)?
+                }
+                B::MoveFromGenericDeprecated(idx) => {
+                    let (def, type_args) = self.struct_instantiation(*idx);
+                    self.global("move_from", def, type_args, &mut stack)?
+                }
+                B::ImmBorrowGlobalGenericDeprecated(idx) => {
+                    let (def, type_args) = self.struct_instantiation(*idx);
+                    self.global("borrow_global", def, type_args, &mut stack)?
+                }
+                B::MutBorrowGlobalGenericDeprecated(idx) => {
+                    let (def, type_args) = self.struct_instantiation(*idx);
+                    self.global("borrow_global_mut", def, type_args, &mut stack)?
+                }
+                B::MoveToDeprecated(_) | B::MoveToGenericDeprecated(_) => {
+                    let type_args = match &code[pc] {
+                        B::MoveToGenericDeprecated(idx) => self.struct_instantiation(*idx).1,
+                        _ => vec![],
+                    };
+                    let args = pop_n(&mut stack, 2)?;
+                    let call = Exp::call("move_to".to_string(), type_args, args);
+                    self.emit(&mut stack, stmts, Stmt::Exp(call));
+                }
+            }
+            pc += 1;
+        }
+
+        // falls through to the next block
+        ensure_empty(&stack, end)?;
+        Ok(Terminator::Jump(end as BlockId + 1))
+    }
+
+    /// Adds `stmt` to `stmts`, first binding the values left on the stack to temporaries, so
+    /// that they are evaluated before the statement, as they are in the bytecode.
+    fn emit(&mut self, stack: &mut [Exp], stmts: &mut Vec<Stmt>, stmt: Stmt) {
+        for e in stack.iter_mut() {
+            if !matches!(e, Exp::Temp(_) | Exp::Value(_)) {
+                let temp = self.temp();
+                let value = std::mem::replace(e, Exp::Temp(temp.clone()));
+                stmts.push(Stmt::Let(vec![temp], value));
+            }
+        }
+        stmts.push(stmt);
+    }
+
+    fn temp(&mut self) -> String {
+        self.next_temp += 1;
+        format!("t{}", self.next_temp - 1)
+    }
+
+    fn local(&self, idx: LocalIndex) -> Exp {
+        Exp::Name(local_name(idx as usize, self.num_params))
+    }
+
+    /// The locals that the `n` values pushed by the instruction at `pc` are stored to, when it is
+    /// followed by a store (or pop) of each of them, in the same block.
+    fn stored_to(&self, pc: usize, end: usize, n: usize) -> Option<Vec<String>> {
+        if n == 0 || pc + n > end {
+            return None;
+        }
+        // the last value pushed is stored first
+        self.code.code[pc + 1..=pc + n]
+            .iter()
+            .rev()
+            .map(|instr| match instr {
+                Bytecode::StLoc(idx) => Some(local_name(*idx as usize, self.num_params)),
+                Bytecode::Pop => Some("_".to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Handles the `n` results of the call at `pc`, returning the offset of the last instruction
+    /// handled.
+    fn results(
+        &mut self,
+        call: Exp,
+        n: usize,
+        pc: usize,
+        end: usize,
+        stack: &mut Vec<Exp>,
+        stmts: &mut Vec<Stmt>,
+    ) -> usize {
+        match n {
+            0 => self.emit(stack, stmts, Stmt::Exp(call)),
+            1 => stack.push(call),
+            n => match self.stored_to(pc, end, n) {
+                Some(locals) => {
+                    self.emit(stack, stmts, Stmt::Assign(locals, call));
+                    return pc + n;
+                }
+                None => {
+                    let temps: Vec<_> = (0..n).map(|_| self.temp()).collect();
+                    self.emit(stack, stmts, Stmt::Let(temps.clone(), call));
+                    stack.extend(temps.into_iter().map(Exp::Temp));
+                }
+            },
+        }
+        pc
+    }
+
+    fn unpack(
+        &mut self,
+        name: String,
+        def: StructDefinitionIndex,
+        pc: usize,
+        end: usize,
+        stack: &mut Vec<Exp>,
+        stmts: &mut Vec<Stmt>,
+    ) -> Result<usize> {
+        let fields = self.field_names(def);
+        let n = fields.len();
+        let exp = pop(stack)?;
+        match self.stored_to(pc, end, n) {
+            Some(locals) => {
+                let fields = fields.into_iter().zip(locals).collect();
+                let unpack = Stmt::Unpack {
+                    bind: false,
+                    name,
+                    fields,
+                    exp,
+                };
+                self.emit(stack, stmts, unpack);
+                Ok(pc + n)
+            }
+            None => {
+                let temps: Vec<_> = (0..n).map(|_| self.temp()).collect();
+                let fields = fields.into_iter().zip(temps.clone()).collect();
+                let unpack = Stmt::Unpack {
+                    bind: true,
+                    name,
+                    fields,
+                    exp,
+                };
+                self.emit(stack, stmts, unpack);
+                stack.extend(temps.into_iter().map(Exp::Temp));
+                Ok(pc)
+            }
+        }
+    }
+
+    fn pack_variant(
+        &mut self,
+        def: EnumDefinitionIndex,
+        tag: VariantTag,
+        stack: &mut Vec<Exp>,
+    ) -> Result<()> {
+        let (name, fields) = self.variant(def, tag);
+        let values = pop_n(stack, fields.len())?;
+        stack.push(Exp::Pack {
+            name,
+            braces: !fields.is_empty(),
+            fields: fields.into_iter().zip(values).collect(),
+        });
+        Ok(())
+    }
+
+    fn unpack_variant(
+        &mut self,
+        def: EnumDefinitionIndex,
+        tag: VariantTag,
+        pc: usize,
+        end: usize,
+        stack: &mut Vec<Exp>,
+        stmts: &mut Vec<Stmt>,
+    ) -> Result<usize> {
+        let (variant, fields) = self.variant(def, tag);
+        let n = fields.len();
+        let exp = Exp::UnpackVariant {
+            variant,
+            braces: n > 0,
+            fields,
+            exp: Box::new(pop(stack)?),
+        };
+        if n == 0 {
+            self.emit(stack, stmts, Stmt::Exp(exp));
+            return Ok(pc);
+        }
+        Ok(self.results(exp, n, pc, end, stack, stmts))
+    }
+
+    fn vector_call(
+        &mut self,
+        function: &str,
+        idx: SignatureIndex,
+        num_args: usize,
+        stack: &mut Vec<Exp>,
+    ) -> Result<()> {
+        let type_ = self.vector_element_type(idx);
+        let args = pop_n(stack, num_args)?;
+        stack.push(Exp::call(
+            format!("0x1::vector::{function}"),
+            vec![type_],
+            args,
+        ));
+        Ok(())
+    }
+
+    fn global(
+        &mut self,
+        function: &str,
+        def: StructDefinitionIndex,
+        type_args: Vec<String>,
+        stack: &mut Vec<Exp>,
+    ) -> Result<()> {
+        let type_ = self.struct_name(def, type_args);
+        let address = pop(stack)?;
+        stack.push(Exp::call(function.to_string(), vec![type_], vec![address]));
+        Ok(())
+    }
+
+    // Names
+
+    fn struct_name(&self, def: StructDefinitionIndex, type_args: Vec<String>) -> String {
+        let handle = self.decompiler.module.struct_def_at(def).struct_handle;
+        let name = self.decompiler.datatype_name(handle);
+        if type_args.is_empty() {
+            name
+        } else {
+            format!("{name}<{}>", type_args.join(", "))
+        }
+    }
+
+    fn struct_instantiation(
+        &self,
+        idx: StructDefInstantiationIndex,
+    ) -> (StructDefinitionIndex, Vec<String>) {
+        let inst = self.decompiler.module.struct_instantiation_at(idx);
+        (
+            inst.def,
+            self.decompiler.signature_types(inst.type_parameters),
+        )
+    }
+
+    fn field_names(&self, def: StructDefinitionIndex) -> Vec<String> {
+        let module = self.decompiler.module;
+        match &module.struct_def_at(def).field_information {
+            StructFieldInformation::Native => vec![],
+            StructFieldInformation::Declared(fields) => fields
+                .iter()
+                .map(|field| identifier(module.identifier_at(field.name).as_str()))
+                .collect(),
+        }
+    }
+
+    fn field_name(&self, idx: FieldHandleIndex) -> String {
+        let handle = self.decompiler.module.field_handle_at(idx);
+        self.field_names(handle.owner)
+            .swap_remove(handle.field as usize)
+    }
+
+    /// The qualified name of a variant, and the names of its fields.
+    fn variant(&self, def: EnumDefinitionIndex, tag: VariantTag) -> (String, Vec<String>) {
+        let module = self.decompiler.module;
+        let def = module.enum_def_at(def);
+        let variant = &def.variants[tag as usize];
+        let name = format!(
+            "{}::{}",
+            self.decompiler.datatype_name(def.enum_handle),
+            identifier(module.identifier_at(variant.variant_name).as_str())
+        );
+        let fields = variant
+            .fields
+            .iter()
+            .map(|field| identifier(module.identifier_at(field.name).as_str()))
+            .collect();
+        (name, fields)
+    }
+
+    /// The pattern matching any value of a variant.
+    fn variant_pattern(&self, def: EnumDefinitionIndex, tag: VariantTag) -> String {
+        let (name, fields) = self.variant(def, tag);
+        if fields.is_empty() {
+            name
+        } else {
+            format!("{name} {{ .. }}")
+        }
+    }
+
+    fn vector_element_type(&self, idx: SignatureIndex) -> String {
+        self.decompiler
+            .signature_types(idx)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+}
+
+fn pop(stack: &mut Vec<Exp>) -> Result<Exp> {
+    match stack.pop() {
+        Some(e) => Ok(e),
+        None => bail!("operand stack underflow"),
+    }
+}
+
+/// Pops `n` values, in the order they were pushed.
+fn pop_n(stack: &mut Vec<Exp>, n: usize) -> Result<Vec<Exp>> {
+    ensure!(stack.len() >= n, "operand stack underflow");
+    Ok(stack.split_off(stack.len() - n))
+}
+
+fn binary(stack: &mut Vec<Exp>, op: &'static str) -> Result<()> {
+    let r = pop(stack)?;
+    let l = pop(stack)?;
+    stack.push(Exp::Binary(op, Box::new(l), Box::new(r)));
+    Ok(())
+}
+
+fn cast(stack: &mut Vec<Exp>, ty: &'static str) -> Result<()> {
+    let e = pop(stack)?;
+    stack.push(Exp::Cast(Box::new(e), ty));
+    Ok(())
+}
+
+fn ensure_empty(stack: &[Exp], pc: usize) -> Result<()> {
+    ensure!(
+        stack.is_empty(),
+        "values left on the operand stack at offset {pc}"
+    );
+    Ok(())
+}
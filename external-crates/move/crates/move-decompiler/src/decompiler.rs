@@ -0,0 +1,423 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ast, body};
+use anyhow::Result;
+use move_binary_format::{
+    file_format::{
+        Ability, AbilitySet, DatatypeHandleIndex, DatatypeTyParameter, FieldDefinition,
+        FunctionDefinition, FunctionHandleIndex, ModuleHandleIndex, SignatureIndex, SignatureToken,
+        StructFieldInformation, Visibility,
+    },
+    CompiledModule,
+};
+use move_core_types::runtime_value::MoveValue;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+/// Reconstructs Move source for a compiled module: its datatypes, constants and functions, with
+/// function bodies recovered as structured code (`if`, `while`, `loop`, `match`, ...).
+///
+/// Names that are not part of the bytecode are made up: constants are named `C<index>`, type
+/// parameters `T<index>`, parameters `arg<index>` and other locals `v<index>`. Functions whose
+/// bodies cannot be structured are emitted with a body that aborts, and a comment explaining why.
+pub struct Decompiler<'a> {
+    pub(crate) module: &'a CompiledModule,
+    /// How other modules are referred to, by their handle
+    aliases: BTreeMap<ModuleHandleIndex, String>,
+}
+
+/// Words that cannot be used as identifiers without escaping them
+const KEYWORDS: &[&str] = &[
+    "abort",
+    "acquires",
+    "as",
+    "break",
+    "const",
+    "continue",
+    "copy",
+    "else",
+    "enum",
+    "false",
+    "for",
+    "friend",
+    "fun",
+    "if",
+    "invariant",
+    "let",
+    "loop",
+    "match",
+    "module",
+    "move",
+    "mut",
+    "native",
+    "public",
+    "return",
+    "spec",
+    "struct",
+    "true",
+    "type",
+    "use",
+    "while",
+];
+
+impl<'a> Decompiler<'a> {
+    pub fn new(module: &'a CompiledModule) -> Self {
+        let self_handle = module.self_handle_idx();
+        let mut taken = BTreeSet::from([module.name().to_string()]);
+        let mut aliases = BTreeMap::new();
+        for (idx, handle) in module.module_handles().iter().enumerate() {
+            let idx = ModuleHandleIndex(idx as u16);
+            if idx == self_handle {
+                continue;
+            }
+            let name = module.identifier_at(handle.name).to_string();
+            let mut alias = name.clone();
+            let mut suffix = 1;
+            while !taken.insert(alias.clone()) {
+                alias = format!("{name}_{suffix}");
+                suffix += 1;
+            }
+            aliases.insert(idx, alias);
+        }
+        Self { module, aliases }
+    }
+
+    pub fn decompile(&self) -> Result<String> {
+        let module = self.module;
+        let mut out = String::new();
+        writeln!(
+            out,
+            "module {}::{} {{",
+            module.address().to_hex_literal(),
+            identifier(module.name().as_str())
+        )?;
+
+        for (idx, handle) in module.module_handles().iter().enumerate() {
+            let Some(alias) = self.aliases.get(&ModuleHandleIndex(idx as u16)) else {
+                continue;
+            };
+            let name = module.identifier_at(handle.name).as_str();
+            write!(
+                out,
+                "\n    use {}::{}",
+                module
+                    .address_identifier_at(handle.address)
+                    .to_hex_literal(),
+                identifier(name)
+            )?;
+            if alias != name {
+                write!(out, " as {alias}")?;
+            }
+            out.push(';');
+        }
+        if module.module_handles().len() > 1 {
+            out.push('\n');
+        }
+
+        for (idx, constant) in module.constant_pool().iter().enumerate() {
+            let value = match constant.deserialize_constant() {
+                Some(value) => render_value(&value),
+                None => "/* unable to decode constant */ 0".to_string(),
+            };
+            writeln!(
+                out,
+                "\n    const C{idx}: {} = {value};",
+                self.type_(&constant.type_)
+            )?;
+        }
+
+        for def in module.struct_defs() {
+            let handle = module.datatype_handle_at(def.struct_handle);
+            write!(out, "\n    ")?;
+            if matches!(def.field_information, StructFieldInformation::Native) {
+                out.push_str("native ");
+            } else {
+                out.push_str("public ");
+            }
+            write!(
+                out,
+                "struct {}{}{}",
+                identifier(module.identifier_at(handle.name).as_str()),
+                datatype_type_params(&handle.type_parameters),
+                abilities(" has ", handle.abilities, ", ")
+            )?;
+            match &def.field_information {
+                StructFieldInformation::Native => out.push_str(";\n"),
+                StructFieldInformation::Declared(fields) => {
+                    out.push_str(" {");
+                    for field in fields {
+                        write!(out, "\n        {},", self.field(field))?;
+                    }
+                    out.push_str("\n    }\n");
+                }
+            }
+        }
+
+        for def in module.enum_defs() {
+            let handle = module.datatype_handle_at(def.enum_handle);
+            writeln!(
+                out,
+                "\n    public enum {}{}{} {{",
+                identifier(module.identifier_at(handle.name).as_str()),
+                datatype_type_params(&handle.type_parameters),
+                abilities(" has ", handle.abilities, ", ")
+            )?;
+            for variant in &def.variants {
+                write!(
+                    out,
+                    "        {}",
+                    identifier(module.identifier_at(variant.variant_name).as_str())
+                )?;
+                if !variant.fields.is_empty() {
+                    out.push_str(" { ");
+                    let fields: Vec<_> = variant.fields.iter().map(|f| self.field(f)).collect();
+                    out.push_str(&fields.join(", "));
+                    out.push_str(" }");
+                }
+                out.push_str(",\n");
+            }
+            out.push_str("    }\n");
+        }
+
+        for def in module.function_defs() {
+            out.push('\n');
+            self.function(&mut out, def)?;
+        }
+
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    fn function(&self, out: &mut String, def: &FunctionDefinition) -> Result<()> {
+        let module = self.module;
+        let handle = module.function_handle_at(def.function);
+        let params = &module.signature_at(handle.parameters).0;
+        let returns = &module.signature_at(handle.return_).0;
+
+        let body = def
+            .code
+            .as_ref()
+            .map(|code| body::decompile(self, params.len(), code));
+        let mutable_params = match &body {
+            Some(Ok(body)) => body.mutable_params.clone(),
+            _ => BTreeSet::new(),
+        };
+
+        out.push_str("    ");
+        match def.visibility {
+            Visibility::Private => (),
+            Visibility::Public => out.push_str("public "),
+            Visibility::Friend => out.push_str("public(package) "),
+        }
+        if def.is_entry {
+            out.push_str("entry ");
+        }
+        if def.is_native() {
+            out.push_str("native ");
+        }
+        write!(
+            out,
+            "fun {}",
+            identifier(module.identifier_at(handle.name).as_str())
+        )?;
+        if !handle.type_parameters.is_empty() {
+            let type_params: Vec<_> = handle
+                .type_parameters
+                .iter()
+                .enumerate()
+                .map(|(i, constraints)| format!("T{i}{}", abilities(": ", *constraints, " + ")))
+                .collect();
+            write!(out, "<{}>", type_params.join(", "))?;
+        }
+        let params: Vec<_> = params
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                let mut_ = if mutable_params.contains(&i) {
+                    "mut "
+                } else {
+                    ""
+                };
+                format!("{mut_}{}: {}", local_name(i, params.len()), self.type_(ty))
+            })
+            .collect();
+        write!(out, "({})", params.join(", "))?;
+        match returns.as_slice() {
+            [] => (),
+            [ty] => write!(out, ": {}", self.type_(ty))?,
+            tys => write!(out, ": ({})", self.types(tys).join(", "))?,
+        }
+
+        match body {
+            None => out.push_str(";\n"),
+            Some(Ok(body)) => {
+                out.push_str(" {");
+                ast::fmt_body(out, &body.declarations, &body.stmts, 2)?;
+                out.push_str("\n    }\n");
+            }
+            Some(Err(e)) => {
+                writeln!(
+                    out,
+                    " {{\n        /* unable to decompile: {e} */\n        abort 0\n    }}"
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn field(&self, field: &FieldDefinition) -> String {
+        format!(
+            "{}: {}",
+            identifier(self.module.identifier_at(field.name).as_str()),
+            self.type_(&field.signature.0)
+        )
+    }
+
+    /// The name of a datatype, qualified by its module's alias if it is not defined in this
+    /// module.
+    pub(crate) fn datatype_name(&self, idx: DatatypeHandleIndex) -> String {
+        let handle = self.module.datatype_handle_at(idx);
+        self.qualified(
+            handle.module,
+            self.module.identifier_at(handle.name).as_str(),
+        )
+    }
+
+    /// The name of a function, qualified by its module's alias if it is not defined in this
+    /// module.
+    pub(crate) fn function_name(&self, idx: FunctionHandleIndex) -> String {
+        let handle = self.module.function_handle_at(idx);
+        self.qualified(
+            handle.module,
+            self.module.identifier_at(handle.name).as_str(),
+        )
+    }
+
+    fn qualified(&self, module: ModuleHandleIndex, name: &str) -> String {
+        match self.aliases.get(&module) {
+            Some(alias) => format!("{alias}::{}", identifier(name)),
+            None => identifier(name),
+        }
+    }
+
+    pub(crate) fn type_(&self, ty: &SignatureToken) -> String {
+        use SignatureToken as S;
+        match ty {
+            S::Bool => "bool".to_string(),
+            S::U8 => "u8".to_string(),
+            S::U16 => "u16".to_string(),
+            S::U32 => "u32".to_string(),
+            S::U64 => "u64".to_string(),
+            S::U128 => "u128".to_string(),
+            S::U256 => "u256".to_string(),
+            S::Address => "address".to_string(),
+            S::Signer => "signer".to_string(),
+            S::Vector(ty) => format!("vector<{}>", self.type_(ty)),
+            S::Datatype(idx) => self.datatype_name(*idx),
+            S::DatatypeInstantiation(inst) => {
+                let (idx, tys) = &**inst;
+                format!(
+                    "{}<{}>",
+                    self.datatype_name(*idx),
+                    self.types(tys).join(", ")
+                )
+            }
+            S::Reference(ty) => format!("&{}", self.type_(ty)),
+            S::MutableReference(ty) => format!("&mut {}", self.type_(ty)),
+            S::TypeParameter(idx) => format!("T{idx}"),
+        }
+    }
+
+    pub(crate) fn types(&self, tys: &[SignatureToken]) -> Vec<String> {
+        tys.iter().map(|ty| self.type_(ty)).collect()
+    }
+
+    pub(crate) fn signature_types(&self, idx: SignatureIndex) -> Vec<String> {
+        self.types(&self.module.signature_at(idx).0)
+    }
+}
+
+/// The name given to the local at `idx`, in a function with `num_params` parameters.
+pub(crate) fn local_name(idx: usize, num_params: usize) -> String {
+    if idx < num_params {
+        format!("arg{idx}")
+    } else {
+        format!("v{idx}")
+    }
+}
+
+pub(crate) fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_string()
+    }
+}
+
+fn datatype_type_params(type_params: &[DatatypeTyParameter]) -> String {
+    if type_params.is_empty() {
+        return String::new();
+    }
+    let type_params: Vec<_> = type_params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let phantom = if param.is_phantom { "phantom " } else { "" };
+            format!("{phantom}T{i}{}", abilities(": ", param.constraints, " + "))
+        })
+        .collect();
+    format!("<{}>", type_params.join(", "))
+}
+
+/// Renders a non-empty set of abilities, after `prefix`.
+fn abilities(prefix: &str, abilities: AbilitySet, separator: &str) -> String {
+    if abilities == AbilitySet::EMPTY {
+        return String::new();
+    }
+    let names: Vec<_> = abilities
+        .into_iter()
+        .map(|ability| match ability {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        })
+        .collect();
+    format!("{prefix}{}", names.join(separator))
+}
+
+fn render_value(value: &MoveValue) -> String {
+    match value {
+        MoveValue::Bool(b) => b.to_string(),
+        MoveValue::U8(n) => format!("{n}u8"),
+        MoveValue::U16(n) => format!("{n}u16"),
+        MoveValue::U32(n) => format!("{n}u32"),
+        MoveValue::U64(n) => n.to_string(),
+        MoveValue::U128(n) => format!("{n}u128"),
+        MoveValue::U256(n) => format!("{n}u256"),
+        MoveValue::Address(a) | MoveValue::Signer(a) => format!("@{}", a.to_hex_literal()),
+        MoveValue::Vector(elems) => {
+            let bytes: Option<Vec<u8>> = elems
+                .iter()
+                .map(|elem| match elem {
+                    MoveValue::U8(b) => Some(*b),
+                    _ => None,
+                })
+                .collect();
+            match bytes {
+                Some(bytes) if !bytes.is_empty() => format!("x\"{}\"", hex::encode(bytes)),
+                _ => {
+                    let elems: Vec<_> = elems.iter().map(render_value).collect();
+                    format!("vector[{}]", elems.join(", "))
+                }
+            }
+        }
+        // constants cannot hold datatypes
+        MoveValue::Struct(_) | MoveValue::Variant(_) => {
+            "/* unable to render constant */ 0".to_string()
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod ast;
+mod body;
+pub mod decompiler;
+mod simplify;
+
+#[cfg(test)]
+mod unit_tests;
@@ -0,0 +1,123 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rewrites the structured code of a function into more idiomatic Move: early returns instead of
+//! nested `else` branches, `while` loops, and `assert!`s.
+
+use crate::ast::{Exp, Stmt};
+use move_abstract_interpreter::control_flow_graph::BlockId;
+
+pub(crate) fn function_body(stmts: &mut Vec<Stmt>) {
+    simplify(stmts);
+    // returning without a value is implicit at the end of a function
+    if matches!(stmts.last(), Some(Stmt::Return(values)) if values.is_empty()) {
+        stmts.pop();
+    }
+}
+
+fn simplify(stmts: &mut Vec<Stmt>) {
+    for stmt in std::mem::take(stmts) {
+        match stmt {
+            Stmt::If(cond, mut then, mut else_) => {
+                simplify(&mut then);
+                simplify(&mut else_);
+                // when a branch always jumps, the other branch can follow the `if`
+                if then.is_empty() && else_.is_empty() {
+                    stmts.push(Stmt::Exp(cond));
+                } else if let Some(assign) = conditional_assign(&cond, &then, &else_) {
+                    stmts.push(assign);
+                } else if !is_abort(&else_) && (always_jumps(&then) || else_.is_empty()) {
+                    push_if(stmts, cond, then);
+                    stmts.append(&mut else_);
+                } else if always_jumps(&else_) || then.is_empty() {
+                    push_if(stmts, cond.negate(), else_);
+                    stmts.append(&mut then);
+                } else {
+                    stmts.push(Stmt::If(cond, then, else_));
+                }
+            }
+            Stmt::Loop(id, mut body) => {
+                strip_trailing_continue(&mut body, id);
+                simplify(&mut body);
+                match body.first() {
+                    Some(Stmt::If(_, then, else_))
+                        if matches!(then.as_slice(), [Stmt::Break(target)] if *target == id)
+                            && else_.is_empty() =>
+                    {
+                        let Stmt::If(cond, _, _) = body.remove(0) else {
+                            unreachable!()
+                        };
+                        stmts.push(Stmt::While(id, cond.negate(), body));
+                    }
+                    _ => stmts.push(Stmt::Loop(id, body)),
+                }
+            }
+            Stmt::Match(subject, mut arms) => {
+                for (_, body) in &mut arms {
+                    simplify(body);
+                }
+                stmts.push(Stmt::Match(subject, arms));
+            }
+            stmt => stmts.push(stmt),
+        }
+    }
+}
+
+/// Turns branches that only assign to the same local into an assignment of a conditional
+/// expression, recovering `&&` and `||`.
+fn conditional_assign(cond: &Exp, then: &[Stmt], else_: &[Stmt]) -> Option<Stmt> {
+    let ([Stmt::Assign(t_locals, t)], [Stmt::Assign(e_locals, e)]) = (then, else_) else {
+        return None;
+    };
+    if t_locals.len() != 1 || t_locals != e_locals {
+        return None;
+    }
+    let cond = Box::new(cond.clone());
+    let value = match (t, e) {
+        (t, Exp::Value(e)) if e == "false" => Exp::Binary("&&", cond, Box::new(t.clone())),
+        (Exp::Value(t), e) if t == "true" => Exp::Binary("||", cond, Box::new(e.clone())),
+        (t, e) => Exp::IfElse(cond, Box::new(t.clone()), Box::new(e.clone())),
+    };
+    Some(Stmt::Assign(t_locals.clone(), value))
+}
+
+fn push_if(stmts: &mut Vec<Stmt>, cond: Exp, then: Vec<Stmt>) {
+    match <[Stmt; 1]>::try_from(then) {
+        Ok([Stmt::Abort(code)]) => stmts.push(Stmt::Assert(cond.negate(), code)),
+        Ok([stmt]) => stmts.push(Stmt::If(cond, vec![stmt], vec![])),
+        Err(then) => stmts.push(Stmt::If(cond, then, vec![])),
+    }
+}
+
+fn is_abort(stmts: &[Stmt]) -> bool {
+    matches!(stmts, [Stmt::Abort(_)])
+}
+
+/// Whether control never reaches the end of `stmts`.
+fn always_jumps(stmts: &[Stmt]) -> bool {
+    match stmts.last() {
+        Some(Stmt::Return(_) | Stmt::Abort(_) | Stmt::Break(_) | Stmt::Continue(_)) => true,
+        Some(Stmt::If(_, then, else_)) => always_jumps(then) && always_jumps(else_),
+        Some(Stmt::Match(_, arms)) => arms.iter().all(|(_, body)| always_jumps(body)),
+        _ => false,
+    }
+}
+
+/// Removes the `continue`s of the loop `id` that are the last thing its body does.
+fn strip_trailing_continue(stmts: &mut Vec<Stmt>, id: BlockId) {
+    match stmts.last_mut() {
+        Some(Stmt::Continue(target)) if *target == id => {
+            stmts.pop();
+        }
+        Some(Stmt::If(_, then, else_)) => {
+            strip_trailing_continue(then, id);
+            strip_trailing_continue(else_, id);
+        }
+        Some(Stmt::Match(_, arms)) => {
+            for (_, body) in arms {
+                strip_trailing_continue(body, id);
+            }
+        }
+        _ => (),
+    }
+}
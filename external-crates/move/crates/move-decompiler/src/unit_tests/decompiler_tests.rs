@@ -0,0 +1,431 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, fs};
+
+use move_binary_format::{
+    file_format::{
+        empty_module, Ability, AbilitySet, Bytecode, CodeUnit, DatatypeHandle, DatatypeHandleIndex,
+        EnumDefinition, EnumDefinitionIndex, FieldDefinition, FunctionDefinition, FunctionHandle,
+        FunctionHandleIndex, IdentifierIndex, JumpTableInner, ModuleHandleIndex, Signature,
+        SignatureIndex, SignatureToken, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, TypeSignature, VariantDefinition, VariantHandle,
+        VariantHandleIndex, VariantJumpTable, VariantJumpTableIndex, Visibility,
+    },
+    CompiledModule,
+};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer,
+    editions::Edition,
+    shared::{NumericalAddress, PackageConfig},
+    Compiler, PASS_PARSER,
+};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+
+use crate::decompiler::Decompiler;
+
+/// An empty module `0x42::m`.
+fn module() -> CompiledModule {
+    let mut m = empty_module();
+    m.identifiers[0] = Identifier::new("m").unwrap();
+    m.address_identifiers[0] = AccountAddress::from_hex_literal("0x42").unwrap();
+    m
+}
+
+fn add_identifier(m: &mut CompiledModule, name: &str) -> IdentifierIndex {
+    m.identifiers.push(Identifier::new(name).unwrap());
+    IdentifierIndex((m.identifiers.len() - 1) as u16)
+}
+
+fn add_signature(m: &mut CompiledModule, tokens: Vec<SignatureToken>) -> SignatureIndex {
+    m.signatures.push(Signature(tokens));
+    SignatureIndex((m.signatures.len() - 1) as u16)
+}
+
+fn add_datatype(m: &mut CompiledModule, name: &str, abilities: AbilitySet) -> DatatypeHandleIndex {
+    let name = add_identifier(m, name);
+    m.datatype_handles.push(DatatypeHandle {
+        module: ModuleHandleIndex(0),
+        name,
+        abilities,
+        type_parameters: vec![],
+    });
+    DatatypeHandleIndex((m.datatype_handles.len() - 1) as u16)
+}
+
+fn field(m: &mut CompiledModule, name: &str, ty: SignatureToken) -> FieldDefinition {
+    FieldDefinition {
+        name: add_identifier(m, name),
+        signature: TypeSignature(ty),
+    }
+}
+
+/// Adds a public function to `m`, with the given signature, additional locals, and body.
+fn add_function(
+    m: &mut CompiledModule,
+    name: &str,
+    params: Vec<SignatureToken>,
+    returns: Vec<SignatureToken>,
+    locals: Vec<SignatureToken>,
+    code: Vec<Bytecode>,
+    jump_tables: Vec<VariantJumpTable>,
+) {
+    let name = add_identifier(m, name);
+    let parameters = add_signature(m, params);
+    let return_ = add_signature(m, returns);
+    let locals = add_signature(m, locals);
+    m.function_handles.push(FunctionHandle {
+        module: ModuleHandleIndex(0),
+        name,
+        parameters,
+        return_,
+        type_parameters: vec![],
+    });
+    m.function_defs.push(FunctionDefinition {
+        function: FunctionHandleIndex((m.function_handles.len() - 1) as u16),
+        visibility: Visibility::Public,
+        is_entry: false,
+        acquires_global_resources: vec![],
+        code: Some(CodeUnit {
+            locals,
+            code,
+            jump_tables,
+        }),
+    });
+}
+
+/// Decompiles a module containing a single public function `f`, with the given signature,
+/// additional locals, and body.
+fn decompile_function(
+    params: Vec<SignatureToken>,
+    returns: Vec<SignatureToken>,
+    locals: Vec<SignatureToken>,
+    code: Vec<Bytecode>,
+) -> String {
+    let mut m = module();
+    add_function(&mut m, "f", params, returns, locals, code, vec![]);
+    decompile(&m)
+}
+
+fn decompile(module: &CompiledModule) -> String {
+    Decompiler::new(module).decompile().unwrap()
+}
+
+/// Checks that `source` is parsed by the compiler without errors.
+fn assert_parses(source: &str) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("m.move");
+    fs::write(&path, source).unwrap();
+    let (files, result) = Compiler::from_files(
+        None,
+        vec![path.to_str().unwrap().to_string()],
+        vec![],
+        BTreeMap::<String, NumericalAddress>::new(),
+    )
+    .set_default_config(PackageConfig {
+        edition: Edition::E2024,
+        ..PackageConfig::default()
+    })
+    .run::<PASS_PARSER>()
+    .unwrap();
+    if let Err((_, diags)) = result {
+        let diags = report_diagnostics_to_buffer(&files, diags, /* ansi_color */ false);
+        panic!("{}\n{source}", String::from_utf8_lossy(&diags));
+    }
+}
+
+fn assert_module(source: &str, expected: &str) {
+    assert_eq!(source, expected, "\n{source}");
+    assert_parses(source);
+}
+
+fn assert_function(source: &str, expected: &str) {
+    assert_module(source, &format!("module 0x42::m {{\n{expected}\n}}\n"));
+}
+
+#[test]
+fn straight_line() {
+    use Bytecode::*;
+    let source = decompile_function(
+        vec![SignatureToken::U64, SignatureToken::U64],
+        vec![SignatureToken::U64],
+        vec![],
+        vec![CopyLoc(0), MoveLoc(1), Add, MoveLoc(0), Mul, Ret],
+    );
+    assert_function(
+        &source,
+        r#"
+    public fun f(arg0: u64, arg1: u64): u64 {
+        (arg0 + arg1) * arg0
+    }"#,
+    );
+}
+
+#[test]
+fn short_circuit() {
+    use Bytecode::*;
+    let source = decompile_function(
+        vec![SignatureToken::U64],
+        vec![SignatureToken::Bool],
+        vec![SignatureToken::Bool],
+        vec![
+            /* L0 */ LdU64(1),
+            /*    */ CopyLoc(0),
+            /*    */ Le,
+            /*    */ BrFalse(9),
+            /* L4 */ CopyLoc(0),
+            /*    */ LdU64(10),
+            /*    */ Lt,
+            /*    */ StLoc(1),
+            /*    */ Branch(11),
+            /* L9 */ LdFalse,
+            /*    */ StLoc(1),
+            /* L11 */ MoveLoc(1),
+            /*    */ Ret,
+        ],
+    );
+    assert_function(
+        &source,
+        r#"
+    public fun f(arg0: u64): bool {
+        let mut v1: bool;
+        v1 = (1 <= arg0) && (arg0 < 10);
+        v1
+    }"#,
+    );
+}
+
+#[test]
+fn while_loop() {
+    use Bytecode::*;
+    let source = decompile_function(
+        vec![SignatureToken::U64],
+        vec![SignatureToken::U64],
+        vec![SignatureToken::U64],
+        vec![
+            /* L0 */ LdU64(0),
+            /*    */ StLoc(1),
+            /* L2 */ CopyLoc(0),
+            /*    */ LdU64(0),
+            /*    */ Gt,
+            /*    */ BrFalse(15),
+            /* L6 */ MoveLoc(1),
+            /*    */ CopyLoc(0),
+            /*    */ Add,
+            /*    */ StLoc(1),
+            /*    */ MoveLoc(0),
+            /*    */ LdU64(1),
+            /*    */ Sub,
+            /*    */ StLoc(0),
+            /*    */ Branch(2),
+            /* L15 */ MoveLoc(1),
+            /*    */ Ret,
+        ],
+    );
+    assert_function(
+        &source,
+        r#"
+    public fun f(mut arg0: u64): u64 {
+        let mut v1: u64;
+        v1 = 0;
+        while (arg0 > 0) {
+            v1 = v1 + arg0;
+            arg0 = arg0 - 1;
+        };
+        v1
+    }"#,
+    );
+}
+
+#[test]
+fn assert_abort() {
+    use Bytecode::*;
+    let source = decompile_function(
+        vec![SignatureToken::Bool],
+        vec![],
+        vec![],
+        vec![
+            /* L0 */ MoveLoc(0),
+            /*    */ BrFalse(3),
+            /* L2 */ Branch(5),
+            /* L3 */ LdU64(7),
+            /*    */ Abort,
+            /* L5 */ Ret,
+        ],
+    );
+    assert_function(
+        &source,
+        r#"
+    public fun f(arg0: bool) {
+        assert!(arg0, 7);
+    }"#,
+    );
+}
+
+#[test]
+fn struct_pack_unpack() {
+    use Bytecode::*;
+    let mut m = module();
+    let point = add_datatype(
+        &mut m,
+        "Point",
+        AbilitySet::EMPTY | Ability::Copy | Ability::Drop,
+    );
+    let fields = vec![
+        field(&mut m, "x", SignatureToken::U64),
+        field(&mut m, "y", SignatureToken::U64),
+    ];
+    m.struct_defs.push(StructDefinition {
+        struct_handle: point,
+        field_information: StructFieldInformation::Declared(fields),
+    });
+    add_function(
+        &mut m,
+        "new",
+        vec![SignatureToken::U64, SignatureToken::U64],
+        vec![SignatureToken::Datatype(point)],
+        vec![],
+        vec![MoveLoc(0), MoveLoc(1), Pack(StructDefinitionIndex(0)), Ret],
+        vec![],
+    );
+    add_function(
+        &mut m,
+        "sum",
+        vec![SignatureToken::Datatype(point)],
+        vec![SignatureToken::U64],
+        vec![SignatureToken::U64, SignatureToken::U64],
+        vec![
+            MoveLoc(0),
+            Unpack(StructDefinitionIndex(0)),
+            StLoc(2),
+            StLoc(1),
+            MoveLoc(1),
+            MoveLoc(2),
+            Add,
+            Ret,
+        ],
+        vec![],
+    );
+    assert_module(
+        &decompile(&m),
+        r#"module 0x42::m {
+
+    public struct Point has copy, drop {
+        x: u64,
+        y: u64,
+    }
+
+    public fun new(arg0: u64, arg1: u64): Point {
+        Point { x: arg0, y: arg1 }
+    }
+
+    public fun sum(arg0: Point): u64 {
+        let mut v1: u64;
+        let mut v2: u64;
+        Point { x: v1, y: v2 } = arg0;
+        v1 + v2
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn enum_match() {
+    use Bytecode::*;
+    let mut m = module();
+    let amount = add_datatype(
+        &mut m,
+        "Amount",
+        AbilitySet::EMPTY | Ability::Copy | Ability::Drop,
+    );
+    let zero = VariantDefinition {
+        variant_name: add_identifier(&mut m, "Zero"),
+        fields: vec![],
+    };
+    let some = VariantDefinition {
+        variant_name: add_identifier(&mut m, "Some"),
+        fields: vec![field(&mut m, "value", SignatureToken::U64)],
+    };
+    m.enum_defs.push(EnumDefinition {
+        enum_handle: amount,
+        variants: vec![zero, some],
+    });
+    for variant in 0..2 {
+        m.variant_handles.push(VariantHandle {
+            enum_def: EnumDefinitionIndex(0),
+            variant,
+        });
+    }
+    add_function(
+        &mut m,
+        "zero",
+        vec![],
+        vec![SignatureToken::Datatype(amount)],
+        vec![],
+        vec![PackVariant(VariantHandleIndex(0)), Ret],
+        vec![],
+    );
+    add_function(
+        &mut m,
+        "some",
+        vec![SignatureToken::U64],
+        vec![SignatureToken::Datatype(amount)],
+        vec![],
+        vec![MoveLoc(0), PackVariant(VariantHandleIndex(1)), Ret],
+        vec![],
+    );
+    add_function(
+        &mut m,
+        "value",
+        vec![SignatureToken::Reference(Box::new(
+            SignatureToken::Datatype(amount),
+        ))],
+        vec![SignatureToken::U64],
+        vec![],
+        vec![
+            /* L0 */ CopyLoc(0),
+            /*    */ VariantSwitch(VariantJumpTableIndex(0)),
+            /* L2 */ LdU64(0),
+            /*    */ Ret,
+            /* L4 */ MoveLoc(0),
+            /*    */ UnpackVariantImmRef(VariantHandleIndex(1)),
+            /*    */ ReadRef,
+            /*    */ Ret,
+        ],
+        vec![VariantJumpTable {
+            head_enum: EnumDefinitionIndex(0),
+            jump_table: JumpTableInner::Full(vec![2, 4]),
+        }],
+    );
+    assert_module(
+        &decompile(&m),
+        r#"module 0x42::m {
+
+    public enum Amount has copy, drop {
+        Zero,
+        Some { value: u64 },
+    }
+
+    public fun zero(): Amount {
+        Amount::Zero
+    }
+
+    public fun some(arg0: u64): Amount {
+        Amount::Some { value: arg0 }
+    }
+
+    public fun value(arg0: &Amount): u64 {
+        match (arg0) {
+            Amount::Zero => {
+                return 0;
+            },
+            Amount::Some { .. } => {
+                return *(match (arg0) { Amount::Some { value: x0 } => x0, _ => abort 0 });
+            },
+        };
+    }
+}
+"#,
+    );
+}
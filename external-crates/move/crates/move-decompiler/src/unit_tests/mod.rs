@@ -0,0 +1,4 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod decompiler_tests;
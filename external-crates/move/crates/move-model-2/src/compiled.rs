@@ -272,6 +272,104 @@ impl Constant {
     }
 }
 
+impl Function {
+    pub fn is_entry(&self) -> bool {
+        self.visibility & Modifiers::Entry as u8 != 0
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.visibility & !(Modifiers::Entry as u8) == Modifiers::Public as u8
+    }
+
+    /// Returns true if the function is `public(package)` (or `public(friend)`)
+    pub fn is_package(&self) -> bool {
+        self.visibility & !(Modifiers::Entry as u8) == Modifiers::Package as u8
+    }
+
+    /// Returns the datatypes used by this function, either in its signature, in the types of its
+    /// locals, or by the instructions in its body.
+    pub fn used_datatypes(&self) -> BTreeSet<QualifiedMemberId> {
+        use Bytecode as B;
+        let mut used = BTreeSet::new();
+        for ty in self.parameters.iter().chain(&self.returns) {
+            ty.collect_datatypes(&mut used);
+        }
+        let Some(code) = &self.code else {
+            return used;
+        };
+        for ty in &code.locals {
+            ty.collect_datatypes(&mut used);
+        }
+        for instr in &code.code {
+            match instr {
+                B::Pack(id) | B::Unpack(id) => {
+                    used.insert(**id);
+                }
+                B::PackGeneric(inst) | B::UnpackGeneric(inst) => {
+                    used.insert(inst.0);
+                    inst.1.iter().for_each(|ty| ty.collect_datatypes(&mut used));
+                }
+                B::MutBorrowField(field) | B::ImmBorrowField(field) => {
+                    used.insert(field.struct_);
+                }
+                B::MutBorrowFieldGeneric(inst) | B::ImmBorrowFieldGeneric(inst) => {
+                    used.insert(inst.0.struct_);
+                    inst.1.iter().for_each(|ty| ty.collect_datatypes(&mut used));
+                }
+                B::PackVariant(variant)
+                | B::UnpackVariant(variant)
+                | B::UnpackVariantImmRef(variant)
+                | B::UnpackVariantMutRef(variant) => {
+                    used.insert(variant.0);
+                }
+                B::PackVariantGeneric(inst)
+                | B::UnpackVariantGeneric(inst)
+                | B::UnpackVariantGenericImmRef(inst)
+                | B::UnpackVariantGenericMutRef(inst) => {
+                    used.insert(inst.0);
+                    inst.2.iter().for_each(|ty| ty.collect_datatypes(&mut used));
+                }
+                B::VariantSwitch(switch) => {
+                    used.insert(switch.0);
+                }
+                B::CallGeneric(inst) => {
+                    inst.1.iter().for_each(|ty| ty.collect_datatypes(&mut used));
+                }
+                B::VecPack(inst) | B::VecUnpack(inst) => inst.0.collect_datatypes(&mut used),
+                B::VecLen(ty)
+                | B::VecImmBorrow(ty)
+                | B::VecMutBorrow(ty)
+                | B::VecPushBack(ty)
+                | B::VecPopBack(ty)
+                | B::VecSwap(ty) => ty.collect_datatypes(&mut used),
+                _ => (),
+            }
+        }
+        used
+    }
+}
+
+impl Type {
+    fn collect_datatypes(&self, acc: &mut BTreeSet<QualifiedMemberId>) {
+        match self {
+            Type::Bool
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::U256
+            | Type::Address
+            | Type::TypeParameter(_) => (),
+            Type::Vector(inner) | Type::Reference(_, inner) => inner.collect_datatypes(acc),
+            Type::Datatype(inst) => {
+                acc.insert(inst.0);
+                inst.1.iter().for_each(|ty| ty.collect_datatypes(acc));
+            }
+        }
+    }
+}
+
 //**************************************************************************************************
 // Traits
 //**************************************************************************************************
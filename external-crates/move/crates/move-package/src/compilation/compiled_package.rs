@@ -7,7 +7,7 @@ use crate::{
     resolution::resolution_graph::{Package, Renaming, ResolvedGraph, ResolvedTable},
     source_package::{
         layout::{SourcePackageLayout, REFERENCE_TEMPLATE_FILENAME},
-        parsed_manifest::{FileName, PackageDigest, PackageName},
+        parsed_manifest::{FileName, Lints, PackageDigest, PackageName},
     },
     BuildConfig,
 };
@@ -25,8 +25,9 @@ use move_command_line_common::files::{
 };
 use move_compiler::{
     compiled_unit::{AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule},
+    diagnostics::warning_filters::{WarningFilter, WarningFiltersBuilder, FILTER_ALL},
     editions::Flavor,
-    linters,
+    linters::{self, LintLevel, LintPack, LintSeverity, ALLOW_ATTR_CATEGORY},
     shared::{
         files::MappedFiles, NamedAddressMap, NumericalAddress, PackageConfig, PackagePaths,
         SaveFlag, SaveHook,
@@ -515,7 +516,7 @@ impl CompiledPackage {
         writeln!(w, "{} {}", "BUILDING".bold().green(), root_package_name)?;
 
         // gather source/dep files with their address mappings
-        let (sources_package_paths, deps_package_paths) = make_source_and_deps_for_compiler(
+        let (mut sources_package_paths, deps_package_paths) = make_source_and_deps_for_compiler(
             resolution_graph,
             &resolved_package,
             transitive_dependencies,
@@ -540,26 +541,46 @@ impl CompiledPackage {
             }
         }
 
+        let sui_mode = resolution_graph.build_options.default_flavor == Some(Flavor::Sui);
+        let mut lint_packs: Vec<Arc<dyn LintPack>> = vec![Arc::new(linters::MoveLints)];
+        if sui_mode {
+            lint_packs.push(Arc::new(sui_mode::linters::SuiLints));
+        }
+        let custom_lint_packs = linters::registered_lint_packs();
+        lint_packs.extend(custom_lint_packs.iter().cloned());
+
+        // apply the root package's `[lints]` configuration
+        let (_, root_config) = sources_package_paths
+            .name
+            .as_mut()
+            .expect("The root package is always named");
+        let lint_level = configure_lints(
+            &mut root_config.warning_filter,
+            &resolved_package.source_package.lints,
+            resolution_graph.build_options.lint_flag.get(),
+            &lint_packs,
+        )?;
+
         // invoke the compiler
         let mut paths = src_deps;
         paths.push(sources_package_paths.clone());
 
-        let lint_level = resolution_graph.build_options.lint_flag.get();
-        let sui_mode = resolution_graph.build_options.default_flavor == Some(Flavor::Sui);
-
         let mut compiler = Compiler::from_package_paths(vfs_root, paths, bytecode_deps)
             .unwrap()
             .set_flags(flags);
         if sui_mode {
             let (filter_attr_name, filters) = sui_mode::linters::known_filters();
-            compiler = compiler
-                .add_custom_known_filters(filter_attr_name, filters)
-                .add_visitors(sui_mode::linters::linter_visitors(lint_level))
+            compiler = compiler.add_custom_known_filters(filter_attr_name, filters);
         }
         let (filter_attr_name, filters) = linters::known_filters();
-        compiler = compiler
-            .add_custom_known_filters(filter_attr_name, filters)
-            .add_visitors(linters::linter_visitors(lint_level));
+        compiler = compiler.add_custom_known_filters(filter_attr_name, filters);
+        for pack in &custom_lint_packs {
+            compiler = compiler
+                .add_custom_known_filters(Some(ALLOW_ATTR_CATEGORY), pack.filters(LintLevel::All));
+        }
+        for pack in &lint_packs {
+            compiler = compiler.add_visitors(pack.visitors(lint_level));
+        }
         Ok(BuildResult {
             root_package_name,
             immediate_dependencies,
@@ -886,6 +907,74 @@ pub(crate) fn apply_named_address_renaming(
         .collect()
 }
 
+/// Applies a package's `[lints]` configuration to its warning filters, returning the level the
+/// linters need to run at. Setting `all` configures every lint, and is overridden by the settings
+/// of individual lints. Enabling a lint that does not run by default runs all linters, allowing
+/// the lints that were not enabled.
+fn configure_lints(
+    warning_filter: &mut WarningFiltersBuilder,
+    lints: &Lints,
+    level: LintLevel,
+    lint_packs: &[Arc<dyn LintPack>],
+) -> Result<LintLevel> {
+    if lints.is_empty() || level == LintLevel::None {
+        return Ok(level);
+    }
+
+    let default_filters: BTreeSet<WarningFilter> = lint_packs
+        .iter()
+        .flat_map(|pack| pack.filters(LintLevel::Default))
+        .collect();
+    // the severity of each known lint, before applying the configuration
+    let mut severities: BTreeMap<&'static str, (WarningFilter, LintSeverity)> = BTreeMap::new();
+    for filter in lint_packs
+        .iter()
+        .flat_map(|pack| pack.filters(LintLevel::All))
+    {
+        let Some(name) = filter.to_str() else {
+            continue;
+        };
+        let severity = if level == LintLevel::All || default_filters.contains(&filter) {
+            LintSeverity::Warn
+        } else {
+            LintSeverity::Allow
+        };
+        severities.insert(name, (filter, severity));
+    }
+
+    if let Some(severity) = lints.get(&Symbol::from(FILTER_ALL)) {
+        for (_, current) in severities.values_mut() {
+            *current = *severity;
+        }
+    }
+    for (name, severity) in lints {
+        if name.as_str() == FILTER_ALL {
+            continue;
+        }
+        let Some((_, current)) = severities.get_mut(name.as_str()) else {
+            anyhow::bail!("Unknown lint '{name}' in the '[lints]' section of the manifest");
+        };
+        *current = *severity;
+    }
+
+    let level = if level == LintLevel::Default
+        && severities.values().any(|(filter, severity)| {
+            *severity != LintSeverity::Allow && !default_filters.contains(filter)
+        }) {
+        LintLevel::All
+    } else {
+        level
+    };
+    for (filter, severity) in severities.into_values() {
+        match severity {
+            LintSeverity::Allow => warning_filter.add(filter),
+            LintSeverity::Warn => (),
+            LintSeverity::Deny => warning_filter.deny(filter),
+        }
+    }
+    Ok(level)
+}
+
 pub(crate) fn make_source_and_deps_for_compiler(
     resolution_graph: &ResolvedGraph,
     root: &Package,
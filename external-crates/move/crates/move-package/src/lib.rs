@@ -11,7 +11,7 @@ pub mod package_hooks;
 pub mod resolution;
 pub mod source_package;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::*;
 use lock_file::LockFile;
 use move_compiler::{
@@ -20,12 +20,17 @@ use move_compiler::{
 };
 use move_core_types::account_address::AccountAddress;
 use move_model_2::source_model;
-use resolution::{dependency_graph::DependencyGraphBuilder, resolution_graph::ResolvedGraph};
+use resolution::{
+    dependency_cache::DependencyCache,
+    dependency_graph::{DependencyGraph, DependencyGraphBuilder},
+    resolution_graph::ResolvedGraph,
+    UpdatedDependency,
+};
 use serde::{Deserialize, Serialize};
 use source_package::{
     layout::SourcePackageLayout,
     manifest_parser::{parse_move_manifest_string, parse_source_manifest},
-    parsed_manifest::{Dependencies, DependencyKind},
+    parsed_manifest::{Dependencies, DependencyKind, PackageName},
 };
 use std::{
     collections::BTreeMap,
@@ -266,6 +271,97 @@ impl BuildConfig {
         Ok(())
     }
 
+    /// The dependency graph of the package at `path`, as recorded in its lock file if that is up to
+    /// date, and otherwise as computed from its manifest. Git dependencies that have already been
+    /// downloaded are not refreshed, regardless of `skip_fetch_latest_git_deps`.
+    pub fn dependency_graph_for_package<W: Write>(
+        &self,
+        path: &Path,
+        writer: &mut W,
+    ) -> Result<DependencyGraph> {
+        let path = SourcePackageLayout::try_find_root(path)?;
+        let manifest_string =
+            std::fs::read_to_string(path.join(SourcePackageLayout::Manifest.path()))?;
+        let lock_string = std::fs::read_to_string(path.join(SourcePackageLayout::Lock.path())).ok();
+        let _mutx = PackageLock::lock(); // held until function returns
+
+        let install_dir = self.install_dir.as_ref().unwrap_or(&path).to_owned();
+        let mut dep_graph_builder = DependencyGraphBuilder::new(
+            /* skip_fetch_latest_git_deps */ true,
+            writer,
+            install_dir,
+            self.implicit_dependencies.clone(),
+        );
+        let (dependency_graph, _) = dep_graph_builder.get_graph(
+            &DependencyKind::default(),
+            path,
+            manifest_string,
+            lock_string,
+        )?;
+
+        Ok(dependency_graph)
+    }
+
+    /// Update the git dependencies named in `names` (or all git dependencies, if `names` is empty)
+    /// of the package at `path` to the latest commit on their branch, and rewrite its lock file.
+    /// Nothing is changed if the dependencies are already up to date. If the updated dependency
+    /// graph does not resolve, e.g. because of conflicting named addresses or substitutions, the
+    /// lock file is left untouched and the dependencies are checked out at their previous commit
+    /// again. Returns the dependencies whose resolved commit changed.
+    pub fn update_dependencies<W: Write>(
+        self,
+        path: &Path,
+        names: &[PackageName],
+        writer: &mut W,
+    ) -> Result<Vec<UpdatedDependency>> {
+        let path = SourcePackageLayout::try_find_root(path)?;
+        let manifest_string =
+            std::fs::read_to_string(path.join(SourcePackageLayout::Manifest.path()))?;
+        let lock_path = path.join(SourcePackageLayout::Lock.path());
+        let lock_string = std::fs::read_to_string(lock_path.clone()).ok();
+        let _mutx = PackageLock::lock(); // held until function returns
+
+        let Some(update) = resolution::update_dependency_repos(
+            manifest_string,
+            lock_string,
+            names,
+            &self,
+            &path,
+            writer,
+        )?
+        else {
+            return Ok(vec![]);
+        };
+
+        let install_dir = self.install_dir.as_ref().unwrap_or(&path).to_owned();
+        let lock_path_override = self.lock_file.clone();
+        let result = update
+            .graph
+            .write_to_lock(install_dir, Some(lock_path.clone()))
+            .and_then(|lock| {
+                // All dependencies have been fetched at this point, so resolving the graph only
+                // checks that the updated packages are still compatible with each other.
+                let mut dependency_cache =
+                    DependencyCache::new(/* skip_fetch_latest_git_deps */ true);
+                ResolvedGraph::resolve(
+                    update.graph.clone(),
+                    self,
+                    &mut dependency_cache,
+                    None,
+                    &mut *writer,
+                )
+                .context("Updated dependencies do not resolve, Move.lock was not modified")?;
+                lock.commit(lock_path_override.unwrap_or(lock_path))
+            });
+
+        if let Err(err) = result {
+            update.rollback()?;
+            return Err(err);
+        }
+
+        Ok(update.updated)
+    }
+
     pub fn resolution_graph_for_package<W: Write>(
         mut self,
         path: &Path,
@@ -19,11 +19,13 @@ pub trait PackageHooks {
 
     /// A resolver for on-chain dependencies in the manifest. This is called to download the
     /// dependency from the dependency into the `info.local_path` location, similar as with git
-    /// dependencies.
+    /// dependencies. `version` is the version of the dependency recorded in the lock file, if
+    /// any, which an already downloaded copy of the dependency is expected to match.
     fn resolve_on_chain_dependency(
         &self,
         dep_name: Symbol,
         info: &OnChainInfo,
+        version: Option<Symbol>,
     ) -> anyhow::Result<()>;
 
     fn custom_resolve_pkg_id(&self, manifest: &SourceManifest)
@@ -43,9 +45,10 @@ pub fn register_package_hooks(hooks: Box<dyn PackageHooks + Send + Sync>) {
 pub(crate) fn resolve_on_chain_dependency(
     dep_name: Symbol,
     info: &OnChainInfo,
+    version: Option<Symbol>,
 ) -> anyhow::Result<()> {
     if let Some(hooks) = &*HOOKS.lock().unwrap() {
-        hooks.resolve_on_chain_dependency(dep_name, info)
+        hooks.resolve_on_chain_dependency(dep_name, info, version)
     } else {
         bail!("use of unsupported on-chain dependency in package manifest")
     }
@@ -3,11 +3,12 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use move_symbol_pool::Symbol;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     ffi::OsStr,
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
@@ -28,6 +29,10 @@ pub struct DependencyCache {
     /// Should a dependency fetched when building a different package be refreshed to the newest
     /// version when building a new package
     skip_fetch_latest_git_deps: bool,
+
+    /// Versions of on-chain dependencies (keyed by id) recorded in lock files, which previously
+    /// fetched copies of these dependencies are checked against
+    locked_on_chain_versions: BTreeMap<Symbol, Symbol>,
 }
 
 impl DependencyCache {
@@ -36,9 +41,17 @@ impl DependencyCache {
         DependencyCache {
             fetched_deps,
             skip_fetch_latest_git_deps,
+            locked_on_chain_versions: BTreeMap::new(),
         }
     }
 
+    /// Records the version of an on-chain dependency found in a lock file. Versions recorded
+    /// first take precedence, so the root package's lock file wins over those of its
+    /// dependencies.
+    pub fn record_locked_on_chain_version(&mut self, id: Symbol, version: Symbol) {
+        self.locked_on_chain_versions.entry(id).or_insert(version);
+    }
+
     pub fn download_and_update_if_remote<Progress: Write>(
         &mut self,
         dep_name: PackageName,
@@ -53,7 +66,8 @@ impl DependencyCache {
                 if !self.fetched_deps.insert(repository_path(kind)) {
                     return Ok(());
                 }
-                package_hooks::resolve_on_chain_dependency(dep_name, info)
+                let version = self.locked_on_chain_versions.get(&info.id).copied();
+                package_hooks::resolve_on_chain_dependency(dep_name, info, version)
             }
 
             DependencyKind::Git(GitInfo {
@@ -127,125 +141,230 @@ impl DependencyCache {
                             )
                         })?;
                 } else if !self.skip_fetch_latest_git_deps {
-                    // Update the git dependency
-                    // Check first that it isn't a git rev (if it doesn't work, just continue with the
-                    // fetch)
-                    if let Ok(rev) = Command::new("git")
-                        .args([
-                            OsStr::new("-C"),
-                            git_path.as_os_str(),
-                            OsStr::new("rev-parse"),
-                            OsStr::new("--verify"),
-                            os_git_rev,
-                        ])
-                        .stdin(Stdio::null())
-                        .output()
-                    {
-                        if let Ok(parsable_version) = String::from_utf8(rev.stdout) {
-                            // If it's exactly the same, then it's a git rev
-                            if parsable_version.trim().starts_with(git_rev.as_str()) {
-                                return Ok(());
-                            }
-                        }
-                    }
+                    fetch_latest_git_rev(dep_name, git_url, git_rev, &git_path, progress_output)?;
+                }
 
-                    let tag = Command::new("git")
-                        .args([
-                            OsStr::new("-C"),
-                            git_path.as_os_str(),
-                            OsStr::new("tag"),
-                            OsStr::new("--list"),
-                            os_git_rev,
-                        ])
-                        .stdin(Stdio::null())
-                        .output();
-
-                    if let Ok(tag) = tag {
-                        if let Ok(parsable_version) = String::from_utf8(tag.stdout) {
-                            // If it's exactly the same, then it's a git tag, for now tags won't be updated
-                            // Tags don't easily update locally and you can't use reset --hard to cleanup
-                            // any extra files
-                            if parsable_version.trim().starts_with(git_rev.as_str()) {
-                                return Ok(());
-                            }
-                        }
-                    }
+                Ok(())
+            }
+        }
+    }
 
-                    writeln!(
-                        progress_output,
-                        "{} {}",
-                        "UPDATING GIT DEPENDENCY".bold().green(),
-                        git_url,
-                    )?;
+    /// Fetch the latest state of the git dependency `dep_name` without changing its local copy,
+    /// even if fetching the latest version of git dependencies is disabled for this cache, and
+    /// return the commit that its branch points to. Returns `None` for dependencies that are not
+    /// fetched from git, and for git dependencies pinned to an exact commit or a tag. Git
+    /// dependencies that have not been downloaded yet are cloned.
+    pub fn fetch_git_dependency<Progress: Write>(
+        &mut self,
+        dep_name: PackageName,
+        kind: &DependencyKind,
+        progress_output: &mut Progress,
+    ) -> Result<Option<String>> {
+        let DependencyKind::Git(GitInfo {
+            git_url,
+            git_rev,
+            subdir: _,
+        }) = kind
+        else {
+            return Ok(None);
+        };
 
-                    // If the current folder exists, do a fetch and reset to ensure that the branch
-                    // is up to date.
-                    //
-                    // NOTE: this means that you must run the package system with a working network
-                    // connection.
+        let git_path = repository_path(kind);
+        if !git_path.exists() {
+            self.download_and_update_if_remote(dep_name, kind, progress_output)?;
+        } else {
+            self.fetched_deps.insert(git_path.clone());
+        }
 
-                    if let Ok(mut output) = Command::new("git")
-                        .args([
-                            OsStr::new("-C"),
-                            git_path.as_os_str(),
-                            OsStr::new("fetch"),
-                            OsStr::new("origin"),
-                        ])
-                        .stdin(Stdio::null())
-                        .spawn()
-                    {
-                        output.wait().map_err(|_| {
-                            anyhow::anyhow!(
-                                "Failed to fetch latest Git state for package '{}', to skip set \
-                             --skip-fetch-latest-git-deps",
-                                dep_name
-                            )
-                        })?;
-                        if output.stdout.is_some() {
-                            writeln!(progress_output, "{:?}", output)?;
-                        }
-                    } else {
-                        return Err(anyhow::anyhow!(
-                            "Failed to fetch latest Git state for package '{}', to skip set \
-                             --skip-fetch-latest-git-deps",
-                            dep_name
-                        ));
-                    }
+        if is_pinned_git_rev(git_rev, &git_path) {
+            return Ok(None);
+        }
 
-                    let status = Command::new("git")
-                        .args([
-                            OsStr::new("-C"),
-                            git_path.as_os_str(),
-                            OsStr::new("reset"),
-                            OsStr::new("--hard"),
-                            OsStr::new(&format!("origin/{}", git_rev)),
-                        ])
-                        .stdin(Stdio::null())
-                        .stdout(Stdio::null())
-                        .stderr(Stdio::null())
-                        .status()
-                        .map_err(|_| {
-                            anyhow::anyhow!(
-                            "Failed to reset to latest Git state '{}' for package '{}', to skip \
-                             set --skip-fetch-latest-git-deps",
-                            git_rev,
-                            dep_name
-                        )
-                        })?;
+        writeln!(
+            progress_output,
+            "{} {}",
+            "FETCHING GIT DEPENDENCY".bold().green(),
+            git_url,
+        )?;
+        fetch_git_repo(dep_name, &git_path, progress_output)?;
 
-                    if !status.success() {
-                        return Err(anyhow::anyhow!(
-                        "Failed to reset to latest Git state '{}' for package '{}', to skip set \
-                         --skip-fetch-latest-git-deps | Exit status: {}",
-                        git_rev,
-                        dep_name,
-                        status
-                    ));
-                    }
-                }
+        let output = Command::new("git")
+            .args([
+                OsStr::new("-C"),
+                git_path.as_os_str(),
+                OsStr::new("rev-parse"),
+                OsStr::new(&format!("origin/{}", git_rev)),
+            ])
+            .stdin(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to find the latest commit of '{}' for package '{}'",
+                git_rev,
+                dep_name
+            ));
+        }
 
-                Ok(())
+        Ok(Some(String::from_utf8(output.stdout)?.trim().to_string()))
+    }
+}
+
+/// Whether `git_rev` names an exact commit or a tag in the git repository cached at `git_path`,
+/// rather than a branch.
+fn is_pinned_git_rev(git_rev: &Symbol, git_path: &Path) -> bool {
+    let os_git_rev = OsStr::new(git_rev.as_str());
+
+    // Check first that it isn't a git rev (if it doesn't work, just continue with the
+    // fetch)
+    if let Ok(rev) = Command::new("git")
+        .args([
+            OsStr::new("-C"),
+            git_path.as_os_str(),
+            OsStr::new("rev-parse"),
+            OsStr::new("--verify"),
+            os_git_rev,
+        ])
+        .stdin(Stdio::null())
+        .output()
+    {
+        if let Ok(parsable_version) = String::from_utf8(rev.stdout) {
+            // If it's exactly the same, then it's a git rev
+            if parsable_version.trim().starts_with(git_rev.as_str()) {
+                return true;
+            }
+        }
+    }
+
+    let tag = Command::new("git")
+        .args([
+            OsStr::new("-C"),
+            git_path.as_os_str(),
+            OsStr::new("tag"),
+            OsStr::new("--list"),
+            os_git_rev,
+        ])
+        .stdin(Stdio::null())
+        .output();
+
+    if let Ok(tag) = tag {
+        if let Ok(parsable_version) = String::from_utf8(tag.stdout) {
+            // If it's exactly the same, then it's a git tag, for now tags won't be updated
+            // Tags don't easily update locally and you can't use reset --hard to cleanup
+            // any extra files
+            if parsable_version.trim().starts_with(git_rev.as_str()) {
+                return true;
             }
         }
     }
+
+    false
+}
+
+/// Fetch the latest state of the git repository cached at `git_path` and reset it to the latest
+/// commit of `git_rev`. Exact revisions and tags are left as they are.
+fn fetch_latest_git_rev<Progress: Write>(
+    dep_name: PackageName,
+    git_url: &Symbol,
+    git_rev: &Symbol,
+    git_path: &Path,
+    progress_output: &mut Progress,
+) -> Result<()> {
+    if is_pinned_git_rev(git_rev, git_path) {
+        return Ok(());
+    }
+
+    writeln!(
+        progress_output,
+        "{} {}",
+        "UPDATING GIT DEPENDENCY".bold().green(),
+        git_url,
+    )?;
+
+    // If the current folder exists, do a fetch and reset to ensure that the branch
+    // is up to date.
+    //
+    // NOTE: this means that you must run the package system with a working network
+    // connection.
+    fetch_git_repo(dep_name, git_path, progress_output)?;
+    reset_git_repo(dep_name, git_rev, git_path, &format!("origin/{}", git_rev))
+}
+
+/// Fetch the latest state of the git repository cached at `git_path`, without changing what is
+/// checked out.
+fn fetch_git_repo<Progress: Write>(
+    dep_name: PackageName,
+    git_path: &Path,
+    progress_output: &mut Progress,
+) -> Result<()> {
+    if let Ok(mut output) = Command::new("git")
+        .args([
+            OsStr::new("-C"),
+            git_path.as_os_str(),
+            OsStr::new("fetch"),
+            OsStr::new("origin"),
+        ])
+        .stdin(Stdio::null())
+        .spawn()
+    {
+        output.wait().map_err(|_| {
+            anyhow::anyhow!(
+                "Failed to fetch latest Git state for package '{}', to skip set \
+                 --skip-fetch-latest-git-deps",
+                dep_name
+            )
+        })?;
+        if output.stdout.is_some() {
+            writeln!(progress_output, "{:?}", output)?;
+        }
+    } else {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch latest Git state for package '{}', to skip set \
+             --skip-fetch-latest-git-deps",
+            dep_name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reset the git repository cached at `git_path` for the dependency on `git_rev` to `target`,
+/// discarding any local changes.
+pub(crate) fn reset_git_repo(
+    dep_name: PackageName,
+    git_rev: &Symbol,
+    git_path: &Path,
+    target: &str,
+) -> Result<()> {
+    let status = Command::new("git")
+        .args([
+            OsStr::new("-C"),
+            git_path.as_os_str(),
+            OsStr::new("reset"),
+            OsStr::new("--hard"),
+            OsStr::new(target),
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Failed to reset to latest Git state '{}' for package '{}', to skip \
+                 set --skip-fetch-latest-git-deps",
+                git_rev,
+                dep_name
+            )
+        })?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to reset to latest Git state '{}' for package '{}', to skip set \
+             --skip-fetch-latest-git-deps | Exit status: {}",
+            git_rev,
+            dep_name,
+            status
+        ));
+    }
+
+    Ok(())
 }
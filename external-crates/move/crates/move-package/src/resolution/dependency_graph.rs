@@ -227,6 +227,31 @@ impl<Progress: Write> DependencyGraphBuilder<Progress> {
         }
     }
 
+    /// Records the versions of the on-chain packages in a lock file with the dependency cache,
+    /// so that previously fetched copies of these packages can be checked against them. Lock
+    /// files that cannot be read are regenerated anyway, so they are ignored here.
+    fn record_locked_on_chain_versions(&mut self, lock_string: &str) {
+        let Ok((packages, _)) = schema::Packages::read(&mut lock_string.as_bytes()) else {
+            return;
+        };
+        for schema::Package {
+            source, version, ..
+        } in packages.packages.into_iter().flatten()
+        {
+            if let (
+                Ok(PM::Dependency::Internal(PM::InternalDependency {
+                    kind: PM::DependencyKind::OnChain(info),
+                    ..
+                })),
+                Some(version),
+            ) = (parse_dependency(source), version)
+            {
+                self.dependency_cache
+                    .record_locked_on_chain_version(info.id, Symbol::from(version));
+            }
+        }
+    }
+
     /// Get a new graph by either reading it from Move.lock file (if this file is up-to-date, in
     /// which case also return false) or by computing a new graph based on the content of the
     /// Move.toml (manifest) file (in which case also return true).
@@ -248,6 +273,9 @@ impl<Progress: Write> DependencyGraphBuilder<Progress> {
         // compute digests eagerly as even if we can't reuse existing lock file, they need to become
         // part of the newly computed dependency graph
         let new_manifest_digest = digest_str(manifest_string.into_bytes().as_slice());
+        if let Some(lock_string) = &lock_string_opt {
+            self.record_locked_on_chain_versions(lock_string);
+        }
         let lock_path = root_path.join(SourcePackageLayout::Lock.path());
         let lock_file = File::open(lock_path);
         let digest_and_lock_contents = lock_file
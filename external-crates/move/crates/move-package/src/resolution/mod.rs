@@ -2,19 +2,26 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use move_command_line_common::env::MOVE_HOME;
+use move_symbol_pool::Symbol;
 use std::{
+    collections::BTreeMap,
     io::Write,
     path::{Path, PathBuf},
+    process::{Command, Stdio},
 };
 
 use crate::{
-    source_package::parsed_manifest::{DependencyKind, GitInfo, OnChainInfo},
+    package_hooks::PackageIdentifier,
+    source_package::parsed_manifest::{DependencyKind, GitInfo, OnChainInfo, PackageName},
     BuildConfig,
 };
 
-use self::dependency_graph::DependencyGraphBuilder;
+use self::{
+    dependency_cache::reset_git_repo,
+    dependency_graph::{DependencyGraph, DependencyGraphBuilder},
+};
 
 pub mod dependency_cache;
 pub mod dependency_graph;
@@ -72,6 +79,193 @@ pub fn download_dependency_repos<Progress: Write>(
     Ok(())
 }
 
+/// A git dependency whose resolved commit changed as a result of `update_dependency_repos`.
+#[derive(Debug, Clone)]
+pub struct UpdatedDependency {
+    pub id: PackageIdentifier,
+    pub kind: DependencyKind,
+    /// The commit the dependency was checked out at before the update, or `None` if it was not a
+    /// dependency (or had not been fetched) before.
+    pub old_revision: Option<String>,
+    pub new_revision: Option<String>,
+}
+
+/// The result of `update_dependency_repos`: the dependency graph recomputed after checking out the
+/// latest commit of the updated git dependencies.
+#[derive(Debug)]
+pub struct DependencyUpdate {
+    pub graph: DependencyGraph,
+    pub updated: Vec<UpdatedDependency>,
+    /// The local copies of git dependencies that were checked out at a new commit, along with the
+    /// commit they were checked out at before.
+    previous_revisions: BTreeMap<PathBuf, (PackageIdentifier, Symbol, String)>,
+}
+
+impl DependencyUpdate {
+    /// Check the updated git dependencies out at the commit they were at before the update.
+    pub fn rollback(&self) -> Result<()> {
+        for (git_path, (dep_name, git_rev, revision)) in &self.previous_revisions {
+            reset_git_repo(*dep_name, git_rev, git_path, revision)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fetch the git dependencies named in `names` (or all git dependencies, if `names` is empty), and
+/// check out the latest commit on their branch for those that are behind it. Other git
+/// dependencies are not refreshed. Returns `None` without touching any local copy if all
+/// dependencies are already up to date, and otherwise the dependency graph of the package at
+/// `root_path`, recomputed from its manifest, along with the dependencies whose resolved commit
+/// changed. Local copies that were checked out at a new commit are restored if the graph cannot be
+/// recomputed, and can be restored by the caller with `DependencyUpdate::rollback`.
+pub fn update_dependency_repos<Progress: Write>(
+    manifest_string: String,
+    lock_string: Option<String>,
+    names: &[PackageName],
+    build_options: &BuildConfig,
+    root_path: &Path,
+    progress_output: &mut Progress,
+) -> Result<Option<DependencyUpdate>> {
+    let install_dir = build_options
+        .install_dir
+        .as_ref()
+        .unwrap_or(&root_path.to_path_buf())
+        .to_owned();
+    let mut dep_graph_builder = DependencyGraphBuilder::new(
+        /* skip_fetch_latest_git_deps */ true,
+        progress_output,
+        install_dir,
+        build_options.implicit_dependencies.clone(),
+    );
+    let (old_graph, _) = dep_graph_builder.get_graph(
+        &DependencyKind::default(),
+        root_path.to_path_buf(),
+        manifest_string.clone(),
+        lock_string,
+    )?;
+
+    for name in names {
+        match old_graph.package_table.get(name) {
+            Some(package) if matches!(package.kind, DependencyKind::Git(_)) => (),
+            Some(_) => bail!("Dependency '{name}' is not a git dependency"),
+            None => bail!("No dependency named '{name}' in this package"),
+        }
+    }
+
+    let old_revisions: BTreeMap<_, _> = old_graph
+        .package_table
+        .iter()
+        .map(|(id, package)| (*id, resolved_git_revision(&package.kind)))
+        .collect();
+
+    // Only fetch at first, so that nothing is checked out unless an update is available.
+    let mut latest_revisions = BTreeMap::new();
+    for (id, package) in &old_graph.package_table {
+        if !names.is_empty() && !names.contains(id) {
+            continue;
+        }
+
+        let DependencyGraphBuilder {
+            ref mut dependency_cache,
+            ref mut progress_output,
+            ..
+        } = dep_graph_builder;
+        let DependencyKind::Git(GitInfo { git_rev, .. }) = &package.kind else {
+            continue;
+        };
+        let Some(latest) =
+            dependency_cache.fetch_git_dependency(*id, &package.kind, progress_output)?
+        else {
+            continue;
+        };
+        let current = resolved_git_revision(&package.kind);
+        if current.as_deref() != Some(latest.as_str()) {
+            latest_revisions.insert(
+                repository_path(&package.kind),
+                (*id, *git_rev, current, latest),
+            );
+        }
+    }
+
+    if latest_revisions.is_empty() {
+        return Ok(None);
+    }
+
+    let mut update = DependencyUpdate {
+        graph: old_graph,
+        updated: vec![],
+        previous_revisions: BTreeMap::new(),
+    };
+    for (git_path, (dep_name, git_rev, current, latest)) in latest_revisions {
+        if let Err(err) = reset_git_repo(dep_name, &git_rev, &git_path, &latest) {
+            update.rollback()?;
+            return Err(err);
+        }
+        if let Some(current) = current {
+            update
+                .previous_revisions
+                .insert(git_path, (dep_name, git_rev, current));
+        }
+    }
+
+    // Ignore the existing lock file, so that the graph is recomputed from the updated manifests.
+    let new_graph = match dep_graph_builder.get_graph(
+        &DependencyKind::default(),
+        root_path.to_path_buf(),
+        manifest_string,
+        None,
+    ) {
+        Ok((graph, _)) => graph,
+        Err(err) => {
+            update.rollback()?;
+            return Err(err);
+        }
+    };
+
+    for (id, package) in &new_graph.package_table {
+        let new_revision = resolved_git_revision(&package.kind);
+        let old_revision = old_revisions.get(id).cloned().flatten();
+        if new_revision.is_some() && new_revision != old_revision {
+            update.updated.push(UpdatedDependency {
+                id: *id,
+                kind: package.kind.clone(),
+                old_revision,
+                new_revision,
+            });
+        }
+    }
+
+    update.graph = new_graph;
+    Ok(Some(update))
+}
+
+/// The commit that the local copy of the git dependency of kind `kind` is checked out at, or
+/// `None` if `kind` is not a git dependency or has not been fetched yet.
+pub fn resolved_git_revision(kind: &DependencyKind) -> Option<String> {
+    let DependencyKind::Git(_) = kind else {
+        return None;
+    };
+
+    let repository_path = repository_path(kind);
+    if !repository_path.exists() {
+        return None;
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository_path)
+        .args(["rev-parse", "HEAD"])
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
 /// The local location of the repository containing the dependency of kind `kind` (and potentially
 /// other, related dependencies).
 fn repository_path(kind: &DependencyKind) -> PathBuf {
@@ -114,7 +308,8 @@ fn local_path(kind: &DependencyKind) -> PathBuf {
     repo_path
 }
 
-fn url_to_file_name(url: &str) -> String {
+/// The name of the directory under `MOVE_HOME` that a remote dependency at `url` is fetched into.
+pub fn url_to_file_name(url: &str) -> String {
     regex::Regex::new(r"/|:|\.|@")
         .unwrap()
         .replace_all(url, "_")
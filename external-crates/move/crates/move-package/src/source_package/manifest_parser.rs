@@ -4,7 +4,10 @@
 
 use crate::{package_hooks, source_package::parsed_manifest as PM};
 use anyhow::{anyhow, bail, format_err, Context, Result};
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    linters::LintSeverity,
+};
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -24,6 +27,7 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const LINTS_NAME: &str = "lints";
 
 const EXTERNAL_RESOLVER_PREFIX: &str = "r";
 
@@ -34,6 +38,7 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    LINTS_NAME,
     EXTERNAL_RESOLVER_PREFIX,
 ];
 
@@ -97,6 +102,12 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 .transpose()
                 .context("Error parsing '[dev-dependencies]' section of manifest")?
                 .unwrap_or_default();
+            let lints = table
+                .remove(LINTS_NAME)
+                .map(parse_lints)
+                .transpose()
+                .context("Error parsing '[lints]' section of manifest")?
+                .unwrap_or_default();
             Ok(PM::SourceManifest {
                 package,
                 addresses,
@@ -104,6 +115,7 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 build,
                 dependencies,
                 dev_dependencies,
+                lints,
             })
         }
         x => {
@@ -241,6 +253,33 @@ pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     }
 }
 
+pub fn parse_lints(tval: TV) -> Result<PM::Lints> {
+    match tval {
+        TV::Table(table) => {
+            let mut lints = BTreeMap::new();
+            for (name, level) in table.into_iter() {
+                let level = match level.as_str() {
+                    Some("allow") => LintSeverity::Allow,
+                    Some("warn") => LintSeverity::Warn,
+                    Some("deny") => LintSeverity::Deny,
+                    _ => bail!(
+                        "Invalid level for lint '{}': {}. Expected one of \"allow\", \"warn\" or \"deny\"",
+                        name,
+                        level
+                    ),
+                };
+                lints.insert(Symbol::from(name), level);
+            }
+            Ok(lints)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_addresses(tval: TV) -> Result<PM::AddressDeclarations> {
     match tval {
         TV::Table(table) => {
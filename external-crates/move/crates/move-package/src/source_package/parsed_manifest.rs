@@ -4,7 +4,10 @@
 
 use anyhow::{bail, Result};
 
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    linters::LintSeverity,
+};
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::symbol::Symbol;
 use serde::{Deserialize, Serialize};
@@ -24,6 +27,7 @@ pub type DevAddressDeclarations = BTreeMap<NamedAddress, AccountAddress>;
 pub type Version = (u64, u64, u64);
 pub type Dependencies = BTreeMap<PackageName, Dependency>;
 pub type Substitution = BTreeMap<NamedAddress, SubstOrRename>;
+pub type Lints = BTreeMap<Symbol, LintSeverity>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SourceManifest {
@@ -33,6 +37,7 @@ pub struct SourceManifest {
     pub build: Option<BuildInfo>,
     pub dependencies: Dependencies,
     pub dev_dependencies: Dependencies,
+    pub lints: Lints,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
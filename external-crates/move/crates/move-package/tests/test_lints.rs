@@ -0,0 +1,121 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests that the `[lints]` section of a package's manifest controls how lints are reported when
+//! the package is built.
+
+use std::fs;
+
+use move_compiler::diagnostics::report_diagnostics_to_buffer;
+use move_package::{compilation::build_plan::BuildPlan, BuildConfig, LintFlag};
+
+const SOURCE: &str = "module lints::m;
+
+public fun count(): u64 {
+    let mut i = 0;
+    while (true) {
+        i = i + 1;
+        if (i > 10) break
+    };
+    i
+}
+";
+
+/// Builds a package with a `while (true)` loop and the given `[lints]` section, returning whether
+/// the build succeeded and the diagnostics it reported.
+fn build(lints: &str, lint_flag: LintFlag) -> (bool, String) {
+    let package = tempfile::tempdir().unwrap();
+    fs::create_dir(package.path().join("sources")).unwrap();
+    fs::write(
+        package.path().join("Move.toml"),
+        format!(
+            "[package]\nname = \"Lints\"\nedition = \"2024.beta\"\n\n\
+             [addresses]\nlints = \"0x0\"\n\n[lints]\n{lints}"
+        ),
+    )
+    .unwrap();
+    fs::write(package.path().join("sources").join("m.move"), SOURCE).unwrap();
+
+    let config = BuildConfig {
+        install_dir: Some(package.path().join("build")),
+        lint_flag,
+        ..Default::default()
+    };
+    let graph = config
+        .resolution_graph_for_package(package.path(), None, &mut std::io::sink())
+        .unwrap();
+
+    let mut diagnostics = String::new();
+    let result =
+        BuildPlan::create(&graph)
+            .unwrap()
+            .compile_with_driver(&mut std::io::sink(), |compiler| {
+                let (files, result) = compiler.build()?;
+                let (units, diags) = match result {
+                    Ok((units, warnings)) => (Some(units), warnings),
+                    Err(errors) => (None, errors),
+                };
+                let buffer =
+                    report_diagnostics_to_buffer(&files, diags, /* ansi_color */ false);
+                diagnostics = String::from_utf8(buffer).unwrap();
+                match units {
+                    Some(units) => Ok((files, units)),
+                    None => anyhow::bail!("Compilation error"),
+                }
+            });
+    (result.is_ok(), diagnostics)
+}
+
+fn assert_builds_without_lints((success, diagnostics): (bool, String)) {
+    assert!(success, "{diagnostics}");
+    assert!(!diagnostics.contains("[Lint "), "{diagnostics}");
+}
+
+#[test]
+fn lints_are_configured_by_the_manifest() {
+    // `while_true` only runs with `--lint`, unless the manifest enables it.
+    assert_builds_without_lints(build("", LintFlag::LEVEL_DEFAULT));
+
+    let (success, diagnostics) = build("", LintFlag::LEVEL_ALL);
+    assert!(success, "{diagnostics}");
+    assert!(diagnostics.contains("warning[Lint "), "{diagnostics}");
+    assert!(diagnostics.contains("while (true)"), "{diagnostics}");
+
+    let (success, diagnostics) = build("while_true = \"warn\"", LintFlag::LEVEL_DEFAULT);
+    assert!(success, "{diagnostics}");
+    assert!(diagnostics.contains("warning[Lint "), "{diagnostics}");
+}
+
+#[test]
+fn denied_lints_are_errors() {
+    for lints in ["while_true = \"deny\"", "all = \"deny\""] {
+        let (success, diagnostics) = build(lints, LintFlag::LEVEL_DEFAULT);
+        assert!(!success, "{lints}: {diagnostics}");
+        assert!(
+            diagnostics.contains("error[Lint "),
+            "{lints}: {diagnostics}"
+        );
+        assert!(
+            diagnostics.contains("while (true)"),
+            "{lints}: {diagnostics}"
+        );
+    }
+}
+
+#[test]
+fn allowed_lints_are_not_reported() {
+    assert_builds_without_lints(build("while_true = \"allow\"", LintFlag::LEVEL_ALL));
+    assert_builds_without_lints(build("all = \"allow\"", LintFlag::LEVEL_ALL));
+
+    // Settings of individual lints override `all`.
+    assert_builds_without_lints(build(
+        "all = \"deny\"\nwhile_true = \"allow\"",
+        LintFlag::LEVEL_DEFAULT,
+    ));
+}
+
+#[test]
+fn unknown_lints_are_rejected() {
+    let (success, _) = build("not_a_lint = \"deny\"", LintFlag::LEVEL_DEFAULT);
+    assert!(!success);
+}
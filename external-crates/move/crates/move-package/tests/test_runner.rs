@@ -165,6 +165,7 @@ impl PackageHooks for TestHooks {
         &self,
         dep_name: Symbol,
         info: &OnChainInfo,
+        _version: Option<Symbol>,
     ) -> anyhow::Result<()> {
         bail!("TestHooks resolve dep {:?} = {:?}", dep_name, info.id,)
     }
@@ -0,0 +1,185 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests reading and updating the git dependencies of a package, fetched from local repositories.
+//! This lives in its own test binary because `MOVE_HOME`, which git dependencies are fetched into,
+//! is read once per process.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+use move_package::{
+    resolution::{resolved_git_revision, url_to_file_name},
+    source_package::parsed_manifest::PackageName,
+    BuildConfig,
+};
+use tempfile::TempDir;
+
+fn move_home() -> &'static Path {
+    static MOVE_HOME: OnceLock<TempDir> = OnceLock::new();
+    MOVE_HOME
+        .get_or_init(|| {
+            let move_home = tempfile::tempdir().unwrap();
+            std::env::set_var("MOVE_HOME", move_home.path());
+            move_home
+        })
+        .path()
+}
+
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Commit all changes in `repo`, and return the new commit.
+fn commit(repo: &Path, message: &str) -> String {
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-q", "-m", message]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+fn write_dep_package(repo: &Path, name: &str, value: u64) {
+    fs::create_dir_all(repo.join("sources")).unwrap();
+    fs::write(
+        repo.join("Move.toml"),
+        format!(
+            "[package]\nname = \"{name}\"\nedition = \"2024.beta\"\n\n\
+             [addresses]\ndep = \"0x0\"\n"
+        ),
+    )
+    .unwrap();
+    fs::write(
+        repo.join("sources").join("dep.move"),
+        format!("module dep::dep;\n\npublic fun value(): u64 {{ {value} }}\n"),
+    )
+    .unwrap();
+}
+
+/// A git repository with a package named `Dep` on branch `main`, and a package depending on it.
+/// Returns the repository, the root package and the commit of the dependency.
+fn setup() -> (TempDir, TempDir, String) {
+    move_home();
+
+    let repo = tempfile::tempdir().unwrap();
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["checkout", "-q", "-b", "main"]);
+    write_dep_package(repo.path(), "Dep", 0);
+    let revision = commit(repo.path(), "Initial version");
+
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir(root.path().join("sources")).unwrap();
+    fs::write(
+        root.path().join("Move.toml"),
+        format!(
+            "[package]\nname = \"Root\"\nedition = \"2024.beta\"\n\n[dependencies]\n\
+             Dep = {{ git = \"{}\", rev = \"main\" }}\n\n[addresses]\nroot = \"0x0\"\n",
+            repo.path().display()
+        ),
+    )
+    .unwrap();
+
+    (repo, root, revision)
+}
+
+/// The local copy of the dependency on branch `main` of `repo`.
+fn cached_dep(repo: &Path) -> PathBuf {
+    move_home().join(format!(
+        "{}_main",
+        url_to_file_name(&repo.display().to_string())
+    ))
+}
+
+fn dep_revision(config: &BuildConfig, root: &Path) -> Option<String> {
+    let graph = config
+        .dependency_graph_for_package(root, &mut std::io::sink())
+        .unwrap();
+    resolved_git_revision(&graph.package_table[&PackageName::from("Dep")].kind)
+}
+
+#[test]
+fn dependency_graph_does_not_fetch() {
+    let (repo, root, revision) = setup();
+    let config = BuildConfig {
+        skip_fetch_latest_git_deps: false,
+        ..Default::default()
+    };
+    assert_eq!(dep_revision(&config, root.path()), Some(revision.clone()));
+
+    // A new commit on the branch is not picked up, and the local copy is left as it is.
+    write_dep_package(repo.path(), "Dep", 1);
+    commit(repo.path(), "Update");
+    let local_change = cached_dep(repo.path()).join("sources").join("dep.move");
+    fs::write(&local_change, "// Local change\n").unwrap();
+
+    assert_eq!(dep_revision(&config, root.path()), Some(revision));
+    assert_eq!(
+        fs::read_to_string(&local_change).unwrap(),
+        "// Local change\n"
+    );
+}
+
+#[test]
+fn update_dependencies() {
+    let (repo, root, revision) = setup();
+    let lock_path = root.path().join("Move.lock");
+    let update = |names: &[PackageName]| {
+        BuildConfig::default().update_dependencies(root.path(), names, &mut std::io::sink())
+    };
+
+    // Dependencies that are up to date are not touched.
+    assert!(update(&[]).unwrap().is_empty());
+    let local_change = cached_dep(repo.path()).join("sources").join("dep.move");
+    fs::write(&local_change, "// Local change\n").unwrap();
+    assert!(update(&[PackageName::from("Dep")]).unwrap().is_empty());
+    assert_eq!(
+        fs::read_to_string(&local_change).unwrap(),
+        "// Local change\n"
+    );
+    assert!(!lock_path.exists());
+
+    // Only git dependencies of the package can be updated.
+    let err = update(&[PackageName::from("Other")]).unwrap_err();
+    assert!(
+        err.to_string().contains("No dependency named 'Other'"),
+        "{err}"
+    );
+
+    // A new commit on the branch is checked out, and the lock file is written.
+    write_dep_package(repo.path(), "Dep", 1);
+    let new_revision = commit(repo.path(), "Update");
+    let updated = update(&[]).unwrap();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].id, PackageName::from("Dep"));
+    assert_eq!(updated[0].old_revision, Some(revision));
+    assert_eq!(updated[0].new_revision, Some(new_revision.clone()));
+    assert_eq!(
+        git(&cached_dep(repo.path()), &["rev-parse", "HEAD"]),
+        new_revision
+    );
+    let lock = fs::read_to_string(&lock_path).unwrap();
+
+    // An update that does not resolve is undone, and the lock file is left as it is.
+    write_dep_package(repo.path(), "Renamed", 2);
+    commit(repo.path(), "Rename");
+    assert!(update(&[]).is_err());
+    assert_eq!(
+        git(&cached_dep(repo.path()), &["rev-parse", "HEAD"]),
+        new_revision
+    );
+    assert_eq!(fs::read_to_string(&lock_path).unwrap(), lock);
+}
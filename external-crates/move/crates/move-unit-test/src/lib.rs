@@ -4,12 +4,17 @@
 
 pub mod cargo_runner;
 pub mod extensions;
+pub mod mutation;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{
+    mutation::{Mutant, MutationTestResults},
+    test_runner::TestRunner,
+};
 use anyhow::{bail, Result};
 use clap::*;
+use colored::Colorize;
 use move_binary_format::CompiledModule;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
@@ -21,9 +26,15 @@ use move_compiler::{
     Compiler, Flags, PASS_CFGIR,
 };
 use move_core_types::language_storage::ModuleId;
+use move_symbol_pool::Symbol;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    marker::Send,
+    sync::Mutex,
+};
 
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
@@ -34,6 +45,7 @@ const DEFAULT_RAND_ITERS: u64 = 10;
 const RAND_NUM_ITERS_FLAG: &str = "rand-num-iters";
 const SEED_FLAG: &str = "seed";
 const TRACE_FLAG: &str = "trace-execution";
+const GAS_PROFILE_FLAG: &str = "gas-profile";
 
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
@@ -126,6 +138,10 @@ pub struct UnitTestingConfig {
     // Enable tracing for tests
     #[clap(long = TRACE_FLAG, value_name = "PATH")]
     pub trace_execution: Option<Option<String>>,
+
+    /// Write a gas profile for each test to the given directory (`gas_profiles` by default)
+    #[clap(long = GAS_PROFILE_FLAG, value_name = "PATH")]
+    pub gas_profile: Option<Option<String>>,
 }
 
 fn format_module_id(
@@ -158,6 +174,7 @@ impl UnitTestingConfig {
             seed: None,
             deterministic_generation: false,
             trace_execution: None,
+            gas_profile: None,
         }
     }
 
@@ -225,18 +242,8 @@ impl UnitTestingConfig {
         Some(test_plan)
     }
 
-    /// Public entry point to Move unit testing as a library
-    /// Returns `true` if all unit tests passed. Otherwise, returns `false`.
-    pub fn run_and_report_unit_tests<W: Write + Send>(
-        &self,
-        test_plan: TestPlan,
-        native_function_table: Option<NativeFunctionTable>,
-        cost_table: Option<CostTable>,
-        writer: W,
-    ) -> Result<(W, bool)> {
-        let shared_writer = Mutex::new(writer);
-
-        let rand_num_iters = match self.rand_num_iters {
+    fn rand_num_iters(&self) -> Result<u64> {
+        Ok(match self.rand_num_iters {
             Some(_) if self.seed.is_some() => {
                 bail!(format!(
                     "Invalid arguments -- '{RAND_NUM_ITERS_FLAG}' and '{SEED_FLAG}' both set. \
@@ -252,8 +259,109 @@ impl UnitTestingConfig {
             Some(n) => n,
             None if self.seed.is_some() => 1,
             None => DEFAULT_RAND_ITERS,
+        })
+    }
+
+    /// Run the tests in `test_plan` against every mutant of the functions in `targets` (keyed by
+    /// module), and report which mutants were caught. Test output is suppressed; `writer` only
+    /// receives progress information. Fails if the tests do not pass on the unmodified code.
+    pub fn run_mutation_tests<W: Write + Send>(
+        &self,
+        test_plan: TestPlan,
+        targets: &BTreeMap<ModuleId, BTreeSet<Symbol>>,
+        native_function_table: Option<NativeFunctionTable>,
+        cost_table: Option<CostTable>,
+        writer: &mut W,
+    ) -> Result<MutationTestResults> {
+        let rand_num_iters = self.rand_num_iters()?;
+        let run_tests = |test_plan: TestPlan| -> Result<bool> {
+            let mut test_runner = TestRunner::new(
+                self.gas_limit.unwrap_or(DEFAULT_EXECUTION_BOUND),
+                self.num_threads,
+                /* report_stacktrace_on_abort */ false,
+                self.seed,
+                rand_num_iters,
+                self.deterministic_generation,
+                /* trace_location */ None,
+                /* gas_profile_location */ None,
+                test_plan,
+                native_function_table.clone(),
+                cost_table.clone(),
+            )?;
+
+            if let Some(filter_str) = &self.filter {
+                test_runner.filter(filter_str)
+            }
+
+            let quiet = Mutex::new(std::io::sink());
+            test_runner.run(&quiet)?.summarize(&quiet)
         };
 
+        let mutants: Vec<Mutant> = targets
+            .iter()
+            .filter_map(|(module_id, functions)| {
+                let module = test_plan.module_info.get(module_id)?;
+                Some(mutation::module_mutants(
+                    module,
+                    functions,
+                    &test_plan.mapped_files,
+                ))
+            })
+            .flatten()
+            .collect();
+
+        writeln!(writer, "Running Move unit tests against unmodified code")?;
+        if !run_tests(copy_test_plan(&test_plan, test_plan.module_info.clone()))? {
+            bail!("Unit tests fail without mutations, fix them before running mutation tests");
+        }
+
+        writeln!(
+            writer,
+            "Running Move unit tests against {} mutants",
+            mutants.len()
+        )?;
+        let mut results = MutationTestResults::default();
+        for mutant in mutants {
+            let mut module_info = test_plan.module_info.clone();
+            let module = module_info
+                .get_mut(&mutant.module_id)
+                .expect("Mutants are generated from modules in the test plan");
+            module.module = mutant.apply(&module.module);
+
+            let killed = !run_tests(copy_test_plan(&test_plan, module_info))?;
+            writeln!(
+                writer,
+                "[{}] {mutant}",
+                if killed {
+                    "KILLED".green()
+                } else {
+                    "SURVIVED".red()
+                },
+            )?;
+
+            if killed {
+                results.killed.push(mutant);
+            } else {
+                results.survived.push(mutant);
+            }
+        }
+
+        results.report(writer)?;
+        Ok(results)
+    }
+
+    /// Public entry point to Move unit testing as a library
+    /// Returns `true` if all unit tests passed. Otherwise, returns `false`.
+    pub fn run_and_report_unit_tests<W: Write + Send>(
+        &self,
+        test_plan: TestPlan,
+        native_function_table: Option<NativeFunctionTable>,
+        cost_table: Option<CostTable>,
+        writer: W,
+    ) -> Result<(W, bool)> {
+        let shared_writer = Mutex::new(writer);
+        let rand_num_iters = self.rand_num_iters()?;
+
         if self.list {
             for (module_id, module_test_plan) in &test_plan.module_tests {
                 for test_name in module_test_plan.tests.keys() {
@@ -274,6 +382,11 @@ impl UnitTestingConfig {
             Some(Some(path)) => Some(path.clone()),
             None => None,
         };
+        let gas_profile_location = match &self.gas_profile {
+            Some(None) => Some("gas_profiles".to_string()),
+            Some(Some(path)) => Some(path.clone()),
+            None => None,
+        };
         let mut test_runner = TestRunner::new(
             self.gas_limit.unwrap_or(DEFAULT_EXECUTION_BOUND),
             self.num_threads,
@@ -282,6 +395,7 @@ impl UnitTestingConfig {
             rand_num_iters,
             self.deterministic_generation,
             trace_location,
+            gas_profile_location,
             test_plan,
             native_function_table,
             cost_table,
@@ -303,3 +417,16 @@ impl UnitTestingConfig {
         Ok((writer, ok))
     }
 }
+
+/// A copy of `test_plan` that runs against the modules in `module_info`.
+fn copy_test_plan(
+    test_plan: &TestPlan,
+    module_info: BTreeMap<ModuleId, NamedCompiledModule>,
+) -> TestPlan {
+    TestPlan {
+        mapped_files: test_plan.mapped_files.clone(),
+        module_tests: test_plan.module_tests.clone(),
+        module_info,
+        bytecode_deps_modules: test_plan.bytecode_deps_modules.clone(),
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mutation testing for Move packages.
+//!
+//! Mutants are generated by changing a single instruction in the compiled bytecode of a module,
+//! e.g. replacing an addition with a subtraction, a constant with its successor or a conditional
+//! branch with its negation. Each mutant is run against the package's unit tests, and mutants that
+//! do not cause any test to fail ("surviving" mutants) point at behavior the tests do not check.
+//!
+//! All mutations replace an instruction with one of the same stack signature, so mutated modules
+//! still pass the bytecode verifier.
+
+use colored::Colorize;
+use move_binary_format::file_format::{
+    Bytecode, CodeOffset, CompiledModule, FunctionDefinitionIndex,
+};
+use move_compiler::{compiled_unit::NamedCompiledModule, shared::files::MappedFiles};
+use move_core_types::{language_storage::ModuleId, u256::U256};
+use move_symbol_pool::Symbol;
+use std::{collections::BTreeSet, fmt, io::Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MutationKind {
+    /// Replace an arithmetic or bitwise operator with another one.
+    Operator,
+    /// Replace a constant with a different value.
+    Constant,
+    /// Change a comparison, a logical operator or a conditional branch.
+    Condition,
+}
+
+/// A single change to the bytecode of a module.
+#[derive(Debug, Clone)]
+pub struct Mutant {
+    pub module_id: ModuleId,
+    pub function_name: Symbol,
+    pub function_index: FunctionDefinitionIndex,
+    pub code_offset: CodeOffset,
+    pub kind: MutationKind,
+    pub original: Bytecode,
+    pub replacement: Bytecode,
+    /// The source file, line and column of the mutated instruction, if known.
+    pub location: Option<(Symbol, usize, usize)>,
+}
+
+/// The outcome of running a package's unit tests against all of its mutants.
+#[derive(Debug, Default)]
+pub struct MutationTestResults {
+    /// Mutants that caused at least one test to fail.
+    pub killed: Vec<Mutant>,
+    /// Mutants that went unnoticed by the tests.
+    pub survived: Vec<Mutant>,
+}
+
+/// Enumerate the mutants of the functions of `module` named in `functions`.
+pub fn module_mutants(
+    module: &NamedCompiledModule,
+    functions: &BTreeSet<Symbol>,
+    mapped_files: &MappedFiles,
+) -> Vec<Mutant> {
+    let compiled = &module.module;
+    let mut mutants = vec![];
+    for (idx, fdef) in compiled.function_defs().iter().enumerate() {
+        let Some(code) = &fdef.code else {
+            continue;
+        };
+
+        let handle = compiled.function_handle_at(fdef.function);
+        let function_name = Symbol::from(compiled.identifier_at(handle.name).as_str());
+        if !functions.contains(&function_name) {
+            continue;
+        }
+
+        let function_index = FunctionDefinitionIndex(idx as u16);
+        for (offset, instr) in code.code.iter().enumerate() {
+            let Some((kind, replacement)) = mutate(instr) else {
+                continue;
+            };
+
+            let code_offset = offset as CodeOffset;
+            let location = module
+                .source_map
+                .get_code_location(function_index, code_offset)
+                .ok()
+                .and_then(|loc| {
+                    let position = mapped_files.start_position_opt(&loc)?;
+                    Some((
+                        mapped_files.filename(&loc.file_hash()),
+                        position.user_line(),
+                        position.user_column(),
+                    ))
+                });
+
+            mutants.push(Mutant {
+                module_id: compiled.self_id(),
+                function_name,
+                function_index,
+                code_offset,
+                kind,
+                original: instr.clone(),
+                replacement,
+                location,
+            });
+        }
+    }
+
+    mutants
+}
+
+/// The mutation applied to `instr`, if any.
+fn mutate(instr: &Bytecode) -> Option<(MutationKind, Bytecode)> {
+    use Bytecode as B;
+    use MutationKind as K;
+
+    Some(match instr {
+        B::Add => (K::Operator, B::Sub),
+        B::Sub => (K::Operator, B::Add),
+        B::Mul => (K::Operator, B::Div),
+        B::Div => (K::Operator, B::Mul),
+        B::Mod => (K::Operator, B::Mul),
+        B::BitOr => (K::Operator, B::BitAnd),
+        B::BitAnd => (K::Operator, B::BitOr),
+        B::Xor => (K::Operator, B::BitOr),
+        B::Shl => (K::Operator, B::Shr),
+        B::Shr => (K::Operator, B::Shl),
+
+        B::LdTrue => (K::Constant, B::LdFalse),
+        B::LdFalse => (K::Constant, B::LdTrue),
+        B::LdU8(c) => (K::Constant, B::LdU8(c.wrapping_add(1))),
+        B::LdU16(c) => (K::Constant, B::LdU16(c.wrapping_add(1))),
+        B::LdU32(c) => (K::Constant, B::LdU32(c.wrapping_add(1))),
+        B::LdU64(c) => (K::Constant, B::LdU64(c.wrapping_add(1))),
+        B::LdU128(c) => (K::Constant, B::LdU128(Box::new(c.wrapping_add(1)))),
+        B::LdU256(c) => (
+            K::Constant,
+            B::LdU256(Box::new(c.wrapping_add(U256::one()))),
+        ),
+
+        // Comparisons are mutated at their boundary, to catch off-by-one errors.
+        B::Lt => (K::Condition, B::Le),
+        B::Le => (K::Condition, B::Lt),
+        B::Gt => (K::Condition, B::Ge),
+        B::Ge => (K::Condition, B::Gt),
+        B::Eq => (K::Condition, B::Neq),
+        B::Neq => (K::Condition, B::Eq),
+        B::And => (K::Condition, B::Or),
+        B::Or => (K::Condition, B::And),
+        B::Not => (K::Condition, B::Nop),
+        B::BrTrue(offset) => (K::Condition, B::BrFalse(*offset)),
+        B::BrFalse(offset) => (K::Condition, B::BrTrue(*offset)),
+
+        _ => return None,
+    })
+}
+
+impl Mutant {
+    /// A copy of `module` with this mutation applied.
+    pub fn apply(&self, module: &CompiledModule) -> CompiledModule {
+        let mut mutated = module.clone();
+        let code = mutated.function_defs[self.function_index.0 as usize]
+            .code
+            .as_mut()
+            .expect("Mutants are only generated for functions with code");
+        code.code[self.code_offset as usize] = self.replacement.clone();
+        mutated
+    }
+}
+
+impl MutationTestResults {
+    pub fn report<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let total = self.killed.len() + self.survived.len();
+        if total == 0 {
+            return writeln!(writer, "No mutants were generated for this package");
+        }
+
+        if !self.survived.is_empty() {
+            writeln!(writer, "\nSurviving mutants:\n")?;
+            for mutant in &self.survived {
+                writeln!(writer, "{mutant}")?;
+            }
+        }
+
+        let score = 100.0 * self.killed.len() as f64 / total as f64;
+        let summary = format!(
+            "\nMutation testing result: {} mutants, {} killed, {} survived (score {:.1}%)",
+            total,
+            self.killed.len(),
+            self.survived.len(),
+            score,
+        );
+        if self.survived.is_empty() {
+            writeln!(writer, "{}", summary.bold().green())
+        } else {
+            writeln!(writer, "{}", summary.bold().yellow())
+        }
+    }
+}
+
+impl fmt::Display for Mutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((file, line, column)) = &self.location {
+            write!(f, "{file}:{line}:{column}: ")?;
+        }
+        write!(
+            f,
+            "{}::{} ({:?}) replaced `{}` with `{}`",
+            self.module_id.short_str_lossless(),
+            self.function_name,
+            self.kind,
+            describe(&self.original),
+            describe(&self.replacement),
+        )
+    }
+}
+
+/// A source-level rendering of the instructions generated by `mutate`.
+fn describe(instr: &Bytecode) -> String {
+    use Bytecode as B;
+
+    match instr {
+        B::Add => "+".to_string(),
+        B::Sub => "-".to_string(),
+        B::Mul => "*".to_string(),
+        B::Div => "/".to_string(),
+        B::Mod => "%".to_string(),
+        B::BitOr => "|".to_string(),
+        B::BitAnd => "&".to_string(),
+        B::Xor => "^".to_string(),
+        B::Shl => "<<".to_string(),
+        B::Shr => ">>".to_string(),
+        B::LdTrue => "true".to_string(),
+        B::LdFalse => "false".to_string(),
+        B::LdU8(c) => c.to_string(),
+        B::LdU16(c) => c.to_string(),
+        B::LdU32(c) => c.to_string(),
+        B::LdU64(c) => c.to_string(),
+        B::LdU128(c) => c.to_string(),
+        B::LdU256(c) => c.to_string(),
+        B::Lt => "<".to_string(),
+        B::Le => "<=".to_string(),
+        B::Gt => ">".to_string(),
+        B::Ge => ">=".to_string(),
+        B::Eq => "==".to_string(),
+        B::Neq => "!=".to_string(),
+        B::And => "&&".to_string(),
+        B::Or => "||".to_string(),
+        B::Not => "!".to_string(),
+        B::Nop => "(nothing)".to_string(),
+        B::BrTrue(_) => "branch if true".to_string(),
+        B::BrFalse(_) => "branch if false".to_string(),
+        _ => format!("{instr:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mutate, Mutant, MutationKind};
+    use move_binary_format::file_format::{
+        basic_test_module, Bytecode as B, FunctionDefinitionIndex,
+    };
+    use move_core_types::u256::U256;
+    use move_symbol_pool::Symbol;
+
+    fn assert_mutation(instr: B, kind: MutationKind, replacement: B) {
+        assert_eq!(
+            mutate(&instr),
+            Some((kind, replacement)),
+            "Mutation of {instr:?}"
+        );
+    }
+
+    #[test]
+    fn test_operator_mutations() {
+        use MutationKind::Operator;
+        assert_mutation(B::Add, Operator, B::Sub);
+        assert_mutation(B::Sub, Operator, B::Add);
+        assert_mutation(B::Mul, Operator, B::Div);
+        assert_mutation(B::Div, Operator, B::Mul);
+        assert_mutation(B::Mod, Operator, B::Mul);
+        assert_mutation(B::BitOr, Operator, B::BitAnd);
+        assert_mutation(B::BitAnd, Operator, B::BitOr);
+        assert_mutation(B::Xor, Operator, B::BitOr);
+        assert_mutation(B::Shl, Operator, B::Shr);
+        assert_mutation(B::Shr, Operator, B::Shl);
+    }
+
+    #[test]
+    fn test_constant_mutations() {
+        use MutationKind::Constant;
+        assert_mutation(B::LdTrue, Constant, B::LdFalse);
+        assert_mutation(B::LdFalse, Constant, B::LdTrue);
+        assert_mutation(B::LdU8(7), Constant, B::LdU8(8));
+        assert_mutation(B::LdU16(7), Constant, B::LdU16(8));
+        assert_mutation(B::LdU32(7), Constant, B::LdU32(8));
+        assert_mutation(B::LdU64(7), Constant, B::LdU64(8));
+        assert_mutation(B::LdU128(Box::new(7)), Constant, B::LdU128(Box::new(8)));
+        assert_mutation(
+            B::LdU256(Box::new(U256::from(7u64))),
+            Constant,
+            B::LdU256(Box::new(U256::from(8u64))),
+        );
+
+        // Constants wrap around at their maximum value.
+        assert_mutation(B::LdU8(u8::MAX), Constant, B::LdU8(0));
+        assert_mutation(B::LdU64(u64::MAX), Constant, B::LdU64(0));
+        assert_mutation(
+            B::LdU256(Box::new(U256::max_value())),
+            Constant,
+            B::LdU256(Box::new(U256::zero())),
+        );
+    }
+
+    #[test]
+    fn test_condition_mutations() {
+        use MutationKind::Condition;
+        assert_mutation(B::Lt, Condition, B::Le);
+        assert_mutation(B::Le, Condition, B::Lt);
+        assert_mutation(B::Gt, Condition, B::Ge);
+        assert_mutation(B::Ge, Condition, B::Gt);
+        assert_mutation(B::Eq, Condition, B::Neq);
+        assert_mutation(B::Neq, Condition, B::Eq);
+        assert_mutation(B::And, Condition, B::Or);
+        assert_mutation(B::Or, Condition, B::And);
+        assert_mutation(B::Not, Condition, B::Nop);
+        assert_mutation(B::BrTrue(3), Condition, B::BrFalse(3));
+        assert_mutation(B::BrFalse(3), Condition, B::BrTrue(3));
+    }
+
+    #[test]
+    fn test_other_instructions_are_not_mutated() {
+        for instr in [
+            B::Pop,
+            B::Ret,
+            B::Branch(0),
+            B::CopyLoc(0),
+            B::Nop,
+            B::Abort,
+        ] {
+            assert_eq!(mutate(&instr), None, "Mutation of {instr:?}");
+        }
+    }
+
+    #[test]
+    fn test_apply_mutant() {
+        let mut module = basic_test_module();
+        let code = vec![B::LdU64(1), B::LdU64(2), B::Add, B::Pop, B::Ret];
+        module.function_defs[0].code.as_mut().unwrap().code = code.clone();
+
+        let mutant = Mutant {
+            module_id: module.self_id(),
+            function_name: Symbol::from("foo"),
+            function_index: FunctionDefinitionIndex(0),
+            code_offset: 2,
+            kind: MutationKind::Operator,
+            original: B::Add,
+            replacement: B::Sub,
+            location: Some((Symbol::from("sources/m.move"), 4, 9)),
+        };
+        let mutated = mutant.apply(&module);
+
+        // Only the mutated instruction changes, and the original module is left as it is.
+        assert_eq!(
+            mutated.function_defs[0].code.as_ref().unwrap().code,
+            vec![B::LdU64(1), B::LdU64(2), B::Sub, B::Pop, B::Ret],
+        );
+        assert_eq!(module.function_defs[0].code.as_ref().unwrap().code, code);
+        assert_eq!(
+            mutant.to_string(),
+            format!(
+                "sources/m.move:4:9: {}::foo (Operator) replaced `+` with `-`",
+                module.self_id().short_str_lossless(),
+            ),
+        );
+    }
+}
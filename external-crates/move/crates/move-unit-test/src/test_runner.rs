@@ -36,9 +36,10 @@ use move_vm_test_utils::{
     gas_schedule::{unit_cost_schedule, CostTable, Gas, GasStatus},
     InMemoryStorage,
 };
+use move_vm_types::gas::GasMeter;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{collections::BTreeMap, io::Write, marker::Send, path::Path, sync::Mutex, time::Instant};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 
@@ -53,6 +54,7 @@ pub struct SharedTestingConfig {
     num_iters: u64,
     deterministic_generation: bool,
     trace_location: Option<String>,
+    gas_profile_location: Option<String>,
 }
 
 pub struct TestRunner {
@@ -115,6 +117,7 @@ impl TestRunner {
         num_iters: u64,
         deterministic_generation: bool,
         trace_location: Option<String>,
+        gas_profile_location: Option<String>,
         tests: TestPlan,
         // TODO: maybe we should require the clients to always pass in a list of native functions so
         // we don't have to make assumptions about their gas parameters.
@@ -130,6 +133,12 @@ impl TestRunner {
                      feature flag set. Rebuild binary with `--features tracing`"
                 ));
             }
+            if gas_profile_location.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Gas profiling is enabled but the binary was not compiled with the `tracing` \
+                     feature flag set. Rebuild binary with `--features tracing`"
+                ));
+            }
         };
 
         let modules = tests.module_info.values().map(|info| &info.module);
@@ -158,6 +167,7 @@ impl TestRunner {
                 num_iters,
                 deterministic_generation,
                 trace_location,
+                gas_profile_location,
             },
             num_threads,
             tests,
@@ -254,6 +264,7 @@ impl SharedTestingConfig {
         test_plan: &ModuleTestPlan,
         function_name: &str,
         arguments: Vec<MoveValue>,
+        gas_profile_path: Option<&Path>,
     ) -> (
         VMResult<ChangeSet>,
         VMResult<NativeContextExtensions>,
@@ -275,11 +286,22 @@ impl SharedTestingConfig {
         let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
         move_vm_profiler::tracing_feature_enabled! {
             use move_vm_profiler::GasProfiler;
-            use move_vm_types::gas::GasMeter;
-            gas_meter.set_profiler(GasProfiler::init_default_cfg(
-                function_name.to_owned(),
-                self.execution_bound,
-            ));
+            let profiler = if gas_profile_path.is_some() {
+                // The profile is written out explicitly once the test is done, so the profiler
+                // only needs a config for it to record frames.
+                GasProfiler::init(
+                    &Some(move_vm_config::runtime::VMProfilerConfig {
+                        full_path: std::path::PathBuf::new(),
+                        track_bytecode_instructions: false,
+                        use_long_function_name: true,
+                    }),
+                    function_name.to_owned(),
+                    self.execution_bound,
+                )
+            } else {
+                GasProfiler::init_default_cfg(function_name.to_owned(), self.execution_bound)
+            };
+            gas_meter.set_profiler(profiler);
         }
 
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
@@ -308,6 +330,15 @@ impl SharedTestingConfig {
         } else {
             None
         };
+        if let (Some(path), Some(profiler)) = (gas_profile_path, gas_meter.get_profiler_mut()) {
+            if let Err(e) = profiler.finish_to_path(path) {
+                eprintln!(
+                    "Unable to save gas profile to {} -- {:?}",
+                    path.display(),
+                    e
+                );
+            }
+        }
         let test_run_info = TestRunInfo::new(
             now.elapsed(),
             // TODO(Gas): This doesn't look quite right...
@@ -433,23 +464,33 @@ impl SharedTestingConfig {
         prng_seed: Option<u64>,
         is_last_execution_of_test: bool,
     ) -> bool {
-        let (_cs_result, _ext_result, exec_result, test_run_info) =
-            self.execute_via_move_vm(test_plan, function_name, arguments);
+        // Trace and gas profile files are named after the test, and the seed it was run with.
+        let output_file_name = format!(
+            "{}__{}{}.json",
+            format_module_id(output.test_info, &output.test_plan.module_id).replace("::", "__"),
+            function_name,
+            if let Some(seed) = prng_seed {
+                format!("_seed_{}", seed)
+            } else {
+                "".to_string()
+            }
+        );
+        let gas_profile_path = self
+            .gas_profile_location
+            .as_ref()
+            .map(|location| Path::new(location).join(&output_file_name));
+
+        let (_cs_result, _ext_result, exec_result, test_run_info) = self.execute_via_move_vm(
+            test_plan,
+            function_name,
+            arguments,
+            gas_profile_path.as_deref(),
+        );
 
         // Save the trace -- one per test -- for each test that we have traced (and if tracing is
         // enabled).
         if let Some(location) = &self.trace_location {
-            let trace_file_location = format!(
-                "{}/{}__{}{}.json",
-                location,
-                format_module_id(output.test_info, &output.test_plan.module_id).replace("::", "__"),
-                function_name,
-                if let Some(seed) = prng_seed {
-                    format!("_seed_{}", seed)
-                } else {
-                    "".to_string()
-                }
-            );
+            let trace_file_location = format!("{}/{}", location, output_file_name);
             if let Err(e) = test_run_info.save_trace(&trace_file_location) {
                 eprintln!("Unable to save trace to {trace_file_location} -- {:?}", e);
             }
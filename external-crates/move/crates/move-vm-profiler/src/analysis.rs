@@ -0,0 +1,313 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline analysis of the profiles written by `GasProfiler`: gas usage per function, comparison
+//! of two profiles of the same workload, and export to the folded-stack format consumed by
+//! flamegraph tools (e.g. `inferno-flamegraph` or `flamegraph.pl`).
+
+use crate::GasProfiler;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{self, BufReader, Write},
+    path::Path,
+};
+
+/// Gas usage of a single function (or bytecode instruction, if those were tracked) across a
+/// profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FunctionGas {
+    /// Number of times the function was called.
+    pub calls: u64,
+    /// Gas used by the function, including the functions it called. Gas used by recursive calls is
+    /// counted once for every frame of the function on the stack.
+    pub inclusive: u64,
+    /// Gas used by the function itself, excluding the functions it called.
+    pub exclusive: u64,
+}
+
+/// The difference in gas usage of a function between two profiles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GasDelta {
+    pub function: String,
+    /// Usage in the baseline profile, `None` if the function was not called.
+    pub base: Option<FunctionGas>,
+    /// Usage in the new profile, `None` if the function was not called.
+    pub new: Option<FunctionGas>,
+}
+
+impl GasProfiler {
+    /// Read a profile previously written by `GasProfiler`.
+    pub fn from_path(path: &Path) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Finish profiling and write the profile to `path`, instead of the location set in the
+    /// profiler's config.
+    pub fn finish_to_path(&mut self, path: &Path) -> io::Result<()> {
+        #[cfg(feature = "tracing")]
+        self.close_top_level_frame();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self)?;
+        std::fs::write(path, json)
+    }
+
+    /// Total gas used over the course of the profile.
+    pub fn total_gas(&self) -> u64 {
+        self.profiles.first().map_or(0, |profile| profile.end_value)
+    }
+
+    /// Gas usage of each function in the profile, keyed by function name.
+    pub fn function_gas(&self) -> BTreeMap<String, FunctionGas> {
+        let mut functions: BTreeMap<String, FunctionGas> = BTreeMap::new();
+        self.visit_frames(|stack, inclusive, exclusive| {
+            let name = stack.last().expect("Visited stacks are not empty");
+            let function = functions.entry(name.to_string()).or_default();
+            function.calls += 1;
+            function.inclusive += inclusive;
+            function.exclusive += exclusive;
+        });
+        functions
+    }
+
+    /// The gas used in each distinct call stack of the profile, keyed by the names of the frames in
+    /// the stack, from outermost to innermost, separated by `;`.
+    pub fn folded_stacks(&self) -> BTreeMap<String, u64> {
+        let mut stacks = BTreeMap::new();
+        self.visit_frames(|stack, _, exclusive| {
+            *stacks.entry(stack.join(";")).or_default() += exclusive;
+        });
+        stacks
+    }
+
+    /// Write the profile in folded-stack format: one line per call stack, followed by the gas used
+    /// in that stack. Stacks that did not use any gas are omitted.
+    pub fn write_folded<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (stack, gas) in self.folded_stacks() {
+            if gas > 0 {
+                writeln!(writer, "{stack} {gas}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Call `visit` for every frame in the profile once it is closed, with the names of the frames
+    /// on the stack (ending with the closed frame), and the inclusive and exclusive gas used by the
+    /// frame.
+    fn visit_frames(&self, mut visit: impl FnMut(&[&str], u64, u64)) {
+        struct OpenFrame<'a> {
+            name: &'a str,
+            at: u64,
+            callees: u64,
+        }
+
+        let Some(profile) = self.profiles.first() else {
+            return;
+        };
+
+        let mut stack: Vec<OpenFrame> = vec![];
+        for event in &profile.events {
+            let name = self
+                .shared
+                .frames
+                .get(event.frame as usize)
+                .map_or("<unknown>", |frame| frame.name.as_str());
+
+            if event.ty == Self::OPEN_FRAME_IDENT {
+                stack.push(OpenFrame {
+                    name,
+                    at: event.at,
+                    callees: 0,
+                });
+            } else if event.ty == Self::CLOSE_FRAME_IDENT {
+                let Some(frame) = stack.pop() else {
+                    continue;
+                };
+
+                let inclusive = event.at.saturating_sub(frame.at);
+                let exclusive = inclusive.saturating_sub(frame.callees);
+                let names: Vec<_> = stack
+                    .iter()
+                    .map(|f| f.name)
+                    .chain(std::iter::once(frame.name))
+                    .collect();
+                visit(&names, inclusive, exclusive);
+
+                if let Some(caller) = stack.last_mut() {
+                    caller.callees += inclusive;
+                }
+            }
+        }
+    }
+}
+
+/// Compare the gas usage per function between `base` and `new`. Only functions whose usage
+/// changed are returned, ordered by the largest change in exclusive gas first.
+pub fn compare_function_gas(
+    base: &BTreeMap<String, FunctionGas>,
+    new: &BTreeMap<String, FunctionGas>,
+) -> Vec<GasDelta> {
+    let functions: BTreeSet<_> = base.keys().chain(new.keys()).collect();
+    let mut deltas: Vec<_> = functions
+        .into_iter()
+        .map(|function| GasDelta {
+            function: function.clone(),
+            base: base.get(function).copied(),
+            new: new.get(function).copied(),
+        })
+        .filter(|delta| delta.base != delta.new)
+        .collect();
+
+    deltas.sort_by_key(|delta| std::cmp::Reverse(delta.exclusive_delta().unsigned_abs()));
+    deltas
+}
+
+impl GasDelta {
+    /// The change in exclusive gas usage, from the baseline to the new profile.
+    pub fn exclusive_delta(&self) -> i128 {
+        let exclusive = |gas: Option<FunctionGas>| gas.map_or(0, |g| g.exclusive as i128);
+        exclusive(self.new) - exclusive(self.base)
+    }
+
+    /// The change in inclusive gas usage, from the baseline to the new profile.
+    pub fn inclusive_delta(&self) -> i128 {
+        let inclusive = |gas: Option<FunctionGas>| gas.map_or(0, |g| g.inclusive as i128);
+        inclusive(self.new) - inclusive(self.base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, FrameName, Profile, Shared};
+
+    fn profile(frames: &[&str], events: &[(&str, u64, u64)]) -> GasProfiler {
+        GasProfiler {
+            exporter: "speedscope@1.15.2".to_string(),
+            name: "test".to_string(),
+            active_profile_index: 0,
+            schema: "https://www.speedscope.app/file-format-schema.json".to_string(),
+            shared: Shared {
+                frames: frames
+                    .iter()
+                    .map(|name| FrameName {
+                        name: name.to_string(),
+                        file: name.to_string(),
+                    })
+                    .collect(),
+                frame_table: BTreeMap::new(),
+            },
+            profiles: vec![Profile {
+                ty: "evented".to_string(),
+                name: "test".to_string(),
+                unit: "none".to_string(),
+                start_value: 0,
+                end_value: events.last().map_or(0, |(_, _, at)| *at),
+                events: events
+                    .iter()
+                    .map(|(ty, frame, at)| Event {
+                        ty: ty.to_string(),
+                        frame: *frame,
+                        at: *at,
+                    })
+                    .collect(),
+            }],
+            start_gas: 0,
+            config: None,
+            finished: true,
+        }
+    }
+
+    // root calls `a` twice, and `a` calls `b` the second time.
+    fn sample() -> GasProfiler {
+        profile(
+            &["root", "a", "b"],
+            &[
+                ("O", 0, 0),
+                ("O", 1, 5),
+                ("C", 1, 15),
+                ("O", 1, 20),
+                ("O", 2, 22),
+                ("C", 2, 30),
+                ("C", 1, 35),
+                ("C", 0, 40),
+            ],
+        )
+    }
+
+    #[test]
+    fn function_gas() {
+        let functions = sample().function_gas();
+        assert_eq!(
+            functions["root"],
+            FunctionGas {
+                calls: 1,
+                inclusive: 40,
+                exclusive: 15,
+            }
+        );
+        assert_eq!(
+            functions["a"],
+            FunctionGas {
+                calls: 2,
+                inclusive: 25,
+                exclusive: 17,
+            }
+        );
+        assert_eq!(
+            functions["b"],
+            FunctionGas {
+                calls: 1,
+                inclusive: 8,
+                exclusive: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn folded_stacks() {
+        let mut folded = vec![];
+        sample().write_folded(&mut folded).unwrap();
+        assert_eq!(
+            String::from_utf8(folded).unwrap(),
+            "root 15\nroot;a 17\nroot;a;b 8\n"
+        );
+    }
+
+    #[test]
+    fn compare() {
+        let base = sample().function_gas();
+        let new = profile(
+            &["root", "a"],
+            &[("O", 0, 0), ("O", 1, 5), ("C", 1, 45), ("C", 0, 50)],
+        )
+        .function_gas();
+
+        let deltas = compare_function_gas(&base, &new);
+        let summary: Vec<_> = deltas
+            .iter()
+            .map(|d| (d.function.as_str(), d.exclusive_delta()))
+            .collect();
+        assert_eq!(summary, vec![("a", 23), ("b", -8), ("root", -5)]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = std::env::temp_dir().join(format!("gas_profile_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profile.json");
+
+        let mut original = sample();
+        original.finish_to_path(&path).unwrap();
+        let read = GasProfiler::from_path(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(read.function_gas(), original.function_gas());
+        assert_eq!(read.total_gas(), 40);
+    }
+}
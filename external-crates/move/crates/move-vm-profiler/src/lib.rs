@@ -1,20 +1,22 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use move_vm_config::runtime::VMProfilerConfig;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+pub mod analysis;
+
 #[cfg(feature = "tracing")]
 use tracing::info;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameName {
     name: String,
     file: String,
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Shared {
     frames: Vec<FrameName>,
 
@@ -22,18 +24,18 @@ pub struct Shared {
     frame_table: BTreeMap<String, usize>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
-    #[serde(rename(serialize = "type"))]
+    #[serde(rename = "type")]
     ty: String,
     frame: u64,
     at: u64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Profile {
-    #[serde(rename(serialize = "type"))]
+    #[serde(rename = "type")]
     ty: String,
     name: String,
     unit: String,
@@ -43,13 +45,13 @@ pub struct Profile {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GasProfiler {
     exporter: String,
     name: String,
     active_profile_index: u64,
-    #[serde(rename(serialize = "$schema"))]
+    #[serde(rename = "$schema")]
     schema: String,
     shared: Shared,
     profiles: Vec<Profile>,
@@ -58,16 +60,23 @@ pub struct GasProfiler {
     pub start_gas: u64,
     #[serde(skip)]
     pub config: Option<VMProfilerConfig>,
-    #[serde(skip)]
+    // Profiles read back from a file are complete, and must not be closed or dumped again.
+    #[serde(skip, default = "finished_on_deserialize")]
     finished: bool,
 }
 
-#[cfg(feature = "tracing")]
+fn finished_on_deserialize() -> bool {
+    true
+}
+
 impl GasProfiler {
     // Used by profiler viz tool
     const OPEN_FRAME_IDENT: &'static str = "O";
     const CLOSE_FRAME_IDENT: &'static str = "C";
+}
 
+#[cfg(feature = "tracing")]
+impl GasProfiler {
     const TOP_LEVEL_FRAME_NAME: &'static str = "root";
 
     #[cfg(feature = "tracing")]
@@ -220,14 +229,23 @@ impl GasProfiler {
 
     #[cfg(feature = "tracing")]
     pub fn finish(&mut self) {
+        if self.close_top_level_frame() {
+            profile_dump_file!(self);
+        }
+    }
+
+    /// Close the top-level frame, if the profile has not already been finished. Returns whether
+    /// the frame was closed.
+    #[cfg(feature = "tracing")]
+    fn close_top_level_frame(&mut self) -> bool {
         if self.finished {
-            return;
+            return false;
         }
         self.finished = true;
         let end_gas = self.start_gas() - self.profiles[0].end_value;
         let mut q = Some(self);
         profile_close_frame_impl!(&mut q, Self::TOP_LEVEL_FRAME_NAME.to_string(), end_gas);
-        profile_dump_file!(q.unwrap());
+        true
     }
 }
 
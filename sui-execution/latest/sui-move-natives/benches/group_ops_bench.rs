@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the operations behind the `group_ops` natives. Each operation is measured the
+//! same way the natives execute it, i.e., decoding the (trusted) inputs, applying the operation
+//! and encoding the result, so the BN254 costs in the protocol config can be derived from the
+//! ratio to the corresponding BLS12-381 costs.
+
+use criterion::measurement::WallTime;
+use criterion::*;
+use fastcrypto::error::FastCryptoResult;
+use fastcrypto::groups::{
+    bls12381 as bls, bn254, FromTrustedByteArray, GroupElement, HashToGroupElement, MultiScalarMul,
+    Pairing,
+};
+use fastcrypto::serde_helpers::ToFromByteArray;
+
+/// The maximal number of inputs of a multi-scalar multiplication, see `group_ops_*_msm_max_len`.
+const MSM_MAX_LEN: usize = 32;
+
+fn bench_decode<G: ToFromByteArray<S> + FromTrustedByteArray<S>, const S: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    element: &G,
+) {
+    let bytes = element.to_byte_array();
+    group.bench_function(name, |b| b.iter(|| G::from_byte_array(&bytes).unwrap()));
+}
+
+fn bench_binary_op<
+    A: ToFromByteArray<SA> + FromTrustedByteArray<SA>,
+    G: ToFromByteArray<S> + FromTrustedByteArray<S>,
+    const SA: usize,
+    const S: usize,
+>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    e1: &A,
+    e2: &G,
+    op: impl Fn(A, G) -> FastCryptoResult<G>,
+) {
+    let e1 = e1.to_byte_array();
+    let e2 = e2.to_byte_array();
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            let e1 = A::from_trusted_byte_array(&e1).unwrap();
+            let e2 = G::from_trusted_byte_array(&e2).unwrap();
+            op(e1, e2).unwrap().to_byte_array()
+        })
+    });
+}
+
+fn bench_msm<
+    G: MultiScalarMul + ToFromByteArray<S> + FromTrustedByteArray<S>,
+    const SS: usize,
+    const S: usize,
+>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    scalar: &G::ScalarType,
+    element: &G,
+) where
+    G::ScalarType: ToFromByteArray<SS> + FromTrustedByteArray<SS>,
+{
+    let scalar = scalar.to_byte_array();
+    let element = element.to_byte_array();
+    // The base cost and the cost per input are derived from the smallest and largest inputs.
+    for len in [1, MSM_MAX_LEN] {
+        group.bench_function(format!("{name}/{len}"), |b| {
+            b.iter(|| {
+                let scalars = (0..len)
+                    .map(|_| G::ScalarType::from_trusted_byte_array(&scalar).unwrap())
+                    .collect::<Vec<_>>();
+                let elements = (0..len)
+                    .map(|_| G::from_trusted_byte_array(&element).unwrap())
+                    .collect::<Vec<_>>();
+                G::multi_scalar_mul(&scalars, &elements)
+                    .unwrap()
+                    .to_byte_array()
+            })
+        });
+    }
+}
+
+macro_rules! bench_curve {
+    ($c:expr, $name:literal, $curve:ident) => {{
+        use $curve::{G1Element, G2Element, GTElement, Scalar};
+
+        let mut group = $c.benchmark_group($name);
+        let s1 = Scalar::from(7u128);
+        let s2 = Scalar::from(11u128);
+        let g1 = G1Element::generator() * s1;
+        let g2 = G2Element::generator() * s1;
+        let gt = GTElement::generator() * s1;
+
+        bench_decode(&mut group, "decode_scalar", &s1);
+        bench_decode(&mut group, "decode_g1", &g1);
+        bench_decode(&mut group, "decode_g2", &g2);
+        bench_decode(&mut group, "decode_gt", &gt);
+
+        bench_binary_op(&mut group, "scalar_add", &s1, &s2, |a, b| Ok(a + b));
+        bench_binary_op(&mut group, "g1_add", &g1, &g1, |a, b| Ok(a + b));
+        bench_binary_op(&mut group, "g2_add", &g2, &g2, |a, b| Ok(a + b));
+        bench_binary_op(&mut group, "gt_add", &gt, &gt, |a, b| Ok(a + b));
+
+        bench_binary_op(&mut group, "scalar_sub", &s1, &s2, |a, b| Ok(a - b));
+        bench_binary_op(&mut group, "g1_sub", &g1, &g1, |a, b| Ok(a - b));
+        bench_binary_op(&mut group, "g2_sub", &g2, &g2, |a, b| Ok(a - b));
+        bench_binary_op(&mut group, "gt_sub", &gt, &gt, |a, b| Ok(a - b));
+
+        bench_binary_op(&mut group, "scalar_mul", &s1, &s2, |a, b| Ok(b * a));
+        bench_binary_op(&mut group, "g1_mul", &s1, &g1, |a, b| Ok(b * a));
+        bench_binary_op(&mut group, "g2_mul", &s1, &g2, |a, b| Ok(b * a));
+        bench_binary_op(&mut group, "gt_mul", &s1, &gt, |a, b| Ok(b * a));
+
+        bench_binary_op(&mut group, "scalar_div", &s1, &s2, |a, b| b / a);
+        bench_binary_op(&mut group, "g1_div", &s1, &g1, |a, b| b / a);
+        bench_binary_op(&mut group, "g2_div", &s1, &g2, |a, b| b / a);
+        bench_binary_op(&mut group, "gt_div", &s1, &gt, |a, b| b / a);
+
+        // The base cost and the cost per byte are derived from the smallest and largest inputs.
+        for len in [1, 1024] {
+            let msg = vec![0u8; len];
+            group.bench_function(format!("g1_hash_to/{len}"), |b| {
+                b.iter(|| G1Element::hash_to_group_element(&msg).to_byte_array())
+            });
+        }
+
+        bench_msm(&mut group, "g1_msm", &s1, &g1);
+        bench_msm(&mut group, "g2_msm", &s1, &g2);
+
+        let g1_bytes = g1.to_byte_array();
+        let g2_bytes = g2.to_byte_array();
+        group.bench_function("pairing", |b| {
+            b.iter(|| {
+                let e1 = G1Element::from_trusted_byte_array(&g1_bytes).unwrap();
+                let e2 = G2Element::from_trusted_byte_array(&g2_bytes).unwrap();
+                e1.pairing(&e2).to_byte_array()
+            })
+        });
+
+        group.finish();
+    }};
+}
+
+fn group_ops_benchmark(c: &mut Criterion) {
+    bench_curve!(c, "bls12381", bls);
+    bench_curve!(c, "bn254", bn254);
+}
+
+criterion_group!(benches, group_ops_benchmark);
+criterion_main!(benches);
@@ -4,7 +4,7 @@ use crate::object_runtime::ObjectRuntime;
 use crate::NativesCostTable;
 use fastcrypto::error::{FastCryptoError, FastCryptoResult};
 use fastcrypto::groups::{
-    bls12381 as bls, FromTrustedByteArray, GroupElement, HashToGroupElement, MultiScalarMul,
+    bls12381 as bls, bn254, FromTrustedByteArray, GroupElement, HashToGroupElement, MultiScalarMul,
     Pairing,
 };
 use fastcrypto::serde_helpers::ToFromByteArray;
@@ -42,6 +42,22 @@ fn is_msm_supported(context: &NativeContext) -> PartialVMResult<bool> {
         .enable_group_ops_native_function_msm())
 }
 
+fn is_bn254_supported(context: &NativeContext) -> PartialVMResult<bool> {
+    Ok(context
+        .extensions()
+        .get::<ObjectRuntime>()?
+        .protocol_config
+        .enable_bn254_group_ops())
+}
+
+// BN254 types are enabled separately from the BLS12-381 types.
+fn is_group_supported(context: &NativeContext, group_type: u8) -> PartialVMResult<bool> {
+    match Groups::from_u8(group_type) {
+        Some(group) if group.is_bn254() => is_bn254_supported(context),
+        _ => Ok(true),
+    }
+}
+
 fn is_uncompressed_g1_supported(context: &NativeContext) -> PartialVMResult<bool> {
     Ok(context
         .extensions()
@@ -135,6 +151,44 @@ pub struct GroupOpsCostParams {
     pub bls12381_uncompressed_g1_sum_cost_per_term: Option<InternalGas>,
     // limit the number of terms in a sum
     pub bls12381_uncompressed_g1_sum_max_terms: Option<u64>,
+    // costs for decode and validate
+    pub bn254_decode_scalar_cost: Option<InternalGas>,
+    pub bn254_decode_g1_cost: Option<InternalGas>,
+    pub bn254_decode_g2_cost: Option<InternalGas>,
+    pub bn254_decode_gt_cost: Option<InternalGas>,
+    // costs for decode, add, and encode output
+    pub bn254_scalar_add_cost: Option<InternalGas>,
+    pub bn254_g1_add_cost: Option<InternalGas>,
+    pub bn254_g2_add_cost: Option<InternalGas>,
+    pub bn254_gt_add_cost: Option<InternalGas>,
+    // costs for decode, sub, and encode output
+    pub bn254_scalar_sub_cost: Option<InternalGas>,
+    pub bn254_g1_sub_cost: Option<InternalGas>,
+    pub bn254_g2_sub_cost: Option<InternalGas>,
+    pub bn254_gt_sub_cost: Option<InternalGas>,
+    // costs for decode, mul, and encode output
+    pub bn254_scalar_mul_cost: Option<InternalGas>,
+    pub bn254_g1_mul_cost: Option<InternalGas>,
+    pub bn254_g2_mul_cost: Option<InternalGas>,
+    pub bn254_gt_mul_cost: Option<InternalGas>,
+    // costs for decode, div, and encode output
+    pub bn254_scalar_div_cost: Option<InternalGas>,
+    pub bn254_g1_div_cost: Option<InternalGas>,
+    pub bn254_g2_div_cost: Option<InternalGas>,
+    pub bn254_gt_div_cost: Option<InternalGas>,
+    // costs for hashing (only to G1)
+    pub bn254_g1_hash_to_base_cost: Option<InternalGas>,
+    pub bn254_g1_hash_to_cost_per_byte: Option<InternalGas>,
+    // costs for encoding the output + base cost for MSM (the |q| doublings) but not decoding
+    pub bn254_g1_msm_base_cost: Option<InternalGas>,
+    pub bn254_g2_msm_base_cost: Option<InternalGas>,
+    // cost that is multiplied with the approximated number of additions
+    pub bn254_g1_msm_base_cost_per_input: Option<InternalGas>,
+    pub bn254_g2_msm_base_cost_per_input: Option<InternalGas>,
+    // limit the length of the input vectors for MSM
+    pub bn254_msm_max_len: Option<u32>,
+    // costs for decode, pairing, and encode output
+    pub bn254_pairing_cost: Option<InternalGas>,
 }
 
 macro_rules! native_charge_gas_early_exit_option {
@@ -159,6 +213,10 @@ enum Groups {
     BLS12381G2 = 2,
     BLS12381GT = 3,
     BLS12381UncompressedG1 = 4,
+    BN254Scalar = 5,
+    BN254G1 = 6,
+    BN254G2 = 7,
+    BN254GT = 8,
 }
 
 impl Groups {
@@ -169,9 +227,20 @@ impl Groups {
             2 => Some(Groups::BLS12381G2),
             3 => Some(Groups::BLS12381GT),
             4 => Some(Groups::BLS12381UncompressedG1),
+            5 => Some(Groups::BN254Scalar),
+            6 => Some(Groups::BN254G1),
+            7 => Some(Groups::BN254G2),
+            8 => Some(Groups::BN254GT),
             _ => None,
         }
     }
+
+    fn is_bn254(&self) -> bool {
+        matches!(
+            self,
+            Groups::BN254Scalar | Groups::BN254G1 | Groups::BN254G2 | Groups::BN254GT
+        )
+    }
 }
 
 fn parse_untrusted<G: ToFromByteArray<S> + FromTrustedByteArray<S>, const S: usize>(
@@ -219,7 +288,7 @@ fn binary_op<G: ToFromByteArray<S> + FromTrustedByteArray<S>, const S: usize>(
 /***************************************************************************************************
  * native fun internal_validate
  * Implementation of the Move native function `internal_validate(type: u8, bytes: &vector<u8>): bool`
- *   gas cost: group_ops_decode_bls12381_X_cost where X is the requested type
+ *   gas cost: group_ops_decode_bls12381_X_cost / group_ops_bn254_decode_X_cost where X is the requested type
  **************************************************************************************************/
 
 pub fn internal_validate(
@@ -239,6 +308,10 @@ pub fn internal_validate(
     let bytes = bytes_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
@@ -258,6 +331,18 @@ pub fn internal_validate(
             native_charge_gas_early_exit_option!(context, cost_params.bls12381_decode_g2_cost);
             parse_untrusted::<bls::G2Element, { bls::G2Element::BYTE_LENGTH }>(&bytes).is_ok()
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_decode_scalar_cost);
+            parse_untrusted::<bn254::Scalar, { bn254::Scalar::BYTE_LENGTH }>(&bytes).is_ok()
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_decode_g1_cost);
+            parse_untrusted::<bn254::G1Element, { bn254::G1Element::BYTE_LENGTH }>(&bytes).is_ok()
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_decode_g2_cost);
+            parse_untrusted::<bn254::G2Element, { bn254::G2Element::BYTE_LENGTH }>(&bytes).is_ok()
+        }
         _ => false,
     };
 
@@ -291,6 +376,10 @@ pub fn internal_add(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
@@ -314,6 +403,34 @@ pub fn internal_add(
             native_charge_gas_early_exit_option!(context, cost_params.bls12381_gt_add_cost);
             binary_op::<bls::GTElement, { bls::GTElement::BYTE_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_add_cost);
+            binary_op::<bn254::Scalar, { bn254::Scalar::BYTE_LENGTH }>(|a, b| Ok(a + b), &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_add_cost);
+            binary_op::<bn254::G1Element, { bn254::G1Element::BYTE_LENGTH }>(
+                |a, b| Ok(a + b),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_add_cost);
+            binary_op::<bn254::G2Element, { bn254::G2Element::BYTE_LENGTH }>(
+                |a, b| Ok(a + b),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_add_cost);
+            binary_op::<bn254::GTElement, { bn254::GTElement::BYTE_LENGTH }>(
+                |a, b| Ok(a + b),
+                &e1,
+                &e2,
+            )
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -344,6 +461,10 @@ pub fn internal_sub(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
@@ -367,6 +488,34 @@ pub fn internal_sub(
             native_charge_gas_early_exit_option!(context, cost_params.bls12381_gt_sub_cost);
             binary_op::<bls::GTElement, { bls::GTElement::BYTE_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_sub_cost);
+            binary_op::<bn254::Scalar, { bn254::Scalar::BYTE_LENGTH }>(|a, b| Ok(a - b), &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_sub_cost);
+            binary_op::<bn254::G1Element, { bn254::G1Element::BYTE_LENGTH }>(
+                |a, b| Ok(a - b),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_sub_cost);
+            binary_op::<bn254::G2Element, { bn254::G2Element::BYTE_LENGTH }>(
+                |a, b| Ok(a - b),
+                &e1,
+                &e2,
+            )
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_sub_cost);
+            binary_op::<bn254::GTElement, { bn254::GTElement::BYTE_LENGTH }>(
+                |a, b| Ok(a - b),
+                &e1,
+                &e2,
+            )
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -397,6 +546,10 @@ pub fn internal_mul(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
@@ -435,6 +588,37 @@ pub fn internal_mul(
                 { bls::GTElement::BYTE_LENGTH },
             >(|a, b| Ok(b * a), &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_mul_cost);
+            binary_op::<bn254::Scalar, { bn254::Scalar::BYTE_LENGTH }>(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_mul_cost);
+            binary_op_diff::<
+                bn254::Scalar,
+                bn254::G1Element,
+                { bn254::Scalar::BYTE_LENGTH },
+                { bn254::G1Element::BYTE_LENGTH },
+            >(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_mul_cost);
+            binary_op_diff::<
+                bn254::Scalar,
+                bn254::G2Element,
+                { bn254::Scalar::BYTE_LENGTH },
+                { bn254::G2Element::BYTE_LENGTH },
+            >(|a, b| Ok(b * a), &e1, &e2)
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_mul_cost);
+            binary_op_diff::<
+                bn254::Scalar,
+                bn254::GTElement,
+                { bn254::Scalar::BYTE_LENGTH },
+                { bn254::GTElement::BYTE_LENGTH },
+            >(|a, b| Ok(b * a), &e1, &e2)
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -465,6 +649,10 @@ pub fn internal_div(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
@@ -503,6 +691,37 @@ pub fn internal_div(
                 { bls::GTElement::BYTE_LENGTH },
             >(|a, b| b / a, &e1, &e2)
         }
+        Some(Groups::BN254Scalar) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_scalar_div_cost);
+            binary_op::<bn254::Scalar, { bn254::Scalar::BYTE_LENGTH }>(|a, b| b / a, &e1, &e2)
+        }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g1_div_cost);
+            binary_op_diff::<
+                bn254::Scalar,
+                bn254::G1Element,
+                { bn254::Scalar::BYTE_LENGTH },
+                { bn254::G1Element::BYTE_LENGTH },
+            >(|a, b| b / a, &e1, &e2)
+        }
+        Some(Groups::BN254G2) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_g2_div_cost);
+            binary_op_diff::<
+                bn254::Scalar,
+                bn254::G2Element,
+                { bn254::Scalar::BYTE_LENGTH },
+                { bn254::G2Element::BYTE_LENGTH },
+            >(|a, b| b / a, &e1, &e2)
+        }
+        Some(Groups::BN254GT) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_gt_div_cost);
+            binary_op_diff::<
+                bn254::Scalar,
+                bn254::GTElement,
+                { bn254::Scalar::BYTE_LENGTH },
+                { bn254::GTElement::BYTE_LENGTH },
+            >(|a, b| b / a, &e1, &e2)
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -532,6 +751,10 @@ pub fn internal_hash_to(
     let m = m_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     if m.is_empty() {
         return Ok(NativeResult::err(cost, INVALID_INPUT_ERROR));
     }
@@ -569,6 +792,19 @@ pub fn internal_hash_to(
                 .to_byte_array()
                 .to_vec())
         }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(
+                context,
+                cost_params
+                    .bn254_g1_hash_to_base_cost
+                    .and_then(|base_cost| cost_params
+                        .bn254_g1_hash_to_cost_per_byte
+                        .map(|per_byte| base_cost + per_byte * (m.len() as u64).into()))
+            );
+            Ok(bn254::G1Element::hash_to_group_element(&m)
+                .to_byte_array()
+                .to_vec())
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
@@ -691,13 +927,21 @@ pub fn internal_multi_scalar_mul(
     let scalars = scalars_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
         .group_ops_cost_params
         .clone();
 
-    let max_len = cost_params.bls12381_msm_max_len.ok_or_else(|| {
+    let max_len = match Groups::from_u8(group_type) {
+        Some(group) if group.is_bn254() => cost_params.bn254_msm_max_len,
+        _ => cost_params.bls12381_msm_max_len,
+    }
+    .ok_or_else(|| {
         PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
             .with_message("Max len for MSM is not set".to_string())
     })?;
@@ -732,6 +976,34 @@ pub fn internal_multi_scalar_mul(
             scalars.as_ref(),
             elements.as_ref(),
         ),
+        Some(Groups::BN254G1) => multi_scalar_mul::<
+            bn254::G1Element,
+            { bn254::Scalar::BYTE_LENGTH },
+            { bn254::G1Element::BYTE_LENGTH },
+        >(
+            context,
+            cost_params.bn254_decode_scalar_cost,
+            cost_params.bn254_decode_g1_cost,
+            cost_params.bn254_g1_msm_base_cost,
+            cost_params.bn254_g1_msm_base_cost_per_input,
+            max_len,
+            scalars.as_ref(),
+            elements.as_ref(),
+        ),
+        Some(Groups::BN254G2) => multi_scalar_mul::<
+            bn254::G2Element,
+            { bn254::Scalar::BYTE_LENGTH },
+            { bn254::G2Element::BYTE_LENGTH },
+        >(
+            context,
+            cost_params.bn254_decode_scalar_cost,
+            cost_params.bn254_decode_g2_cost,
+            cost_params.bn254_g2_msm_base_cost,
+            cost_params.bn254_g2_msm_base_cost_per_input,
+            max_len,
+            scalars.as_ref(),
+            elements.as_ref(),
+        ),
         _ => Ok(NativeResult::err(
             v2_native_charge(context, cost)?,
             INVALID_INPUT_ERROR,
@@ -763,6 +1035,10 @@ pub fn internal_pairing(
     let e1 = e1_ref.as_bytes_ref();
     let group_type = pop_arg!(args, u8);
 
+    if !is_group_supported(context, group_type)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
     let cost_params = &context
         .extensions()
         .get::<NativesCostTable>()?
@@ -779,6 +1055,19 @@ pub fn internal_pairing(
                 })
             })
         }
+        Some(Groups::BN254G1) => {
+            native_charge_gas_early_exit_option!(context, cost_params.bn254_pairing_cost);
+            parse_trusted::<bn254::G1Element, { bn254::G1Element::BYTE_LENGTH }>(&e1).and_then(
+                |e1| {
+                    parse_trusted::<bn254::G2Element, { bn254::G2Element::BYTE_LENGTH }>(&e2).map(
+                        |e2| {
+                            let e3 = e1.pairing(&e2);
+                            e3.to_byte_array().to_vec()
+                        },
+                    )
+                },
+            )
+        }
         _ => Err(FastCryptoError::InvalidInput),
     };
 
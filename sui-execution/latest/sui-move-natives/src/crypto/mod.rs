@@ -12,5 +12,6 @@ pub mod hash;
 pub mod hmac;
 pub mod nitro_attestation;
 pub mod poseidon;
+pub mod schnorr_secp256k1;
 pub mod vdf;
 pub mod zklogin;
@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{object_runtime::ObjectRuntime, NativesCostTable};
+use fastcrypto::{
+    secp256k1::schnorr::{SchnorrPublicKey, SchnorrSignature},
+    traits::{ToFromBytes, VerifyingKey},
+};
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::{gas_algebra::InternalGas, vm_status::StatusCode};
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub const NOT_SUPPORTED_ERROR: u64 = 0;
+
+const MESSAGE_LENGTH: usize = 32;
+
+fn is_supported(context: &NativeContext) -> PartialVMResult<bool> {
+    Ok(context
+        .extensions()
+        .get::<ObjectRuntime>()?
+        .protocol_config
+        .enable_schnorr_secp256k1())
+}
+
+#[derive(Clone)]
+pub struct SchnorrSecp256k1VerifyCostParams {
+    /// Base cost for invoking the `verify` function
+    pub schnorr_secp256k1_verify_cost_base: Option<InternalGas>,
+}
+
+/***************************************************************************************************
+ * native fun verify
+ * Implementation of the Move native function `schnorr_secp256k1::verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;`
+ *   gas cost: schnorr_secp256k1_verify_cost_base | base cost for function call and fixed opers
+ * Note: `signature`, `public_key` and `msg` are fixed size, so their costs are included in the base
+ *       cost. The challenge is computed with the `BIP0340/challenge` tagged hash as specified in
+ *       BIP-340.
+ **************************************************************************************************/
+pub fn verify(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let cost = context.gas_used();
+    if !is_supported(context)? {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
+    // Load the cost parameters from the protocol config
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()?
+        .schnorr_secp256k1_verify_cost_params
+        .clone();
+
+    // Charge the base cost for this oper
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .schnorr_secp256k1_verify_cost_base
+            .ok_or_else(
+                || PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR).with_message(
+                    "Gas cost for schnorr_secp256k1_verify not available".to_string()
+                )
+            )?
+    );
+
+    let msg = pop_arg!(args, VectorRef);
+    let msg_ref = msg.as_bytes_ref();
+    let public_key_bytes = pop_arg!(args, VectorRef);
+    let public_key_bytes_ref = public_key_bytes.as_bytes_ref();
+    let signature_bytes = pop_arg!(args, VectorRef);
+    let signature_bytes_ref = signature_bytes.as_bytes_ref();
+
+    let cost = context.gas_used();
+
+    // BIP-340 messages signed by Bitcoin (e.g. Taproot sighashes) are 32 bytes.
+    if msg_ref.len() != MESSAGE_LENGTH {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    }
+
+    let Ok(signature) = <SchnorrSignature as ToFromBytes>::from_bytes(&signature_bytes_ref) else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let Ok(public_key) = <SchnorrPublicKey as ToFromBytes>::from_bytes(&public_key_bytes_ref)
+    else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let result = public_key.verify(&msg_ref, &signature).is_ok();
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(result)]))
+}
@@ -20,6 +20,7 @@ use self::{
         hash::{HashBlake2b256CostParams, HashKeccak256CostParams},
         hmac::HmacHmacSha3256CostParams,
         poseidon,
+        schnorr_secp256k1::{self, SchnorrSecp256k1VerifyCostParams},
     },
     dynamic_field::{
         DynamicFieldAddChildObjectCostParams, DynamicFieldBorrowChildObjectCostParams,
@@ -191,6 +192,9 @@ pub struct NativesCostTable {
 
     // nitro attestation
     pub nitro_attestation_cost_params: NitroAttestationCostParams,
+
+    // schnorr_secp256k1
+    pub schnorr_secp256k1_verify_cost_params: SchnorrSecp256k1VerifyCostParams,
 }
 
 impl NativeExtensionMarker<'_> for NativesCostTable {}
@@ -731,6 +735,88 @@ impl NativesCostTable {
                     .map(Into::into),
                 bls12381_uncompressed_g1_sum_max_terms: protocol_config
                     .group_ops_bls12381_uncompressed_g1_sum_max_terms_as_option(),
+                bn254_decode_scalar_cost: protocol_config
+                    .group_ops_bn254_decode_scalar_cost_as_option()
+                    .map(Into::into),
+                bn254_decode_g1_cost: protocol_config
+                    .group_ops_bn254_decode_g1_cost_as_option()
+                    .map(Into::into),
+                bn254_decode_g2_cost: protocol_config
+                    .group_ops_bn254_decode_g2_cost_as_option()
+                    .map(Into::into),
+                bn254_decode_gt_cost: protocol_config
+                    .group_ops_bn254_decode_gt_cost_as_option()
+                    .map(Into::into),
+                bn254_scalar_add_cost: protocol_config
+                    .group_ops_bn254_scalar_add_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_add_cost: protocol_config
+                    .group_ops_bn254_g1_add_cost_as_option()
+                    .map(Into::into),
+                bn254_g2_add_cost: protocol_config
+                    .group_ops_bn254_g2_add_cost_as_option()
+                    .map(Into::into),
+                bn254_gt_add_cost: protocol_config
+                    .group_ops_bn254_gt_add_cost_as_option()
+                    .map(Into::into),
+                bn254_scalar_sub_cost: protocol_config
+                    .group_ops_bn254_scalar_sub_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_sub_cost: protocol_config
+                    .group_ops_bn254_g1_sub_cost_as_option()
+                    .map(Into::into),
+                bn254_g2_sub_cost: protocol_config
+                    .group_ops_bn254_g2_sub_cost_as_option()
+                    .map(Into::into),
+                bn254_gt_sub_cost: protocol_config
+                    .group_ops_bn254_gt_sub_cost_as_option()
+                    .map(Into::into),
+                bn254_scalar_mul_cost: protocol_config
+                    .group_ops_bn254_scalar_mul_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_mul_cost: protocol_config
+                    .group_ops_bn254_g1_mul_cost_as_option()
+                    .map(Into::into),
+                bn254_g2_mul_cost: protocol_config
+                    .group_ops_bn254_g2_mul_cost_as_option()
+                    .map(Into::into),
+                bn254_gt_mul_cost: protocol_config
+                    .group_ops_bn254_gt_mul_cost_as_option()
+                    .map(Into::into),
+                bn254_scalar_div_cost: protocol_config
+                    .group_ops_bn254_scalar_div_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_div_cost: protocol_config
+                    .group_ops_bn254_g1_div_cost_as_option()
+                    .map(Into::into),
+                bn254_g2_div_cost: protocol_config
+                    .group_ops_bn254_g2_div_cost_as_option()
+                    .map(Into::into),
+                bn254_gt_div_cost: protocol_config
+                    .group_ops_bn254_gt_div_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_hash_to_base_cost: protocol_config
+                    .group_ops_bn254_g1_hash_to_base_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_hash_to_cost_per_byte: protocol_config
+                    .group_ops_bn254_g1_hash_to_cost_per_byte_as_option()
+                    .map(Into::into),
+                bn254_g1_msm_base_cost: protocol_config
+                    .group_ops_bn254_g1_msm_base_cost_as_option()
+                    .map(Into::into),
+                bn254_g2_msm_base_cost: protocol_config
+                    .group_ops_bn254_g2_msm_base_cost_as_option()
+                    .map(Into::into),
+                bn254_g1_msm_base_cost_per_input: protocol_config
+                    .group_ops_bn254_g1_msm_base_cost_per_input_as_option()
+                    .map(Into::into),
+                bn254_g2_msm_base_cost_per_input: protocol_config
+                    .group_ops_bn254_g2_msm_base_cost_per_input_as_option()
+                    .map(Into::into),
+                bn254_msm_max_len: protocol_config.group_ops_bn254_msm_max_len_as_option(),
+                bn254_pairing_cost: protocol_config
+                    .group_ops_bn254_pairing_cost_as_option()
+                    .map(Into::into),
             },
             vdf_cost_params: VDFCostParams {
                 vdf_verify_cost: protocol_config
@@ -754,6 +840,11 @@ impl NativesCostTable {
                     .nitro_attestation_verify_cost_per_cert_as_option()
                     .map(Into::into),
             },
+            schnorr_secp256k1_verify_cost_params: SchnorrSecp256k1VerifyCostParams {
+                schnorr_secp256k1_verify_cost_base: protocol_config
+                    .schnorr_secp256k1_verify_cost_base_as_option()
+                    .map(Into::into),
+            },
         }
     }
 }
@@ -1213,6 +1304,11 @@ pub fn all_natives(silent: bool, protocol_config: &ProtocolConfig) -> NativeFunc
             "load_nitro_attestation_internal",
             make_native!(nitro_attestation::load_nitro_attestation_internal),
         ),
+        (
+            "schnorr_secp256k1",
+            "verify",
+            make_native!(schnorr_secp256k1::verify),
+        ),
     ];
     let sui_framework_natives_iter =
         sui_framework_natives